@@ -21,6 +21,7 @@ fn create_test_sound(num_samples: usize) -> StaticSoundData {
 		frames: Arc::from(frames),
 		settings: StaticSoundSettings::new().loop_region(0.0..),
 		slice: None,
+		metadata: None,
 	}
 }
 
@@ -32,6 +33,7 @@ fn sounds(c: &mut Criterion) {
 		let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
 			backend_settings: MockBackendSettings {
 				sample_rate: SAMPLE_RATE,
+				..Default::default()
 			},
 			main_track_builder: MainTrackBuilder::new().sound_capacity(NUM_SOUNDS),
 			..Default::default()
@@ -54,6 +56,7 @@ fn sounds(c: &mut Criterion) {
 		let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
 			backend_settings: MockBackendSettings {
 				sample_rate: SAMPLE_RATE,
+				..Default::default()
 			},
 			main_track_builder: MainTrackBuilder::new().sound_capacity(NUM_SOUNDS),
 			..Default::default()
@@ -40,9 +40,9 @@ fn main() -> Result<(), Box<dyn Error>> {
 		StaticSoundSettings::new().loop_region(music_duration / 2.0..music_duration);
 	// load the sounds, linking the volumes to the tweener when appropriate
 	let arp = StaticSoundData::from_file("crates/examples/assets/dynamic/arp.ogg")?
-		.with_settings(common_sound_settings);
+		.with_settings(common_sound_settings.clone());
 	let bass = StaticSoundData::from_file("crates/examples/assets/dynamic/bass.ogg")?
-		.with_settings(common_sound_settings)
+		.with_settings(common_sound_settings.clone())
 		.volume(Value::from_modulator(
 			&underwater_tweener,
 			Mapping {
@@ -52,7 +52,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 			},
 		));
 	let drums = StaticSoundData::from_file("crates/examples/assets/dynamic/drums.ogg")?
-		.with_settings(common_sound_settings)
+		.with_settings(common_sound_settings.clone())
 		.volume(Value::from_modulator(
 			&underwater_tweener,
 			Mapping {
@@ -62,9 +62,9 @@ fn main() -> Result<(), Box<dyn Error>> {
 			},
 		));
 	let lead = StaticSoundData::from_file("crates/examples/assets/dynamic/lead.ogg")?
-		.with_settings(common_sound_settings);
+		.with_settings(common_sound_settings.clone());
 	let pad = StaticSoundData::from_file("crates/examples/assets/dynamic/pad.ogg")?
-		.with_settings(common_sound_settings)
+		.with_settings(common_sound_settings.clone())
 		.volume(Value::from_modulator(
 			&underwater_tweener,
 			Mapping {
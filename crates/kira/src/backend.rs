@@ -2,9 +2,13 @@
 
 #[cfg(feature = "cpal")]
 pub mod cpal;
+pub mod input;
 pub mod mock;
+pub mod offline;
+pub mod pull;
 mod renderer;
 pub(crate) mod resources;
+mod wav;
 
 pub use renderer::*;
 
@@ -3,9 +3,17 @@
 #![cfg_attr(docsrs, doc(cfg(feature = "cpal")))]
 
 mod error;
-use cpal::{BufferSize, Device};
+use cpal::{traits::HostTrait, BufferSize, Device};
 pub use error::*;
 
+/// Returns the audio output devices available on the system.
+///
+/// Any of these devices can be used for
+/// [`CpalBackendSettings::device`] or `CpalBackend::set_device`.
+pub fn output_devices() -> Result<Vec<Device>, Error> {
+	Ok(cpal::default_host().output_devices()?.collect())
+}
+
 /// Settings for the cpal backend.
 pub struct CpalBackendSettings {
 	/// The output audio device to use. If [`None`], the default output
@@ -38,3 +46,8 @@ pub use wasm::CpalBackend;
 mod desktop;
 #[cfg(not(target_arch = "wasm32"))]
 pub use desktop::CpalBackend;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod input;
+#[cfg(not(target_arch = "wasm32"))]
+pub use input::*;
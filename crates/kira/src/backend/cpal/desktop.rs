@@ -1,7 +1,10 @@
 mod renderer_with_cpu_usage;
 mod stream_manager;
 
-use std::sync::{ Arc, Mutex };
+use std::{
+	sync::{Arc, Mutex},
+	time::Duration,
+};
 
 use renderer_with_cpu_usage::RendererWithCpuUsage;
 use ringbuf::{ HeapRb, Cons as Consumer, consumer::Consumer as _ };
@@ -55,6 +58,76 @@ impl CpalBackend {
 			.unwrap()
 			.try_pop()
 	}
+
+	/**
+	Moves audio output to a different device without interrupting any
+	playing sounds.
+
+	If `device` is [`None`], the default output device will be used,
+	and audio output will automatically move to a new default device
+	if the default device changes.
+
+	This blocks until audio output has started on the new device and
+	returns the new sample rate. If audio output can't be started on
+	the new device, the default device will be used instead, and the
+	error will be returned.
+
+	Returns [`Error::StreamManagerStopped`] if the backend hasn't been
+	started yet.
+
+	# Examples
+
+	```no_run
+	use kira::{
+		AudioManager, AudioManagerSettings, DefaultBackend,
+		backend::cpal::output_devices,
+	};
+	use cpal::traits::DeviceTrait;
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let devices = output_devices()?;
+	for device in &devices {
+		println!("{}", device.name()?);
+	}
+	if let Some(device) = devices.into_iter().next() {
+		let sample_rate = manager.backend_mut().set_device(Some(device))?;
+		println!("now playing at {}hz", sample_rate);
+	}
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn set_device(&mut self, device: Option<Device>) -> Result<u32, Error> {
+		if let State::Initialized {
+			stream_manager_controller,
+		} = &self.state
+		{
+			stream_manager_controller.set_device(device)
+		} else {
+			Err(Error::StreamManagerStopped)
+		}
+	}
+
+	/**
+	Returns the most recent output latency reported by the audio device,
+	which is the time between audio being rendered and it being heard.
+
+	Returns `None` if the backend hasn't been started yet or the
+	device hasn't reported its latency.
+
+	[`AudioManager::output_latency`](crate::AudioManager::output_latency)
+	also includes the delay added by the [output stage](crate::output_stage).
+	*/
+	#[must_use]
+	pub fn output_latency(&self) -> Option<Duration> {
+		if let State::Initialized {
+			stream_manager_controller,
+		} = &self.state
+		{
+			stream_manager_controller.output_latency()
+		} else {
+			None
+		}
+	}
 }
 
 impl Backend for CpalBackend {
@@ -2,7 +2,8 @@ mod send_on_drop;
 
 use std::{
 	sync::{
-		atomic::{AtomicBool, Ordering},
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		mpsc::{self, RecvTimeoutError},
 		Arc,
 	},
 	time::Duration,
@@ -11,7 +12,7 @@ use std::{
 use super::renderer_with_cpu_usage::RendererWithCpuUsage;
 use cpal::{
 	traits::{DeviceTrait, HostTrait, StreamTrait},
-	BufferSize, Device, Stream, StreamConfig, StreamError,
+	BufferSize, Device, OutputCallbackInfo, Stream, StreamConfig, StreamError,
 };
 use send_on_drop::SendOnDrop;
 
@@ -23,6 +24,8 @@ type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
 use super::super::Error;
 
 const CHECK_STREAM_INTERVAL: Duration = Duration::from_millis(500);
+/// Stored as the output latency when the device hasn't reported it.
+const UNKNOWN_LATENCY: u64 = u64::MAX;
 
 #[allow(clippy::large_enum_variant)]
 enum State {
@@ -37,24 +40,64 @@ enum State {
 	},
 }
 
+enum StreamManagerCommand {
+	SetDevice {
+		device: Option<Device>,
+		result_sender: mpsc::Sender<Result<u32, Error>>,
+	},
+}
+
 pub(super) struct StreamManagerController {
 	should_drop: Arc<AtomicBool>,
+	command_sender: mpsc::Sender<StreamManagerCommand>,
+	output_latency: Arc<AtomicU64>,
 }
 
 impl StreamManagerController {
 	pub fn stop(&self) {
 		self.should_drop.store(true, Ordering::SeqCst);
 	}
+
+	/// Returns the most recent output latency reported by the device.
+	pub fn output_latency(&self) -> Option<Duration> {
+		match self.output_latency.load(Ordering::SeqCst) {
+			UNKNOWN_LATENCY => None,
+			nanos => Some(Duration::from_nanos(nanos)),
+		}
+	}
+
+	/// Moves the renderer to a new output device and waits for
+	/// the new stream to start.
+	///
+	/// Returns the sample rate of the new stream.
+	pub fn set_device(&self, device: Option<Device>) -> Result<u32, Error> {
+		let (result_sender, result_receiver) = mpsc::channel();
+		self.command_sender
+			.send(StreamManagerCommand::SetDevice {
+				device,
+				result_sender,
+			})
+			.map_err(|_| Error::StreamManagerStopped)?;
+		result_receiver
+			.recv()
+			.map_err(|_| Error::StreamManagerStopped)?
+	}
 }
 
 /// Starts a cpal stream and restarts it if needed
 /// in the case of device changes or disconnections.
 pub(super) struct StreamManager {
 	state: State,
+	/// The device and config of the most recently started stream.
+	device: Device,
+	config: StreamConfig,
 	device_name: String,
 	sample_rate: u32,
 	custom_device: bool,
 	buffer_size: BufferSize,
+	/// The time between the audio callback being called and the
+	/// audio it produces being heard, in nanoseconds.
+	output_latency: Arc<AtomicU64>,
 }
 
 impl StreamManager {
@@ -67,17 +110,34 @@ impl StreamManager {
 	) -> StreamManagerController {
 		let should_drop = Arc::new(AtomicBool::new(false));
 		let should_drop_clone = should_drop.clone();
+		let (command_sender, command_receiver) = mpsc::channel();
+		let output_latency = Arc::new(AtomicU64::new(UNKNOWN_LATENCY));
+		let output_latency_clone = output_latency.clone();
 		std::thread::spawn(move || {
 			let mut stream_manager = StreamManager {
 				state: State::Idle { renderer },
+				device: device.clone(),
+				config: config.clone(),
 				device_name: device_name(&device),
 				sample_rate: config.sample_rate.0,
 				custom_device,
 				buffer_size,
+				output_latency: output_latency_clone,
 			};
-			stream_manager.start_stream(&device, &mut config).unwrap();
+			// if the stream can't be started, `check_stream` will keep
+			// trying to start it on the default device
+			stream_manager.start_stream(&device, &mut config).ok();
 			loop {
-				std::thread::sleep(CHECK_STREAM_INTERVAL);
+				match command_receiver.recv_timeout(CHECK_STREAM_INTERVAL) {
+					Ok(StreamManagerCommand::SetDevice {
+						device,
+						result_sender,
+					}) => {
+						result_sender.send(stream_manager.set_device(device)).ok();
+					}
+					Err(RecvTimeoutError::Timeout) => {}
+					Err(RecvTimeoutError::Disconnected) => break,
+				}
 				if should_drop.load(Ordering::SeqCst) {
 					break;
 				}
@@ -86,11 +146,55 @@ impl StreamManager {
 		});
 		StreamManagerController {
 			should_drop: should_drop_clone,
+			command_sender,
+			output_latency,
 		}
 	}
 
-	/// Restarts the stream if the audio device gets disconnected.
+	/// Moves the renderer to a new output device. If `device` is [`None`],
+	/// the default output device will be used, and the stream will follow
+	/// changes to the default device.
+	///
+	/// If the stream can't be started on the new device, the previous
+	/// device will be used instead.
+	fn set_device(&mut self, device: Option<Device>) -> Result<u32, Error> {
+		let result = match device {
+			Some(device) => device
+				.default_output_config()
+				.map_err(Error::from)
+				.map(|config| (device, config.config(), true)),
+			None => default_device_and_config().map(|(device, config)| (device, config, false)),
+		};
+		let (device, mut config, custom_device) = result?;
+		if let State::Running { .. } = &self.state {
+			self.stop_stream();
+		}
+		let old_device = self.device.clone();
+		let mut old_config = self.config.clone();
+		let old_custom_device = self.custom_device;
+		self.custom_device = custom_device;
+		if let Err(error) = self.start_stream(&device, &mut config) {
+			// if the previous device can't be restarted either, `check_stream`
+			// will keep trying to start the stream on the default device
+			self.custom_device = old_custom_device;
+			self.start_stream(&old_device, &mut old_config).ok();
+			return Err(error);
+		}
+		Ok(self.sample_rate)
+	}
+
+	/// Restarts the stream if the audio device gets disconnected,
+	/// and starts it on the default device if it isn't running.
+	///
+	/// Errors are ignored, since the stream will be checked again
+	/// after [`CHECK_STREAM_INTERVAL`].
 	fn check_stream(&mut self) {
+		if let State::Idle { .. } = &self.state {
+			if let Ok((device, mut config)) = default_device_and_config() {
+				self.start_stream(&device, &mut config).ok();
+			}
+			return;
+		}
 		if let State::Running {
 			stream_error_consumer,
 			..
@@ -100,9 +204,9 @@ impl StreamManager {
 			if let Some(StreamError::DeviceNotAvailable) = stream_error_consumer.try_pop() {
 				self.stop_stream();
 				if let Ok((device, mut config)) = default_device_and_config() {
-					// TODO: gracefully handle errors that occur in this function
-					self.start_stream(&device, &mut config).unwrap();
+					self.start_stream(&device, &mut config).ok();
 				}
+				return;
 			}
 			// check for device changes if a custom device hasn't been specified
 			// Disabled on macos due to audio artifacts that seem to occur when the device is
@@ -115,7 +219,7 @@ impl StreamManager {
 					let sample_rate = config.sample_rate.0;
 					if device_name != self.device_name || sample_rate != self.sample_rate {
 						self.stop_stream();
-						self.start_stream(&device, &mut config).unwrap();
+						self.start_stream(&device, &mut config).ok();
 					}
 				}
 			}
@@ -137,37 +241,72 @@ impl StreamManager {
 		}
 		self.device_name = device_name;
 		self.sample_rate = sample_rate;
-		let (mut renderer_wrapper, renderer_consumer) = SendOnDrop::new(renderer);
+		let (mut renderer_wrapper, mut renderer_consumer) = SendOnDrop::new(renderer);
 
 		let rb = Arc::new(RingBuffer::new(1));
 		let mut stream_error_producer = Producer::new(rb.clone());
 		let stream_error_consumer = Consumer::new(rb);
 
 		let channels = config.channels;
-		let stream = device.build_output_stream(
-			config,
-			move |data: &mut [f32], _| {
-				#[cfg(feature = "assert_no_alloc")]
-				assert_no_alloc::assert_no_alloc(|| {
-					process_renderer(&mut renderer_wrapper, data, channels, sample_rate);
-				});
-				#[cfg(not(feature = "assert_no_alloc"))]
-				process_renderer(&mut renderer_wrapper, data, channels, sample_rate);
-			},
-			move |error| {
-				stream_error_producer
-					.try_push(error)
-					.expect("Stream error producer is full");
-			},
-			None,
-		)?;
-		stream.play()?;
-		self.state = State::Running {
-			stream,
-			stream_error_consumer,
-			renderer_consumer,
-		};
-		Ok(())
+		// the new device's latency isn't known until it's reported
+		self.output_latency.store(UNKNOWN_LATENCY, Ordering::SeqCst);
+		let output_latency = self.output_latency.clone();
+		let stream = device
+			.build_output_stream(
+				config,
+				move |data: &mut [f32], info: &OutputCallbackInfo| {
+					let timestamp = info.timestamp();
+					let latency = timestamp.playback.duration_since(&timestamp.callback);
+					if let Some(latency) = latency {
+						output_latency.store(latency.as_nanos() as u64, Ordering::SeqCst);
+					}
+					#[cfg(feature = "assert_no_alloc")]
+					assert_no_alloc::assert_no_alloc(|| {
+						process_renderer(
+							&mut renderer_wrapper,
+							data,
+							channels,
+							sample_rate,
+							latency,
+						);
+					});
+					#[cfg(not(feature = "assert_no_alloc"))]
+					process_renderer(&mut renderer_wrapper, data, channels, sample_rate, latency);
+				},
+				move |error| {
+					stream_error_producer
+						.try_push(error)
+						.expect("Stream error producer is full");
+				},
+				None,
+			)
+			.map_err(Error::from)
+			.and_then(|stream| {
+				stream.play()?;
+				Ok(stream)
+			});
+		match stream {
+			Ok(stream) => {
+				self.device = device.clone();
+				self.config = config.clone();
+				self.state = State::Running {
+					stream,
+					stream_error_consumer,
+					renderer_consumer,
+				};
+				Ok(())
+			}
+			Err(error) => {
+				// the renderer is sent back when the stream's callback is dropped,
+				// so we can try again later
+				self.state = State::Idle {
+					renderer: renderer_consumer
+						.try_pop()
+						.expect("Could not retrieve the renderer after failing to start a stream"),
+				};
+				Err(error)
+			}
+		}
 	}
 
 	fn stop_stream(&mut self) {
@@ -208,7 +347,11 @@ fn process_renderer(
 	data: &mut [f32],
 	channels: u16,
 	sample_rate: u32,
+	latency: Option<Duration>,
 ) {
+	if let Some(latency) = latency {
+		renderer.set_output_latency(latency);
+	}
 	renderer.on_start_processing();
 	renderer.process(data, channels, sample_rate);
 }
@@ -1,18 +1,27 @@
 use std::fmt::{Display, Formatter};
 
-use cpal::{BuildStreamError, DefaultStreamConfigError, PlayStreamError};
+use cpal::{BuildStreamError, DefaultStreamConfigError, DevicesError, PlayStreamError};
 
 /// Errors that can occur when using the cpal backend.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
 	/// A default audio output device could not be determined.
 	NoDefaultOutputDevice,
+	/// A default audio input device could not be determined.
+	NoDefaultInputDevice,
+	/// An error occurred when listing the available audio devices.
+	DevicesError(DevicesError),
 	/// An error occurred when getting the default output configuration.
 	DefaultStreamConfigError(DefaultStreamConfigError),
 	/// An error occurred when building the audio stream.
 	BuildStreamError(BuildStreamError),
 	/// An error occurred when starting the audio stream.
 	PlayStreamError(PlayStreamError),
+	/// The thread that manages the audio stream isn't running, either
+	/// because the backend hasn't been started yet or because the
+	/// thread has stopped.
+	StreamManagerStopped,
 }
 
 impl Display for Error {
@@ -21,9 +30,12 @@ impl Display for Error {
 			Error::NoDefaultOutputDevice => {
 				f.write_str("Cannot find the default audio output device")
 			}
+			Error::NoDefaultInputDevice => f.write_str("Cannot find the default audio input device"),
+			Error::DevicesError(error) => error.fmt(f),
 			Error::DefaultStreamConfigError(error) => error.fmt(f),
 			Error::BuildStreamError(error) => error.fmt(f),
 			Error::PlayStreamError(error) => error.fmt(f),
+			Error::StreamManagerStopped => f.write_str("The stream manager thread isn't running"),
 		}
 	}
 }
@@ -31,6 +43,7 @@ impl Display for Error {
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
 		match self {
+			Error::DevicesError(error) => Some(error),
 			Error::DefaultStreamConfigError(error) => Some(error),
 			Error::BuildStreamError(error) => Some(error),
 			Error::PlayStreamError(error) => Some(error),
@@ -39,6 +52,12 @@ impl std::error::Error for Error {
 	}
 }
 
+impl From<DevicesError> for Error {
+	fn from(v: DevicesError) -> Self {
+		Self::DevicesError(v)
+	}
+}
+
 impl From<DefaultStreamConfigError> for Error {
 	fn from(v: DefaultStreamConfigError) -> Self {
 		Self::DefaultStreamConfigError(v)
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use cpal::{
+	traits::{DeviceTrait, HostTrait, StreamTrait},
+	BufferSize, Device, Stream, StreamConfig, StreamError,
+};
+
+use crate::backend::input::{input_stream, InputBackend, InputStream};
+
+use ringbuf::{ Cons, Prod, HeapRb as RingBuffer, producer::Producer as _, consumer::Consumer as _ };
+type Producer<T> = Prod<Arc<RingBuffer<T>>>;
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use super::Error;
+
+const STREAM_ERROR_CAPACITY: usize = 64;
+
+/// Settings for the cpal input backend.
+pub struct CpalInputBackendSettings {
+	/// The input audio device to use. If [`None`], the default input
+	/// device will be used.
+	pub device: Option<Device>,
+	/// The buffer size used by the device. If it is set to [`BufferSize::Default`],
+	/// the default buffer size for the device will be used.
+	pub buffer_size: BufferSize,
+	/// The maximum number of frames that can be captured before they're played.
+	pub buffer_capacity: usize,
+}
+
+impl Default for CpalInputBackendSettings {
+	fn default() -> Self {
+		Self {
+			device: None,
+			buffer_size: BufferSize::Default,
+			buffer_capacity: 16_384,
+		}
+	}
+}
+
+/// An input backend that uses [cpal](https://crates.io/crates/cpal) to
+/// capture audio.
+///
+/// Audio is captured until the backend is dropped.
+pub struct CpalInputBackend {
+	_stream: Stream,
+	stream_error_consumer: Consumer<StreamError>,
+}
+
+impl CpalInputBackend {
+	/// Returns the oldest reported error from the input stream, if any.
+	pub fn pop_error(&mut self) -> Option<StreamError> {
+		self.stream_error_consumer.try_pop()
+	}
+}
+
+impl InputBackend for CpalInputBackend {
+	type Settings = CpalInputBackendSettings;
+
+	type Error = Error;
+
+	fn start(settings: Self::Settings) -> Result<(Self, InputStream), Self::Error> {
+		let device = match settings.device {
+			Some(device) => device,
+			None => cpal::default_host()
+				.default_input_device()
+				.ok_or(Error::NoDefaultInputDevice)?,
+		};
+		let mut config: StreamConfig = device.default_input_config()?.config();
+		config.buffer_size = settings.buffer_size;
+		let num_channels = config.channels;
+		let (mut writer, stream) = input_stream(config.sample_rate.0, settings.buffer_capacity);
+		let rb = Arc::new(RingBuffer::new(STREAM_ERROR_CAPACITY));
+		let mut stream_error_producer = Producer::new(rb.clone());
+		let stream_error_consumer = Consumer::new(rb);
+		let cpal_stream = device.build_input_stream(
+			&config,
+			move |data: &[f32], _| {
+				writer.write_interleaved(data, num_channels);
+			},
+			move |error| {
+				stream_error_producer.try_push(error).ok();
+			},
+			None,
+		)?;
+		cpal_stream.play()?;
+		Ok((
+			Self {
+				_stream: cpal_stream,
+				stream_error_consumer,
+			},
+			stream,
+		))
+	}
+}
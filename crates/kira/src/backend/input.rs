@@ -0,0 +1,156 @@
+/*!
+Capturing audio from input devices, like microphones.
+
+An [`InputBackend`] captures audio and writes it to an [`InputStream`].
+The stream can be played through the mixer (including any effects on
+the track it's played on) using
+[`InputSoundData`](crate::sound::input::InputSoundData).
+
+```no_run
+# extern crate kira;
+#
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	backend::{cpal::CpalInputBackend, input::InputBackend},
+	sound::input::InputSoundData,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+// Audio is captured until the backend is dropped.
+let (input_backend, input_stream) = CpalInputBackend::start(Default::default())?;
+let input_sound = manager.play(InputSoundData::new(input_stream))?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+
+pub mod mock;
+
+use std::sync::{
+	atomic::{AtomicU32, Ordering},
+	Arc,
+};
+
+use crate::Frame;
+
+use ringbuf::{ Cons, Prod, HeapRb as RingBuffer, producer::Producer as _ };
+type Producer<T> = Prod<Arc<RingBuffer<T>>>;
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+/// Captures audio from a lower level audio API.
+pub trait InputBackend: Sized {
+	/// Settings for this backend.
+	type Settings;
+
+	/// Errors that can occur when using this backend.
+	type Error;
+
+	/// Starts capturing audio and returns itself and the stream
+	/// the captured audio will be written to.
+	///
+	/// Audio will be captured until the backend is dropped.
+	fn start(settings: Self::Settings) -> Result<(Self, InputStream), Self::Error>;
+}
+
+/// Creates an [`InputStreamWriter`] and a connected [`InputStream`].
+///
+/// The `capacity` is the maximum number of frames that can be
+/// written to the stream before they're played.
+///
+/// This is mainly useful for implementing [`InputBackend`]s.
+#[must_use]
+pub fn input_stream(sample_rate: u32, capacity: usize) -> (InputStreamWriter, InputStream) {
+	let shared = Arc::new(InputStreamShared {
+		sample_rate: AtomicU32::new(sample_rate),
+	});
+	let rb = Arc::new(RingBuffer::new(capacity));
+	let producer = Producer::new(rb.clone());
+	let consumer = Consumer::new(rb);
+	(
+		InputStreamWriter {
+			producer,
+			shared: shared.clone(),
+		},
+		InputStream { consumer, shared },
+	)
+}
+
+/// Writes captured audio to an [`InputStream`].
+pub struct InputStreamWriter {
+	producer: Producer<Frame>,
+	shared: Arc<InputStreamShared>,
+}
+
+impl InputStreamWriter {
+	/// Writes frames of audio to the stream.
+	///
+	/// If the stream is full, the remaining frames will be dropped.
+	/// Returns the number of frames that were written.
+	pub fn write(&mut self, frames: &[Frame]) -> usize {
+		self.producer.push_slice(frames)
+	}
+
+	/// Writes interleaved samples with the given number of channels to
+	/// the stream.
+	///
+	/// Mono audio will be played from both speakers, and any channels after
+	/// the first two will be ignored. If the stream is full, the remaining
+	/// samples will be dropped. Returns the number of frames that were written.
+	pub fn write_interleaved(&mut self, samples: &[f32], num_channels: u16) -> usize {
+		let mut num_frames_written = 0;
+		for channels in samples.chunks_exact(num_channels.max(1).into()) {
+			let frame = match channels {
+				[mono] => Frame::from_mono(*mono),
+				[left, right, ..] => Frame::new(*left, *right),
+				[] => unreachable!(),
+			};
+			if self.producer.try_push(frame).is_err() {
+				break;
+			}
+			num_frames_written += 1;
+		}
+		num_frames_written
+	}
+
+	/// Returns the sample rate of the captured audio.
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.shared.sample_rate.load(Ordering::SeqCst)
+	}
+
+	/// Sets the sample rate of the captured audio.
+	///
+	/// Backends should call this if the sample rate of the input
+	/// device changes.
+	pub fn set_sample_rate(&mut self, sample_rate: u32) {
+		self.shared.sample_rate.store(sample_rate, Ordering::SeqCst);
+	}
+}
+
+/// A stream of audio captured by an [`InputBackend`].
+///
+/// Pass this to [`InputSoundData::new`](crate::sound::input::InputSoundData::new)
+/// to play the captured audio.
+pub struct InputStream {
+	pub(crate) consumer: Consumer<Frame>,
+	pub(crate) shared: Arc<InputStreamShared>,
+}
+
+impl InputStream {
+	/// Returns the sample rate of the captured audio.
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.shared.sample_rate()
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct InputStreamShared {
+	sample_rate: AtomicU32,
+}
+
+impl InputStreamShared {
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate.load(Ordering::SeqCst)
+	}
+}
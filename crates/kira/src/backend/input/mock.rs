@@ -0,0 +1,80 @@
+//! Useful for testing audio input without an input device.
+
+use std::convert::Infallible;
+
+use crate::Frame;
+
+use super::{input_stream, InputBackend, InputStream, InputStreamWriter};
+
+/// Settings for the mock input backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MockInputBackendSettings {
+	/// The sample rate of the captured audio.
+	pub sample_rate: u32,
+	/// The maximum number of frames that can be captured
+	/// before they're played.
+	pub buffer_capacity: usize,
+}
+
+impl Default for MockInputBackendSettings {
+	fn default() -> Self {
+		Self {
+			sample_rate: 1,
+			buffer_capacity: 16_384,
+		}
+	}
+}
+
+/**
+An input backend that doesn't connect to any input devices,
+but allows manually pushing frames of audio to the [`InputStream`].
+
+To simulate capturing audio from a file, load the file as
+[`StaticSoundData`](crate::sound::static_sound::StaticSoundData)
+and push its frames:
+
+```no_run
+# extern crate kira;
+#
+use kira::{
+	backend::input::{mock::{MockInputBackend, MockInputBackendSettings}, InputBackend},
+	sound::static_sound::StaticSoundData,
+};
+
+let sound_data = StaticSoundData::from_file("voice.ogg")?;
+let (mut input_backend, input_stream) = MockInputBackend::start(MockInputBackendSettings {
+	sample_rate: sound_data.sample_rate,
+	buffer_capacity: sound_data.frames.len(),
+})?;
+input_backend.push_frames(&sound_data.frames);
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+pub struct MockInputBackend {
+	writer: InputStreamWriter,
+}
+
+impl MockInputBackend {
+	/// Pushes frames of audio to the [`InputStream`].
+	///
+	/// Returns the number of frames that fit in the stream.
+	pub fn push_frames(&mut self, frames: &[Frame]) -> usize {
+		self.writer.write(frames)
+	}
+
+	/// Changes the sample rate of the captured audio.
+	pub fn set_sample_rate(&mut self, sample_rate: u32) {
+		self.writer.set_sample_rate(sample_rate);
+	}
+}
+
+impl InputBackend for MockInputBackend {
+	type Settings = MockInputBackendSettings;
+
+	type Error = Infallible;
+
+	fn start(settings: Self::Settings) -> Result<(Self, InputStream), Self::Error> {
+		let (writer, stream) = input_stream(settings.sample_rate, settings.buffer_capacity);
+		Ok((Self { writer }, stream))
+	}
+}
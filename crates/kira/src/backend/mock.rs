@@ -1,8 +1,60 @@
-//! Useful for testing and benchmarking.
+/*!
+Useful for testing and benchmarking.
 
-use std::sync::Mutex;
+# Golden file tests
 
-use super::{Backend, Renderer};
+The [`MockBackend`] can record everything it renders, which lets you
+compare the output of a mixer setup to a reference WAV file:
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings,
+	backend::mock::{MockBackend, MockBackendSettings},
+};
+
+let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+	backend_settings: MockBackendSettings {
+		sample_rate: 48_000,
+		capture_output: true,
+	},
+	..Default::default()
+})
+.unwrap();
+// set up tracks and play sounds...
+for _ in 0..100 {
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+}
+// the first time, save the output as the reference:
+// manager.backend_mut().save_captured_output_to_wav_file("reference.wav")?;
+manager
+	.backend_mut()
+	.compare_captured_output_to_wav_file("reference.wav", 0.0001)?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+
+#[cfg(test)]
+mod test;
+
+mod output_comparison;
+
+pub use output_comparison::*;
+
+use std::{
+	fs::File,
+	io::{self, BufReader, BufWriter, Read, Seek, Write},
+	path::Path,
+	sync::Mutex,
+};
+
+use super::{
+	wav::{read_wav, WavWriter},
+	Backend, Renderer,
+};
+
+/// The number of channels the [`MockBackend`] renders.
+const NUM_CHANNELS: u16 = 2;
 
 enum State {
 	Uninitialized,
@@ -14,11 +66,19 @@ enum State {
 pub struct MockBackendSettings {
 	/// The sample rate that the [`Renderer`] should run at.
 	pub sample_rate: u32,
+	/// Whether the backend should record all of the audio it renders.
+	///
+	/// The recorded audio can be accessed with
+	/// [`MockBackend::captured_output`].
+	pub capture_output: bool,
 }
 
 impl Default for MockBackendSettings {
 	fn default() -> Self {
-		Self { sample_rate: 1 }
+		Self {
+			sample_rate: 1,
+			capture_output: false,
+		}
 	}
 }
 
@@ -31,6 +91,8 @@ pub struct MockBackend {
 	sample_rate: u32,
 	state: State,
 	frames: Vec<f32>,
+	capture_output: bool,
+	captured_output: Vec<f32>,
 }
 
 impl MockBackend {
@@ -64,11 +126,104 @@ impl MockBackend {
 			renderer
 				.get_mut()
 				.expect("mutex poisoned")
-				.process(&mut self.frames, 2)
+				.process(&mut self.frames, NUM_CHANNELS);
+			if self.capture_output {
+				self.captured_output.extend_from_slice(&self.frames);
+			}
 		} else {
 			panic!("backend is not initialized")
 		}
 	}
+
+	/// Returns the interleaved stereo samples rendered by the last
+	/// call to [`process`](Self::process).
+	#[must_use]
+	pub fn output(&self) -> &[f32] {
+		&self.frames
+	}
+
+	/// Returns the interleaved stereo samples rendered by every call to
+	/// [`process`](Self::process) since the output was last cleared.
+	///
+	/// This is empty unless [`MockBackendSettings::capture_output`]
+	/// is `true`.
+	#[must_use]
+	pub fn captured_output(&self) -> &[f32] {
+		&self.captured_output
+	}
+
+	/// Removes all of the recorded audio.
+	pub fn clear_captured_output(&mut self) {
+		self.captured_output.clear();
+	}
+
+	/// Writes the recorded audio to the writer as a 32-bit float WAV file.
+	pub fn save_captured_output_to_wav(&self, writer: impl Write + Seek) -> io::Result<()> {
+		let mut wav_writer = WavWriter::new(writer, NUM_CHANNELS, self.sample_rate)?;
+		wav_writer.write_samples(&self.captured_output)?;
+		wav_writer.finish()?;
+		Ok(())
+	}
+
+	/// Saves the recorded audio to a 32-bit float WAV file at the given path.
+	pub fn save_captured_output_to_wav_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+		self.save_captured_output_to_wav(BufWriter::new(File::create(path)?))
+	}
+
+	/// Compares the recorded audio to the audio in a reference WAV file.
+	///
+	/// Samples are considered equal if they differ by no more than
+	/// `tolerance`. If any samples are not equal, the returned
+	/// [`OutputMismatch`] describes where the audio diverges. If the
+	/// reference audio has a different length, the samples both have
+	/// are compared first, and a [`CompareOutputError::LengthMismatch`]
+	/// is only returned if they're all equal.
+	pub fn compare_captured_output_to_wav(
+		&self,
+		reader: impl Read,
+		tolerance: f32,
+	) -> Result<(), CompareOutputError> {
+		let reference = read_wav(reader)?;
+		if reference.sample_rate != self.sample_rate || reference.num_channels != NUM_CHANNELS {
+			return Err(CompareOutputError::FormatMismatch {
+				expected_sample_rate: reference.sample_rate,
+				expected_num_channels: reference.num_channels,
+				actual_sample_rate: self.sample_rate,
+				actual_num_channels: NUM_CHANNELS,
+			});
+		}
+		// the first sample that differs is more useful to know
+		// than the lengths, so the samples both have are compared first
+		if let Some(mismatch) = compare_samples(
+			&reference.samples,
+			&self.captured_output,
+			NUM_CHANNELS,
+			tolerance,
+		) {
+			return Err(CompareOutputError::OutputMismatch(mismatch));
+		}
+		if reference.samples.len() != self.captured_output.len() {
+			return Err(CompareOutputError::LengthMismatch {
+				expected_num_samples: reference.samples.len(),
+				actual_num_samples: self.captured_output.len(),
+				first_sample_index: reference.samples.len().min(self.captured_output.len()),
+			});
+		}
+		Ok(())
+	}
+
+	/// Compares the recorded audio to the audio in the reference WAV
+	/// file at the given path.
+	///
+	/// See [`compare_captured_output_to_wav`](Self::compare_captured_output_to_wav)
+	/// for more details.
+	pub fn compare_captured_output_to_wav_file(
+		&self,
+		path: impl AsRef<Path>,
+		tolerance: f32,
+	) -> Result<(), CompareOutputError> {
+		self.compare_captured_output_to_wav(BufReader::new(File::open(path)?), tolerance)
+	}
 }
 
 impl Backend for MockBackend {
@@ -84,7 +239,9 @@ impl Backend for MockBackend {
 			Self {
 				sample_rate: settings.sample_rate,
 				state: State::Uninitialized,
-				frames: vec![0.0; internal_buffer_size * 2],
+				frames: vec![0.0; internal_buffer_size * NUM_CHANNELS as usize],
+				capture_output: settings.capture_output,
+				captured_output: vec![],
 			},
 			settings.sample_rate,
 		))
@@ -0,0 +1,157 @@
+use std::{
+	error::Error,
+	fmt::{Display, Formatter},
+	io,
+};
+
+/// Describes where captured output differs from reference audio.
+///
+/// Sample indices count interleaved samples, so for stereo output,
+/// the frame index is the sample index divided by 2, and the channel
+/// is the sample index modulo 2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputMismatch {
+	/// The index of the first sample that differs from the reference
+	/// by more than the tolerance.
+	pub first_sample_index: usize,
+	/// The index of the sample that differs the most from the reference.
+	pub peak_error_sample_index: usize,
+	/// The largest difference between a captured sample and the
+	/// corresponding reference sample.
+	pub peak_error: f32,
+	/// The number of samples that differ from the reference by more
+	/// than the tolerance.
+	pub num_mismatched_samples: usize,
+	/// The number of channels in the compared audio.
+	pub num_channels: u16,
+}
+
+impl Display for OutputMismatch {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let num_channels = self.num_channels.max(1) as usize;
+		write!(
+			f,
+			"{} samples differ from the reference, starting at sample {} (frame {}, channel {}). The peak error is {} at sample {} (frame {}, channel {})",
+			self.num_mismatched_samples,
+			self.first_sample_index,
+			self.first_sample_index / num_channels,
+			self.first_sample_index % num_channels,
+			self.peak_error,
+			self.peak_error_sample_index,
+			self.peak_error_sample_index / num_channels,
+			self.peak_error_sample_index % num_channels,
+		)
+	}
+}
+
+/// Errors that can occur when comparing the output captured by a
+/// [`MockBackend`](super::MockBackend) to a reference WAV file.
+#[derive(Debug)]
+pub enum CompareOutputError {
+	/// The reference file could not be read.
+	IoError(io::Error),
+	/// The reference audio has a different sample rate or number of channels
+	/// than the captured output.
+	FormatMismatch {
+		/// The sample rate of the reference audio.
+		expected_sample_rate: u32,
+		/// The number of channels of the reference audio.
+		expected_num_channels: u16,
+		/// The sample rate of the captured output.
+		actual_sample_rate: u32,
+		/// The number of channels of the captured output.
+		actual_num_channels: u16,
+	},
+	/// The reference audio has a different number of samples than
+	/// the captured output, but the samples they both have are within
+	/// the tolerance.
+	LengthMismatch {
+		/// The number of samples in the reference audio.
+		expected_num_samples: usize,
+		/// The number of samples in the captured output.
+		actual_num_samples: usize,
+		/// The index of the first sample that's only in one of them.
+		first_sample_index: usize,
+	},
+	/// Some samples differ from the reference audio by more than the tolerance.
+	OutputMismatch(OutputMismatch),
+}
+
+impl Display for CompareOutputError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CompareOutputError::IoError(error) => error.fmt(f),
+			CompareOutputError::FormatMismatch {
+				expected_sample_rate,
+				expected_num_channels,
+				actual_sample_rate,
+				actual_num_channels,
+			} => write!(
+				f,
+				"Expected {} channels at {}Hz, but the captured output has {} channels at {}Hz",
+				expected_num_channels,
+				expected_sample_rate,
+				actual_num_channels,
+				actual_sample_rate
+			),
+			CompareOutputError::LengthMismatch {
+				expected_num_samples,
+				actual_num_samples,
+				first_sample_index,
+			} => write!(
+				f,
+				"Expected {} samples, but the captured output has {} samples. The samples match until sample {}",
+				expected_num_samples, actual_num_samples, first_sample_index
+			),
+			CompareOutputError::OutputMismatch(mismatch) => mismatch.fmt(f),
+		}
+	}
+}
+
+impl Error for CompareOutputError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			CompareOutputError::IoError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for CompareOutputError {
+	fn from(v: io::Error) -> Self {
+		Self::IoError(v)
+	}
+}
+
+/// Returns where `actual` differs from `expected` by more than `tolerance`,
+/// or `None` if every sample is within the tolerance.
+#[must_use]
+pub(super) fn compare_samples(
+	expected: &[f32],
+	actual: &[f32],
+	num_channels: u16,
+	tolerance: f32,
+) -> Option<OutputMismatch> {
+	let mut mismatch: Option<OutputMismatch> = None;
+	for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+		let error = (expected - actual).abs();
+		// NaN errors count as mismatches
+		if error <= tolerance {
+			continue;
+		}
+		let error = if error.is_nan() { f32::INFINITY } else { error };
+		let mismatch = mismatch.get_or_insert(OutputMismatch {
+			first_sample_index: i,
+			peak_error_sample_index: i,
+			peak_error: error,
+			num_mismatched_samples: 0,
+			num_channels,
+		});
+		mismatch.num_mismatched_samples += 1;
+		if error > mismatch.peak_error {
+			mismatch.peak_error = error;
+			mismatch.peak_error_sample_index = i;
+		}
+	}
+	mismatch
+}
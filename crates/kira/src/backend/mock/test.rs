@@ -0,0 +1,164 @@
+use std::io::Cursor;
+
+use crate::{
+	backend::wav::WavWriter,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	AudioManager, AudioManagerSettings, Frame,
+};
+
+use super::{CompareOutputError, MockBackend, MockBackendSettings};
+
+fn create_manager(capture_output: bool) -> AudioManager<MockBackend> {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 4,
+		backend_settings: MockBackendSettings {
+			sample_rate: 100,
+			capture_output,
+		},
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: (0..100)
+				.map(|i| Frame::from_mono(i as f32 / 100.0))
+				.collect::<Vec<_>>()
+				.into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	manager
+}
+
+fn process(manager: &mut AudioManager<MockBackend>, num_times: usize) {
+	for _ in 0..num_times {
+		manager.backend_mut().on_start_processing();
+		manager.backend_mut().process();
+	}
+}
+
+fn reference_wav(sample_rate: u32, num_channels: u16, samples: &[f32]) -> Vec<u8> {
+	let mut wav_writer = WavWriter::new(Cursor::new(vec![]), num_channels, sample_rate).unwrap();
+	wav_writer.write_samples(samples).unwrap();
+	wav_writer.finish().unwrap().into_inner()
+}
+
+/// Tests that output is only recorded when capturing is enabled.
+#[test]
+fn captures_output_only_when_enabled() {
+	let mut manager = create_manager(false);
+	process(&mut manager, 3);
+	assert!(manager.backend_mut().captured_output().is_empty());
+	assert_eq!(manager.backend_mut().output().len(), 8);
+	assert!(manager
+		.backend_mut()
+		.output()
+		.iter()
+		.any(|sample| *sample != 0.0));
+}
+
+/// Tests that the output of every call to `process` is recorded in order.
+#[test]
+fn captures_output_across_buffers() {
+	let mut manager = create_manager(true);
+	let mut expected = vec![];
+	for _ in 0..5 {
+		process(&mut manager, 1);
+		expected.extend_from_slice(manager.backend_mut().output());
+	}
+	assert_eq!(manager.backend_mut().captured_output(), expected);
+	assert_eq!(expected.len(), 40);
+	manager.backend_mut().clear_captured_output();
+	assert!(manager.backend_mut().captured_output().is_empty());
+}
+
+/// Tests that saved output matches the output it was saved from.
+#[test]
+fn saved_output_matches() {
+	let mut manager = create_manager(true);
+	process(&mut manager, 10);
+	let mut wav = Cursor::new(vec![]);
+	manager
+		.backend_mut()
+		.save_captured_output_to_wav(&mut wav)
+		.unwrap();
+	manager
+		.backend_mut()
+		.compare_captured_output_to_wav(wav.into_inner().as_slice(), 0.0)
+		.unwrap();
+}
+
+/// Tests that the location and size of differences from the reference
+/// audio are reported, and that differences within the tolerance
+/// are ignored.
+#[test]
+fn reports_mismatches() {
+	let mut manager = create_manager(true);
+	process(&mut manager, 10);
+	let mut samples = manager.backend_mut().captured_output().to_vec();
+	samples[13] += 0.001;
+	samples[21] -= 0.5;
+	samples[30] += 0.25;
+	let reference = reference_wav(100, 2, &samples);
+	let Err(CompareOutputError::OutputMismatch(mismatch)) = manager
+		.backend_mut()
+		.compare_captured_output_to_wav(reference.as_slice(), 0.01)
+	else {
+		panic!("expected the output to mismatch");
+	};
+	assert_eq!(mismatch.first_sample_index, 21);
+	assert_eq!(mismatch.peak_error_sample_index, 21);
+	assert!((mismatch.peak_error - 0.5).abs() < 0.0001);
+	assert_eq!(mismatch.num_mismatched_samples, 2);
+	assert_eq!(mismatch.num_channels, 2);
+}
+
+/// Tests that reference audio with a different format or length is rejected.
+#[test]
+fn reports_format_and_length_mismatches() {
+	let mut manager = create_manager(true);
+	process(&mut manager, 2);
+	let samples = manager.backend_mut().captured_output().to_vec();
+	assert!(matches!(
+		manager
+			.backend_mut()
+			.compare_captured_output_to_wav(reference_wav(48_000, 2, &samples).as_slice(), 0.0),
+		Err(CompareOutputError::FormatMismatch {
+			expected_sample_rate: 48_000,
+			actual_sample_rate: 100,
+			..
+		})
+	));
+	assert!(matches!(
+		manager
+			.backend_mut()
+			.compare_captured_output_to_wav(reference_wav(100, 2, &samples[..8]).as_slice(), 0.0),
+		Err(CompareOutputError::LengthMismatch {
+			expected_num_samples: 8,
+			actual_num_samples: 16,
+			first_sample_index: 8,
+		})
+	));
+}
+
+/// Tests that reference audio with a different length is still
+/// compared sample by sample, so the first sample that differs
+/// is reported instead of the lengths.
+#[test]
+fn reports_mismatches_before_length_mismatches() {
+	let mut manager = create_manager(true);
+	process(&mut manager, 2);
+	let mut samples = manager.backend_mut().captured_output()[..8].to_vec();
+	samples[5] += 0.5;
+	let Err(CompareOutputError::OutputMismatch(mismatch)) = manager
+		.backend_mut()
+		.compare_captured_output_to_wav(reference_wav(100, 2, &samples).as_slice(), 0.01)
+	else {
+		panic!("expected the output to mismatch");
+	};
+	assert_eq!(mismatch.first_sample_index, 5);
+	assert_eq!(mismatch.num_mismatched_samples, 1);
+}
@@ -0,0 +1,224 @@
+//! Renders audio faster than realtime, useful for exporting audio to files.
+//!
+//! The [`OfflineBackend`] doesn't connect to any audio device. Instead,
+//! audio is only produced when you ask for it, and it's produced as fast
+//! as the CPU allows.
+//!
+//! ```no_run
+//! # extern crate kira;
+//! #
+//! use std::time::Duration;
+//!
+//! use kira::{
+//! 	AudioManager, AudioManagerSettings,
+//! 	backend::offline::{OfflineBackend, RenderLength},
+//! 	sound::static_sound::StaticSoundData,
+//! };
+//!
+//! let mut manager = AudioManager::<OfflineBackend>::new(AudioManagerSettings::default())?;
+//! manager.play(StaticSoundData::from_file("sound.ogg")?)?;
+//! manager.backend_mut().render_to_wav_file(
+//! 	RenderLength::UntilSoundsFinish {
+//! 		max_duration: Duration::from_secs(60),
+//! 	},
+//! 	"bounce.wav",
+//! )?;
+//! # Result::<(), Box<dyn std::error::Error>>::Ok(())
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use std::{
+	convert::Infallible,
+	fs::File,
+	io::{self, BufWriter, Seek, Write},
+	path::Path,
+	time::Duration,
+};
+
+use super::{wav::WavWriter, Backend, Renderer};
+
+/// Settings for the offline backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OfflineBackendSettings {
+	/// The sample rate that the [`Renderer`] should run at.
+	pub sample_rate: u32,
+	/// The number of channels of audio to output.
+	pub num_channels: u16,
+}
+
+impl Default for OfflineBackendSettings {
+	fn default() -> Self {
+		Self {
+			sample_rate: 48_000,
+			num_channels: 2,
+		}
+	}
+}
+
+/// How much audio an [`OfflineBackend`] should render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderLength {
+	/// Render a fixed amount of audio.
+	Duration(Duration),
+	/// Render until no sounds are playing on any track.
+	///
+	/// Sounds waiting to start (for example, sounds with a delayed
+	/// [`StartTime`](crate::StartTime)) count as playing.
+	UntilSoundsFinish {
+		/// The maximum amount of audio to render, in case a sound
+		/// never finishes (for example, a looping sound).
+		max_duration: Duration,
+	},
+}
+
+impl RenderLength {
+	#[must_use]
+	fn max_num_frames(self, sample_rate: u32) -> usize {
+		let duration = match self {
+			RenderLength::Duration(duration) => duration,
+			RenderLength::UntilSoundsFinish { max_duration } => max_duration,
+		};
+		(duration.as_secs_f64() * sample_rate as f64).round() as usize
+	}
+}
+
+/// A backend that renders audio on demand instead of
+/// sending it to an audio device.
+///
+/// Audio is rendered in chunks of the
+/// [internal buffer size](crate::AudioManagerSettings::internal_buffer_size),
+/// so the results are the same as they would be when playing audio in
+/// realtime with the same settings. Each render call continues where the
+/// last one left off.
+pub struct OfflineBackend {
+	sample_rate: u32,
+	num_channels: u16,
+	renderer: Option<Renderer>,
+	buffer: Vec<f32>,
+}
+
+impl OfflineBackend {
+	/// Returns the sample rate audio is rendered at.
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Returns the number of channels of audio that are rendered.
+	#[must_use]
+	pub fn num_channels(&self) -> u16 {
+		self.num_channels
+	}
+
+	/// Renders audio and passes each chunk of interleaved samples
+	/// to the `write` callback.
+	///
+	/// Returns the number of frames that were rendered.
+	pub fn render<E>(
+		&mut self,
+		length: RenderLength,
+		mut write: impl FnMut(&[f32]) -> Result<(), E>,
+	) -> Result<usize, E> {
+		let renderer = self.renderer.as_mut().expect("backend is not initialized");
+		let num_channels = self.num_channels as usize;
+		let max_num_frames = length.max_num_frames(self.sample_rate);
+		let mut num_frames_rendered = 0;
+		while num_frames_rendered < max_num_frames {
+			renderer.on_start_processing();
+			if matches!(length, RenderLength::UntilSoundsFinish { .. }) && !renderer.has_sounds() {
+				break;
+			}
+			let num_frames =
+				(max_num_frames - num_frames_rendered).min(self.buffer.len() / num_channels);
+			let chunk = &mut self.buffer[..num_frames * num_channels];
+			renderer.process(chunk, self.num_channels);
+			write(chunk)?;
+			num_frames_rendered += num_frames;
+		}
+		Ok(num_frames_rendered)
+	}
+
+	/// Renders audio and returns the interleaved samples.
+	#[must_use]
+	pub fn render_to_vec(&mut self, length: RenderLength) -> Vec<f32> {
+		let mut samples = vec![];
+		self.render(length, |chunk| {
+			samples.extend_from_slice(chunk);
+			Result::<(), Infallible>::Ok(())
+		})
+		.ok();
+		samples
+	}
+
+	/// Renders audio and writes the interleaved samples to the writer
+	/// as raw 32-bit little-endian floats.
+	///
+	/// Returns the number of frames that were rendered.
+	pub fn render_to_writer(
+		&mut self,
+		length: RenderLength,
+		mut writer: impl Write,
+	) -> io::Result<usize> {
+		let num_frames = self.render(length, |chunk| {
+			for sample in chunk {
+				writer.write_all(&sample.to_le_bytes())?;
+			}
+			io::Result::Ok(())
+		})?;
+		writer.flush()?;
+		Ok(num_frames)
+	}
+
+	/// Renders audio and writes it to the writer as a 32-bit float WAV file.
+	///
+	/// Returns the number of frames that were rendered.
+	pub fn render_to_wav(
+		&mut self,
+		length: RenderLength,
+		writer: impl Write + Seek,
+	) -> io::Result<usize> {
+		let mut wav_writer = WavWriter::new(writer, self.num_channels, self.sample_rate)?;
+		let num_frames = self.render(length, |chunk| wav_writer.write_samples(chunk))?;
+		wav_writer.finish()?;
+		Ok(num_frames)
+	}
+
+	/// Renders audio and saves it to a 32-bit float WAV file at the given path.
+	///
+	/// Returns the number of frames that were rendered.
+	pub fn render_to_wav_file(
+		&mut self,
+		length: RenderLength,
+		path: impl AsRef<Path>,
+	) -> io::Result<usize> {
+		self.render_to_wav(length, BufWriter::new(File::create(path)?))
+	}
+}
+
+impl Backend for OfflineBackend {
+	type Settings = OfflineBackendSettings;
+
+	type Error = Infallible;
+
+	fn setup(
+		settings: Self::Settings,
+		internal_buffer_size: usize,
+	) -> Result<(Self, u32), Self::Error> {
+		Ok((
+			Self {
+				sample_rate: settings.sample_rate,
+				num_channels: settings.num_channels,
+				renderer: None,
+				buffer: vec![0.0; internal_buffer_size * settings.num_channels as usize],
+			},
+			settings.sample_rate,
+		))
+	}
+
+	fn start(&mut self, renderer: Renderer) -> Result<(), Self::Error> {
+		self.renderer = Some(renderer);
+		Ok(())
+	}
+}
@@ -0,0 +1,128 @@
+use std::{io::Cursor, time::Duration};
+
+use crate::{
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	AudioManager, AudioManagerSettings, Frame, StartTime,
+};
+
+use super::{OfflineBackend, OfflineBackendSettings, RenderLength};
+
+const SAMPLE_RATE: u32 = 100;
+
+fn create_manager() -> AudioManager<OfflineBackend> {
+	AudioManager::new(AudioManagerSettings {
+		internal_buffer_size: 8,
+		backend_settings: OfflineBackendSettings {
+			sample_rate: SAMPLE_RATE,
+			num_channels: 2,
+		},
+		..Default::default()
+	})
+	.unwrap()
+}
+
+fn sound_data(num_frames: usize) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: SAMPLE_RATE,
+		frames: vec![Frame::from_mono(0.5); num_frames].into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// Tests that the offline backend renders exactly the requested
+/// amount of audio, even when that isn't a multiple of the
+/// internal buffer size.
+#[test]
+fn renders_fixed_duration() {
+	let mut manager = create_manager();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::Duration(Duration::from_millis(1010)));
+	assert_eq!(samples.len(), 101 * 2);
+	assert!(samples.iter().all(|sample| *sample == 0.0));
+}
+
+/// Tests that the offline backend stops rendering once all sounds
+/// have finished.
+#[test]
+fn renders_until_sounds_finish() {
+	let mut manager = create_manager();
+	manager.play(sound_data(20)).unwrap();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::UntilSoundsFinish {
+			max_duration: Duration::from_secs(10),
+		});
+	let num_frames = samples.len() / 2;
+	assert!(num_frames >= 20);
+	assert!(num_frames < SAMPLE_RATE as usize);
+	assert!(samples.iter().any(|sample| *sample != 0.0));
+
+	// with no sounds playing, nothing should be rendered
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::UntilSoundsFinish {
+			max_duration: Duration::from_secs(10),
+		});
+	assert!(samples.is_empty());
+}
+
+/// Tests that rendering stops at the maximum duration if sounds
+/// don't finish.
+#[test]
+fn respects_max_duration() {
+	let mut manager = create_manager();
+	manager.play(sound_data(20).loop_region(..)).unwrap();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::UntilSoundsFinish {
+			max_duration: Duration::from_secs(1),
+		});
+	assert_eq!(samples.len(), SAMPLE_RATE as usize * 2);
+}
+
+/// Tests that delayed start times are respected the same way
+/// they are when rendering in realtime.
+#[test]
+fn respects_delayed_start_time() {
+	let mut manager = create_manager();
+	manager
+		.play(sound_data(20).start_time(StartTime::Delayed(Duration::from_millis(480))))
+		.unwrap();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::UntilSoundsFinish {
+			max_duration: Duration::from_secs(10),
+		});
+	let first_audible_frame = samples.chunks(2).position(|frame| frame[0] != 0.0).unwrap();
+	// delayed start times are checked once per internal buffer, so the
+	// sound starts at the beginning of the buffer where the delay elapses
+	assert_eq!(first_audible_frame, 40);
+}
+
+/// Tests that the WAV header describes the rendered audio.
+#[test]
+fn writes_wav_header() {
+	let mut manager = create_manager();
+	let mut cursor = Cursor::new(vec![]);
+	let num_frames = manager
+		.backend_mut()
+		.render_to_wav(RenderLength::Duration(Duration::from_secs(1)), &mut cursor)
+		.unwrap();
+	assert_eq!(num_frames, 100);
+	let bytes = cursor.into_inner();
+	let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+	let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+	assert_eq!(&bytes[0..4], b"RIFF");
+	assert_eq!(u32_at(4) as usize, bytes.len() - 8);
+	assert_eq!(&bytes[8..12], b"WAVE");
+	assert_eq!(u16_at(20), 3);
+	assert_eq!(u16_at(22), 2);
+	assert_eq!(u32_at(24), SAMPLE_RATE);
+	assert_eq!(u32_at(46), 100);
+	assert_eq!(&bytes[50..54], b"data");
+	assert_eq!(u32_at(54), 100 * 2 * 4);
+	assert_eq!(bytes.len(), 58 + 100 * 2 * 4);
+}
@@ -0,0 +1,209 @@
+//! Lets you request audio from Kira in an audio callback you own.
+//!
+//! This is useful if you're embedding Kira in another audio engine or
+//! framework that already has its own audio output.
+//!
+//! ```no_run
+//! # extern crate kira;
+//! #
+//! use kira::{
+//! 	AudioManager, AudioManagerSettings,
+//! 	backend::pull::PullBackend,
+//! };
+//!
+//! let mut manager = AudioManager::<PullBackend>::new(AudioManagerSettings::default())?;
+//! let mut renderer = manager.backend_mut().take_renderer().unwrap();
+//! // move the renderer to the audio thread, and in the audio callback:
+//! # let mut buffer = vec![0.0; 1024];
+//! renderer.fill_f32(&mut buffer, 2, 48_000);
+//! # Result::<(), Box<dyn std::error::Error>>::Ok(())
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use std::{convert::Infallible, time::Duration};
+
+use crate::channel_layout::MAX_CHANNELS;
+
+use super::{Backend, Renderer};
+
+/// Settings for the pull backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PullBackendSettings {
+	/// The sample rate that the [`Renderer`] should start at.
+	///
+	/// If audio is requested at a different sample rate, the
+	/// [`Renderer`] will switch to that sample rate.
+	pub sample_rate: u32,
+}
+
+impl Default for PullBackendSettings {
+	fn default() -> Self {
+		Self {
+			sample_rate: 48_000,
+		}
+	}
+}
+
+/// A backend that doesn't output audio on its own, but instead gives you a
+/// [`PullRenderer`] you can request audio from.
+pub struct PullBackend {
+	sample_rate: u32,
+	internal_buffer_size: usize,
+	renderer: Option<PullRenderer>,
+}
+
+impl PullBackend {
+	/// Returns the [`PullRenderer`] used to request audio.
+	///
+	/// This returns [`None`] if the renderer has already been taken.
+	#[must_use]
+	pub fn take_renderer(&mut self) -> Option<PullRenderer> {
+		self.renderer.take()
+	}
+}
+
+impl Backend for PullBackend {
+	type Settings = PullBackendSettings;
+
+	type Error = Infallible;
+
+	fn setup(
+		settings: Self::Settings,
+		internal_buffer_size: usize,
+	) -> Result<(Self, u32), Self::Error> {
+		Ok((
+			Self {
+				sample_rate: settings.sample_rate,
+				internal_buffer_size,
+				renderer: None,
+			},
+			settings.sample_rate,
+		))
+	}
+
+	fn start(&mut self, renderer: Renderer) -> Result<(), Self::Error> {
+		// allocate enough space for any channel layout up front so the
+		// buffer doesn't have to be reallocated on the audio thread
+		let mut buffer = Vec::with_capacity(self.internal_buffer_size * MAX_CHANNELS);
+		buffer.resize(self.internal_buffer_size * 2, 0.0);
+		self.renderer = Some(PullRenderer {
+			renderer,
+			sample_rate: self.sample_rate,
+			num_channels: 2,
+			buffer_position: buffer.len(),
+			buffer,
+			internal_buffer_size: self.internal_buffer_size,
+		});
+		Ok(())
+	}
+}
+
+/// Produces audio whenever it's requested.
+///
+/// Audio is always rendered in chunks of
+/// [`internal_buffer_size`](crate::AudioManagerSettings::internal_buffer_size)
+/// frames, so clocks and modulators update at the same rate no matter how
+/// much audio is requested at a time. Rendered frames that don't fit in
+/// the requested buffer are saved for the next request.
+pub struct PullRenderer {
+	renderer: Renderer,
+	sample_rate: u32,
+	num_channels: u16,
+	/// A chunk of rendered audio.
+	buffer: Vec<f32>,
+	/// The position of the first sample in `buffer` that hasn't
+	/// been output yet.
+	buffer_position: usize,
+	internal_buffer_size: usize,
+}
+
+impl PullRenderer {
+	/// Returns the sample rate the renderer is currently running at.
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Tells the renderer how long it will take for the next audio it
+	/// renders to be heard.
+	///
+	/// Call this before filling a buffer if your audio API reports
+	/// its latency. This is used to find when clock times and sound
+	/// positions will be heard.
+	pub fn set_output_latency(&mut self, latency: Duration) {
+		// audio that was already rendered will be heard first
+		let buffered_frames =
+			(self.buffer.len() - self.buffer_position) / self.num_channels as usize;
+		self.renderer.set_output_latency(
+			latency + Duration::from_secs_f64(buffered_frames as f64 / self.sample_rate as f64),
+		);
+	}
+
+	/// Fills `out` with interleaved samples for the given number of
+	/// channels at the given sample rate.
+	///
+	/// `out` can be any length, but its length should be a multiple of
+	/// `num_channels`.
+	pub fn fill_f32(&mut self, out: &mut [f32], num_channels: u16, sample_rate: u32) {
+		self.fill(out, num_channels, sample_rate, |sample| sample);
+	}
+
+	/// Fills `out` with interleaved 16-bit samples for the given number
+	/// of channels at the given sample rate.
+	///
+	/// `out` can be any length, but its length should be a multiple of
+	/// `num_channels`.
+	pub fn fill_i16(&mut self, out: &mut [i16], num_channels: u16, sample_rate: u32) {
+		self.fill(out, num_channels, sample_rate, |sample| {
+			(sample * i16::MAX as f32).round() as i16
+		});
+	}
+
+	fn fill<T>(
+		&mut self,
+		out: &mut [T],
+		num_channels: u16,
+		sample_rate: u32,
+		convert: impl Fn(f32) -> T,
+	) {
+		if num_channels == 0 {
+			return;
+		}
+		if sample_rate != self.sample_rate {
+			// audio that was rendered at a different sample rate
+			// would play at the wrong speed
+			self.sample_rate = sample_rate;
+			self.renderer.on_change_sample_rate(sample_rate);
+			self.buffer_position = self.buffer.len();
+		}
+		if num_channels != self.num_channels {
+			// audio that was rendered for a different number of
+			// channels can't be used anymore. this only allocates
+			// if there's more than `MAX_CHANNELS` channels
+			self.num_channels = num_channels;
+			self.buffer.clear();
+			self.buffer
+				.resize(self.internal_buffer_size * num_channels as usize, 0.0);
+			self.buffer_position = self.buffer.len();
+		}
+		let mut position = 0;
+		while position < out.len() {
+			if self.buffer_position >= self.buffer.len() {
+				self.renderer.on_start_processing();
+				self.renderer.process(&mut self.buffer, num_channels);
+				self.buffer_position = 0;
+			}
+			let num_samples = (out.len() - position).min(self.buffer.len() - self.buffer_position);
+			for (out, sample) in out[position..position + num_samples]
+				.iter_mut()
+				.zip(&self.buffer[self.buffer_position..])
+			{
+				*out = convert(*sample);
+			}
+			position += num_samples;
+			self.buffer_position += num_samples;
+		}
+	}
+}
@@ -0,0 +1,130 @@
+use crate::{
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	AudioManager, AudioManagerSettings, Frame,
+};
+
+use super::{PullBackend, PullBackendSettings, PullRenderer};
+
+fn renderer_playing_ramp() -> (AudioManager<PullBackend>, PullRenderer) {
+	let mut manager = AudioManager::<PullBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 8,
+		backend_settings: PullBackendSettings { sample_rate: 100 },
+		..Default::default()
+	})
+	.unwrap();
+	let renderer = manager.backend_mut().take_renderer().unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: (0..100)
+				.map(|i| Frame::from_mono(i as f32 / 100.0))
+				.collect::<Vec<_>>()
+				.into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	(manager, renderer)
+}
+
+/// Tests that the renderer can only be taken once.
+#[test]
+fn renderer_can_only_be_taken_once() {
+	let mut manager = AudioManager::<PullBackend>::new(AudioManagerSettings::default()).unwrap();
+	assert!(manager.backend_mut().take_renderer().is_some());
+	assert!(manager.backend_mut().take_renderer().is_none());
+}
+
+/// Tests that the output doesn't depend on how much audio
+/// is requested at a time.
+#[test]
+fn output_does_not_depend_on_request_size() {
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut expected = vec![0.0; 240];
+	renderer.fill_f32(&mut expected, 2, 100);
+
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut actual = vec![0.0; 240];
+	let mut position = 0;
+	for request_size in [6, 14, 2, 30, 18, 50, 120] {
+		renderer.fill_f32(&mut actual[position..position + request_size], 2, 100);
+		position += request_size;
+	}
+	assert_eq!(position, actual.len());
+	assert_eq!(actual, expected);
+	assert!(expected.iter().any(|sample| *sample != 0.0));
+}
+
+/// Tests that 16-bit output is scaled to the full range of `i16`.
+#[test]
+fn fills_i16() {
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut expected = vec![0.0; 200];
+	renderer.fill_f32(&mut expected, 2, 100);
+
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut actual = vec![0; 200];
+	renderer.fill_i16(&mut actual, 2, 100);
+	for (actual, expected) in actual.iter().zip(expected) {
+		assert_eq!(*actual, (expected * 32767.0).round() as i16);
+	}
+}
+
+/// Tests that the renderer switches to the requested sample rate.
+#[test]
+fn changes_sample_rate() {
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	assert_eq!(renderer.sample_rate(), 100);
+	// at double the sample rate, the sound should last twice
+	// as many frames
+	let mut out = vec![0.0; 440];
+	renderer.fill_f32(&mut out, 2, 200);
+	assert_eq!(renderer.sample_rate(), 200);
+	let last_audible_frame = out.chunks(2).rposition(|frame| frame[0] != 0.0).unwrap();
+	assert!(last_audible_frame > 190);
+}
+
+/// Tests that audio rendered at the previous sample rate is
+/// discarded when the sample rate changes.
+#[test]
+fn discards_buffered_audio_when_sample_rate_changes() {
+	// render a full chunk at the old sample rate
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut out = vec![0.0; 16];
+	renderer.fill_f32(&mut out, 2, 100);
+	let mut expected = vec![0.0; 40];
+	renderer.fill_f32(&mut expected, 2, 200);
+
+	// render a full chunk at the old sample rate, but only
+	// output part of it
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut out = vec![0.0; 4];
+	renderer.fill_f32(&mut out, 2, 100);
+	let mut actual = vec![0.0; 40];
+	renderer.fill_f32(&mut actual, 2, 200);
+
+	assert_eq!(actual, expected);
+}
+
+/// Tests that the renderer outputs the requested number of channels.
+#[test]
+fn changes_num_channels() {
+	let (_manager, mut renderer) = renderer_playing_ramp();
+	let mut out = vec![0.0; 40];
+	renderer.fill_f32(&mut out, 2, 100);
+	let mut out = vec![0.0; 60];
+	renderer.fill_f32(&mut out, 3, 100);
+	for frame in out.chunks(3) {
+		assert_eq!(frame[0], frame[1]);
+		assert_eq!(frame[2], 0.0);
+	}
+	assert!(out.iter().any(|sample| *sample != 0.0));
+}
+
+/// Tests that the renderer can be sent to an audio thread.
+#[test]
+fn renderer_is_send() {
+	fn assert_send<T: Send>() {}
+	assert_send::<PullRenderer>();
+}
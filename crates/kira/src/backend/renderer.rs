@@ -1,22 +1,43 @@
-use std::sync::{
-	atomic::{AtomicU32, Ordering},
-	Arc,
+#[cfg(test)]
+mod test;
+
+mod resampler;
+
+use std::{
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
 };
 
-use crate::Frame;
+use crate::{
+	channel_layout::{MixingMatrix, MAX_CHANNELS},
+	output_stage::{OutputStage, OutputStageSettings},
+	playback_timing::PlaybackTiming,
+	ChannelLayout, Frame,
+};
 
 use super::resources::Resources;
 
+use resampler::OutputResampler;
+
 #[derive(Debug)]
 pub(crate) struct RendererShared {
 	pub(crate) sample_rate: AtomicU32,
+	pub(crate) channel_layout: ChannelLayout,
+	pub(crate) profiling: bool,
+	pub(crate) playback_timing: PlaybackTiming,
 }
 
 impl RendererShared {
 	#[must_use]
-	pub fn new(sample_rate: u32) -> Self {
+	pub fn new(sample_rate: u32, channel_layout: ChannelLayout, profiling: bool) -> Self {
 		Self {
 			sample_rate: AtomicU32::new(sample_rate),
+			channel_layout,
+			profiling,
+			playback_timing: PlaybackTiming::new(),
 		}
 	}
 }
@@ -27,11 +48,29 @@ impl RendererShared {
 /// You will probably not need to interact with [`Renderer`]s
 /// directly unless you're writing a [`Backend`](super::Backend).
 pub struct Renderer {
+	/// The time between frames rendered by the mixer.
 	dt: f64,
+	/// The time between frames sent to the backend.
+	output_dt: f64,
 	shared: Arc<RendererShared>,
 	resources: Resources,
 	internal_buffer_size: usize,
 	temp_buffer: Vec<Frame>,
+	surround_buffer: Vec<f32>,
+	/// The mixer's output in the mixer's [`ChannelLayout`].
+	layout_buffer: Vec<[f32; MAX_CHANNELS]>,
+	/// The position of the first frame in `layout_buffer` that
+	/// hasn't been resampled yet.
+	layout_buffer_position: usize,
+	output_mapping: OutputMapping,
+	output_stage: OutputStage,
+	/// The sample rate the mixer always runs at, if set.
+	internal_sample_rate: Option<u32>,
+	resampler: OutputResampler,
+	/// The number of mixer frames per output frame.
+	resample_ratio: f64,
+	/// The number of seconds of audio that have been rendered.
+	audio_time: f64,
 }
 
 impl Renderer {
@@ -40,43 +79,143 @@ impl Renderer {
 		shared: Arc<RendererShared>,
 		internal_buffer_size: usize,
 		resources: Resources,
+		output_stage_settings: OutputStageSettings,
+		internal_sample_rate: Option<u32>,
+		output_sample_rate: u32,
 	) -> Self {
+		let channel_layout = shared.channel_layout;
+		let sample_rate = shared.sample_rate.load(Ordering::SeqCst);
 		Self {
-			dt: 1.0 / shared.sample_rate.load(Ordering::SeqCst) as f64,
+			dt: 1.0 / sample_rate as f64,
+			output_dt: 1.0 / output_sample_rate as f64,
 			shared,
 			resources,
 			internal_buffer_size,
 			temp_buffer: vec![Frame::ZERO; internal_buffer_size],
+			surround_buffer: if channel_layout.is_surround() {
+				vec![0.0; internal_buffer_size * channel_layout.num_channels() as usize]
+			} else {
+				vec![]
+			},
+			layout_buffer: vec![[0.0; MAX_CHANNELS]; internal_buffer_size],
+			layout_buffer_position: internal_buffer_size,
+			output_mapping: OutputMapping::new(channel_layout, 2),
+			output_stage: OutputStage::new(output_stage_settings, output_sample_rate),
+			internal_sample_rate,
+			resampler: OutputResampler::new(),
+			resample_ratio: sample_rate as f64 / output_sample_rate as f64,
+			audio_time: 0.0,
 		}
 	}
 
 	/// Called by the backend when the sample rate of the
 	/// audio output changes.
 	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
-		self.dt = 1.0 / sample_rate as f64;
-		self.shared.sample_rate.store(sample_rate, Ordering::SeqCst);
-		self.resources.mixer.on_change_sample_rate(sample_rate);
+		self.output_dt = 1.0 / sample_rate as f64;
+		self.output_stage.on_change_sample_rate(sample_rate);
+		match self.internal_sample_rate {
+			// the mixer keeps running at the same sample rate, so
+			// only the output needs to be resampled differently
+			Some(internal_sample_rate) => {
+				self.resample_ratio = internal_sample_rate as f64 / sample_rate as f64;
+			}
+			None => {
+				self.dt = 1.0 / sample_rate as f64;
+				self.shared.sample_rate.store(sample_rate, Ordering::SeqCst);
+				self.resources.mixer.on_change_sample_rate(sample_rate);
+				self.resample_ratio = 1.0;
+			}
+		}
+	}
+
+	/// Called by the backend before [`process`](Renderer::process)
+	/// with how long it will take for the next frame of audio to be
+	/// heard, if the audio API reports it.
+	///
+	/// This is used to find when clock times and sound positions
+	/// will be heard.
+	pub fn set_output_latency(&mut self, latency: Duration) {
+		let mut internal_latency = self.output_stage.latency() as f64 * self.output_dt;
+		if self.internal_sample_rate.is_some() {
+			// rendered frames that are waiting to be resampled
+			// will be heard first
+			let buffered_frames = self.layout_buffer.len() - self.layout_buffer_position;
+			internal_latency += (buffered_frames as f64 + self.resampler.latency()) * self.dt;
+		}
+		self.shared.playback_timing.update(
+			self.audio_time,
+			latency + Duration::from_secs_f64(internal_latency),
+		);
 	}
 
 	/// Called by the backend when it's time to process
 	/// a new batch of samples.
 	pub fn on_start_processing(&mut self) {
+		let playback_nanos = self.shared.playback_timing.playback_nanos(self.audio_time);
 		self.resources.mixer.on_start_processing();
-		self.resources.clocks.on_start_processing();
+		self.resources.clocks.on_start_processing(playback_nanos);
 		self.resources.listeners.on_start_processing();
 		self.resources.modulators.on_start_processing();
 	}
 
+	/// Returns `true` if any sounds are currently playing.
+	///
+	/// Sounds that have been played since the last call to
+	/// [`on_start_processing`](Renderer::on_start_processing)
+	/// are not counted.
+	#[must_use]
+	pub(crate) fn has_sounds(&self) -> bool {
+		self.resources.mixer.has_sounds()
+	}
+
 	/// Produces the next [`Frame`]s of audio.
 	pub fn process(&mut self, out: &mut [f32], num_channels: u16) {
+		if self.output_mapping.num_channels != num_channels {
+			self.output_mapping = OutputMapping::new(self.shared.channel_layout, num_channels);
+		}
+		if self.internal_sample_rate.is_some() {
+			self.process_resampled(out, num_channels);
+			return;
+		}
 		for chunk in out.chunks_mut(self.internal_buffer_size * num_channels as usize) {
-			self.process_chunk(chunk, num_channels);
+			let num_frames = chunk.len() / num_channels as usize;
+			self.render_chunk(num_frames);
+			for (layout_frame, channels) in self.layout_buffer[..num_frames]
+				.iter()
+				.zip(chunk.chunks_mut(num_channels.into()))
+			{
+				self.output_mapping
+					.apply(layout_frame, &mut self.output_stage, channels);
+			}
 		}
 	}
 
-	fn process_chunk(&mut self, chunk: &mut [f32], num_channels: u16) {
-		let num_frames = chunk.len() / num_channels as usize;
+	/// Produces the next frames of audio when the mixer runs at the
+	/// internal sample rate.
+	///
+	/// The mixer always renders full chunks of
+	/// `internal_buffer_size` frames, and the frames that haven't been
+	/// resampled yet are kept for the next call.
+	fn process_resampled(&mut self, out: &mut [f32], num_channels: u16) {
+		for channels in out.chunks_mut(num_channels.into()) {
+			while self.resampler.needs_frame() {
+				if self.layout_buffer_position >= self.layout_buffer.len() {
+					self.render_chunk(self.layout_buffer.len());
+					self.layout_buffer_position = 0;
+				}
+				self.resampler
+					.push_frame(self.layout_buffer[self.layout_buffer_position]);
+				self.layout_buffer_position += 1;
+			}
+			let layout_frame = self.resampler.next_frame(self.resample_ratio);
+			self.output_mapping
+				.apply(&layout_frame, &mut self.output_stage, channels);
+		}
+	}
 
+	/// Renders `num_frames` frames of audio from the mixer to
+	/// `layout_buffer`.
+	fn render_chunk(&mut self, num_frames: usize) {
 		self.resources.modulators.process(
 			self.dt * num_frames as f64,
 			&self.resources.clocks,
@@ -93,34 +232,94 @@ impl Renderer {
 			&self.resources.modulators,
 		);
 
+		let channel_layout = self.shared.channel_layout;
+		let num_layout_channels = channel_layout.num_channels() as usize;
+		let surround_buffer_len = self
+			.surround_buffer
+			.len()
+			.min(num_frames * num_layout_channels);
+		self.audio_time += self.dt * num_frames as f64;
 		self.resources.mixer.process(
 			&mut self.temp_buffer[..num_frames],
+			&mut self.surround_buffer[..surround_buffer_len],
 			self.dt,
 			&self.resources.clocks,
 			&self.resources.modulators,
 			&self.resources.listeners,
+			self.shared.playback_timing.playback_nanos(self.audio_time),
 		);
 
-		// convert from frames to requested number of channels
-		for (i, channels) in chunk.chunks_mut(num_channels.into()).enumerate() {
-			let mut frame = self.temp_buffer[i];
-			frame.left = frame.left.clamp(-1.0, 1.0);
-			frame.right = frame.right.clamp(-1.0, 1.0);
-			if num_channels == 1 {
-				channels[0] = (frame.left + frame.right) / 2.0;
+		for (i, layout_frame) in self.layout_buffer[..num_frames].iter_mut().enumerate() {
+			let frame = self.temp_buffer[i];
+			*layout_frame = [0.0; MAX_CHANNELS];
+			if channel_layout == ChannelLayout::Mono {
+				layout_frame[0] = (frame.left + frame.right) / 2.0;
 			} else {
-				channels[0] = frame.left;
-				channels[1] = frame.right;
-				/*
-					if there's more channels, send silence to them. if we don't,
-					we might get bad sounds outputted to those channels.
-					(https://github.com/tesselode/kira/issues/50)
-				*/
-				for channel in channels.iter_mut().skip(2) {
-					*channel = 0.0;
+				layout_frame[0] = frame.left;
+				layout_frame[1] = frame.right;
+			}
+			if let Some(surround_frame) = self
+				.surround_buffer
+				.get(i * num_layout_channels..(i + 1) * num_layout_channels)
+			{
+				for (sample, surround_sample) in layout_frame.iter_mut().zip(surround_frame) {
+					*sample += *surround_sample;
 				}
 			}
 		}
 		self.temp_buffer.fill(Frame::ZERO);
+		self.surround_buffer.fill(0.0);
+	}
+}
+
+/// Converts audio in the mixer's [`ChannelLayout`] to the number of
+/// channels the backend asked for.
+struct OutputMapping {
+	num_channels: u16,
+	num_output_layout_channels: usize,
+	matrix: MixingMatrix,
+}
+
+impl OutputMapping {
+	#[must_use]
+	fn new(channel_layout: ChannelLayout, num_channels: u16) -> Self {
+		/*
+			if the device has an unusual number of channels, output to the
+			largest layout that fits and send silence to the other channels.
+			if we don't, we might get bad sounds outputted to those channels.
+			(https://github.com/tesselode/kira/issues/50)
+		*/
+		let output_layout = (1..=num_channels)
+			.rev()
+			.find_map(ChannelLayout::from_num_channels)
+			.unwrap_or(ChannelLayout::Mono);
+		Self {
+			num_channels,
+			num_output_layout_channels: output_layout.num_channels() as usize,
+			matrix: channel_layout.mixing_matrix(output_layout),
+		}
+	}
+
+	fn apply(
+		&self,
+		layout_frame: &[f32; MAX_CHANNELS],
+		output_stage: &mut OutputStage,
+		out: &mut [f32],
+	) {
+		let mut output_frame = [0.0; MAX_CHANNELS];
+		for (sample, gains) in output_frame[..self.num_output_layout_channels]
+			.iter_mut()
+			.zip(&self.matrix)
+		{
+			*sample = gains
+				.iter()
+				.zip(layout_frame)
+				.map(|(gain, input)| gain * input)
+				.sum();
+		}
+		output_stage.process(&mut output_frame[..self.num_output_layout_channels]);
+		for (output_channel, sample) in out.iter_mut().enumerate() {
+			*sample = output_frame.get(output_channel).copied().unwrap_or(0.0);
+		}
 	}
 }
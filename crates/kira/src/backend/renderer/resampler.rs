@@ -0,0 +1,71 @@
+use crate::channel_layout::MAX_CHANNELS;
+
+/// Converts the mixer's output from the internal sample rate
+/// to the sample rate of the audio device.
+pub(super) struct OutputResampler {
+	/// The 4 most recent frames from the mixer.
+	frames: [[f32; MAX_CHANNELS]; 4],
+	/// How far between `frames[1]` and `frames[2]` the next output
+	/// frame is.
+	///
+	/// When this is `1.0` or greater, a new frame needs to be pushed
+	/// before the next output frame can be produced.
+	fractional_position: f64,
+}
+
+impl OutputResampler {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			frames: [[0.0; MAX_CHANNELS]; 4],
+			fractional_position: 1.0,
+		}
+	}
+
+	#[must_use]
+	pub fn needs_frame(&self) -> bool {
+		self.fractional_position >= 1.0
+	}
+
+	pub fn push_frame(&mut self, frame: [f32; MAX_CHANNELS]) {
+		self.frames.copy_within(1.., 0);
+		self.frames[self.frames.len() - 1] = frame;
+		self.fractional_position -= 1.0;
+	}
+
+	/// Returns the next output frame and advances by `step` input frames.
+	#[must_use]
+	pub fn next_frame(&mut self, step: f64) -> [f32; MAX_CHANNELS] {
+		let fraction = self.fractional_position as f32;
+		let mut frame = [0.0; MAX_CHANNELS];
+		for (channel, sample) in frame.iter_mut().enumerate() {
+			*sample = interpolate_sample(
+				self.frames[0][channel],
+				self.frames[1][channel],
+				self.frames[2][channel],
+				self.frames[3][channel],
+				fraction,
+			);
+		}
+		self.fractional_position += step;
+		frame
+	}
+
+	/// Returns how many input frames behind the most recently pushed
+	/// frame the next output frame is.
+	#[must_use]
+	pub fn latency(&self) -> f64 {
+		// the output is mainly made of `frames[1]` and `frames[2]`
+		3.0 - self.fractional_position
+	}
+}
+
+/// The single-channel version of [`interpolate_frame`](crate::frame::interpolate_frame).
+#[must_use]
+fn interpolate_sample(previous: f32, current: f32, next_1: f32, next_2: f32, fraction: f32) -> f32 {
+	let c0 = current;
+	let c1 = (next_1 - previous) * 0.5;
+	let c2 = previous - current * 2.5 + next_1 * 2.0 - next_2 * 0.5;
+	let c3 = (next_2 - previous) * 0.5 + (current - next_1) * 1.5;
+	((c3 * fraction + c2) * fraction + c1) * fraction + c0
+}
@@ -0,0 +1,89 @@
+use crate::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	channel_layout::MAX_CHANNELS,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	AudioManager, AudioManagerSettings, Frame,
+};
+
+use super::resampler::OutputResampler;
+
+/// Plays a second of a constant signal at 100Hz and returns
+/// everything the backend outputs.
+fn render_constant_signal(sample_rate: u32, internal_sample_rate: Option<u32>) -> Vec<f32> {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 10,
+		internal_sample_rate,
+		backend_settings: MockBackendSettings {
+			sample_rate,
+			capture_output: true,
+		},
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: vec![Frame::from_mono(0.5); 100].into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	for _ in 0..(sample_rate / 5) {
+		manager.backend_mut().on_start_processing();
+		manager.backend_mut().process();
+	}
+	manager.backend_mut().captured_output().to_vec()
+}
+
+/// Tests that the resampler reproduces a linear signal exactly.
+#[test]
+fn resampler_interpolates() {
+	let mut resampler = OutputResampler::new();
+	let mut next_input = 0.0;
+	let mut output = vec![];
+	for _ in 0..20 {
+		while resampler.needs_frame() {
+			let mut frame = [0.0; MAX_CHANNELS];
+			frame[0] = next_input;
+			resampler.push_frame(frame);
+			next_input += 1.0;
+		}
+		output.push(resampler.next_frame(0.5)[0]);
+	}
+	// once the resampler is full, each output frame is half an input
+	// frame after the previous one, delayed by 2 input frames
+	for (i, sample) in output.iter().enumerate().skip(6) {
+		assert!((sample - (i as f32 * 0.5 - 2.0)).abs() < 0.0001);
+	}
+}
+
+/// Tests that with an internal sample rate, sounds play at the
+/// right speed on devices with a different sample rate.
+#[test]
+fn resamples_output() {
+	let reference = render_constant_signal(100, None);
+	let resampled = render_constant_signal(200, Some(100));
+	let steady_sample = reference[100];
+	assert!(steady_sample > 0.0);
+	assert!(resampled[200..300]
+		.iter()
+		.all(|sample| (sample - steady_sample).abs() < 0.0001));
+	let count_audible_frames =
+		|output: &[f32]| output.iter().filter(|sample| sample.abs() > 0.0001).count() / 2;
+	let reference_length = count_audible_frames(&reference);
+	let resampled_length = count_audible_frames(&resampled);
+	assert!(resampled_length.abs_diff(reference_length * 2) <= 6);
+}
+
+/// Tests that the output is unchanged if the internal sample rate
+/// matches the device's sample rate, apart from the resampler's delay.
+#[test]
+fn matching_sample_rates() {
+	let reference = render_constant_signal(100, None);
+	let resampled = render_constant_signal(100, Some(100));
+	assert_eq!(reference.len(), resampled.len());
+	for (reference, resampled) in reference.iter().zip(&resampled[4..]) {
+		assert!((reference - resampled).abs() < 0.0001);
+	}
+}
@@ -74,18 +74,23 @@ impl<T> ResourceStorage<T> {
 		}
 	}
 
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&T> {
+		self.resources.get(key)
+	}
+
 	#[must_use]
 	pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
 		self.resources.get_mut(key)
 	}
 
 	#[must_use]
-	pub fn iter(&self) -> atomic_arena::iter::Iter<T> {
+	pub fn iter(&self) -> atomic_arena::iter::Iter<'_, T> {
 		self.resources.iter()
 	}
 
 	#[must_use]
-	pub fn iter_mut(&mut self) -> atomic_arena::iter::IterMut<T> {
+	pub fn iter_mut(&mut self) -> atomic_arena::iter::IterMut<'_, T> {
 		self.resources.iter_mut()
 	}
 
@@ -162,7 +167,7 @@ impl<T> SelfReferentialResourceStorage<T> {
 	}
 
 	#[must_use]
-	pub fn iter_mut(&mut self) -> atomic_arena::iter::IterMut<T> {
+	pub fn iter_mut(&mut self) -> atomic_arena::iter::IterMut<'_, T> {
 		self.resources.iter_mut()
 	}
 
@@ -296,13 +301,16 @@ pub(crate) fn create_resources(
 	main_track_builder: MainTrackBuilder,
 	sample_rate: u32,
 	internal_buffer_size: usize,
+	profiling: bool,
+	parallel_mixing_threads: usize,
 ) -> (Resources, ResourceControllers) {
 	let (mixer, sub_track_controller, send_track_controller, main_track_handle) = Mixer::new(
-		capacities.sub_track_capacity,
-		capacities.send_track_capacity,
+		capacities,
 		sample_rate,
 		internal_buffer_size,
 		main_track_builder,
+		profiling,
+		parallel_mixing_threads,
 	);
 	let (clocks, clock_controller) = Clocks::new(capacities.clock_capacity);
 	let (modulators, modulator_controller) = Modulators::new(capacities.modulator_capacity);
@@ -14,11 +14,12 @@ impl Clocks {
 		(Self(storage), controller)
 	}
 
-	pub(crate) fn on_start_processing(&mut self) {
+	pub(crate) fn on_start_processing(&mut self, playback_nanos: Option<i64>) {
 		self.0
 			.remove_and_add(|clock| clock.shared().is_marked_for_removal());
 		for (_, clock) in &mut self.0 {
 			clock.on_start_processing();
+			clock.set_playback_nanos(playback_nanos);
 		}
 	}
 
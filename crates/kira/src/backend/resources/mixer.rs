@@ -1,6 +1,14 @@
+#[cfg(test)]
+mod test;
+
+mod worker_pool;
+
 use crate::{
+	channel_layout::MAX_CHANNELS,
 	frame::Frame,
-	info::Info,
+	info::{Info, ModulatorValues},
+	manager::Capacities,
+	modulator::ModulatorId,
 	track::{MainTrack, MainTrackBuilder, MainTrackHandle, SendTrack, Track},
 };
 
@@ -9,37 +17,93 @@ use super::{
 	ResourceStorage,
 };
 
+use worker_pool::{Task, WorkerPool};
+
 pub(crate) struct Mixer {
 	main_track: MainTrack,
 	sub_tracks: ResourceStorage<Track>,
 	send_tracks: ResourceStorage<SendTrack>,
 	temp_buffer: Vec<Frame>,
+	internal_buffer_size: usize,
+	parallel_mixing: Option<ParallelMixing>,
+}
+
+/// The state needed to process sub-tracks in parallel.
+struct ParallelMixing {
+	worker_pool: WorkerPool,
+	/// Sub-tracks that don't output to send tracks, which can be
+	/// processed on any thread.
+	parallel_tasks: Vec<Task>,
+	/// Sub-tracks that output to send tracks, which are processed
+	/// on the audio thread in order.
+	sequential_tasks: Vec<Task>,
+	/// The output of each sub-track, `internal_buffer_size` frames apiece.
+	track_outputs: Vec<Frame>,
+	/// The surround output of each sub-track, `internal_buffer_size *
+	/// MAX_CHANNELS` samples apiece.
+	track_surround_outputs: Vec<f32>,
+	/// The value of each modulator, which the worker threads
+	/// read instead of the modulators themselves.
+	modulator_values: Vec<(ModulatorId, f64)>,
+}
+
+impl ParallelMixing {
+	#[must_use]
+	fn new(
+		num_threads: usize,
+		capacities: Capacities,
+		internal_buffer_size: usize,
+	) -> Option<Self> {
+		if num_threads == 0 {
+			return None;
+		}
+		let worker_pool = WorkerPool::new(num_threads)?;
+		let track_output_len = capacities.sub_track_capacity * internal_buffer_size;
+		Some(Self {
+			worker_pool,
+			parallel_tasks: Vec::with_capacity(capacities.sub_track_capacity),
+			sequential_tasks: Vec::with_capacity(capacities.sub_track_capacity),
+			track_outputs: vec![Frame::ZERO; track_output_len],
+			track_surround_outputs: vec![0.0; track_output_len * MAX_CHANNELS],
+			modulator_values: Vec::with_capacity(capacities.modulator_capacity),
+		})
+	}
 }
 
 impl Mixer {
 	#[must_use]
 	pub fn new(
-		sub_track_capacity: usize,
-		send_track_capacity: usize,
+		capacities: Capacities,
 		sample_rate: u32,
 		internal_buffer_size: usize,
 		main_track_builder: MainTrackBuilder,
+		profiling: bool,
+		parallel_mixing_threads: usize,
 	) -> (
 		Self,
 		ResourceController<Track>,
 		ResourceController<SendTrack>,
 		MainTrackHandle,
 	) {
-		let (mut main_track, main_track_handle) = main_track_builder.build(internal_buffer_size);
+		let (mut main_track, main_track_handle) =
+			main_track_builder.build(internal_buffer_size, profiling);
 		main_track.init_effects(sample_rate);
-		let (sub_tracks, sub_track_controller) = ResourceStorage::new(sub_track_capacity);
-		let (send_tracks, send_track_controller) = ResourceStorage::new(send_track_capacity);
+		let (sub_tracks, sub_track_controller) =
+			ResourceStorage::new(capacities.sub_track_capacity);
+		let (send_tracks, send_track_controller) =
+			ResourceStorage::new(capacities.send_track_capacity);
 		(
 			Self {
 				main_track,
 				sub_tracks,
 				send_tracks,
 				temp_buffer: vec![Frame::ZERO; internal_buffer_size],
+				internal_buffer_size,
+				parallel_mixing: ParallelMixing::new(
+					parallel_mixing_threads,
+					capacities,
+					internal_buffer_size,
+				),
 			},
 			sub_track_controller,
 			send_track_controller,
@@ -57,6 +121,12 @@ impl Mixer {
 		}
 	}
 
+	/// Returns `true` if any track is playing any sounds.
+	#[must_use]
+	pub fn has_sounds(&self) -> bool {
+		self.main_track.has_sounds() || self.sub_tracks.iter().any(|(_, track)| track.has_sounds())
+	}
+
 	pub fn on_start_processing(&mut self) {
 		self.sub_tracks
 			.remove_and_add(|track| track.should_be_removed());
@@ -71,35 +141,55 @@ impl Mixer {
 		self.main_track.on_start_processing();
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub fn process(
 		&mut self,
 		out: &mut [Frame],
+		surround_out: &mut [f32],
 		dt: f64,
 		clocks: &Clocks,
 		modulators: &Modulators,
 		listeners: &Listeners,
+		playback_nanos: Option<i64>,
 	) {
-		for (_, track) in &mut self.sub_tracks {
-			track.process(
-				&mut self.temp_buffer[..out.len()],
+		let start_time = self.main_track.start_profiling();
+		if self.parallel_mixing.is_some() {
+			self.process_sub_tracks_in_parallel(
+				out,
+				surround_out,
 				dt,
 				clocks,
 				modulators,
 				listeners,
-				None,
-				&mut self.send_tracks,
+				playback_nanos,
 			);
-			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
-				*summed_out += sound_out;
+		} else {
+			for (_, track) in &mut self.sub_tracks {
+				track.process(
+					&mut self.temp_buffer[..out.len()],
+					dt,
+					clocks,
+					(&modulators.0.resources).into(),
+					listeners,
+					None,
+					Some(&mut self.send_tracks),
+					surround_out,
+					playback_nanos,
+				);
+				for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied())
+				{
+					*summed_out += sound_out;
+				}
+				self.temp_buffer.fill(Frame::ZERO);
 			}
-			self.temp_buffer.fill(Frame::ZERO);
 		}
 		let info = Info::new(
 			&clocks.0.resources,
 			&modulators.0.resources,
 			&listeners.0.resources,
 			None,
-		);
+		)
+		.with_playback_nanos(playback_nanos);
 		for (_, track) in &mut self.send_tracks {
 			track.process(&mut self.temp_buffer[..out.len()], dt, &info);
 			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
@@ -107,6 +197,112 @@ impl Mixer {
 			}
 			self.temp_buffer.fill(Frame::ZERO);
 		}
-		self.main_track.process(out, dt, &info);
+		self.main_track
+			.process(out, surround_out, dt, &info, start_time);
+	}
+
+	/// Processes sub-tracks on the worker pool and sums their
+	/// output in the same order as if they were processed one
+	/// at a time, so the result is the same.
+	#[allow(clippy::too_many_arguments)]
+	fn process_sub_tracks_in_parallel(
+		&mut self,
+		out: &mut [Frame],
+		surround_out: &mut [f32],
+		dt: f64,
+		clocks: &Clocks,
+		modulators: &Modulators,
+		listeners: &Listeners,
+		playback_nanos: Option<i64>,
+	) {
+		let Some(parallel_mixing) = &mut self.parallel_mixing else {
+			return;
+		};
+		let num_frames = out.len();
+		let surround_len = surround_out.len();
+		let internal_buffer_size = self.internal_buffer_size;
+
+		// give each track its own output buffers
+		parallel_mixing.parallel_tasks.clear();
+		parallel_mixing.sequential_tasks.clear();
+		let mut num_tracks = 0;
+		for (((_, track), track_output), track_surround_output) in (&mut self.sub_tracks)
+			.into_iter()
+			.zip(
+				parallel_mixing
+					.track_outputs
+					.chunks_mut(internal_buffer_size),
+			)
+			.zip(
+				parallel_mixing
+					.track_surround_outputs
+					.chunks_mut(internal_buffer_size * MAX_CHANNELS),
+			) {
+			let has_sends = track.has_sends();
+			let task = Task {
+				track,
+				out: &mut track_output[..num_frames],
+				surround_out: &mut track_surround_output[..surround_len],
+			};
+			if has_sends {
+				parallel_mixing.sequential_tasks.push(task);
+			} else {
+				parallel_mixing.parallel_tasks.push(task);
+			}
+			num_tracks += 1;
+		}
+
+		// the sub-tracks processed on this thread use the same modulator
+		// values as the ones processed on the worker threads
+		modulators.copy_values(&mut parallel_mixing.modulator_values);
+		let running_job = parallel_mixing.worker_pool.start(
+			&parallel_mixing.parallel_tasks,
+			dt,
+			clocks,
+			&parallel_mixing.modulator_values,
+			listeners,
+			playback_nanos,
+		);
+		for task in &parallel_mixing.sequential_tasks {
+			// SAFETY: every task points to a different track and different
+			// buffers, and the worker pool only processes the parallel tasks
+			unsafe {
+				(*task.track).process(
+					&mut *task.out,
+					dt,
+					clocks,
+					ModulatorValues::Copied(&parallel_mixing.modulator_values),
+					listeners,
+					None,
+					Some(&mut self.send_tracks),
+					&mut *task.surround_out,
+					playback_nanos,
+				);
+			}
+		}
+		drop(running_job);
+
+		for (track_output, track_surround_output) in parallel_mixing
+			.track_outputs
+			.chunks_mut(internal_buffer_size)
+			.zip(
+				parallel_mixing
+					.track_surround_outputs
+					.chunks_mut(internal_buffer_size * MAX_CHANNELS),
+			)
+			.take(num_tracks)
+		{
+			for (summed_out, track_out) in out.iter_mut().zip(&track_output[..num_frames]) {
+				*summed_out += *track_out;
+			}
+			for (summed_out, track_out) in surround_out
+				.iter_mut()
+				.zip(&track_surround_output[..surround_len])
+			{
+				*summed_out += *track_out;
+			}
+			track_output.fill(Frame::ZERO);
+			track_surround_output.fill(0.0);
+		}
 	}
 }
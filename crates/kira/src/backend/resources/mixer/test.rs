@@ -0,0 +1,113 @@
+use glam::{Quat, Vec3};
+
+use crate::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	effect::delay::DelayBuilder,
+	modulator::lfo::LfoBuilder,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::{SendTrackBuilder, SpatialTrackBuilder, TrackBuilder},
+	AudioManager, AudioManagerSettings, ChannelLayout, Decibels, Easing, Frame, Mapping, Value,
+};
+
+fn sound(seed: usize) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 100,
+		frames: (0..200)
+			.map(|i| Frame::from_mono((((i * (seed + 3)) % 17) as f32 / 17.0) - 0.5))
+			.collect::<Vec<_>>()
+			.into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// Renders a mix of sub-tracks, some of which output to send tracks
+/// or have volumes linked to a modulator, and returns everything the
+/// backend outputs.
+fn render_mix(channel_layout: ChannelLayout, parallel_mixing_threads: usize) -> Vec<f32> {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 10,
+		channel_layout,
+		parallel_mixing_threads,
+		backend_settings: MockBackendSettings {
+			sample_rate: 100,
+			capture_output: true,
+		},
+		..Default::default()
+	})
+	.unwrap();
+	let send_track = manager
+		.add_send_track({
+			let mut builder = SendTrackBuilder::new();
+			builder.add_effect(DelayBuilder::new());
+			builder
+		})
+		.unwrap();
+	let listener = manager.add_listener(Vec3::ZERO, Quat::IDENTITY).unwrap();
+	let lfo = manager
+		.add_modulator(LfoBuilder::new().frequency(3.0))
+		.unwrap();
+	let mut tracks = vec![];
+	for i in 0..12 {
+		let builder = if i % 3 == 1 {
+			TrackBuilder::new().volume(Value::FromModulator {
+				id: lfo.id(),
+				mapping: Mapping {
+					input_range: (-1.0, 1.0),
+					output_range: (Decibels(-12.0), Decibels(0.0)),
+					easing: Easing::Linear,
+				},
+			})
+		} else {
+			TrackBuilder::new().volume(Decibels(-(i as f32)))
+		};
+		let builder = if i % 4 == 0 {
+			builder.with_send(&send_track, Decibels(-6.0))
+		} else {
+			builder
+		};
+		let mut track = manager.add_sub_track(builder).unwrap();
+		track.play(sound(i)).unwrap();
+		let mut child_track = track.add_sub_track(TrackBuilder::new()).unwrap();
+		child_track.play(sound(i + 20)).unwrap();
+		tracks.push(track);
+		tracks.push(child_track);
+	}
+	let mut spatial_tracks = vec![];
+	for i in 0..4 {
+		let mut track = manager
+			.add_spatial_sub_track(
+				&listener,
+				Vec3::new(i as f32 - 2.0, 0.0, -1.0),
+				SpatialTrackBuilder::new(),
+			)
+			.unwrap();
+		track.play(sound(i + 40)).unwrap();
+		spatial_tracks.push(track);
+	}
+	for _ in 0..30 {
+		manager.backend_mut().on_start_processing();
+		manager.backend_mut().process();
+	}
+	manager.backend_mut().captured_output().to_vec()
+}
+
+/// Tests that processing sub-tracks in parallel produces exactly the
+/// same output as processing them one at a time.
+#[test]
+fn parallel_mixing_matches_sequential_mixing() {
+	let sequential = render_mix(ChannelLayout::Stereo, 0);
+	assert!(sequential.iter().any(|sample| *sample != 0.0));
+	assert_eq!(render_mix(ChannelLayout::Stereo, 1), sequential);
+	assert_eq!(render_mix(ChannelLayout::Stereo, 3), sequential);
+}
+
+/// Tests that parallel mixing also matches sequential mixing when
+/// tracks are panned across surround speakers.
+#[test]
+fn parallel_mixing_matches_sequential_mixing_in_surround() {
+	let sequential = render_mix(ChannelLayout::Quad, 0);
+	assert!(sequential.iter().any(|sample| *sample != 0.0));
+	assert_eq!(render_mix(ChannelLayout::Quad, 2), sequential);
+}
@@ -0,0 +1,263 @@
+use std::{
+	cell::UnsafeCell,
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+		Arc,
+	},
+	thread::{self, Thread},
+};
+
+use crate::{
+	backend::resources::{clocks::Clocks, listeners::Listeners},
+	info::ModulatorValues,
+	modulator::ModulatorId,
+	track::Track,
+	Frame,
+};
+
+/// A sub-track to process and the buffers to write its output to.
+#[derive(Clone, Copy)]
+pub(super) struct Task {
+	pub track: *mut Track,
+	pub out: *mut [Frame],
+	pub surround_out: *mut [f32],
+}
+
+// SAFETY: tasks are only dereferenced while the mixer is processing
+// sub-tracks, and the tracks they point to are `Send`
+unsafe impl Send for Task {}
+
+/// A batch of [`Task`]s and the resources they need.
+#[derive(Clone, Copy)]
+struct Job {
+	tasks: *const Task,
+	num_tasks: usize,
+	dt: f64,
+	clocks: *const Clocks,
+	/// The value of each modulator. Modulators aren't `Sync`, so
+	/// worker threads can't read them directly.
+	modulator_values: *const [(ModulatorId, f64)],
+	listeners: *const Listeners,
+	playback_nanos: Option<i64>,
+}
+
+/*
+	SAFETY: jobs are only used while `RunningJob` borrows the tasks
+	and resources they point to, and every task points to a different
+	track and different buffers. Tracks (and the sounds and effects
+	they own) are `Send`, and clocks and listeners are `Sync`. Clocks,
+	modulator values, and listeners are only read while a job is running.
+*/
+unsafe impl Send for Job {}
+
+impl Job {
+	/// Processes tasks until there's none left to claim.
+	fn run_tasks(&self, shared: &Shared) {
+		loop {
+			let index = shared.next_task.fetch_add(1, Ordering::Relaxed);
+			if index >= self.num_tasks {
+				break;
+			}
+			// SAFETY: each task is claimed by exactly one thread
+			unsafe {
+				let task = *self.tasks.add(index);
+				(*task.track).process(
+					&mut *task.out,
+					self.dt,
+					&*self.clocks,
+					ModulatorValues::Copied(&*self.modulator_values),
+					&*self.listeners,
+					None,
+					None,
+					&mut *task.surround_out,
+					self.playback_nanos,
+				);
+			}
+			shared.remaining_tasks.fetch_sub(1, Ordering::Release);
+		}
+	}
+}
+
+/// The state shared between the audio thread and the worker threads.
+///
+/// The audio thread never blocks on a lock. Instead, it publishes
+/// jobs and waits for them using atomics, and it wakes up the worker
+/// threads with [`Thread::unpark`].
+struct Shared {
+	/// The job that's currently running.
+	///
+	/// This is only written by the audio thread while no worker
+	/// is working on a job.
+	job: UnsafeCell<Option<Job>>,
+	/// The ID of the job that's currently running, or `0` if
+	/// no job is running.
+	current_job_id: AtomicU64,
+	/// The number of worker threads that are working on the current job.
+	active_workers: AtomicUsize,
+	next_task: AtomicUsize,
+	remaining_tasks: AtomicUsize,
+	shutdown: AtomicBool,
+}
+
+// SAFETY: `job` is only accessed as described above
+unsafe impl Sync for Shared {}
+
+/// A set of threads that process sub-tracks alongside the audio thread.
+///
+/// The threads are started up front and wait for jobs, so running
+/// a job doesn't allocate.
+pub(super) struct WorkerPool {
+	shared: Arc<Shared>,
+	threads: Vec<Thread>,
+	/// The ID of the most recently started job.
+	last_job_id: u64,
+}
+
+impl WorkerPool {
+	/// Starts up to `num_threads` worker threads.
+	///
+	/// Returns `None` if no threads could be started, which is
+	/// the case on platforms without threads.
+	#[must_use]
+	pub fn new(num_threads: usize) -> Option<Self> {
+		let shared = Arc::new(Shared {
+			job: UnsafeCell::new(None),
+			current_job_id: AtomicU64::new(0),
+			active_workers: AtomicUsize::new(0),
+			next_task: AtomicUsize::new(0),
+			remaining_tasks: AtomicUsize::new(0),
+			shutdown: AtomicBool::new(false),
+		});
+		let mut threads = Vec::with_capacity(num_threads);
+		for i in 0..num_threads {
+			let shared = shared.clone();
+			if let Ok(handle) = thread::Builder::new()
+				.name(format!("kira-mixer-worker-{}", i))
+				.spawn(move || work(&shared))
+			{
+				threads.push(handle.thread().clone());
+			}
+		}
+		if threads.is_empty() {
+			return None;
+		}
+		Some(Self {
+			shared,
+			threads,
+			last_job_id: 0,
+		})
+	}
+
+	/// Starts processing `tasks` on the worker threads.
+	///
+	/// When the returned [`RunningJob`] is dropped, the current thread
+	/// helps with the remaining tasks and waits for all of them to be
+	/// finished.
+	#[allow(clippy::too_many_arguments)]
+	pub fn start<'a>(
+		&'a mut self,
+		tasks: &'a [Task],
+		dt: f64,
+		clocks: &'a Clocks,
+		modulator_values: &'a [(ModulatorId, f64)],
+		listeners: &'a Listeners,
+		playback_nanos: Option<i64>,
+	) -> RunningJob<'a> {
+		let job = Job {
+			tasks: tasks.as_ptr(),
+			num_tasks: tasks.len(),
+			dt,
+			clocks,
+			modulator_values,
+			listeners,
+			playback_nanos,
+		};
+		self.shared.next_task.store(0, Ordering::Relaxed);
+		self.shared
+			.remaining_tasks
+			.store(tasks.len(), Ordering::Relaxed);
+		if !tasks.is_empty() {
+			// SAFETY: the previous `RunningJob` waited for every worker
+			// to stop working on its job, and workers don't read the job
+			// until the new job ID is published
+			unsafe {
+				*self.shared.job.get() = Some(job);
+			}
+			self.last_job_id += 1;
+			self.shared
+				.current_job_id
+				.store(self.last_job_id, Ordering::SeqCst);
+			for thread in &self.threads {
+				thread.unpark();
+			}
+		}
+		RunningJob {
+			shared: &self.shared,
+			job,
+		}
+	}
+}
+
+impl Drop for WorkerPool {
+	fn drop(&mut self) {
+		self.shared.shutdown.store(true, Ordering::SeqCst);
+		for thread in &self.threads {
+			thread.unpark();
+		}
+	}
+}
+
+/// A job that's being processed by a [`WorkerPool`].
+#[must_use = "Dropping a RunningJob waits for the job to be finished"]
+pub(super) struct RunningJob<'a> {
+	shared: &'a Shared,
+	job: Job,
+}
+
+impl Drop for RunningJob<'_> {
+	fn drop(&mut self) {
+		// the current thread claims every task that hasn't been claimed
+		// yet, so it only has to wait for the tasks that workers are
+		// already processing
+		self.job.run_tasks(self.shared);
+		while self.shared.remaining_tasks.load(Ordering::Acquire) > 0 {
+			std::hint::spin_loop();
+		}
+		if self.job.num_tasks == 0 {
+			return;
+		}
+		// make sure no worker is still holding onto the job. workers
+		// that haven't started the job yet will see that it's over
+		// and give up right away
+		self.shared.current_job_id.store(0, Ordering::SeqCst);
+		while self.shared.active_workers.load(Ordering::SeqCst) > 0 {
+			std::hint::spin_loop();
+		}
+	}
+}
+
+fn work(shared: &Shared) {
+	let mut last_job_id = 0;
+	loop {
+		if shared.shutdown.load(Ordering::SeqCst) {
+			return;
+		}
+		let job_id = shared.current_job_id.load(Ordering::SeqCst);
+		if job_id == 0 || job_id == last_job_id {
+			thread::park();
+			continue;
+		}
+		last_job_id = job_id;
+		shared.active_workers.fetch_add(1, Ordering::SeqCst);
+		// the job might have finished before this worker
+		// registered itself as active
+		if shared.current_job_id.load(Ordering::SeqCst) == job_id {
+			// SAFETY: the audio thread doesn't change the job until
+			// every active worker is finished with it
+			if let Some(job) = unsafe { *shared.job.get() } {
+				job.run_tasks(shared);
+			}
+		}
+		shared.active_workers.fetch_sub(1, Ordering::SeqCst);
+	}
+}
@@ -1,4 +1,7 @@
-use crate::{info::Info, modulator::Modulator};
+use crate::{
+	info::Info,
+	modulator::{Modulator, ModulatorId},
+};
 
 use super::{
 	clocks::Clocks, listeners::Listeners, ResourceController, SelfReferentialResourceStorage,
@@ -20,11 +23,26 @@ impl Modulators {
 		}
 	}
 
+	/// Replaces the contents of `values` with the current value of
+	/// every modulator.
+	///
+	/// `values` should have enough capacity for every modulator so
+	/// that this doesn't allocate.
+	pub fn copy_values(&self, values: &mut Vec<(ModulatorId, f64)>) {
+		values.clear();
+		values.extend(
+			self.0
+				.resources
+				.iter()
+				.map(|(key, modulator)| (ModulatorId(key), modulator.value())),
+		);
+	}
+
 	pub fn process(&mut self, dt: f64, clocks: &Clocks, listeners: &Listeners) {
 		self.0.for_each(|modulator, others| {
 			modulator.update(
 				dt,
-				&Info::new(&clocks.0.resources, others, &listeners.0.resources, None),
+				&Info::new(&clocks.0.resources, &*others, &listeners.0.resources, None),
 			);
 		});
 	}
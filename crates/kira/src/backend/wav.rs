@@ -0,0 +1,214 @@
+//! Minimal WAV file reading and writing for backends that work with
+//! audio files.
+
+#[cfg(test)]
+mod test;
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+const BYTES_PER_SAMPLE: u16 = 4;
+/// The size of everything in the header that comes before the sample data.
+const HEADER_SIZE: u32 = 58;
+
+/// Writes interleaved 32-bit float samples to a WAV file.
+///
+/// The sizes in the header are filled in when [`WavWriter::finish`]
+/// is called, so the length of the audio doesn't need to be
+/// known ahead of time.
+pub(crate) struct WavWriter<W: Write + Seek> {
+	writer: W,
+	start_position: u64,
+	num_channels: u16,
+	num_samples: u64,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+	pub fn new(mut writer: W, num_channels: u16, sample_rate: u32) -> io::Result<Self> {
+		let start_position = writer.stream_position()?;
+		let block_align = num_channels * BYTES_PER_SAMPLE;
+		// RIFF header
+		writer.write_all(b"RIFF")?;
+		writer.write_all(&0u32.to_le_bytes())?;
+		writer.write_all(b"WAVE")?;
+		// format chunk
+		writer.write_all(b"fmt ")?;
+		writer.write_all(&18u32.to_le_bytes())?;
+		writer.write_all(&WAVE_FORMAT_IEEE_FLOAT.to_le_bytes())?;
+		writer.write_all(&num_channels.to_le_bytes())?;
+		writer.write_all(&sample_rate.to_le_bytes())?;
+		writer.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+		writer.write_all(&block_align.to_le_bytes())?;
+		writer.write_all(&(BYTES_PER_SAMPLE * 8).to_le_bytes())?;
+		writer.write_all(&0u16.to_le_bytes())?;
+		// fact chunk (required for non-PCM formats)
+		writer.write_all(b"fact")?;
+		writer.write_all(&4u32.to_le_bytes())?;
+		writer.write_all(&0u32.to_le_bytes())?;
+		// data chunk
+		writer.write_all(b"data")?;
+		writer.write_all(&0u32.to_le_bytes())?;
+		Ok(Self {
+			writer,
+			start_position,
+			num_channels,
+			num_samples: 0,
+		})
+	}
+
+	pub fn write_samples(&mut self, samples: &[f32]) -> io::Result<()> {
+		for sample in samples {
+			self.writer.write_all(&sample.to_le_bytes())?;
+		}
+		self.num_samples += samples.len() as u64;
+		Ok(())
+	}
+
+	/// Fills in the sizes in the header and returns the underlying writer.
+	pub fn finish(mut self) -> io::Result<W> {
+		let data_size = u32::try_from(self.num_samples * BYTES_PER_SAMPLE as u64)
+			.map_err(|_| io::Error::other("audio data is too long to fit in a WAV file"))?;
+		let num_frames = (self.num_samples / self.num_channels as u64) as u32;
+		let end_position = self.writer.stream_position()?;
+		self.writer.seek(SeekFrom::Start(self.start_position + 4))?;
+		self.writer
+			.write_all(&(HEADER_SIZE - 8 + data_size).to_le_bytes())?;
+		self.writer
+			.seek(SeekFrom::Start(self.start_position + 46))?;
+		self.writer.write_all(&num_frames.to_le_bytes())?;
+		self.writer
+			.seek(SeekFrom::Start(self.start_position + 54))?;
+		self.writer.write_all(&data_size.to_le_bytes())?;
+		self.writer.seek(SeekFrom::Start(end_position))?;
+		self.writer.flush()?;
+		Ok(self.writer)
+	}
+}
+
+/// Audio read from a WAV file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WavData {
+	pub sample_rate: u32,
+	pub num_channels: u16,
+	/// Interleaved samples in the -1.0 to 1.0 range.
+	pub samples: Vec<f32>,
+}
+
+/// Reads a WAV file containing 8, 16, 24, or 32-bit integer samples or
+/// 32 or 64-bit float samples.
+pub(crate) fn read_wav(mut reader: impl Read) -> io::Result<WavData> {
+	let mut riff_header = [0; 12];
+	reader.read_exact(&mut riff_header)?;
+	if &riff_header[0..4] != b"RIFF" || &riff_header[8..12] != b"WAVE" {
+		return Err(invalid_data("not a WAV file"));
+	}
+	let mut format = None;
+	loop {
+		let mut chunk_header = [0; 8];
+		reader.read_exact(&mut chunk_header)?;
+		let chunk_size = u32::from_le_bytes(chunk_header[4..8].try_into().unwrap()) as usize;
+		// chunks are padded to an even number of bytes
+		let padded_chunk_size = chunk_size + chunk_size % 2;
+		match &chunk_header[0..4] {
+			b"fmt " => {
+				let chunk = read_chunk(&mut reader, padded_chunk_size)?;
+				format = Some(WavFormat::parse(&chunk)?);
+			}
+			b"data" => {
+				let format = format.ok_or_else(|| invalid_data("missing format chunk"))?;
+				let data = read_chunk(&mut reader, chunk_size)?;
+				return Ok(WavData {
+					sample_rate: format.sample_rate,
+					num_channels: format.num_channels,
+					samples: format.decode_samples(&data),
+				});
+			}
+			_ => {
+				io::copy(
+					&mut (&mut reader).take(padded_chunk_size as u64),
+					&mut io::sink(),
+				)?;
+			}
+		}
+	}
+}
+
+/// Reads a chunk of `size` bytes.
+///
+/// The chunk size comes from the file, so the buffer only grows as
+/// bytes are actually read instead of being allocated up front.
+fn read_chunk(reader: impl Read, size: usize) -> io::Result<Vec<u8>> {
+	let mut chunk = vec![];
+	reader.take(size as u64).read_to_end(&mut chunk)?;
+	if chunk.len() < size {
+		return Err(io::Error::new(
+			io::ErrorKind::UnexpectedEof,
+			"chunk is shorter than its size",
+		));
+	}
+	Ok(chunk)
+}
+
+struct WavFormat {
+	is_float: bool,
+	num_channels: u16,
+	sample_rate: u32,
+	bits_per_sample: u16,
+}
+
+impl WavFormat {
+	fn parse(chunk: &[u8]) -> io::Result<Self> {
+		if chunk.len() < 16 {
+			return Err(invalid_data("format chunk is too short"));
+		}
+		let read_u16 = |offset: usize| u16::from_le_bytes([chunk[offset], chunk[offset + 1]]);
+		let mut format_tag = read_u16(0);
+		if format_tag == WAVE_FORMAT_EXTENSIBLE {
+			// the actual format is the first two bytes of the sub-format GUID
+			if chunk.len() < 26 {
+				return Err(invalid_data("format chunk is too short"));
+			}
+			format_tag = read_u16(24);
+		}
+		let format = Self {
+			is_float: format_tag == WAVE_FORMAT_IEEE_FLOAT,
+			num_channels: read_u16(2),
+			sample_rate: u32::from_le_bytes(chunk[4..8].try_into().unwrap()),
+			bits_per_sample: read_u16(14),
+		};
+		let supported = match format_tag {
+			WAVE_FORMAT_PCM => matches!(format.bits_per_sample, 8 | 16 | 24 | 32),
+			WAVE_FORMAT_IEEE_FLOAT => matches!(format.bits_per_sample, 32 | 64),
+			_ => false,
+		};
+		if !supported || format.num_channels == 0 {
+			return Err(invalid_data("unsupported sample format"));
+		}
+		Ok(format)
+	}
+
+	fn decode_samples(&self, data: &[u8]) -> Vec<f32> {
+		let bytes_per_sample = self.bits_per_sample as usize / 8;
+		data.chunks_exact(bytes_per_sample)
+			.map(|bytes| match (self.is_float, bytes.len()) {
+				(true, 4) => f32::from_le_bytes(bytes.try_into().unwrap()),
+				(true, _) => f64::from_le_bytes(bytes.try_into().unwrap()) as f32,
+				// 8-bit samples are unsigned
+				(false, 1) => (bytes[0] as f32 - 128.0) / 128.0,
+				(false, 2) => i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32768.0,
+				(false, 3) => {
+					i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) as f32 / 2_147_483_648.0
+				}
+				(false, _) => {
+					i32::from_le_bytes(bytes.try_into().unwrap()) as f32 / 2_147_483_648.0
+				}
+			})
+			.collect()
+	}
+}
+
+fn invalid_data(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message)
+}
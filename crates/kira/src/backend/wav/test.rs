@@ -0,0 +1,89 @@
+use std::io::Cursor;
+
+use super::{read_wav, WavData, WavWriter};
+
+fn wav_file(format_chunk: &[u8], extra_chunk: &[u8], data: &[u8]) -> Vec<u8> {
+	let mut bytes = vec![];
+	bytes.extend_from_slice(b"RIFF");
+	bytes.extend_from_slice(&0u32.to_le_bytes());
+	bytes.extend_from_slice(b"WAVE");
+	bytes.extend_from_slice(b"fmt ");
+	bytes.extend_from_slice(&(format_chunk.len() as u32).to_le_bytes());
+	bytes.extend_from_slice(format_chunk);
+	bytes.extend_from_slice(extra_chunk);
+	bytes.extend_from_slice(b"data");
+	bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+	bytes.extend_from_slice(data);
+	bytes
+}
+
+/// Tests that files written by the [`WavWriter`] can be read back.
+#[test]
+fn reads_written_files() {
+	let samples = [0.0, 0.25, -0.5, 1.0, 0.125, -1.0];
+	let mut wav_writer = WavWriter::new(Cursor::new(vec![]), 3, 44_100).unwrap();
+	wav_writer.write_samples(&samples).unwrap();
+	let bytes = wav_writer.finish().unwrap().into_inner();
+	assert_eq!(
+		read_wav(bytes.as_slice()).unwrap(),
+		WavData {
+			sample_rate: 44_100,
+			num_channels: 3,
+			samples: samples.to_vec(),
+		}
+	);
+}
+
+/// Tests that 16-bit integer samples are read, and unknown chunks
+/// (including ones with an odd size) are skipped.
+#[test]
+fn reads_16_bit_files() {
+	let mut format_chunk = vec![];
+	format_chunk.extend_from_slice(&1u16.to_le_bytes());
+	format_chunk.extend_from_slice(&1u16.to_le_bytes());
+	format_chunk.extend_from_slice(&8000u32.to_le_bytes());
+	format_chunk.extend_from_slice(&16000u32.to_le_bytes());
+	format_chunk.extend_from_slice(&2u16.to_le_bytes());
+	format_chunk.extend_from_slice(&16u16.to_le_bytes());
+	let mut extra_chunk = vec![];
+	extra_chunk.extend_from_slice(b"LIST");
+	extra_chunk.extend_from_slice(&3u32.to_le_bytes());
+	extra_chunk.extend_from_slice(&[1, 2, 3, 0]);
+	let mut data = vec![];
+	for sample in [0i16, 16384, -32768] {
+		data.extend_from_slice(&sample.to_le_bytes());
+	}
+	assert_eq!(
+		read_wav(wav_file(&format_chunk, &extra_chunk, &data).as_slice()).unwrap(),
+		WavData {
+			sample_rate: 8000,
+			num_channels: 1,
+			samples: vec![0.0, 0.5, -1.0],
+		}
+	);
+}
+
+/// Tests that files that aren't WAV files are rejected.
+#[test]
+fn rejects_invalid_files() {
+	assert!(read_wav(&b"OggS not a wav file"[..]).is_err());
+}
+
+/// Tests that a chunk that claims to be larger than the rest of
+/// the file is rejected instead of being allocated up front.
+#[test]
+fn rejects_truncated_chunks() {
+	let mut format_chunk = vec![];
+	format_chunk.extend_from_slice(&1u16.to_le_bytes());
+	format_chunk.extend_from_slice(&1u16.to_le_bytes());
+	format_chunk.extend_from_slice(&8000u32.to_le_bytes());
+	format_chunk.extend_from_slice(&16000u32.to_le_bytes());
+	format_chunk.extend_from_slice(&2u16.to_le_bytes());
+	format_chunk.extend_from_slice(&16u16.to_le_bytes());
+	let mut bytes = wav_file(&format_chunk, &[], &[0, 0]);
+	// change the size of the data chunk to 4 GB
+	let data_size_position = bytes.len() - 6;
+	bytes[data_size_position..data_size_position + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+	let error = read_wav(bytes.as_slice()).unwrap_err();
+	assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
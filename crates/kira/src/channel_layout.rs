@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod test;
+
+use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
+
+/// The maximum number of channels in any [`ChannelLayout`].
+pub(crate) const MAX_CHANNELS: usize = 8;
+
+/// A matrix of gains that maps the channels of one [`ChannelLayout`]
+/// to another, indexed by `[output channel][input channel]`.
+pub(crate) type MixingMatrix = [[f32; MAX_CHANNELS]; MAX_CHANNELS];
+
+/**
+The speaker arrangement the mixer renders audio for.
+
+By default, Kira mixes in [`ChannelLayout::Stereo`]. When a surround layout
+is used, [spatial tracks](crate::track#spatial-tracks) are panned across all
+of the speakers in the layout instead of just the left and right channels.
+Non-spatial audio is always output to the front left and front right speakers.
+
+If the audio device has fewer channels than the layout, the output is
+downmixed to fit. If it has more channels, the layout's channels are mapped
+to the matching speakers of the device and the other channels are left silent.
+
+Channels are ordered the same way as in WAV files.
+*/
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelLayout {
+	/// A single center channel.
+	Mono,
+	/// Front left and front right channels.
+	#[default]
+	Stereo,
+	/// Front left, front right, back left, and back right channels.
+	Quad,
+	/// Front left, front right, center, low frequency, back left,
+	/// and back right channels.
+	Surround51,
+	/// Front left, front right, center, low frequency, back left,
+	/// back right, side left, and side right channels.
+	Surround71,
+}
+
+impl ChannelLayout {
+	/// Returns the layout that fits a device with the given number of
+	/// channels, or [`None`] if there is no layout with exactly that
+	/// many channels.
+	#[must_use]
+	pub fn from_num_channels(num_channels: u16) -> Option<Self> {
+		match num_channels {
+			1 => Some(Self::Mono),
+			2 => Some(Self::Stereo),
+			4 => Some(Self::Quad),
+			6 => Some(Self::Surround51),
+			8 => Some(Self::Surround71),
+			_ => None,
+		}
+	}
+
+	/// Returns the number of channels in the layout.
+	#[must_use]
+	pub fn num_channels(self) -> u16 {
+		self.speakers().len() as u16
+	}
+
+	/// Returns `true` if the layout has more speakers than
+	/// the front left and front right speakers.
+	#[must_use]
+	pub fn is_surround(self) -> bool {
+		self.num_channels() > 2
+	}
+
+	#[must_use]
+	pub(crate) fn speakers(self) -> &'static [Speaker] {
+		use Speaker::*;
+		match self {
+			Self::Mono => &[FrontCenter],
+			Self::Stereo => &[FrontLeft, FrontRight],
+			Self::Quad => &[FrontLeft, FrontRight, BackLeft, BackRight],
+			Self::Surround51 => &[
+				FrontLeft,
+				FrontRight,
+				FrontCenter,
+				LowFrequency,
+				BackLeft,
+				BackRight,
+			],
+			Self::Surround71 => &[
+				FrontLeft,
+				FrontRight,
+				FrontCenter,
+				LowFrequency,
+				BackLeft,
+				BackRight,
+				SideLeft,
+				SideRight,
+			],
+		}
+	}
+
+	/// The angles (in degrees clockwise from the front) and channel indices
+	/// of every full range speaker in the layout, sorted by angle.
+	#[must_use]
+	fn speaker_ring(self) -> &'static [(f32, usize)] {
+		match self {
+			Self::Mono => &[(0.0, 0)],
+			Self::Stereo => &[(-30.0, 0), (30.0, 1)],
+			Self::Quad => &[(-135.0, 2), (-45.0, 0), (45.0, 1), (135.0, 3)],
+			Self::Surround51 => &[(-110.0, 4), (-30.0, 0), (0.0, 2), (30.0, 1), (110.0, 5)],
+			Self::Surround71 => &[
+				(-150.0, 4),
+				(-90.0, 6),
+				(-30.0, 0),
+				(0.0, 2),
+				(30.0, 1),
+				(90.0, 7),
+				(150.0, 5),
+			],
+		}
+	}
+
+	#[must_use]
+	fn speaker_azimuth(self, channel: usize) -> Option<f32> {
+		self.speaker_ring()
+			.iter()
+			.find(|(_, speaker_channel)| *speaker_channel == channel)
+			.map(|(azimuth, _)| *azimuth)
+	}
+
+	/// Returns the channels and gains to use to play a sound coming
+	/// from the given angle (in degrees clockwise from the front).
+	///
+	/// The sound is panned between the two closest speakers with constant
+	/// power. If there's no speaker within 180 degrees on one side of the
+	/// angle (for example, a sound behind the listener in a stereo layout),
+	/// the sound is sent to the closest speaker at a reduced volume instead.
+	#[must_use]
+	pub(crate) fn pan(self, azimuth: f32) -> [(usize, f32); 2] {
+		let ring = self.speaker_ring();
+		if let [(_, channel)] = ring {
+			return [(*channel, 1.0), (*channel, 0.0)];
+		}
+		let azimuth = (azimuth + 180.0).rem_euclid(360.0) - 180.0;
+		let next_index = ring
+			.iter()
+			.position(|(speaker_azimuth, _)| *speaker_azimuth > azimuth)
+			.unwrap_or(0);
+		let previous_index = (next_index + ring.len() - 1) % ring.len();
+		let (previous_azimuth, previous_channel) = ring[previous_index];
+		let (next_azimuth, next_channel) = ring[next_index];
+		let span = (next_azimuth - previous_azimuth).rem_euclid(360.0);
+		let offset = (azimuth - previous_azimuth).rem_euclid(360.0);
+		if offset == 0.0 {
+			return [(previous_channel, 1.0), (next_channel, 0.0)];
+		}
+		if span > 180.0 {
+			let closest_channel = if offset < span - offset {
+				previous_channel
+			} else {
+				next_channel
+			};
+			return [(closest_channel, FRAC_1_SQRT_2), (closest_channel, 0.0)];
+		}
+		let fraction = offset / span;
+		[
+			(previous_channel, (fraction * FRAC_PI_2).cos()),
+			(next_channel, (fraction * FRAC_PI_2).sin()),
+		]
+	}
+
+	/// Returns the gains to use to convert audio in this layout
+	/// to the given layout.
+	#[must_use]
+	pub(crate) fn mixing_matrix(self, output_layout: ChannelLayout) -> MixingMatrix {
+		let mut matrix = [[0.0; MAX_CHANNELS]; MAX_CHANNELS];
+		let output_speakers = output_layout.speakers();
+		for (input_channel, speaker) in self.speakers().iter().enumerate() {
+			// speakers the output layout also has are passed through unchanged
+			if let Some(output_channel) = output_speakers.iter().position(|s| s == speaker) {
+				matrix[output_channel][input_channel] = 1.0;
+				continue;
+			}
+			// the low frequency channel is dropped if the output layout doesn't have one
+			let Some(azimuth) = self.speaker_azimuth(input_channel) else {
+				continue;
+			};
+			// downmixing to mono is done by averaging a stereo downmix, the same as
+			// for the stereo output of the mixer
+			if output_layout == Self::Mono {
+				for (_, gain) in Self::Stereo.pan(azimuth) {
+					matrix[0][input_channel] += gain / 2.0;
+				}
+				continue;
+			}
+			for (output_channel, gain) in output_layout.pan(azimuth) {
+				matrix[output_channel][input_channel] += gain;
+			}
+		}
+		matrix
+	}
+}
+
+/// A speaker position in a [`ChannelLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Speaker {
+	FrontLeft,
+	FrontRight,
+	FrontCenter,
+	LowFrequency,
+	BackLeft,
+	BackRight,
+	SideLeft,
+	SideRight,
+}
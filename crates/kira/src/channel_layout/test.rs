@@ -0,0 +1,179 @@
+use std::{f32::consts::FRAC_1_SQRT_2, time::Duration};
+
+use approx::assert_relative_eq;
+use glam::{Quat, Vec3};
+
+use crate::{
+	backend::offline::{OfflineBackend, OfflineBackendSettings, RenderLength},
+	effect::volume_control::VolumeControlBuilder,
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::{MainTrackBuilder, SpatialTrackBuilder},
+	AudioManager, AudioManagerSettings, ChannelLayout, Decibels, Frame,
+};
+
+/// Tests that sounds are panned between the two closest speakers
+/// with constant power.
+#[test]
+fn pans_between_closest_speakers() {
+	assert_eq!(ChannelLayout::Surround51.pan(0.0), [(2, 1.0), (1, 0.0)]);
+	let [(first_channel, first_gain), (second_channel, second_gain)] =
+		ChannelLayout::Surround51.pan(15.0);
+	assert_eq!((first_channel, second_channel), (2, 1));
+	assert_relative_eq!(first_gain, FRAC_1_SQRT_2);
+	assert_relative_eq!(second_gain, FRAC_1_SQRT_2);
+	// panning wraps around behind the listener
+	let [(first_channel, first_gain), (second_channel, second_gain)] =
+		ChannelLayout::Quad.pan(180.0);
+	assert_eq!((first_channel, second_channel), (3, 2));
+	assert_relative_eq!(first_gain, FRAC_1_SQRT_2);
+	assert_relative_eq!(second_gain, FRAC_1_SQRT_2);
+}
+
+/// Tests that sounds outside the range of the speakers are sent
+/// to the closest speaker at a reduced volume.
+#[test]
+fn pans_outside_speaker_range_to_closest_speaker() {
+	assert_eq!(
+		ChannelLayout::Stereo.pan(-110.0),
+		[(0, FRAC_1_SQRT_2), (0, 0.0)]
+	);
+	assert_eq!(
+		ChannelLayout::Stereo.pan(150.0),
+		[(1, FRAC_1_SQRT_2), (1, 0.0)]
+	);
+}
+
+/// Tests that 5.1 audio is downmixed to stereo sensibly.
+#[test]
+fn downmixes_surround_to_stereo() {
+	let matrix = ChannelLayout::Surround51.mixing_matrix(ChannelLayout::Stereo);
+	// front left, front right, center, low frequency, back left, back right
+	assert_eq!(
+		matrix[0][..6],
+		[1.0, 0.0, FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2, 0.0]
+	);
+	assert_eq!(
+		matrix[1][..6],
+		[0.0, 1.0, FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2]
+	);
+}
+
+/// Tests that stereo audio is sent to the front speakers of
+/// surround layouts, and everything else is left silent.
+#[test]
+fn upmixes_stereo_to_front_speakers() {
+	let matrix = ChannelLayout::Stereo.mixing_matrix(ChannelLayout::Surround71);
+	for (output_channel, gains) in matrix.iter().enumerate() {
+		match output_channel {
+			0 => assert_eq!(gains[..2], [1.0, 0.0]),
+			1 => assert_eq!(gains[..2], [0.0, 1.0]),
+			_ => assert_eq!(gains[..2], [0.0, 0.0]),
+		}
+	}
+}
+
+/// Tests that stereo audio is downmixed to mono by averaging
+/// the left and right channels.
+#[test]
+fn downmixes_stereo_to_mono() {
+	let matrix = ChannelLayout::Stereo.mixing_matrix(ChannelLayout::Mono);
+	assert_eq!(matrix[0][..2], [0.5, 0.5]);
+}
+
+/// Tests that spatial tracks are panned across surround speakers.
+#[test]
+fn spatial_tracks_output_to_surround_speakers() {
+	let mut manager = AudioManager::<OfflineBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 8,
+		channel_layout: ChannelLayout::Surround51,
+		backend_settings: OfflineBackendSettings {
+			sample_rate: 100,
+			num_channels: 6,
+		},
+		..Default::default()
+	})
+	.unwrap();
+	let listener = manager.add_listener(Vec3::ZERO, Quat::IDENTITY).unwrap();
+	// behind and to the left of the listener, which faces -Z
+	let mut track = manager
+		.add_spatial_sub_track(
+			&listener,
+			Vec3::new(-1.0, 0.0, 1.0),
+			SpatialTrackBuilder::new()
+				.attenuation_function(None)
+				.spatialization_strength(1.0),
+		)
+		.unwrap();
+	track
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: vec![Frame::from_mono(0.5); 100].into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::Duration(Duration::from_millis(500)));
+	let mut expected_frame = [0.0; 6];
+	for (channel, gain) in ChannelLayout::Surround51.pan(-135.0) {
+		expected_frame[channel] += 0.5 * gain;
+	}
+	// mostly back left, with a little back right
+	assert!(expected_frame[4] > expected_frame[5]);
+	assert!(expected_frame[5] > 0.0);
+	let last_frame = &samples[samples.len() - 6..];
+	for (sample, expected) in last_frame.iter().zip(expected_frame) {
+		assert_relative_eq!(*sample, expected, epsilon = 0.0001);
+	}
+}
+
+/// Tests that the main track's effects process audio from spatial
+/// tracks without changing where it's panned.
+#[test]
+fn main_track_effects_process_surround_audio() {
+	let mut manager = AudioManager::<OfflineBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 8,
+		channel_layout: ChannelLayout::Surround51,
+		main_track_builder: MainTrackBuilder::new()
+			.with_effect(VolumeControlBuilder::new(Decibels(-6.0))),
+		backend_settings: OfflineBackendSettings {
+			sample_rate: 100,
+			num_channels: 6,
+		},
+		..Default::default()
+	})
+	.unwrap();
+	let listener = manager.add_listener(Vec3::ZERO, Quat::IDENTITY).unwrap();
+	// behind and to the left of the listener, which faces -Z
+	let mut track = manager
+		.add_spatial_sub_track(
+			&listener,
+			Vec3::new(-1.0, 0.0, 1.0),
+			SpatialTrackBuilder::new()
+				.attenuation_function(None)
+				.spatialization_strength(1.0),
+		)
+		.unwrap();
+	track
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: vec![Frame::from_mono(0.5); 100].into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::Duration(Duration::from_millis(500)));
+	let mut expected_frame = [0.0; 6];
+	for (channel, gain) in ChannelLayout::Surround51.pan(-135.0) {
+		expected_frame[channel] += 0.5 * gain * Decibels(-6.0).as_amplitude();
+	}
+	let last_frame = &samples[samples.len() - 6..];
+	for (sample, expected) in last_frame.iter().zip(expected_frame) {
+		assert_relative_eq!(*sample, expected, epsilon = 0.0001);
+	}
+}
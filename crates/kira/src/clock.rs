@@ -147,18 +147,24 @@ pub use clock_speed::*;
 pub use handle::*;
 pub use time::*;
 
-use std::sync::{
-	atomic::{AtomicBool, AtomicU64, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		Arc,
+	},
+	time::Instant,
 };
 
 use crate::{
-	command::{read_commands_into_parameters, ValueChangeCommand},
-	command_writers_and_readers,
-	info::Info,
-	Parameter, Value,
+	command::ValueChangeCommand, command_writers_and_readers, info::Info,
+	playback_timing::AtomicInstant, Parameter, StartTime, Tween, Value,
 };
 
+/// How many speed changes that start at a time on the clock itself
+/// can be waiting to start. Changes set while this many are waiting
+/// are discarded.
+const MAX_PENDING_SPEED_CHANGES: usize = 16;
+
 /// A unique identifier for a clock.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ClockId(pub(crate) Key);
@@ -168,6 +174,11 @@ pub(crate) struct ClockShared {
 	ticking: AtomicBool,
 	ticks: AtomicU64,
 	fractional_position: AtomicU64,
+	/// When the current time of the clock will be heard.
+	playback_instant: AtomicInstant,
+	/// The current speed of the clock, or `0.0` if the clock
+	/// isn't ticking.
+	ticks_per_second: AtomicU64,
 	removed: AtomicBool,
 }
 
@@ -178,6 +189,8 @@ impl ClockShared {
 			ticking: AtomicBool::new(false),
 			ticks: AtomicU64::new(0),
 			fractional_position: AtomicU64::new(0.0f64.to_bits()),
+			playback_instant: AtomicInstant::unknown(),
+			ticks_per_second: AtomicU64::new(0.0f64.to_bits()),
 			removed: AtomicBool::new(false),
 		}
 	}
@@ -197,6 +210,16 @@ impl ClockShared {
 		f64::from_bits(self.fractional_position.load(Ordering::SeqCst))
 	}
 
+	#[must_use]
+	pub fn playback_instant(&self) -> Option<Instant> {
+		self.playback_instant.load()
+	}
+
+	#[must_use]
+	pub fn ticks_per_second(&self) -> f64 {
+		f64::from_bits(self.ticks_per_second.load(Ordering::SeqCst))
+	}
+
 	#[must_use]
 	pub fn is_marked_for_removal(&self) -> bool {
 		self.removed.load(Ordering::SeqCst)
@@ -219,8 +242,12 @@ pub(crate) enum State {
 pub(crate) struct Clock {
 	command_readers: CommandReaders,
 	shared: Arc<ClockShared>,
+	id: Option<ClockId>,
 	ticking: bool,
 	speed: Parameter<ClockSpeed>,
+	/// Speed changes that should start when this clock reaches
+	/// a certain time, sorted by start time.
+	pending_speed_changes: Vec<(ClockTime, ValueChangeCommand<ClockSpeed>)>,
 	state: State,
 }
 
@@ -233,8 +260,10 @@ impl Clock {
 			Self {
 				command_readers,
 				shared: shared.clone(),
+				id: Some(id),
 				ticking: false,
 				speed: Parameter::new(speed, ClockSpeed::TicksPerMinute(120.0)),
+				pending_speed_changes: Vec::with_capacity(MAX_PENDING_SPEED_CHANGES),
 				state: State::NotStarted,
 			},
 			ClockHandle {
@@ -251,8 +280,10 @@ impl Clock {
 		Self {
 			command_readers,
 			shared: Arc::new(ClockShared::new()),
+			id: None,
 			ticking: false,
 			speed: Parameter::new(speed, ClockSpeed::TicksPerMinute(120.0)),
+			pending_speed_changes: Vec::with_capacity(MAX_PENDING_SPEED_CHANGES),
 			state: State::NotStarted,
 		}
 	}
@@ -273,7 +304,9 @@ impl Clock {
 	}
 
 	pub(crate) fn on_start_processing(&mut self) {
-		read_commands_into_parameters!(self, speed);
+		if let Some(command) = self.command_readers.set_speed.read() {
+			self.set_speed(command);
+		}
 		if let Some(ticking) = self.command_readers.set_ticking.read() {
 			self.set_ticking(ticking);
 		}
@@ -283,6 +316,33 @@ impl Clock {
 		self.update_shared();
 	}
 
+	/// Sets when the clock's current time will be heard.
+	pub(crate) fn set_playback_nanos(&self, playback_nanos: Option<i64>) {
+		self.shared.playback_instant.store_nanos(playback_nanos);
+	}
+
+	fn set_speed(&mut self, command: ValueChangeCommand<ClockSpeed>) {
+		// a clock can't look up its own time in the `Info` it's given,
+		// so speed changes that start at a time on this clock are
+		// started by the clock itself
+		match command.tween.start_time {
+			StartTime::ClockTime(time) if Some(time.clock) == self.id => {
+				if self.pending_speed_changes.len() == MAX_PENDING_SPEED_CHANGES {
+					return;
+				}
+				// changes that start at the same time are started in
+				// the order they were set, so the last one wins
+				let index = self
+					.pending_speed_changes
+					.partition_point(|(start_time, _)| {
+						(start_time.ticks, start_time.fraction) <= (time.ticks, time.fraction)
+					});
+				self.pending_speed_changes.insert(index, (time, command));
+			}
+			_ => self.speed.set(command.target, command.tween),
+		}
+	}
+
 	fn set_ticking(&mut self, ticking: bool) {
 		self.ticking = ticking;
 		self.shared.ticking.store(ticking, Ordering::SeqCst);
@@ -305,6 +365,14 @@ impl Clock {
 		self.shared
 			.fractional_position
 			.store(fractional_position.to_bits(), Ordering::SeqCst);
+		let ticks_per_second = if self.ticking {
+			self.speed.value().as_ticks_per_second()
+		} else {
+			0.0
+		};
+		self.shared
+			.ticks_per_second
+			.store(ticks_per_second.to_bits(), Ordering::SeqCst);
 	}
 
 	/// Updates the [`Clock`].
@@ -312,6 +380,29 @@ impl Clock {
 	/// If the tick count changes this update, returns `Some(tick_number)`.
 	/// Otherwise, returns `None`.
 	pub(crate) fn update(&mut self, dt: f64, info: &Info) -> Option<u64> {
+		if self.ticking {
+			let (ticks, fractional_position) = match self.state {
+				State::NotStarted => (0, 0.0),
+				State::Started {
+					ticks,
+					fractional_position,
+				} => (ticks, fractional_position),
+			};
+			let num_started = self
+				.pending_speed_changes
+				.partition_point(|(start_time, _)| {
+					(start_time.ticks, start_time.fraction) <= (ticks, fractional_position)
+				});
+			for (_, command) in self.pending_speed_changes.drain(..num_started) {
+				self.speed.set(
+					command.target,
+					Tween {
+						start_time: StartTime::Immediate,
+						..command.tween
+					},
+				);
+			}
+		}
 		self.speed.update(dt, info);
 		if !self.ticking {
 			return None;
@@ -1,6 +1,12 @@
-use std::sync::{atomic::Ordering, Arc};
+use std::{
+	sync::{atomic::Ordering, Arc},
+	time::Instant,
+};
 
-use crate::command::handle_param_setters;
+use crate::{
+	command::handle_param_setters,
+	playback_timing::{offset_instant, seconds_between},
+};
 
 use super::{ClockId, ClockShared, ClockSpeed, ClockTime, CommandWriters};
 
@@ -39,8 +45,91 @@ impl ClockHandle {
 		}
 	}
 
+	/**
+	Returns when the given time of this clock will be heard, or
+	when it was heard if the time has already passed.
+
+	This accounts for the output latency and assumes the clock keeps
+	ticking at its current speed, so the result for a time in the
+	future may change if the clock's speed changes.
+
+	Returns `None` if `time` is for a different clock, the backend
+	doesn't report its output latency, or the time is in the future and
+	the clock isn't ticking.
+
+	# Examples
+
+	```no_run
+	use kira::{AudioManager, AudioManagerSettings, DefaultBackend, clock::ClockSpeed};
+
+	let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+	clock.start();
+	// later...
+	if let Some(instant) = clock.playback_instant(clock.time() + 1) {
+		println!("the next beat will be heard in {:?}", instant - std::time::Instant::now());
+	}
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn playback_instant(&self, time: ClockTime) -> Option<Instant> {
+		if time.clock != self.id {
+			return None;
+		}
+		let current_time = self.time();
+		let current_instant = self.shared.playback_instant()?;
+		let tick_difference = (time.ticks as f64 + time.fraction)
+			- (current_time.ticks as f64 + current_time.fraction);
+		if tick_difference == 0.0 {
+			return Some(current_instant);
+		}
+		let ticks_per_second = self.shared.ticks_per_second();
+		if tick_difference > 0.0 && ticks_per_second <= 0.0 {
+			return None;
+		}
+		let seconds = tick_difference / ticks_per_second;
+		if !seconds.is_finite() {
+			return None;
+		}
+		Some(offset_instant(current_instant, seconds))
+	}
+
+	/**
+	Returns the time of this clock that will be heard (or was heard)
+	at the given instant.
+
+	This is useful for finding where an input event lines up with
+	the music.
+
+	Returns `None` if the backend doesn't report its output latency
+	or the instant is before the clock started.
+	*/
+	#[must_use]
+	pub fn time_at_instant(&self, instant: Instant) -> Option<ClockTime> {
+		let current_time = self.time();
+		let current_instant = self.shared.playback_instant()?;
+		let ticks = current_time.ticks as f64
+			+ current_time.fraction
+			+ seconds_between(current_instant, instant) * self.shared.ticks_per_second();
+		if !ticks.is_finite() || ticks < 0.0 {
+			return None;
+		}
+		Some(ClockTime {
+			clock: self.id,
+			ticks: ticks as u64,
+			fraction: ticks.fract(),
+		})
+	}
+
 	handle_param_setters! {
 		/// Sets the speed of the clock.
+		///
+		/// If the tween starts at a time on this clock, the change
+		/// waits for the clock to reach that time without replacing
+		/// other changes that are waiting to start. Up to 16 changes
+		/// can be waiting at once, and changes set while 16 are
+		/// waiting are discarded.
 		speed: ClockSpeed,
 	}
 
@@ -140,7 +140,7 @@ fn set_speed_with_clock_time_start() {
 
 	let (mut clock, mut handle) = Clock::new(
 		Value::Fixed(ClockSpeed::SecondsPerTick(1.0)),
-		fake_clock_id(),
+		other_clock_id(),
 	);
 	handle.start();
 	clock.on_start_processing();
@@ -179,6 +179,59 @@ fn set_speed_with_clock_time_start() {
 	assert_eq!(handle.time().ticks, 6);
 }
 
+/// Tests that a clock speed tween can start at a time on the
+/// same clock.
+#[test]
+fn set_speed_with_own_clock_time_start() {
+	let (mut clock, mut handle) = Clock::new(
+		Value::Fixed(ClockSpeed::SecondsPerTick(1.0)),
+		fake_clock_id(),
+	);
+	handle.start();
+	handle.set_speed(
+		Value::Fixed(ClockSpeed::SecondsPerTick(0.5)),
+		Tween {
+			duration: Duration::ZERO,
+			start_time: StartTime::ClockTime(handle.time() + 2),
+			..Default::default()
+		},
+	);
+	clock.on_start_processing();
+	for expected_ticks in [1, 2, 4, 6] {
+		clock.update(1.0, &MockInfoBuilder::new().build());
+		clock.on_start_processing();
+		assert_eq!(handle.time().ticks, expected_ticks);
+	}
+}
+
+/// Tests that speed changes that start at different times on
+/// the same clock don't replace each other.
+#[test]
+fn set_speed_with_multiple_own_clock_time_starts() {
+	let (mut clock, mut handle) = Clock::new(
+		Value::Fixed(ClockSpeed::SecondsPerTick(1.0)),
+		fake_clock_id(),
+	);
+	handle.start();
+	clock.on_start_processing();
+	for (speed, start_ticks) in [(0.5, 2), (0.25, 4)] {
+		handle.set_speed(
+			Value::Fixed(ClockSpeed::SecondsPerTick(speed)),
+			Tween {
+				duration: Duration::ZERO,
+				start_time: StartTime::ClockTime(handle.time() + start_ticks),
+				..Default::default()
+			},
+		);
+		clock.on_start_processing();
+	}
+	for expected_ticks in [1, 2, 4, 8, 12] {
+		clock.update(1.0, &MockInfoBuilder::new().build());
+		clock.on_start_processing();
+		assert_eq!(handle.time().ticks, expected_ticks);
+	}
+}
+
 /// Tests that a clock correctly reports its fractional position.
 #[test]
 fn fractional_position() {
@@ -207,3 +260,12 @@ fn fake_clock_id() -> ClockId {
 	let key = arena.insert(()).unwrap();
 	ClockId(key)
 }
+
+/// Returns a clock ID that doesn't match the first clock added
+/// to a [`MockInfoBuilder`].
+fn other_clock_id() -> ClockId {
+	let mut arena = Arena::new(2);
+	arena.insert(()).unwrap();
+	let key = arena.insert(()).unwrap();
+	ClockId(key)
+}
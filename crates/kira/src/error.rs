@@ -27,6 +27,29 @@ impl<E> Display for PlaySoundError<E> {
 
 impl<E: std::fmt::Debug> Error for PlaySoundError<E> {}
 
+/// Errors that can occur when starting a crossfade.
+#[derive(Debug)]
+pub enum CrossfadeError<E> {
+	/// Could not start the crossfade because the maximum number of
+	/// modulators has been reached.
+	ModulatorLimitReached,
+	/// Could not play the incoming sound.
+	PlaySoundError(PlaySoundError<E>),
+}
+
+impl<E> Display for CrossfadeError<E> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CrossfadeError::ModulatorLimitReached => f.write_str(
+				"Could not start the crossfade because the maximum number of modulators has been reached.",
+			),
+			CrossfadeError::PlaySoundError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl<E: std::fmt::Debug> Error for CrossfadeError<E> {}
+
 /// An error that is returned when a resource cannot be added because the
 /// maximum capacity for that resource has been reached.
 ///
@@ -5,6 +5,8 @@
  * like [`Sound`](crate::sound::Sound) or [`Effect`](crate::effect::Effect).
  */
 
+use std::time::Instant;
+
 use atomic_arena::Arena;
 use glam::{Quat, Vec3};
 
@@ -12,6 +14,7 @@ use crate::{
 	clock::{Clock, ClockId, ClockTime, State as ClockState},
 	listener::{Listener, ListenerId},
 	modulator::{Modulator, ModulatorId},
+	playback_timing::nanos_to_instant,
 };
 
 /// Provides info about resources on the audio thread.
@@ -21,25 +24,50 @@ use crate::{
 pub struct Info<'a> {
 	kind: InfoKind<'a>,
 	spatial_track_info: Option<SpatialTrackInfo>,
+	playback_nanos: Option<i64>,
 }
 
 impl<'a> Info<'a> {
 	pub(crate) fn new(
 		clocks: &'a Arena<Clock>,
-		modulators: &'a Arena<Box<dyn Modulator>>,
+		modulators: impl Into<ModulatorValues<'a>>,
 		listeners: &'a Arena<Listener>,
 		spatial_track_info: Option<SpatialTrackInfo>,
 	) -> Self {
 		Self {
 			kind: InfoKind::Real {
 				clocks,
-				modulators,
+				modulators: modulators.into(),
 				listeners,
 			},
 			spatial_track_info,
+			playback_nanos: None,
 		}
 	}
 
+	/// Sets when the end of the audio currently being processed will be heard.
+	#[must_use]
+	pub(crate) fn with_playback_nanos(self, playback_nanos: Option<i64>) -> Self {
+		Self {
+			playback_nanos,
+			..self
+		}
+	}
+
+	/// Returns when the end of the audio currently being processed
+	/// will be heard.
+	///
+	/// Returns `None` if the backend doesn't report its output latency.
+	#[must_use]
+	pub fn playback_instant(&self) -> Option<Instant> {
+		self.playback_nanos.map(nanos_to_instant)
+	}
+
+	#[must_use]
+	pub(crate) fn playback_nanos(&self) -> Option<i64> {
+		self.playback_nanos
+	}
+
 	/// Gets information about the clock with the given ID if it
 	/// exists, returns `None` otherwise.
 	#[must_use]
@@ -86,9 +114,7 @@ impl<'a> Info<'a> {
 	#[must_use]
 	pub fn modulator_value(&self, id: ModulatorId) -> Option<f64> {
 		match &self.kind {
-			InfoKind::Real { modulators, .. } => {
-				modulators.get(id.0).map(|modulator| modulator.value())
-			}
+			InfoKind::Real { modulators, .. } => modulators.get(id),
 			InfoKind::Mock {
 				modulator_values, ..
 			} => modulator_values.get(id.0).copied(),
@@ -263,6 +289,7 @@ impl MockInfoBuilder {
 				listener_info: self.listener_info,
 			},
 			spatial_track_info: self.spatial_track_info,
+			playback_nanos: None,
 		}
 	}
 }
@@ -276,7 +303,7 @@ impl Default for MockInfoBuilder {
 enum InfoKind<'a> {
 	Real {
 		clocks: &'a Arena<Clock>,
-		modulators: &'a Arena<Box<dyn Modulator>>,
+		modulators: ModulatorValues<'a>,
 		listeners: &'a Arena<Listener>,
 	},
 	Mock {
@@ -286,6 +313,39 @@ enum InfoKind<'a> {
 	},
 }
 
+/// Where an [`Info`] gets the values of modulators from.
+#[derive(Clone, Copy)]
+pub(crate) enum ModulatorValues<'a> {
+	/// The modulators themselves.
+	Modulators(&'a Arena<Box<dyn Modulator>>),
+	/// Values copied from the modulators.
+	///
+	/// [`Modulator`]s don't have to be `Sync`, so this is used when
+	/// tracks are processed on several threads at once.
+	Copied(&'a [(ModulatorId, f64)]),
+}
+
+impl ModulatorValues<'_> {
+	#[must_use]
+	fn get(self, id: ModulatorId) -> Option<f64> {
+		match self {
+			ModulatorValues::Modulators(modulators) => {
+				modulators.get(id.0).map(|modulator| modulator.value())
+			}
+			ModulatorValues::Copied(values) => values
+				.iter()
+				.find(|(modulator_id, _)| *modulator_id == id)
+				.map(|(_, value)| *value),
+		}
+	}
+}
+
+impl<'a> From<&'a Arena<Box<dyn Modulator>>> for ModulatorValues<'a> {
+	fn from(modulators: &'a Arena<Box<dyn Modulator>>) -> Self {
+		Self::Modulators(modulators)
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct SpatialTrackInfo {
 	pub position: Vec3,
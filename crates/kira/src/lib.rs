@@ -125,28 +125,33 @@ The Kira crate has the following feature flags:
   users should leave this enabled.
 - `symphonia` (enabled by default) - allows loading and streaming audio from common
   audio formats, like MP3 and WAV.
-	- `mp3` (enabled by default) - enables support for loading and streaming MP3 audio (enables
-	  the `symphonia` feature automatically)
-	- `ogg` (enabled by default) - enables support for loading and streaming OGG audio (enables
-	  the `symphonia` feature automatically)
-	- `flac` (enabled by default) - enables support for loading and streaming FLAC audio (enables
-	  the `symphonia` feature automatically)
-	- `wav` (enabled by default) - enables support for loading and streaming WAV audio (enables
-	  the `symphonia` feature automatically)
+  - `mp3` (enabled by default) - enables support for loading and streaming MP3 audio (enables
+    the `symphonia` feature automatically)
+  - `ogg` (enabled by default) - enables support for loading and streaming OGG audio (enables
+    the `symphonia` feature automatically)
+  - `flac` (enabled by default) - enables support for loading and streaming FLAC audio (enables
+    the `symphonia` feature automatically)
+  - `wav` (enabled by default) - enables support for loading and streaming WAV audio (enables
+    the `symphonia` feature automatically)
 - `serde` - adds `Serialize` and `Deserialize` implementations for the following types:
 	- [`Capacities`]
+	- [`ChannelLayout`]
 	- [`ClockSpeed`](crate::clock::ClockSpeed)
 	- [`DistortionKind`](crate::effect::distortion::DistortionKind)
+	- [`DitherSettings`](crate::output_stage::DitherSettings)
 	- [`Easing`]
 	- [`EndPosition`](crate::sound::EndPosition)
 	- [`EqFilterKind`](crate::effect::eq_filter::EqFilterKind)
 	- [`FilterMode`](crate::effect::filter::FilterMode)
 	- [`Frame`]
+	- [`LimiterSettings`](crate::output_stage::LimiterSettings)
 	- [`Mapping`]
+	- [`OutputStageSettings`](crate::output_stage::OutputStageSettings)
 	- [`PlaybackPosition`](crate::sound::PlaybackPosition)
 	- [`PlaybackRate`]
 	- [`PlaybackState`](crate::sound::PlaybackState)
 	- [`Region`](crate::sound::Region)
+	- [`SoftClipperSettings`](crate::output_stage::SoftClipperSettings)
 	- [`Decibels`]
 	- [`Waveform`](crate::modulator::lfo::Waveform)
 - `assert_no_alloc` - uses the [`assert_no_alloc`](https://crates.io/crates/assert_no_alloc) crate
@@ -233,6 +238,7 @@ and compile times for games.
 #![allow(clippy::tabs_in_doc_comments)]
 
 pub mod backend;
+mod channel_layout;
 pub mod clock;
 pub mod command;
 mod decibels;
@@ -242,12 +248,17 @@ mod frame;
 pub mod info;
 pub mod listener;
 mod manager;
+pub mod midi;
 mod mix;
 pub mod modulator;
+pub mod output_stage;
 mod panning;
 mod parameter;
 mod playback_rate;
 mod playback_state_manager;
+mod playback_timing;
+pub mod profiling;
+mod random;
 mod semitones;
 pub mod sound;
 mod start_time;
@@ -258,6 +269,7 @@ mod tween;
 mod value;
 
 pub use backend::DefaultBackend;
+pub use channel_layout::*;
 pub use decibels::*;
 pub use error::*;
 pub use frame::*;
@@ -9,7 +9,10 @@ mod settings;
 
 pub use settings::*;
 
-use std::sync::{atomic::Ordering, Arc};
+use std::{
+	sync::{atomic::Ordering, Arc},
+	time::Duration,
+};
 
 use crate::{
 	backend::{
@@ -19,12 +22,18 @@ use crate::{
 	clock::{Clock, ClockHandle, ClockId, ClockSpeed},
 	listener::{Listener, ListenerHandle, ListenerId},
 	modulator::{ModulatorBuilder, ModulatorId},
-	sound::SoundData,
+	sound::{
+		crossfade::{
+			CrossfadeHandle, CrossfadeIncoming, CrossfadeOutgoing, CrossfadeSettings,
+			CurveDirection, EqualPowerCurveBuilder, IncomingSoundData,
+		},
+		SoundData,
+	},
 	track::{
 		MainTrackHandle, SendTrackBuilder, SendTrackHandle, SendTrackId, SpatialTrackBuilder,
 		SpatialTrackHandle, TrackBuilder, TrackHandle,
 	},
-	PlaySoundError, ResourceLimitReached, Value,
+	CrossfadeError, PlaySoundError, ResourceLimitReached, Value,
 };
 
 /// Controls audio from gameplay code.
@@ -70,17 +79,28 @@ impl<B: Backend> AudioManager<B> {
 	pub fn new(settings: AudioManagerSettings<B>) -> Result<Self, B::Error> {
 		let (mut backend, sample_rate) =
 			B::setup(settings.backend_settings, settings.internal_buffer_size)?;
-		let renderer_shared = Arc::new(RendererShared::new(sample_rate));
+		let output_sample_rate = sample_rate;
+		let sample_rate = settings.internal_sample_rate.unwrap_or(sample_rate);
+		let renderer_shared = Arc::new(RendererShared::new(
+			sample_rate,
+			settings.channel_layout,
+			settings.profiling,
+		));
 		let (resources, resource_controllers) = create_resources(
 			settings.capacities,
 			settings.main_track_builder,
 			sample_rate,
 			settings.internal_buffer_size,
+			settings.profiling,
+			settings.parallel_mixing_threads,
 		);
 		let renderer = Renderer::new(
 			renderer_shared.clone(),
 			settings.internal_buffer_size,
 			resources,
+			settings.output_stage,
+			settings.internal_sample_rate,
+			output_sample_rate,
 		);
 		backend.start(renderer)?;
 		Ok(Self {
@@ -113,6 +133,60 @@ impl<B: Backend> AudioManager<B> {
 		self.main_track().play(sound_data)
 	}
 
+	/**
+	Fades out a playing sound while fading in a new one.
+
+	The sounds are faded using equal-power curves. The outgoing sound
+	is stopped once the crossfade is finished.
+
+	# Examples
+
+	```no_run
+	# use kira::{AudioManager, AudioManagerSettings, DefaultBackend};
+	use kira::sound::{crossfade::CrossfadeSettings, static_sound::StaticSoundData};
+
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	let mut outgoing = manager.play(StaticSoundData::from_file("calm.ogg")?)?;
+	let crossfade = manager.crossfade(
+		&mut outgoing,
+		StaticSoundData::from_file("battle.ogg")?,
+		CrossfadeSettings::new(),
+	)?;
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	pub fn crossfade<D: CrossfadeIncoming>(
+		&mut self,
+		outgoing: &mut impl CrossfadeOutgoing,
+		incoming: D,
+		settings: CrossfadeSettings,
+	) -> Result<CrossfadeHandle<D::Handle>, CrossfadeError<D::Error>> {
+		let fade_out = self
+			.add_modulator(EqualPowerCurveBuilder {
+				direction: CurveDirection::FadeOut,
+				settings,
+			})
+			.map_err(|_| CrossfadeError::ModulatorLimitReached)?;
+		let fade_in = self
+			.add_modulator(EqualPowerCurveBuilder {
+				direction: CurveDirection::FadeIn,
+				settings,
+			})
+			.map_err(|_| CrossfadeError::ModulatorLimitReached)?;
+		let incoming = self
+			.play(IncomingSoundData {
+				data: incoming,
+				volume: fade_in.volume(),
+				start_time: settings.start_time,
+			})
+			.map_err(CrossfadeError::PlaySoundError)?;
+		outgoing.crossfade_out(fade_out.volume(), settings);
+		Ok(CrossfadeHandle {
+			incoming,
+			shared: fade_out.shared,
+		})
+	}
+
 	/// Creates a mixer sub-track.
 	pub fn add_sub_track(
 		&mut self,
@@ -157,7 +231,11 @@ impl<B: Backend> AudioManager<B> {
 			.send_track_controller
 			.try_reserve()?;
 		let id = SendTrackId(key);
-		let (mut track, handle) = builder.build(id, self.internal_buffer_size);
+		let (mut track, handle) = builder.build(
+			id,
+			self.internal_buffer_size,
+			self.renderer_shared.profiling,
+		);
 		track.init_effects(self.renderer_shared.sample_rate.load(Ordering::SeqCst));
 		self.resource_controllers
 			.send_track_controller
@@ -322,6 +400,16 @@ impl<B: Backend> AudioManager<B> {
 		self.resource_controllers.modulator_controller.len()
 	}
 
+	/// Returns how long it takes for audio to be heard after it's
+	/// rendered, including the delay added by the
+	/// [output stage](crate::output_stage).
+	///
+	/// Returns `None` if the backend doesn't report its output latency.
+	#[must_use]
+	pub fn output_latency(&self) -> Option<Duration> {
+		self.renderer_shared.playback_timing.output_latency()
+	}
+
 	/// Returns a mutable reference to this manager's backend.
 	#[must_use]
 	pub fn backend_mut(&mut self) -> &mut B {
@@ -1,4 +1,4 @@
-use crate::track::MainTrackBuilder;
+use crate::{output_stage::OutputStageSettings, track::MainTrackBuilder, ChannelLayout};
 
 use crate::backend::Backend;
 
@@ -46,6 +46,48 @@ pub struct AudioManagerSettings<B: Backend> {
 	/// Decreasing this value increases the precision of clocks and modulators
 	/// at the expense of higher CPU usage.
 	pub internal_buffer_size: usize,
+	/// The speaker arrangement the mixer renders audio for.
+	///
+	/// If the audio device has fewer channels than this layout,
+	/// the output will be downmixed to fit.
+	pub channel_layout: ChannelLayout,
+	/// Configures the processing applied to the final mix.
+	///
+	/// By default, the final mix is hard clipped to the `-1.0` to `1.0` range.
+	pub output_stage: OutputStageSettings,
+	/// The sample rate the mixer runs at, regardless of the sample
+	/// rate of the audio device.
+	///
+	/// If this is set, sounds and effects always process audio at this
+	/// sample rate, and the final mix is resampled to the audio device's
+	/// sample rate. This keeps the sound of effects consistent across
+	/// devices and means sounds with this sample rate don't need to be
+	/// resampled individually.
+	///
+	/// If this is `None`, the mixer runs at the audio device's
+	/// sample rate.
+	pub internal_sample_rate: Option<u32>,
+	/// Whether to measure how long each track, effect, and group
+	/// of sounds takes to process.
+	///
+	/// See the [`profiling`](crate::profiling) module for more details.
+	pub profiling: bool,
+	/// The number of extra threads used to process mixer sub-tracks
+	/// in parallel.
+	///
+	/// If this is greater than `0`, sub-tracks added directly to the
+	/// main track are processed on a pool of worker threads alongside
+	/// the audio thread, and their outputs are summed in the same order
+	/// as they would be otherwise, so the final mix is identical.
+	/// Sub-tracks that output to send tracks (or have sub-tracks that do)
+	/// are always processed on the audio thread.
+	///
+	/// While sub-tracks are being processed in parallel, modulators
+	/// may be read from multiple threads at once.
+	///
+	/// The worker threads are started when the [`AudioManager`](crate::AudioManager)
+	/// is created. Platforms without threads, like the web, ignore this setting.
+	pub parallel_mixing_threads: usize,
 	/// Configures the backend.
 	pub backend_settings: B::Settings,
 }
@@ -59,6 +101,11 @@ where
 			capacities: Capacities::default(),
 			main_track_builder: MainTrackBuilder::default(),
 			internal_buffer_size: 128,
+			channel_layout: ChannelLayout::default(),
+			output_stage: OutputStageSettings::default(),
+			internal_sample_rate: None,
+			profiling: false,
+			parallel_mixing_threads: 0,
 			backend_settings: B::Settings::default(),
 		}
 	}
@@ -0,0 +1,58 @@
+/*!
+Plays standard MIDI files using clocks.
+
+A [`MidiSequencer`] reads the notes from a [`MidiFile`] and schedules
+them on a [clock](crate::clock). One clock tick lasts as long as one
+tick of the MIDI file, and the sequencer changes the clock's
+[speed](crate::clock::ClockSpeed) to follow the file's tempo map.
+
+Notes are played by a [`MidiInstrument`], which can be any closure
+that takes a [`ScheduledMidiNote`]. The instrument is given the clock
+times the note should start and end at, which it can use as the start
+time of a sound.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend, Semitones,
+	clock::ClockSpeed,
+	midi::{MidiFile, MidiSequencer, ScheduledMidiNote},
+	sound::static_sound::StaticSoundData,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+let sample = StaticSoundData::from_file("piano-c4.ogg")?;
+let mut sequencer = MidiSequencer::new(
+	MidiFile::from_file("song.mid")?,
+	clock,
+	move |note: ScheduledMidiNote| {
+		manager
+			.play(
+				sample
+					.start_time(note.start_time)
+					.playback_rate(Semitones(note.note.key as f64 - 60.0)),
+			)
+			.ok();
+	},
+);
+sequencer.start();
+loop {
+	// call this regularly, like once per frame
+	sequencer.update();
+	# break;
+}
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+Notes are only scheduled shortly before they need to play, so
+[`MidiSequencer::update`] should be called regularly. How far ahead notes
+are scheduled can be changed with [`MidiSequencer::lookahead`].
+*/
+
+mod error;
+mod file;
+mod sequencer;
+
+pub use error::*;
+pub use file::*;
+pub use sequencer::*;
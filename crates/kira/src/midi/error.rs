@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+/// Errors that can occur when loading a standard MIDI file.
+#[derive(Debug)]
+pub enum MidiFileError {
+	/// The data doesn't start with a MIDI file header.
+	InvalidHeader,
+	/// The file uses format 2 (independent sequences), which
+	/// isn't supported.
+	UnsupportedFormat(u16),
+	/// The file measures time in SMPTE frames instead of ticks per
+	/// quarter note, which isn't supported.
+	UnsupportedTimeDivision,
+	/// A track contains an event that couldn't be parsed.
+	InvalidEvent {
+		/// The index of the track.
+		track: usize,
+		/// The position of the event in the file (in bytes).
+		position: usize,
+	},
+	/// The file ended in the middle of a chunk or event.
+	UnexpectedEndOfFile,
+	/// An error occurred while reading the file from the filesystem.
+	IoError(std::io::Error),
+}
+
+impl Display for MidiFileError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MidiFileError::InvalidHeader => f.write_str("The data is not a standard MIDI file"),
+			MidiFileError::UnsupportedFormat(format) => {
+				write!(f, "MIDI file format {} is not supported", format)
+			}
+			MidiFileError::UnsupportedTimeDivision => {
+				f.write_str("MIDI files with SMPTE time divisions are not supported")
+			}
+			MidiFileError::InvalidEvent { track, position } => {
+				write!(f, "Invalid event in track {} at byte {}", track, position)
+			}
+			MidiFileError::UnexpectedEndOfFile => f.write_str("The MIDI file ended unexpectedly"),
+			MidiFileError::IoError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl std::error::Error for MidiFileError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			MidiFileError::IoError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<std::io::Error> for MidiFileError {
+	fn from(v: std::io::Error) -> Self {
+		Self::IoError(v)
+	}
+}
@@ -0,0 +1,325 @@
+#[cfg(test)]
+mod test;
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::clock::ClockSpeed;
+
+use super::MidiFileError;
+
+/// The tempo MIDI files use until a tempo is set (120 BPM).
+const DEFAULT_MICROSECONDS_PER_QUARTER_NOTE: u32 = 500_000;
+
+/// A note in a MIDI file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MidiNote {
+	/// The tick the note starts on.
+	pub start_tick: u64,
+	/// The tick the note ends on.
+	pub end_tick: u64,
+	/// The MIDI channel of the note (from `0` to `15`).
+	pub channel: u8,
+	/// The key of the note (from `0` to `127`), where `60` is middle C.
+	pub key: u8,
+	/// The velocity of the note (from `1` to `127`).
+	pub velocity: u8,
+}
+
+/// A change in tempo in a MIDI file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MidiTempoChange {
+	/// The tick the tempo changes on.
+	pub tick: u64,
+	/// The length of a quarter note (in microseconds).
+	pub microseconds_per_quarter_note: u32,
+}
+
+/// The notes and tempo map of a standard MIDI file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MidiFile {
+	ticks_per_quarter_note: u16,
+	tempo_changes: Vec<MidiTempoChange>,
+	notes: Vec<MidiNote>,
+}
+
+impl MidiFile {
+	/// Loads a standard MIDI file.
+	pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, MidiFileError> {
+		Self::from_bytes(&std::fs::read(path)?)
+	}
+
+	/// Parses the contents of a standard MIDI file.
+	///
+	/// Notes from every track are merged together. Events other than
+	/// notes and tempo changes are ignored.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, MidiFileError> {
+		let mut reader = Reader {
+			bytes,
+			position: 0,
+			offset: 0,
+			track: 0,
+		};
+		if reader.read_bytes(4).ok() != Some(b"MThd".as_slice()) {
+			return Err(MidiFileError::InvalidHeader);
+		}
+		let header_length = reader.read_u32()? as usize;
+		if header_length < 6 {
+			return Err(MidiFileError::InvalidHeader);
+		}
+		let format = reader.read_u16()?;
+		if format > 1 {
+			return Err(MidiFileError::UnsupportedFormat(format));
+		}
+		let num_tracks = reader.read_u16()? as usize;
+		let ticks_per_quarter_note = reader.read_u16()?;
+		if ticks_per_quarter_note & 0x8000 != 0 || ticks_per_quarter_note == 0 {
+			return Err(MidiFileError::UnsupportedTimeDivision);
+		}
+		reader.read_bytes(header_length - 6)?;
+
+		let mut tempo_changes = vec![];
+		let mut notes = vec![];
+		let mut track_index = 0;
+		while track_index < num_tracks && reader.position < bytes.len() {
+			let chunk_type = reader.read_bytes(4)?;
+			let chunk_length = reader.read_u32()? as usize;
+			let chunk_start = reader.position;
+			let chunk = reader.read_bytes(chunk_length)?;
+			// unknown chunks should be skipped
+			if chunk_type != b"MTrk" {
+				continue;
+			}
+			read_track(
+				Reader {
+					bytes: chunk,
+					position: 0,
+					offset: chunk_start,
+					track: track_index,
+				},
+				&mut tempo_changes,
+				&mut notes,
+			)?;
+			track_index += 1;
+		}
+
+		tempo_changes.sort_by_key(|change: &MidiTempoChange| change.tick);
+		// if there's multiple tempo changes on the same tick, the last one wins
+		tempo_changes.reverse();
+		tempo_changes.dedup_by_key(|change| change.tick);
+		tempo_changes.reverse();
+		if tempo_changes.first().map(|change| change.tick) != Some(0) {
+			tempo_changes.insert(
+				0,
+				MidiTempoChange {
+					tick: 0,
+					microseconds_per_quarter_note: DEFAULT_MICROSECONDS_PER_QUARTER_NOTE,
+				},
+			);
+		}
+		notes.sort_by_key(|note: &MidiNote| (note.start_tick, note.channel, note.key));
+		Ok(Self {
+			ticks_per_quarter_note,
+			tempo_changes,
+			notes,
+		})
+	}
+
+	/// Returns the number of ticks in a quarter note.
+	#[must_use]
+	pub fn ticks_per_quarter_note(&self) -> u16 {
+		self.ticks_per_quarter_note
+	}
+
+	/// Returns the changes in tempo, sorted by tick.
+	///
+	/// The first tempo change is always on tick 0.
+	#[must_use]
+	pub fn tempo_changes(&self) -> &[MidiTempoChange] {
+		&self.tempo_changes
+	}
+
+	/// Returns the notes from every track, sorted by start tick,
+	/// channel, and key.
+	#[must_use]
+	pub fn notes(&self) -> &[MidiNote] {
+		&self.notes
+	}
+
+	/// Returns the tick the last note ends on.
+	#[must_use]
+	pub fn end_tick(&self) -> u64 {
+		self.notes
+			.iter()
+			.map(|note| note.end_tick)
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Returns the [`ClockSpeed`] that makes one clock tick last
+	/// as long as one tick of this file at the given tempo.
+	#[must_use]
+	pub fn clock_speed(&self, tempo_change: MidiTempoChange) -> ClockSpeed {
+		ClockSpeed::SecondsPerTick(
+			tempo_change.microseconds_per_quarter_note as f64
+				/ 1_000_000.0
+				/ self.ticks_per_quarter_note as f64,
+		)
+	}
+}
+
+fn read_track(
+	mut reader: Reader,
+	tempo_changes: &mut Vec<MidiTempoChange>,
+	notes: &mut Vec<MidiNote>,
+) -> Result<(), MidiFileError> {
+	let mut tick = 0;
+	let mut running_status = None;
+	// the start ticks and velocities of notes that haven't ended yet
+	let mut held_notes = HashMap::<(u8, u8), VecDeque<(u64, u8)>>::new();
+	while reader.position < reader.bytes.len() {
+		tick += reader.read_variable_length_quantity()? as u64;
+		let invalid_event = reader.invalid_event();
+		let mut status = reader.read_u8()?;
+		match status {
+			// meta events
+			0xFF => {
+				let meta_type = reader.read_u8()?;
+				let length = reader.read_variable_length_quantity()? as usize;
+				let data = reader.read_bytes(length)?;
+				match meta_type {
+					// set tempo
+					0x51 => {
+						let [a, b, c] = data else {
+							return Err(invalid_event);
+						};
+						tempo_changes.push(MidiTempoChange {
+							tick,
+							microseconds_per_quarter_note: u32::from_be_bytes([0, *a, *b, *c]),
+						});
+					}
+					// end of track
+					0x2F => break,
+					_ => {}
+				}
+				continue;
+			}
+			// sysex events
+			0xF0 | 0xF7 => {
+				let length = reader.read_variable_length_quantity()? as usize;
+				reader.read_bytes(length)?;
+				continue;
+			}
+			_ => {}
+		}
+		let first_data_byte = if status < 0x80 {
+			// running status: the status byte is omitted and the
+			// previous one is reused
+			let data_byte = status;
+			status = running_status.ok_or(invalid_event)?;
+			data_byte
+		} else if status < 0xF0 {
+			running_status = Some(status);
+			reader.read_u8()?
+		} else {
+			return Err(invalid_event);
+		};
+		let channel = status & 0x0F;
+		match status & 0xF0 {
+			0x80 | 0x90 => {
+				let key = first_data_byte;
+				let velocity = reader.read_u8()?;
+				if status & 0xF0 == 0x90 && velocity > 0 {
+					held_notes
+						.entry((channel, key))
+						.or_default()
+						.push_back((tick, velocity));
+				} else if let Some((start_tick, velocity)) = held_notes
+					.get_mut(&(channel, key))
+					.and_then(VecDeque::pop_front)
+				{
+					notes.push(MidiNote {
+						start_tick,
+						end_tick: tick,
+						channel,
+						key,
+						velocity,
+					});
+				}
+			}
+			// program changes and channel pressure only have one data byte
+			0xC0 | 0xD0 => {}
+			_ => {
+				reader.read_u8()?;
+			}
+		}
+	}
+	// notes that are never released end when the track ends
+	for ((channel, key), held_notes) in held_notes {
+		for (start_tick, velocity) in held_notes {
+			notes.push(MidiNote {
+				start_tick,
+				end_tick: tick,
+				channel,
+				key,
+				velocity,
+			});
+		}
+	}
+	Ok(())
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	position: usize,
+	/// The position of `bytes` in the file.
+	offset: usize,
+	/// The index of the track being read.
+	track: usize,
+}
+
+impl<'a> Reader<'a> {
+	#[must_use]
+	fn invalid_event(&self) -> MidiFileError {
+		MidiFileError::InvalidEvent {
+			track: self.track,
+			position: self.offset + self.position,
+		}
+	}
+
+	fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], MidiFileError> {
+		let bytes = self
+			.bytes
+			.get(self.position..self.position + length)
+			.ok_or(MidiFileError::UnexpectedEndOfFile)?;
+		self.position += length;
+		Ok(bytes)
+	}
+
+	fn read_u8(&mut self) -> Result<u8, MidiFileError> {
+		Ok(self.read_bytes(1)?[0])
+	}
+
+	fn read_u16(&mut self) -> Result<u16, MidiFileError> {
+		let bytes = self.read_bytes(2)?;
+		Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+	}
+
+	fn read_u32(&mut self) -> Result<u32, MidiFileError> {
+		let bytes = self.read_bytes(4)?;
+		Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+	}
+
+	/// Reads a number stored 7 bits at a time, where the highest bit
+	/// of each byte is set if there are more bytes.
+	fn read_variable_length_quantity(&mut self) -> Result<u32, MidiFileError> {
+		let mut value = 0;
+		for _ in 0..4 {
+			let byte = self.read_u8()?;
+			value = (value << 7) | (byte & 0x7F) as u32;
+			if byte & 0x80 == 0 {
+				return Ok(value);
+			}
+		}
+		Err(self.invalid_event())
+	}
+}
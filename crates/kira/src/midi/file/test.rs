@@ -0,0 +1,127 @@
+use crate::{
+	midi::{MidiFile, MidiFileError, MidiNote, MidiTempoChange},
+	test_helpers::midi_file,
+};
+
+/// Tests that notes and tempo changes are read from every track.
+#[test]
+fn reads_notes_and_tempo_changes() {
+	let file = MidiFile::from_bytes(&midi_file(
+		96,
+		&[
+			&[
+				// set tempo to 600,000 microseconds per quarter note
+				0x00, 0xFF, 0x51, 0x03, 0x09, 0x27, 0xC0,
+				// set tempo to 300,000 microseconds per quarter note at tick 192
+				0x81, 0x40, 0xFF, 0x51, 0x03, 0x04, 0x93, 0xE0, // end of track
+				0x00, 0xFF, 0x2F, 0x00,
+			],
+			&[
+				// note on (channel 1, key 60, velocity 100)
+				0x00, 0x91, 60, 100, // sysex event
+				0x00, 0xF0, 0x02, 0x01, 0xF7, // note on (key 64) using running status
+				0x10, 64, 90, // program change
+				0x00, 0xC1, 0x05, // note off (key 60) at tick 96
+				0x50, 0x81, 60, 0x00, // note on with velocity 0 (key 64) at tick 144
+				0x30, 0x91, 64, 0x00, 0x00, 0xFF, 0x2F, 0x00,
+			],
+		],
+	))
+	.unwrap();
+	assert_eq!(file.ticks_per_quarter_note(), 96);
+	assert_eq!(
+		file.tempo_changes(),
+		&[
+			MidiTempoChange {
+				tick: 0,
+				microseconds_per_quarter_note: 600_000,
+			},
+			MidiTempoChange {
+				tick: 192,
+				microseconds_per_quarter_note: 300_000,
+			},
+		]
+	);
+	assert_eq!(
+		file.notes(),
+		&[
+			MidiNote {
+				start_tick: 0,
+				end_tick: 96,
+				channel: 1,
+				key: 60,
+				velocity: 100,
+			},
+			MidiNote {
+				start_tick: 16,
+				end_tick: 144,
+				channel: 1,
+				key: 64,
+				velocity: 90,
+			},
+		]
+	);
+	assert_eq!(file.end_tick(), 144);
+	assert_eq!(
+		file.clock_speed(file.tempo_changes()[0])
+			.as_seconds_per_tick(),
+		0.6 / 96.0
+	);
+}
+
+/// Tests that files without a tempo at tick 0 start at 120 BPM.
+#[test]
+fn default_tempo() {
+	let file = MidiFile::from_bytes(&midi_file(4, &[&[0x00, 0xFF, 0x2F, 0x00]])).unwrap();
+	assert_eq!(
+		file.tempo_changes(),
+		&[MidiTempoChange {
+			tick: 0,
+			microseconds_per_quarter_note: 500_000,
+		}]
+	);
+	assert!(file.notes().is_empty());
+}
+
+/// Tests that notes that are never released end at the end of the track.
+#[test]
+fn unreleased_notes() {
+	let file = MidiFile::from_bytes(&midi_file(
+		4,
+		&[&[0x00, 0x90, 60, 100, 0x08, 0xFF, 0x2F, 0x00]],
+	))
+	.unwrap();
+	assert_eq!(file.notes()[0].end_tick, 8);
+}
+
+/// Tests that invalid and unsupported files produce errors.
+#[test]
+fn errors() {
+	assert!(matches!(
+		MidiFile::from_bytes(b"RIFF"),
+		Err(MidiFileError::InvalidHeader)
+	));
+	let mut smpte = midi_file(4, &[]);
+	smpte[12] = 0xE7;
+	assert!(matches!(
+		MidiFile::from_bytes(&smpte),
+		Err(MidiFileError::UnsupportedTimeDivision)
+	));
+	let mut format_2 = midi_file(4, &[]);
+	format_2[9] = 2;
+	assert!(matches!(
+		MidiFile::from_bytes(&format_2),
+		Err(MidiFileError::UnsupportedFormat(2))
+	));
+	// a data byte without a previous status byte
+	assert!(matches!(
+		MidiFile::from_bytes(&midi_file(4, &[&[0x00, 60, 100]])),
+		Err(MidiFileError::InvalidEvent { track: 0, .. })
+	));
+	let mut truncated = midi_file(4, &[&[0x00, 0x90, 60, 100]]);
+	truncated.pop();
+	assert!(matches!(
+		MidiFile::from_bytes(&truncated),
+		Err(MidiFileError::UnexpectedEndOfFile)
+	));
+}
@@ -0,0 +1,292 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use crate::{
+	clock::{ClockHandle, ClockSpeed, ClockTime},
+	StartTime, Tween,
+};
+
+use super::{MidiFile, MidiNote};
+
+/// A note that a [`MidiSequencer`] wants to play.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledMidiNote {
+	/// The note from the MIDI file.
+	pub note: MidiNote,
+	/// When the note should start.
+	pub start_time: ClockTime,
+	/// When the note should end.
+	pub end_time: ClockTime,
+}
+
+/// Plays the notes scheduled by a [`MidiSequencer`].
+///
+/// This is implemented for closures that take a [`ScheduledMidiNote`].
+#[allow(unused_variables)]
+pub trait MidiInstrument {
+	/// Called when a note should be scheduled.
+	///
+	/// Notes are scheduled ahead of time, so the note's start and end
+	/// times are usually in the future.
+	fn schedule_note(&mut self, note: ScheduledMidiNote);
+
+	/// Called when a note that was scheduled hasn't started yet
+	/// and shouldn't play anymore, like when the sequencer is stopped.
+	///
+	/// Instruments that start sounds at the note's start time should
+	/// stop those sounds here. Otherwise, they'll play when the clock
+	/// reaches the note's start time again. The default implementation
+	/// does nothing.
+	fn cancel_note(&mut self, note: ScheduledMidiNote) {}
+}
+
+impl<F: FnMut(ScheduledMidiNote)> MidiInstrument for F {
+	fn schedule_note(&mut self, note: ScheduledMidiNote) {
+		self(note)
+	}
+}
+
+/// Plays a [`MidiFile`] by scheduling its notes on a clock.
+pub struct MidiSequencer<I: MidiInstrument> {
+	file: MidiFile,
+	clock: ClockHandle,
+	instrument: I,
+	lookahead: Duration,
+	tempo_scale: f64,
+	next_note_index: usize,
+	/// The index of the tempo change whose speed the clock is set to,
+	/// or `None` if the clock's speed needs to be set.
+	current_tempo_index: Option<usize>,
+	/// The index of the tempo change that's scheduled to be applied
+	/// when the clock reaches it.
+	scheduled_tempo_index: Option<usize>,
+	/// The clock time when the last speed change was scheduled.
+	///
+	/// Only one speed change can be sent to the clock at a time, so
+	/// the next one isn't scheduled until the clock has advanced, which
+	/// means it has received the previous one.
+	last_scheduled_speed_change_time: Option<ClockTime>,
+}
+
+impl<I: MidiInstrument> MidiSequencer<I> {
+	/// Creates a new [`MidiSequencer`] that schedules notes on the
+	/// given clock.
+	///
+	/// The clock is stopped and reset, since MIDI ticks are mapped
+	/// to clock ticks starting from tick 0.
+	#[must_use]
+	pub fn new(file: MidiFile, mut clock: ClockHandle, instrument: I) -> Self {
+		clock.stop();
+		let mut sequencer = Self {
+			file,
+			clock,
+			instrument,
+			lookahead: Duration::from_millis(250),
+			tempo_scale: 1.0,
+			next_note_index: 0,
+			current_tempo_index: None,
+			scheduled_tempo_index: None,
+			last_scheduled_speed_change_time: None,
+		};
+		sequencer.update_tempo();
+		sequencer
+	}
+
+	/// Sets how far ahead of time notes are scheduled.
+	///
+	/// This should be longer than the time between calls to
+	/// [`update`](Self::update). The default is 250 milliseconds.
+	#[must_use = "This method consumes self and returns a modified MidiSequencer, so the return value should be used"]
+	pub fn lookahead(self, lookahead: Duration) -> Self {
+		Self { lookahead, ..self }
+	}
+
+	/// Returns the MIDI file being played.
+	#[must_use]
+	pub fn file(&self) -> &MidiFile {
+		&self.file
+	}
+
+	/// Returns the clock notes are scheduled on.
+	#[must_use]
+	pub fn clock(&self) -> &ClockHandle {
+		&self.clock
+	}
+
+	/// Returns the instrument that plays the notes.
+	#[must_use]
+	pub fn instrument(&self) -> &I {
+		&self.instrument
+	}
+
+	/// Returns a mutable reference to the instrument that plays the notes.
+	#[must_use]
+	pub fn instrument_mut(&mut self) -> &mut I {
+		&mut self.instrument
+	}
+
+	/// Returns the factor the tempo of the MIDI file is multiplied by.
+	#[must_use]
+	pub fn tempo_scale(&self) -> f64 {
+		self.tempo_scale
+	}
+
+	/// Sets the factor the tempo of the MIDI file is multiplied by.
+	///
+	/// For example, `2.0` plays the file twice as fast. Notes that are
+	/// already scheduled will still play at the right tick.
+	pub fn set_tempo_scale(&mut self, tempo_scale: f64) {
+		self.tempo_scale = tempo_scale;
+		self.current_tempo_index = None;
+		self.scheduled_tempo_index = None;
+		self.update_tempo();
+	}
+
+	/// Returns `true` if every note has been scheduled and the clock
+	/// has reached the end of the last note.
+	#[must_use]
+	pub fn finished(&self) -> bool {
+		self.next_note_index >= self.file.notes().len()
+			&& self.clock.time().ticks >= self.file.end_tick()
+	}
+
+	/// Starts or resumes playback.
+	pub fn start(&mut self) {
+		self.update();
+		self.clock.start();
+	}
+
+	/// Pauses playback.
+	pub fn pause(&mut self) {
+		self.clock.pause();
+	}
+
+	/// Stops playback and goes back to the beginning of the file.
+	///
+	/// Notes that were scheduled but haven't started yet are passed to
+	/// [`MidiInstrument::cancel_note`]. Sounds that were already started
+	/// by the instrument are not stopped.
+	pub fn stop(&mut self) {
+		self.cancel_unstarted_notes();
+		self.clock.stop();
+		self.next_note_index = 0;
+		self.current_tempo_index = None;
+		self.scheduled_tempo_index = None;
+		self.last_scheduled_speed_change_time = None;
+		self.update_tempo();
+	}
+
+	/// Schedules upcoming notes and tempo changes.
+	///
+	/// This should be called regularly, like once per frame.
+	pub fn update(&mut self) {
+		self.update_tempo();
+		let time = self.clock.time();
+		let lookahead_ticks = self.lookahead.as_secs_f64()
+			/ self
+				.clock_speed(self.tempo_index(time))
+				.as_seconds_per_tick();
+		let schedule_until = time.ticks as f64 + time.fraction + lookahead_ticks;
+		while let Some(note) = self.file.notes().get(self.next_note_index).copied() {
+			if note.start_tick as f64 > schedule_until {
+				break;
+			}
+			self.instrument.schedule_note(ScheduledMidiNote {
+				note,
+				start_time: ClockTime::from_ticks_u64(&self.clock, note.start_tick),
+				end_time: ClockTime::from_ticks_u64(&self.clock, note.end_tick),
+			});
+			self.next_note_index += 1;
+		}
+	}
+
+	/// Cancels the scheduled notes that the clock hasn't reached yet.
+	fn cancel_unstarted_notes(&mut self) {
+		let time = self.clock.time();
+		let ticking = self.clock.ticking();
+		// notes are sorted by start tick, so the unstarted notes are
+		// at the end of the scheduled ones
+		for note in self.file.notes()[..self.next_note_index].iter().rev() {
+			let started = note.start_tick < time.ticks
+				|| (note.start_tick == time.ticks && (ticking || time.fraction > 0.0));
+			if started {
+				break;
+			}
+			self.instrument.cancel_note(ScheduledMidiNote {
+				note: *note,
+				start_time: ClockTime::from_ticks_u64(&self.clock, note.start_tick),
+				end_time: ClockTime::from_ticks_u64(&self.clock, note.end_tick),
+			});
+		}
+	}
+
+	/// Makes sure the clock's speed matches the current tempo and
+	/// schedules the next tempo change.
+	fn update_tempo(&mut self) {
+		let time = self.clock.time();
+		let tempo_index = self.tempo_index(time);
+		if let Some(scheduled_tempo_index) = self.scheduled_tempo_index {
+			// the clock applies a scheduled speed change at the start of
+			// the first update after it reaches the change's tick, so the
+			// change has only been applied once the clock is past that tick.
+			// scheduling another speed change before then would replace it
+			let tick = self.file.tempo_changes()[scheduled_tempo_index].tick;
+			if time > ClockTime::from_ticks_u64(&self.clock, tick) {
+				self.current_tempo_index = Some(scheduled_tempo_index);
+				self.scheduled_tempo_index = None;
+			}
+		}
+		if self.current_tempo_index != Some(tempo_index) {
+			// the clock is at the wrong speed, so change it right away
+			self.clock.set_speed(
+				self.clock_speed(tempo_index),
+				Tween {
+					duration: Duration::ZERO,
+					..Default::default()
+				},
+			);
+			self.current_tempo_index = Some(tempo_index);
+			self.scheduled_tempo_index = None;
+			self.last_scheduled_speed_change_time = Some(time);
+			return;
+		}
+		let next_tempo_index = tempo_index + 1;
+		if self.scheduled_tempo_index.is_some()
+			|| next_tempo_index >= self.file.tempo_changes().len()
+			|| self.last_scheduled_speed_change_time == Some(time)
+		{
+			return;
+		}
+		let tick = self.file.tempo_changes()[next_tempo_index].tick;
+		self.clock.set_speed(
+			self.clock_speed(next_tempo_index),
+			Tween {
+				start_time: StartTime::ClockTime(ClockTime::from_ticks_u64(&self.clock, tick)),
+				duration: Duration::ZERO,
+				..Default::default()
+			},
+		);
+		self.scheduled_tempo_index = Some(next_tempo_index);
+		self.last_scheduled_speed_change_time = Some(time);
+	}
+
+	/// Returns the index of the tempo change that applies at the given time.
+	#[must_use]
+	fn tempo_index(&self, time: ClockTime) -> usize {
+		self.file
+			.tempo_changes()
+			.iter()
+			.rposition(|change| change.tick <= time.ticks)
+			.unwrap_or(0)
+	}
+
+	#[must_use]
+	fn clock_speed(&self, tempo_index: usize) -> ClockSpeed {
+		let speed = self
+			.file
+			.clock_speed(self.file.tempo_changes()[tempo_index]);
+		ClockSpeed::SecondsPerTick(speed.as_seconds_per_tick() / self.tempo_scale)
+	}
+}
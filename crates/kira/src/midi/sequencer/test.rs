@@ -0,0 +1,202 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	clock::ClockSpeed,
+	midi::{MidiFile, MidiInstrument, MidiSequencer, ScheduledMidiNote},
+	test_helpers::midi_file,
+	AudioManager, AudioManagerSettings,
+};
+
+/// Returns a MIDI file with 2 ticks per quarter note, a tempo of
+/// 0.25 seconds per tick that changes to 0.125 seconds per tick at
+/// tick 4, and a note every 2 ticks.
+fn file() -> MidiFile {
+	MidiFile::from_bytes(&midi_file(
+		2,
+		&[
+			&[
+				0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, // 500,000
+				0x04, 0xFF, 0x51, 0x03, 0x03, 0xD0, 0x90, // 250,000
+				0x00, 0xFF, 0x2F, 0x00,
+			],
+			&[
+				0x00, 0x90, 60, 100, 0x01, 0x80, 60, 0, // ticks 0-1
+				0x01, 0x90, 62, 100, 0x01, 0x80, 62, 0, // ticks 2-3
+				0x01, 0x90, 64, 100, 0x01, 0x80, 64, 0, // ticks 4-5
+				0x01, 0x90, 65, 100, 0x01, 0x80, 65, 0, // ticks 6-7
+				0x00, 0xFF, 0x2F, 0x00,
+			],
+		],
+	))
+	.unwrap()
+}
+
+/// Creates a manager that processes 0.05 seconds of audio at a time.
+fn manager() -> AudioManager<MockBackend> {
+	AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 5,
+		backend_settings: MockBackendSettings {
+			sample_rate: 100,
+			..Default::default()
+		},
+		..Default::default()
+	})
+	.unwrap()
+}
+
+/// Processes audio for the given number of seconds, updating the
+/// sequencer before each chunk.
+///
+/// Clock handles are updated when the backend starts processing, so
+/// this finishes by calling `on_start_processing`.
+fn run(
+	manager: &mut AudioManager<MockBackend>,
+	sequencer: &mut MidiSequencer<impl MidiInstrument>,
+	seconds: f64,
+) {
+	for _ in 0..(seconds / 0.05).round() as usize {
+		sequencer.update();
+		manager.backend_mut().on_start_processing();
+		manager.backend_mut().process();
+	}
+	manager.backend_mut().on_start_processing();
+}
+
+/// Tests that notes are scheduled on the clock shortly before
+/// they need to play.
+#[test]
+fn schedules_notes() {
+	let mut manager = manager();
+	let clock = manager.add_clock(ClockSpeed::TicksPerSecond(1.0)).unwrap();
+	let clock_id = clock.id();
+	let notes = Rc::new(RefCell::new(vec![]));
+	let mut sequencer = MidiSequencer::new(file(), clock, {
+		let notes = notes.clone();
+		move |note: ScheduledMidiNote| notes.borrow_mut().push(note)
+	})
+	.lookahead(Duration::from_millis(300));
+	sequencer.start();
+	// with a lookahead of 300ms, only the first note is scheduled
+	// at the start
+	assert_eq!(notes.borrow().len(), 1);
+	let first_note = notes.borrow()[0];
+	assert_eq!(first_note.note.key, 60);
+	assert_eq!(first_note.start_time.clock, clock_id);
+	assert_eq!(first_note.start_time.ticks, 0);
+	assert_eq!(first_note.end_time.ticks, 1);
+	run(&mut manager, &mut sequencer, 0.5);
+	assert_eq!(notes.borrow().len(), 2);
+	assert_eq!(notes.borrow()[1].start_time.ticks, 2);
+	run(&mut manager, &mut sequencer, 2.0);
+	let keys = notes
+		.borrow()
+		.iter()
+		.map(|note| note.note.key)
+		.collect::<Vec<_>>();
+	assert_eq!(keys, [60, 62, 64, 65]);
+	assert!(sequencer.finished());
+}
+
+/// Tests that the clock follows the file's tempo map.
+#[test]
+fn follows_tempo_map() {
+	let mut manager = manager();
+	let clock = manager.add_clock(ClockSpeed::TicksPerSecond(1.0)).unwrap();
+	let mut sequencer = MidiSequencer::new(file(), clock, |_| {});
+	sequencer.start();
+	// 4 ticks at 0.25 seconds per tick
+	run(&mut manager, &mut sequencer, 1.0);
+	assert_eq!(sequencer.clock().time().ticks, 4);
+	// 4 ticks at 0.125 seconds per tick
+	run(&mut manager, &mut sequencer, 0.5);
+	assert_eq!(sequencer.clock().time().ticks, 8);
+}
+
+/// Tests that the tempo scale speeds up or slows down playback.
+#[test]
+fn tempo_scale() {
+	let mut manager = manager();
+	let clock = manager.add_clock(ClockSpeed::TicksPerSecond(1.0)).unwrap();
+	let mut sequencer = MidiSequencer::new(file(), clock, |_| {});
+	sequencer.set_tempo_scale(2.0);
+	sequencer.start();
+	// 4 ticks at 0.125 seconds per tick
+	run(&mut manager, &mut sequencer, 0.5);
+	assert_eq!(sequencer.clock().time().ticks, 4);
+	// 4 ticks at 0.0625 seconds per tick
+	run(&mut manager, &mut sequencer, 0.25);
+	assert_eq!(sequencer.clock().time().ticks, 8);
+}
+
+/// Tests that stopping the sequencer cancels the notes that
+/// were scheduled but haven't started yet.
+#[test]
+fn stop_cancels_unstarted_notes() {
+	#[derive(Default)]
+	struct Instrument {
+		scheduled: Vec<u8>,
+		cancelled: Vec<u8>,
+	}
+
+	impl MidiInstrument for Instrument {
+		fn schedule_note(&mut self, note: ScheduledMidiNote) {
+			self.scheduled.push(note.note.key);
+		}
+
+		fn cancel_note(&mut self, note: ScheduledMidiNote) {
+			self.cancelled.push(note.note.key);
+		}
+	}
+
+	let mut manager = manager();
+	let clock = manager.add_clock(ClockSpeed::TicksPerSecond(1.0)).unwrap();
+	let mut sequencer = MidiSequencer::new(file(), clock, Instrument::default())
+		.lookahead(Duration::from_millis(600));
+	// stopping before the clock starts cancels every scheduled note
+	sequencer.start();
+	sequencer.stop();
+	assert_eq!(sequencer.instrument().scheduled, [60, 62]);
+	assert_eq!(sequencer.instrument().cancelled, [62, 60]);
+	// at tick 2.4, the first 2 notes have started and the
+	// third one is scheduled
+	sequencer.instrument_mut().scheduled.clear();
+	sequencer.instrument_mut().cancelled.clear();
+	sequencer.start();
+	run(&mut manager, &mut sequencer, 0.6);
+	sequencer.stop();
+	assert_eq!(sequencer.instrument().scheduled, [60, 62, 64]);
+	assert_eq!(sequencer.instrument().cancelled, [64]);
+}
+
+/// Tests that tempo changes that are closer together than the
+/// lookahead and the length of a chunk are all applied.
+#[test]
+fn close_tempo_changes() {
+	// 0.25 seconds per tick, then 0.125 seconds per tick at tick 2,
+	// then 0.0625 seconds per tick at tick 3
+	let file = MidiFile::from_bytes(&midi_file(
+		2,
+		&[&[
+			0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, // 500,000
+			0x02, 0xFF, 0x51, 0x03, 0x03, 0xD0, 0x90, // 250,000
+			0x01, 0xFF, 0x51, 0x03, 0x01, 0xE8, 0x48, // 125,000
+			0x00, 0x90, 60, 100, 0x10, 0x80, 60, 0, // ticks 3-19
+			0x00, 0xFF, 0x2F, 0x00,
+		]],
+	))
+	.unwrap();
+	let mut manager = manager();
+	let clock = manager.add_clock(ClockSpeed::TicksPerSecond(1.0)).unwrap();
+	let mut sequencer = MidiSequencer::new(file, clock, |_| {});
+	sequencer.start();
+	// 2 ticks at 0.25 seconds per tick
+	run(&mut manager, &mut sequencer, 0.5);
+	assert_eq!(sequencer.clock().time().ticks, 2);
+	// 0.15 seconds later, the clock has played a tick at 0.125 seconds
+	// per tick and is playing at 0.0625 seconds per tick
+	run(&mut manager, &mut sequencer, 0.15);
+	assert_eq!(sequencer.clock().time().ticks, 3);
+	run(&mut manager, &mut sequencer, 0.35);
+	assert_eq!(sequencer.clock().time().ticks, 8);
+}
@@ -104,7 +104,7 @@ pub enum Waveform {
 
 impl Waveform {
 	#[must_use]
-	fn value(self, phase: f64) -> f64 {
+	pub(crate) fn value(self, phase: f64) -> f64 {
 		match self {
 			Waveform::Sine => (phase * TAU).sin(),
 			Waveform::Triangle => ((phase + 0.75).fract() - 0.5).abs() * 4.0 - 1.0,
@@ -0,0 +1,282 @@
+/*!
+Processing applied to the final mix before it's sent to the audio device.
+
+Audio devices can't play anything louder than 0dBFS, so by default,
+Kira hard clips the final mix to the `-1.0` to `1.0` range. Hard clipping
+sounds harsh, so if your mix can get loud, you can use the output stage
+to reduce the volume of the loudest parts of the mix more gracefully:
+
+- A [limiter](LimiterSettings) turns down the volume of the mix just before it would
+  go over a ceiling, including peaks that happen between samples.
+- A [soft clipper](SoftClipperSettings) gradually squashes loud samples instead of
+  cutting them off abruptly.
+- [Dither](DitherSettings) adds a tiny amount of noise to the mix, which masks
+  distortion caused by converting the output to a lower bit depth.
+
+These stages run in that order, and the output is hard clipped afterwards
+as a last resort.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	output_stage::{LimiterSettings, OutputStageSettings},
+};
+
+let audio_manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings {
+	output_stage: OutputStageSettings::new().limiter(LimiterSettings::new()),
+	..Default::default()
+})?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+*/
+
+mod limiter;
+
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use crate::{random::Random, Decibels};
+
+use limiter::Limiter;
+
+/// Configures the processing applied to the final mix.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutputStageSettings {
+	/// If set, a true peak limiter will be applied to the mix.
+	pub limiter: Option<LimiterSettings>,
+	/// If set, a soft clipper will be applied to the mix.
+	pub soft_clipper: Option<SoftClipperSettings>,
+	/// If set, dither will be added to the mix.
+	pub dither: Option<DitherSettings>,
+}
+
+impl OutputStageSettings {
+	/// Creates a new [`OutputStageSettings`] with the default settings,
+	/// which hard clips the final mix.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Applies a true peak limiter to the mix.
+	#[must_use = "This method consumes self and returns a modified OutputStageSettings, so the return value should be used"]
+	pub fn limiter(self, limiter: LimiterSettings) -> Self {
+		Self {
+			limiter: Some(limiter),
+			..self
+		}
+	}
+
+	/// Applies a soft clipper to the mix.
+	#[must_use = "This method consumes self and returns a modified OutputStageSettings, so the return value should be used"]
+	pub fn soft_clipper(self, soft_clipper: SoftClipperSettings) -> Self {
+		Self {
+			soft_clipper: Some(soft_clipper),
+			..self
+		}
+	}
+
+	/// Adds dither to the mix.
+	#[must_use = "This method consumes self and returns a modified OutputStageSettings, so the return value should be used"]
+	pub fn dither(self, dither: DitherSettings) -> Self {
+		Self {
+			dither: Some(dither),
+			..self
+		}
+	}
+}
+
+/// Configures a true peak limiter.
+///
+/// The limiter looks ahead at upcoming audio so it can turn down the
+/// volume before a peak arrives. This delays the output by the
+/// [`lookahead`](LimiterSettings::lookahead) duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimiterSettings {
+	/// The maximum volume of the output, including peaks between samples.
+	pub ceiling: Decibels,
+	/// How far ahead the limiter looks for peaks.
+	///
+	/// This is also how long it takes for the volume to be turned
+	/// down before a peak.
+	pub lookahead: Duration,
+	/// How long it takes for the volume to return to normal after a peak.
+	pub release: Duration,
+}
+
+impl LimiterSettings {
+	/// Creates a new [`LimiterSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			ceiling: Decibels(-1.0),
+			lookahead: Duration::from_millis(5),
+			release: Duration::from_millis(100),
+		}
+	}
+
+	/// Sets the maximum volume of the output, including peaks between samples.
+	#[must_use = "This method consumes self and returns a modified LimiterSettings, so the return value should be used"]
+	pub fn ceiling(self, ceiling: Decibels) -> Self {
+		Self { ceiling, ..self }
+	}
+
+	/// Sets how far ahead the limiter looks for peaks.
+	#[must_use = "This method consumes self and returns a modified LimiterSettings, so the return value should be used"]
+	pub fn lookahead(self, lookahead: Duration) -> Self {
+		Self { lookahead, ..self }
+	}
+
+	/// Sets how long it takes for the volume to return to normal after a peak.
+	#[must_use = "This method consumes self and returns a modified LimiterSettings, so the return value should be used"]
+	pub fn release(self, release: Duration) -> Self {
+		Self { release, ..self }
+	}
+}
+
+impl Default for LimiterSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Configures a soft clipper.
+///
+/// Samples quieter than the threshold are left unchanged, and louder
+/// samples are smoothly squashed so they never exceed 0dBFS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftClipperSettings {
+	/// The volume above which samples will start to be squashed.
+	pub threshold: Decibels,
+}
+
+impl SoftClipperSettings {
+	/// Creates a new [`SoftClipperSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			threshold: Decibels(-6.0),
+		}
+	}
+
+	/// Sets the volume above which samples will start to be squashed.
+	#[must_use = "This method consumes self and returns a modified SoftClipperSettings, so the return value should be used"]
+	pub fn threshold(self, threshold: Decibels) -> Self {
+		Self { threshold }
+	}
+}
+
+impl Default for SoftClipperSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Configures triangular (TPDF) dither.
+///
+/// Dither is only useful if the output is converted to integer samples,
+/// for example by a backend writing 16-bit audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DitherSettings {
+	/// The number of bits per sample the output will be converted to.
+	pub bit_depth: u32,
+}
+
+impl DitherSettings {
+	/// Creates a new [`DitherSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self { bit_depth: 16 }
+	}
+
+	/// Sets the number of bits per sample the output will be converted to.
+	#[must_use = "This method consumes self and returns a modified DitherSettings, so the return value should be used"]
+	pub fn bit_depth(self, bit_depth: u32) -> Self {
+		Self { bit_depth }
+	}
+}
+
+impl Default for DitherSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+pub(crate) struct OutputStage {
+	limiter: Option<Limiter>,
+	/// The amplitude above which samples are soft clipped.
+	soft_clip_threshold: Option<f32>,
+	/// The amplitude of the smallest step between integer sample values.
+	dither_step: Option<f32>,
+	random: Random,
+}
+
+impl OutputStage {
+	#[must_use]
+	pub fn new(settings: OutputStageSettings, sample_rate: u32) -> Self {
+		Self {
+			limiter: settings
+				.limiter
+				.map(|limiter| Limiter::new(limiter, sample_rate)),
+			soft_clip_threshold: settings
+				.soft_clipper
+				.map(|soft_clipper| soft_clipper.threshold.as_amplitude().min(0.99)),
+			dither_step: settings
+				.dither
+				.map(|dither| 2.0f32.powi(1 - dither.bit_depth.clamp(1, 32) as i32)),
+			random: Random::new(0),
+		}
+	}
+
+	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		if let Some(limiter) = &mut self.limiter {
+			limiter.on_change_sample_rate(sample_rate);
+		}
+	}
+
+	/// Returns how many frames the output is delayed by.
+	#[must_use]
+	pub fn latency(&self) -> usize {
+		self.limiter.as_ref().map_or(0, Limiter::latency)
+	}
+
+	/// Processes one frame of output audio, where each
+	/// sample is for a different output channel.
+	pub fn process(&mut self, frame: &mut [f32]) {
+		if let Some(limiter) = &mut self.limiter {
+			limiter.process(frame);
+		}
+		if let Some(threshold) = self.soft_clip_threshold {
+			for sample in frame.iter_mut() {
+				*sample = soft_clip(*sample, threshold);
+			}
+		}
+		if let Some(step) = self.dither_step {
+			for sample in frame.iter_mut() {
+				*sample += (self.random.next_f32() - self.random.next_f32()) * step;
+			}
+		}
+		for sample in frame {
+			*sample = sample.clamp(-1.0, 1.0);
+		}
+	}
+}
+
+/// Passes through samples quieter than the threshold and smoothly
+/// squashes louder samples towards `1.0`.
+#[must_use]
+fn soft_clip(sample: f32, threshold: f32) -> f32 {
+	let amplitude = sample.abs();
+	if amplitude <= threshold {
+		return sample;
+	}
+	let headroom = 1.0 - threshold;
+	let squashed = threshold + headroom * ((amplitude - threshold) / headroom).tanh();
+	squashed.copysign(sample)
+}
@@ -0,0 +1,213 @@
+use std::{collections::VecDeque, f32::consts::PI};
+
+use crate::channel_layout::MAX_CHANNELS;
+
+use super::LimiterSettings;
+
+/// How many times the audio is oversampled to find peaks between samples.
+const OVERSAMPLING: usize = 4;
+/// The number of input frames used to calculate each oversampled value.
+const NUM_TAPS: usize = 16;
+/// The highest sample rate the limiter can switch to without
+/// allocating memory.
+const MAX_SAMPLE_RATE: u32 = 192_000;
+
+/*
+	The limiter works in a few steps:
+
+	1. For each incoming frame, it finds the loudest peak around it
+	   (including peaks between samples, found by oversampling) and
+	   calculates the gain needed to bring that peak down to the ceiling.
+	   Oversampling needs some future frames, so peaks between samples are
+	   found a few frames late, which is why the lookahead window has a
+	   minimum size.
+	2. The gain is held at the minimum needed over the lookahead window,
+	   so the gain starts decreasing a full window before a peak is output.
+	3. When the held gain rises again, it's slowly released.
+	4. The released gain is averaged over the lookahead window, which smooths
+	   out sudden changes. Since every gain in the window is no higher than
+	   the gain needed for the frame being output, neither is the average.
+
+	The audio is delayed by the lookahead window so it lines up with the gain.
+*/
+pub(super) struct Limiter {
+	settings: LimiterSettings,
+	ceiling: f32,
+	release_coefficient: f32,
+	/// The number of frames to look ahead for peaks.
+	window_size: usize,
+	/// Frames of audio waiting to be output.
+	delay_line: Vec<[f32; MAX_CHANNELS]>,
+	/// Coefficients of the windowed sinc filters used to calculate
+	/// each point between two frames.
+	oversampling_filters: [[f32; NUM_TAPS]; OVERSAMPLING - 1],
+	/// Recent frames of input audio.
+	history: [[f32; MAX_CHANNELS]; NUM_TAPS],
+	/// Candidates for the lowest required gain in the lookahead window,
+	/// as (frame index, gain) pairs. The gains are always increasing,
+	/// so the first item is the minimum.
+	minimum_gains: VecDeque<(u64, f32)>,
+	envelope: f32,
+	/// Recent envelope values, averaged to get the gain to apply.
+	envelope_history: Vec<f32>,
+	envelope_sum: f64,
+	frame_index: u64,
+}
+
+impl Limiter {
+	#[must_use]
+	pub fn new(settings: LimiterSettings, sample_rate: u32) -> Self {
+		// allocate enough memory for any sample rate up front so that changing
+		// the sample rate on the audio thread doesn't have to
+		let max_window_size = window_size(settings, sample_rate.max(MAX_SAMPLE_RATE));
+		let mut limiter = Self {
+			settings,
+			ceiling: settings.ceiling.as_amplitude().clamp(0.001, 1.0),
+			release_coefficient: 0.0,
+			window_size: 0,
+			delay_line: Vec::with_capacity(max_window_size),
+			oversampling_filters: oversampling_filters(),
+			history: [[0.0; MAX_CHANNELS]; NUM_TAPS],
+			minimum_gains: VecDeque::with_capacity(max_window_size + 2),
+			envelope: 1.0,
+			envelope_history: Vec::with_capacity(max_window_size + 1),
+			envelope_sum: 0.0,
+			frame_index: 0,
+		};
+		limiter.on_change_sample_rate(sample_rate);
+		limiter
+	}
+
+	/// Resets the limiter for a new sample rate.
+	///
+	/// This only allocates memory if the sample rate is higher than both
+	/// [`MAX_SAMPLE_RATE`] and the sample rate the limiter was created with.
+	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.window_size = window_size(self.settings, sample_rate);
+		let release_frames = self.settings.release.as_secs_f64() * sample_rate as f64;
+		self.release_coefficient = if release_frames > 0.0 {
+			(-1.0 / release_frames).exp() as f32
+		} else {
+			0.0
+		};
+		self.delay_line.clear();
+		self.delay_line
+			.resize(self.window_size, [0.0; MAX_CHANNELS]);
+		self.history = [[0.0; MAX_CHANNELS]; NUM_TAPS];
+		self.minimum_gains.clear();
+		self.envelope = 1.0;
+		self.envelope_history.clear();
+		self.envelope_history.resize(self.window_size + 1, 1.0);
+		self.envelope_sum = (self.window_size + 1) as f64;
+		self.frame_index = 0;
+	}
+
+	/// Returns how many frames the audio is delayed by.
+	#[must_use]
+	pub fn latency(&self) -> usize {
+		self.window_size
+	}
+
+	pub fn process(&mut self, frame: &mut [f32]) {
+		let num_channels = frame.len().min(MAX_CHANNELS);
+		let mut input = [0.0; MAX_CHANNELS];
+		input[..num_channels].copy_from_slice(&frame[..num_channels]);
+
+		self.history[(self.frame_index % NUM_TAPS as u64) as usize] = input;
+		let required_gain = match self.peak(num_channels) {
+			peak if peak > self.ceiling => self.ceiling / peak,
+			_ => 1.0,
+		};
+
+		// hold the lowest gain required in the lookahead window
+		while self
+			.minimum_gains
+			.back()
+			.is_some_and(|(_, gain)| *gain >= required_gain)
+		{
+			self.minimum_gains.pop_back();
+		}
+		self.minimum_gains
+			.push_back((self.frame_index, required_gain));
+		let window_length = self.envelope_history.len() as u64;
+		while self
+			.minimum_gains
+			.front()
+			.is_some_and(|(index, _)| index + window_length <= self.frame_index)
+		{
+			self.minimum_gains.pop_front();
+		}
+		let held_gain = self.minimum_gains.front().map_or(1.0, |(_, gain)| *gain);
+
+		// release slowly, but never let the gain be higher than the held gain
+		self.envelope = if held_gain < self.envelope {
+			held_gain
+		} else {
+			held_gain + (self.envelope - held_gain) * self.release_coefficient
+		};
+
+		// smooth the gain
+		let history_index = (self.frame_index % window_length) as usize;
+		self.envelope_sum += self.envelope as f64 - self.envelope_history[history_index] as f64;
+		self.envelope_history[history_index] = self.envelope;
+		let gain = (self.envelope_sum / window_length as f64) as f32;
+
+		// output the delayed audio
+		let delay_index = (self.frame_index % self.window_size as u64) as usize;
+		let delayed = std::mem::replace(&mut self.delay_line[delay_index], input);
+		for (sample, delayed) in frame.iter_mut().zip(delayed) {
+			*sample = delayed * gain;
+		}
+
+		self.frame_index += 1;
+	}
+
+	/// Finds the loudest peak of the newest input frame and the peaks
+	/// between the two frames in the middle of the history.
+	#[must_use]
+	fn peak(&self, num_channels: usize) -> f32 {
+		let newest_index = (self.frame_index % NUM_TAPS as u64) as usize;
+		let mut peak = 0.0f32;
+		for channel in 0..num_channels {
+			peak = peak.max(self.history[newest_index][channel].abs());
+			for filter in &self.oversampling_filters {
+				let mut interpolated = 0.0;
+				for (tap, coefficient) in filter.iter().enumerate() {
+					// the oldest frame is the one after the newest frame
+					let history_index = (newest_index + 1 + tap) % NUM_TAPS;
+					interpolated += self.history[history_index][channel] * coefficient;
+				}
+				peak = peak.max(interpolated.abs());
+			}
+		}
+		peak
+	}
+}
+
+/// Returns the number of frames to look ahead for peaks.
+#[must_use]
+fn window_size(settings: LimiterSettings, sample_rate: u32) -> usize {
+	((settings.lookahead.as_secs_f64() * sample_rate as f64).round() as usize).max(NUM_TAPS / 2)
+}
+
+/// Creates Hann-windowed sinc filters for calculating the points between
+/// the two middle frames of the history.
+#[must_use]
+fn oversampling_filters() -> [[f32; NUM_TAPS]; OVERSAMPLING - 1] {
+	let mut filters = [[0.0; NUM_TAPS]; OVERSAMPLING - 1];
+	let half_width = (NUM_TAPS / 2) as f32;
+	for (i, filter) in filters.iter_mut().enumerate() {
+		let fraction = (i + 1) as f32 / OVERSAMPLING as f32;
+		for (tap, coefficient) in filter.iter_mut().enumerate() {
+			let distance = tap as f32 - (half_width - 1.0) - fraction;
+			let sinc = (PI * distance).sin() / (PI * distance);
+			let window = 0.5 * (1.0 + (PI * distance / half_width).cos());
+			*coefficient = sinc * window;
+		}
+		let sum: f32 = filter.iter().sum();
+		for coefficient in filter {
+			*coefficient /= sum;
+		}
+	}
+	filters
+}
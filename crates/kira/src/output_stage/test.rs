@@ -0,0 +1,177 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use approx::assert_relative_eq;
+
+use crate::{
+	backend::offline::{OfflineBackend, OfflineBackendSettings, RenderLength},
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	AudioManager, AudioManagerSettings, Decibels, Frame,
+};
+
+use super::{
+	DitherSettings, LimiterSettings, OutputStage, OutputStageSettings, SoftClipperSettings,
+};
+
+/// Tests that the output is hard clipped by default.
+#[test]
+fn hard_clips_by_default() {
+	let mut output_stage = OutputStage::new(OutputStageSettings::default(), 48_000);
+	let mut frame = [1.5, -2.0, 0.5];
+	output_stage.process(&mut frame);
+	assert_eq!(frame, [1.0, -1.0, 0.5]);
+}
+
+/// Tests that the soft clipper leaves quiet samples alone and
+/// smoothly squashes loud samples.
+#[test]
+fn soft_clips() {
+	let mut output_stage = OutputStage::new(
+		OutputStageSettings::new().soft_clipper(SoftClipperSettings::new()),
+		48_000,
+	);
+	let mut previous_output = 0.0;
+	for i in 0..=40 {
+		let input = i as f32 * 0.05;
+		let mut frame = [input, -input];
+		output_stage.process(&mut frame);
+		if input <= 0.5 {
+			assert_eq!(frame, [input, -input]);
+		} else {
+			assert!(frame[0] > previous_output);
+			assert!(frame[0] < 1.0);
+			assert_eq!(frame[1], -frame[0]);
+		}
+		previous_output = frame[0];
+	}
+}
+
+/// Tests that the limiter delays audio by the lookahead duration
+/// and leaves quiet audio unchanged.
+#[test]
+fn limiter_delays_quiet_audio() {
+	let mut output_stage = OutputStage::new(
+		OutputStageSettings::new()
+			.limiter(LimiterSettings::new().lookahead(Duration::from_secs(10))),
+		1,
+	);
+	let mut outputs = vec![];
+	for i in 1..=20 {
+		let mut frame = [i as f32 * 0.04];
+		output_stage.process(&mut frame);
+		outputs.push(frame[0]);
+	}
+	for (i, output) in outputs.iter().enumerate() {
+		let expected = i.checked_sub(10).map_or(0.0, |i| (i + 1) as f32 * 0.04);
+		assert_relative_eq!(*output, expected);
+	}
+}
+
+/// Tests that the limiter's lookahead window and delayed audio
+/// are reset when the sample rate changes.
+#[test]
+fn limiter_changes_sample_rate() {
+	let mut output_stage = OutputStage::new(
+		OutputStageSettings::new()
+			.limiter(LimiterSettings::new().lookahead(Duration::from_secs(10))),
+		1,
+	);
+	for _ in 0..5 {
+		output_stage.process(&mut [0.5]);
+	}
+	output_stage.on_change_sample_rate(2);
+	assert_eq!(output_stage.latency(), 20);
+	let mut outputs = vec![];
+	for _ in 0..25 {
+		let mut frame = [0.5];
+		output_stage.process(&mut frame);
+		outputs.push(frame[0]);
+	}
+	for (i, output) in outputs.iter().enumerate() {
+		let expected = if i < 20 { 0.0 } else { 0.5 };
+		assert_relative_eq!(*output, expected);
+	}
+}
+
+/// Tests that the limiter keeps peaks, including peaks between
+/// samples, below the ceiling.
+#[test]
+fn limiter_keeps_peaks_below_ceiling() {
+	const SAMPLE_RATE: u32 = 48_000;
+	let ceiling = Decibels(-1.0);
+	let mut output_stage = OutputStage::new(
+		OutputStageSettings::new().limiter(LimiterSettings::new().ceiling(ceiling)),
+		SAMPLE_RATE,
+	);
+	// a sine wave at a quarter of the sample rate with samples
+	// that fall between its peaks
+	let mut outputs = vec![];
+	for i in 0..SAMPLE_RATE / 10 {
+		let phase = i as f32 / 4.0 + 0.125;
+		let mut frame = [(phase * TAU).sin() * 4.0; 2];
+		output_stage.process(&mut frame);
+		outputs.push(frame[0]);
+	}
+	let ceiling_amplitude = ceiling.as_amplitude();
+	for output in &outputs {
+		assert!(output.abs() <= ceiling_amplitude + 0.0001);
+	}
+	// the peaks between samples are louder than the samples themselves,
+	// so the samples should end up well below the ceiling
+	let loudest = outputs
+		.iter()
+		.fold(0.0f32, |loudest, output| loudest.max(output.abs()));
+	assert!(loudest > ceiling_amplitude * 0.5);
+	assert!(loudest < ceiling_amplitude * 0.75);
+}
+
+/// Tests that dither adds a small amount of noise to the output.
+#[test]
+fn dithers() {
+	let mut output_stage = OutputStage::new(
+		OutputStageSettings::new().dither(DitherSettings::new().bit_depth(16)),
+		48_000,
+	);
+	let step = 1.0 / 32768.0;
+	let mut num_non_zero_samples = 0;
+	for _ in 0..1000 {
+		let mut frame = [0.0; 2];
+		output_stage.process(&mut frame);
+		for sample in frame {
+			assert!(sample.abs() < step);
+			if sample != 0.0 {
+				num_non_zero_samples += 1;
+			}
+		}
+	}
+	assert!(num_non_zero_samples > 1900);
+}
+
+/// Tests that the output stage is applied to the output of the renderer.
+#[test]
+fn applies_to_renderer_output() {
+	let mut manager = AudioManager::<OfflineBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 8,
+		output_stage: OutputStageSettings::new().soft_clipper(SoftClipperSettings::new()),
+		backend_settings: OfflineBackendSettings {
+			sample_rate: 100,
+			num_channels: 2,
+		},
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: vec![Frame::from_mono(2.0); 100].into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	let samples = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::Duration(Duration::from_millis(500)));
+	let last_sample = samples[samples.len() - 1];
+	assert!(last_sample > 0.5);
+	assert!(last_sample < 1.0);
+}
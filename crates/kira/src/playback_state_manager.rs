@@ -1,7 +1,63 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	sync::atomic::{AtomicBool, AtomicU8, Ordering},
+	time::Duration,
+};
+
 use crate::{
-	info::Info, parameter::Parameter, sound::PlaybackState, Decibels, StartTime, Tween, Value,
+	command::CommandReader, info::Info, parameter::Parameter, sound::PlaybackState, Decibels,
+	StartTime, Tween, Value,
 };
 
+/// The playback state of a sound, shared between the audio thread
+/// and the sound's handle.
+#[derive(Debug)]
+pub(crate) struct SharedPlaybackState {
+	state: AtomicU8,
+	removed: AtomicBool,
+}
+
+impl SharedPlaybackState {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: AtomicU8::new(PlaybackState::Playing as u8),
+			removed: AtomicBool::new(false),
+		}
+	}
+
+	#[must_use]
+	pub fn load(&self) -> PlaybackState {
+		match self.state.load(Ordering::SeqCst) {
+			0 => PlaybackState::Playing,
+			1 => PlaybackState::Pausing,
+			2 => PlaybackState::Paused,
+			3 => PlaybackState::WaitingToResume,
+			4 => PlaybackState::Resuming,
+			5 => PlaybackState::Stopping,
+			6 => PlaybackState::Stopped,
+			_ => panic!("Invalid playback state"),
+		}
+	}
+
+	pub fn store(&self, state: PlaybackState) {
+		self.state.store(state as u8, Ordering::SeqCst);
+	}
+
+	/// Marks the sound as removed. Handles call this when they're
+	/// dropped, since there's no way to control the sound afterward.
+	pub fn mark_removed(&self) {
+		self.removed.store(true, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn removed(&self) -> bool {
+		self.removed.load(Ordering::SeqCst)
+	}
+}
+
 pub(crate) struct PlaybackStateManager {
 	state: State,
 	volume_fade: Parameter<Decibels>,
@@ -35,7 +91,7 @@ impl PlaybackStateManager {
 			State::Paused => PlaybackState::Paused,
 			State::WaitingToResume { .. } => PlaybackState::WaitingToResume,
 			State::Resuming => PlaybackState::Resuming,
-			State::Stopping => PlaybackState::Stopping,
+			State::Stopping | State::CrossfadingOut { .. } => PlaybackState::Stopping,
 			State::Stopped => PlaybackState::Stopped,
 		}
 	}
@@ -74,6 +130,71 @@ impl PlaybackStateManager {
 			.set(Value::Fixed(Decibels::SILENCE), fade_out_tween);
 	}
 
+	/// Makes the fade volume follow the incoming side of a crossfade.
+	///
+	/// This replaces the fade-in, if there is one.
+	pub fn crossfade_in(&mut self, volume: Value<Decibels>) {
+		self.volume_fade = Parameter::new(volume, Decibels::SILENCE);
+	}
+
+	/// Makes the fade volume follow the outgoing side of a crossfade
+	/// starting at `start_time`, and stops playback once the volume
+	/// has faded to silence.
+	pub fn crossfade_out(&mut self, volume: Value<Decibels>, start_time: StartTime) {
+		if let State::Stopped = &self.state {
+			return;
+		}
+		self.state = State::CrossfadingOut { started: false };
+		self.volume_fade.set(
+			volume,
+			Tween {
+				start_time,
+				duration: Duration::ZERO,
+				..Default::default()
+			},
+		);
+	}
+
+	/// Pauses, resumes, and stops playback in response to commands
+	/// from a sound handle and stores the new playback state in `shared_state`.
+	pub fn read_commands(
+		&mut self,
+		pause: &mut CommandReader<Tween>,
+		resume: &mut CommandReader<(StartTime, Tween)>,
+		stop: &mut CommandReader<Tween>,
+		shared_state: &SharedPlaybackState,
+	) {
+		let mut changed = false;
+		if let Some(tween) = pause.read() {
+			self.pause(tween);
+			changed = true;
+		}
+		if let Some((start_time, tween)) = resume.read() {
+			self.resume(start_time, tween);
+			changed = true;
+		}
+		if let Some(tween) = stop.read() {
+			self.stop(tween);
+			changed = true;
+		}
+		if changed {
+			self.share_state(shared_state);
+		}
+	}
+
+	/// Returns `true` if playback has stopped or the sound's handle
+	/// was dropped.
+	#[must_use]
+	pub fn finished(&self, shared_state: &SharedPlaybackState) -> bool {
+		self.playback_state() == PlaybackState::Stopped || shared_state.removed()
+	}
+
+	/// Stores the current playback state in `shared_state` so that
+	/// handles can read it.
+	pub fn share_state(&self, shared_state: &SharedPlaybackState) {
+		shared_state.store(self.playback_state());
+	}
+
 	pub fn mark_as_stopped(&mut self) {
 		self.state = State::Stopped;
 	}
@@ -116,6 +237,13 @@ impl PlaybackStateManager {
 					return true;
 				}
 			}
+			State::CrossfadingOut { started } => {
+				*started |= finished;
+				if *started && self.volume_fade.value() <= Decibels::SILENCE {
+					self.state = State::Stopped;
+					return true;
+				}
+			}
 			State::Stopped => {}
 		}
 		false
@@ -134,5 +262,10 @@ enum State {
 	},
 	Resuming,
 	Stopping,
+	/// Following the outgoing side of a crossfade. `started` is `true`
+	/// once the crossfade has started.
+	CrossfadingOut {
+		started: bool,
+	},
 	Stopped,
 }
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use crate::{
+	command::command_writer_and_reader, info::MockInfoBuilder, sound::PlaybackState, Tween,
+};
+
+use super::{PlaybackStateManager, SharedPlaybackState};
+
+/// Tests that stopping with a fade-out reports `Stopping` until
+/// the fade-out finishes and then reports `Stopped`.
+#[test]
+fn stop() {
+	let info = MockInfoBuilder::new().build();
+	let shared_state = SharedPlaybackState::new();
+	let mut playback_state_manager = PlaybackStateManager::new(None);
+	let (_, mut pause) = command_writer_and_reader();
+	let (_, mut resume) = command_writer_and_reader();
+	let (mut stop_writer, mut stop) = command_writer_and_reader();
+	stop_writer.write(Tween {
+		duration: Duration::from_secs(1),
+		..Default::default()
+	});
+	playback_state_manager.read_commands(&mut pause, &mut resume, &mut stop, &shared_state);
+	assert_eq!(shared_state.load(), PlaybackState::Stopping);
+
+	assert!(!playback_state_manager.update(0.5, &info));
+	assert_eq!(
+		playback_state_manager.playback_state(),
+		PlaybackState::Stopping
+	);
+	assert!(!playback_state_manager.finished(&shared_state));
+
+	assert!(playback_state_manager.update(0.5, &info));
+	playback_state_manager.share_state(&shared_state);
+	assert_eq!(shared_state.load(), PlaybackState::Stopped);
+	assert!(playback_state_manager.finished(&shared_state));
+}
+
+/// Tests that a sound is finished once its handle is dropped,
+/// even if it's still playing.
+#[test]
+fn removed() {
+	let shared_state = SharedPlaybackState::new();
+	let playback_state_manager = PlaybackStateManager::new(None);
+	assert!(!playback_state_manager.finished(&shared_state));
+	shared_state.mark_removed();
+	assert_eq!(
+		playback_state_manager.playback_state(),
+		PlaybackState::Playing
+	);
+	assert!(playback_state_manager.finished(&shared_state));
+}
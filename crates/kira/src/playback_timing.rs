@@ -0,0 +1,147 @@
+//! Keeps track of when rendered audio will be heard.
+
+#[cfg(test)]
+mod test;
+
+use std::{
+	sync::{
+		atomic::{AtomicI64, AtomicU64, Ordering},
+		OnceLock,
+	},
+	time::{Duration, Instant},
+};
+
+/// Stored in place of an instant that isn't known yet.
+const UNKNOWN: i64 = i64::MIN;
+
+/// Converts an [`Instant`] to a number of nanoseconds so it can be
+/// stored in an atomic.
+#[must_use]
+pub(crate) fn instant_to_nanos(instant: Instant) -> i64 {
+	let epoch = epoch();
+	if instant >= epoch {
+		(instant - epoch).as_nanos() as i64
+	} else {
+		-((epoch - instant).as_nanos() as i64)
+	}
+}
+
+/// Converts a number of nanoseconds from [`instant_to_nanos`] back
+/// to an [`Instant`].
+#[must_use]
+pub(crate) fn nanos_to_instant(nanos: i64) -> Instant {
+	let epoch = epoch();
+	if nanos >= 0 {
+		epoch + Duration::from_nanos(nanos as u64)
+	} else {
+		epoch - Duration::from_nanos(nanos.unsigned_abs())
+	}
+}
+
+/// An atomic [`Instant`] that may not be known.
+#[derive(Debug)]
+pub(crate) struct AtomicInstant(AtomicI64);
+
+impl AtomicInstant {
+	#[must_use]
+	pub fn unknown() -> Self {
+		Self(AtomicI64::new(UNKNOWN))
+	}
+
+	#[must_use]
+	pub fn load_nanos(&self) -> Option<i64> {
+		Some(self.0.load(Ordering::SeqCst)).filter(|nanos| *nanos != UNKNOWN)
+	}
+
+	#[must_use]
+	pub fn load(&self) -> Option<Instant> {
+		self.load_nanos().map(nanos_to_instant)
+	}
+
+	pub fn store_nanos(&self, nanos: Option<i64>) {
+		self.0.store(nanos.unwrap_or(UNKNOWN), Ordering::SeqCst);
+	}
+}
+
+/// Maps the renderer's audio time (the number of seconds of audio
+/// it has rendered) to the time that audio will be heard.
+///
+/// This is only known once the backend has reported the output latency.
+#[derive(Debug)]
+pub(crate) struct PlaybackTiming {
+	/// The instant (from [`instant_to_nanos`]) at which audio time `0.0`
+	/// was (or would have been) heard.
+	audio_time_zero: AtomicInstant,
+	/// The total output latency in nanoseconds, or `u64::MAX` if unknown.
+	output_latency: AtomicU64,
+}
+
+impl PlaybackTiming {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			audio_time_zero: AtomicInstant::unknown(),
+			output_latency: AtomicU64::new(u64::MAX),
+		}
+	}
+
+	/// Records that the audio at `audio_time` will be heard after
+	/// `output_latency` has passed.
+	pub fn update(&self, audio_time: f64, output_latency: Duration) {
+		let playback_nanos = instant_to_nanos(Instant::now() + output_latency);
+		self.audio_time_zero
+			.store_nanos(Some(playback_nanos - seconds_to_nanos(audio_time)));
+		self.output_latency
+			.store(output_latency.as_nanos() as u64, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	pub fn output_latency(&self) -> Option<Duration> {
+		Some(self.output_latency.load(Ordering::SeqCst))
+			.filter(|nanos| *nanos != u64::MAX)
+			.map(Duration::from_nanos)
+	}
+
+	/// Returns when the audio at `audio_time` will be heard, in
+	/// nanoseconds from [`instant_to_nanos`].
+	#[must_use]
+	pub fn playback_nanos(&self, audio_time: f64) -> Option<i64> {
+		self.audio_time_zero
+			.load_nanos()
+			.map(|zero| zero + seconds_to_nanos(audio_time))
+	}
+}
+
+/// Returns the number of seconds it takes to go from `from` to `to`.
+///
+/// Returns a negative number if `to` is before `from`.
+#[must_use]
+pub(crate) fn seconds_between(from: Instant, to: Instant) -> f64 {
+	if to >= from {
+		(to - from).as_secs_f64()
+	} else {
+		-(from - to).as_secs_f64()
+	}
+}
+
+/// Offsets an instant by a possibly negative number of seconds.
+#[must_use]
+pub(crate) fn offset_instant(instant: Instant, seconds: f64) -> Instant {
+	let offset = Duration::from_secs_f64(seconds.abs());
+	if seconds >= 0.0 {
+		instant + offset
+	} else {
+		instant.checked_sub(offset).unwrap_or(instant)
+	}
+}
+
+#[must_use]
+fn seconds_to_nanos(seconds: f64) -> i64 {
+	(seconds * 1_000_000_000.0) as i64
+}
+
+#[must_use]
+fn epoch() -> Instant {
+	static EPOCH: OnceLock<Instant> = OnceLock::new();
+	*EPOCH.get_or_init(Instant::now)
+}
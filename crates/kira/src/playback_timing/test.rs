@@ -0,0 +1,150 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+	backend::pull::{PullBackend, PullBackendSettings},
+	clock::{ClockSpeed, ClockTime},
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	AudioManager, AudioManagerSettings, Frame,
+};
+
+use super::{instant_to_nanos, nanos_to_instant, PlaybackTiming};
+
+fn create_manager() -> AudioManager<PullBackend> {
+	AudioManager::<PullBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 10,
+		backend_settings: PullBackendSettings { sample_rate: 100 },
+		..Default::default()
+	})
+	.unwrap()
+}
+
+/// Tests that instants can be converted to nanoseconds and back.
+#[test]
+fn converts_instants() {
+	let now = Instant::now();
+	for instant in [
+		now,
+		now + Duration::from_secs(5),
+		now - Duration::from_millis(5),
+	] {
+		assert_eq!(nanos_to_instant(instant_to_nanos(instant)), instant);
+	}
+}
+
+/// Tests that audio time is mapped to playback instants once the
+/// output latency is known.
+#[test]
+fn maps_audio_time_to_instants() {
+	let timing = PlaybackTiming::new();
+	assert_eq!(timing.output_latency(), None);
+	assert_eq!(timing.playback_nanos(0.0), None);
+	let before = Instant::now();
+	timing.update(2.0, Duration::from_millis(50));
+	let after = Instant::now();
+	assert_eq!(timing.output_latency(), Some(Duration::from_millis(50)));
+	let playback_instant = nanos_to_instant(timing.playback_nanos(2.0).unwrap());
+	assert!(playback_instant >= before + Duration::from_millis(50));
+	assert!(playback_instant <= after + Duration::from_millis(50));
+	assert_eq!(
+		timing.playback_nanos(3.5).unwrap() - timing.playback_nanos(2.0).unwrap(),
+		1_500_000_000
+	);
+}
+
+/// Tests that nothing is mapped to playback instants if the backend
+/// doesn't report its latency.
+#[test]
+fn unknown_without_latency() {
+	let mut manager = create_manager();
+	let mut renderer = manager.backend_mut().take_renderer().unwrap();
+	let mut clock = manager.add_clock(ClockSpeed::TicksPerSecond(10.0)).unwrap();
+	clock.start();
+	renderer.fill_f32(&mut [0.0; 100], 2, 100);
+	assert_eq!(manager.output_latency(), None);
+	assert_eq!(clock.playback_instant(clock.time()), None);
+	assert_eq!(clock.time_at_instant(Instant::now()), None);
+}
+
+/// Tests that clock times are mapped to the instants they'll be heard.
+#[test]
+fn maps_clock_times_to_instants() {
+	let mut manager = create_manager();
+	let mut renderer = manager.backend_mut().take_renderer().unwrap();
+	let mut clock = manager.add_clock(ClockSpeed::TicksPerSecond(10.0)).unwrap();
+	clock.start();
+	let before = Instant::now();
+	for _ in 0..5 {
+		renderer.set_output_latency(Duration::from_secs(1));
+		renderer.fill_f32(&mut [0.0; 20], 2, 100);
+	}
+	let after = Instant::now();
+	assert_eq!(manager.output_latency(), Some(Duration::from_secs(1)));
+
+	// the current time will be heard after the output latency
+	let time = clock.time();
+	assert!(time.ticks > 0);
+	let instant = clock.playback_instant(time).unwrap();
+	assert!(instant >= before + Duration::from_secs(1));
+	assert!(instant <= after + Duration::from_secs(1));
+
+	// later ticks are heard later, according to the clock speed
+	let next_instant = clock.playback_instant(time + 3).unwrap();
+	assert!(((next_instant - instant).as_secs_f64() - 0.3).abs() < 0.000_001);
+
+	// the mapping works in reverse
+	let mapped_time = clock.time_at_instant(next_instant).unwrap();
+	assert_eq!(mapped_time.ticks, time.ticks + 3);
+	assert!((mapped_time.fraction - time.fraction).abs() < 0.000_001);
+
+	// times before the clock started aren't mapped
+	assert_eq!(
+		clock.time_at_instant(before - Duration::from_secs(10)),
+		None
+	);
+
+	// times from other clocks aren't mapped
+	let other_clock = manager.add_clock(ClockSpeed::TicksPerSecond(1.0)).unwrap();
+	assert_eq!(
+		clock.playback_instant(ClockTime {
+			clock: other_clock.id(),
+			ticks: 0,
+			fraction: 0.0,
+		}),
+		None
+	);
+
+	// future times can't be mapped when the clock is paused
+	clock.pause();
+	renderer.set_output_latency(Duration::from_secs(1));
+	renderer.fill_f32(&mut [0.0; 20], 2, 100);
+	assert!(clock.playback_instant(clock.time()).is_some());
+	assert_eq!(clock.playback_instant(clock.time() + 1), None);
+}
+
+/// Tests that sound positions are mapped to the instants they'll be heard.
+#[test]
+fn maps_sound_positions_to_instants() {
+	let mut manager = create_manager();
+	let mut renderer = manager.backend_mut().take_renderer().unwrap();
+	let sound = manager
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: vec![Frame::from_mono(0.5); 1000].into(),
+			settings: StaticSoundSettings::new(),
+			slice: None,
+			metadata: None,
+		})
+		.unwrap();
+	assert_eq!(sound.position_playback_instant(), None);
+	let before = Instant::now();
+	for _ in 0..5 {
+		renderer.set_output_latency(Duration::from_millis(200));
+		renderer.fill_f32(&mut [0.0; 20], 2, 100);
+	}
+	let after = Instant::now();
+	// the position was last updated at the start of the last chunk,
+	// which was the end of the second to last chunk
+	let instant = sound.position_playback_instant().unwrap();
+	assert!(instant >= before + Duration::from_millis(200));
+	assert!(instant <= after + Duration::from_millis(200) + Duration::from_millis(100));
+}
@@ -0,0 +1,376 @@
+/*!
+Measuring how much time the mixer spends processing audio.
+
+Profiling is disabled by default. To enable it, set
+[`AudioManagerSettings::profiling`](crate::AudioManagerSettings::profiling)
+to `true`. Then you can get a [`TrackProfile`] from any track handle:
+
+```no_run
+use kira::{AudioManager, AudioManagerSettings, DefaultBackend, track::TrackBuilder};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings {
+	profiling: true,
+	..Default::default()
+})?;
+let track = manager.add_sub_track(TrackBuilder::new())?;
+// later...
+if let Some(profile) = track.profile() {
+	println!("average time: {:?}", profile.total.average);
+	for (i, effect) in profile.effects.iter().enumerate() {
+		println!("effect {}: {:?}", i, effect.average);
+	}
+	for sound in &profile.each_sound {
+		println!("sound {:?}: {:?}", sound.id, sound.time.average);
+	}
+}
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+To find out which entry of [`TrackProfile::each_sound`] belongs to a sound,
+call `last_sound_id` on the track handle right after playing the sound.
+
+To judge whether the mixer is over budget, compare these times to
+the duration of audio processed at a time, which is
+[`internal_buffer_size`](crate::AudioManagerSettings::internal_buffer_size)
+frames divided by the sample rate.
+
+Profiling is not available on `wasm32` targets.
+*/
+
+#[cfg(test)]
+mod test;
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		Arc,
+	},
+	time::{Duration, Instant},
+};
+
+use crate::{info::Info, sound::Sound, Frame};
+
+/// How many of the most recent measurements are used to
+/// calculate a [`ProcessingTime`].
+const NUM_MEASUREMENTS: usize = 100;
+
+/// Statistics about how long something took to process recently.
+///
+/// These are calculated from the last 100 times something was
+/// processed. Each time is the time it took to process one chunk
+/// of [`internal_buffer_size`](crate::AudioManagerSettings::internal_buffer_size)
+/// frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ProcessingTime {
+	/// The average processing time.
+	pub average: Duration,
+	/// The longest processing time.
+	pub peak: Duration,
+}
+
+/// Statistics about how long a mixer track took to process recently.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TrackProfile {
+	/// The time spent processing the track, including its sounds,
+	/// effects, and sub-tracks.
+	///
+	/// For the main track, this is the time spent processing the
+	/// whole mixer.
+	pub total: ProcessingTime,
+	/// The time spent processing all of the sounds playing on the track.
+	///
+	/// This does not include sounds playing on sub-tracks.
+	pub sounds: ProcessingTime,
+	/// The time spent processing each effect on the track, in the
+	/// order the effects were added.
+	pub effects: Vec<ProcessingTime>,
+	/// The time spent processing each sound playing on the track,
+	/// in the order the sounds were played.
+	///
+	/// This does not include sounds playing on sub-tracks.
+	pub each_sound: Vec<SoundProfile>,
+}
+
+/// Identifies a sound in a [`TrackProfile`].
+///
+/// Sounds are numbered in the order they were played on a track,
+/// so IDs are only unique within a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SoundId(pub(crate) u64);
+
+/// Statistics about how long a sound took to process recently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SoundProfile {
+	/// The sound these statistics are for.
+	pub id: SoundId,
+	/// The time spent processing the sound.
+	///
+	/// This is calculated from the last 100 chunks of audio the sound
+	/// processed, which may be fewer than the track processed if the
+	/// sound started recently.
+	pub time: ProcessingTime,
+}
+
+/// Records processing times on the audio thread.
+pub(crate) struct TrackProfiler {
+	shared: Arc<TrackProfilerShared>,
+	num_recorded: u64,
+}
+
+impl TrackProfiler {
+	/// Creates a [`TrackProfiler`], or returns `None` if profiling is disabled.
+	#[must_use]
+	pub fn new(enabled: bool, num_effects: usize) -> Option<Self> {
+		if !enabled || cfg!(target_arch = "wasm32") {
+			return None;
+		}
+		Some(Self {
+			shared: Arc::new(TrackProfilerShared {
+				total: Measurements::new(),
+				sounds: Measurements::new(),
+				effects: (0..num_effects).map(|_| Measurements::new()).collect(),
+				num_recorded: AtomicU64::new(0),
+			}),
+			num_recorded: 0,
+		})
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<TrackProfilerShared> {
+		self.shared.clone()
+	}
+
+	/// Returns the current time if profiling is enabled.
+	#[must_use]
+	pub fn start(profiler: &Option<Self>) -> Option<Instant> {
+		profiler.as_ref().map(|_| Instant::now())
+	}
+
+	pub fn record_total(&mut self, start_time: Option<Instant>) {
+		if let Some(start_time) = start_time {
+			self.shared
+				.total
+				.record(self.num_recorded, start_time.elapsed());
+		}
+		// the total is always recorded last, so this marks the end
+		// of a set of measurements
+		self.num_recorded += 1;
+		self.shared
+			.num_recorded
+			.store(self.num_recorded, Ordering::SeqCst);
+	}
+
+	/// Records a set of measurements where nothing but the total
+	/// time was measured, for example, because the track was paused.
+	pub fn record_idle(&mut self, start_time: Option<Instant>) {
+		self.shared.sounds.record(self.num_recorded, Duration::ZERO);
+		for effect in &self.shared.effects {
+			effect.record(self.num_recorded, Duration::ZERO);
+		}
+		self.record_total(start_time);
+	}
+
+	pub fn record_sounds(&mut self, start_time: Option<Instant>) {
+		if let Some(start_time) = start_time {
+			self.shared
+				.sounds
+				.record(self.num_recorded, start_time.elapsed());
+		}
+	}
+
+	pub fn record_effect(&mut self, index: usize, start_time: Option<Instant>) {
+		if let Some(start_time) = start_time {
+			self.shared.effects[index].record(self.num_recorded, start_time.elapsed());
+		}
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct TrackProfilerShared {
+	total: Measurements,
+	sounds: Measurements,
+	effects: Vec<Measurements>,
+	num_recorded: AtomicU64,
+}
+
+impl TrackProfilerShared {
+	#[must_use]
+	pub fn profile(&self, sound_profilers: Option<&SoundProfilers>) -> TrackProfile {
+		let num_recorded = self.num_recorded.load(Ordering::SeqCst);
+		TrackProfile {
+			total: self.total.processing_time(num_recorded),
+			sounds: self.sounds.processing_time(num_recorded),
+			effects: self
+				.effects
+				.iter()
+				.map(|effect| effect.processing_time(num_recorded))
+				.collect(),
+			each_sound: sound_profilers
+				.map(SoundProfilers::profiles)
+				.unwrap_or_default(),
+		}
+	}
+}
+
+/// Keeps track of the processing times of the sounds playing on a track.
+#[derive(Debug)]
+pub(crate) struct SoundProfilers {
+	next_id: u64,
+	/// The processing times of each sound that has been played.
+	sounds: Vec<(SoundId, Arc<SoundMeasurements>)>,
+}
+
+impl SoundProfilers {
+	/// Creates a [`SoundProfilers`], or returns `None` if profiling is disabled.
+	#[must_use]
+	pub fn new(enabled: bool) -> Option<Self> {
+		if !enabled || cfg!(target_arch = "wasm32") {
+			return None;
+		}
+		Some(Self {
+			next_id: 0,
+			sounds: vec![],
+		})
+	}
+
+	/// Returns the ID of the most recently played sound.
+	#[must_use]
+	pub fn last_sound_id(&self) -> Option<SoundId> {
+		self.sounds.last().map(|(id, _)| *id)
+	}
+
+	/// Wraps a sound so that its processing time is recorded.
+	#[must_use]
+	pub fn add(&mut self, sound: Box<dyn Sound>) -> Box<dyn Sound> {
+		self.sounds
+			.retain(|(_, measurements)| measurements.is_playing());
+		let measurements = Arc::new(SoundMeasurements {
+			measurements: Measurements::new(),
+			num_recorded: AtomicU64::new(0),
+			finished: AtomicBool::new(false),
+		});
+		self.sounds
+			.push((SoundId(self.next_id), measurements.clone()));
+		self.next_id += 1;
+		Box::new(ProfiledSound {
+			sound,
+			measurements,
+		})
+	}
+
+	#[must_use]
+	fn profiles(&self) -> Vec<SoundProfile> {
+		self.sounds
+			.iter()
+			.filter(|(_, measurements)| measurements.is_playing())
+			.map(|(id, measurements)| SoundProfile {
+				id: *id,
+				time: measurements
+					.measurements
+					.processing_time(measurements.num_recorded.load(Ordering::SeqCst)),
+			})
+			.collect()
+	}
+}
+
+#[derive(Debug)]
+struct SoundMeasurements {
+	measurements: Measurements,
+	num_recorded: AtomicU64,
+	/// Whether the track is about to remove the sound.
+	finished: AtomicBool,
+}
+
+impl SoundMeasurements {
+	/// Returns `true` if the sound is still on the track.
+	#[must_use]
+	fn is_playing(self: &Arc<Self>) -> bool {
+		// if the `ProfiledSound` was dropped without finishing, it
+		// was never added to the track
+		!self.finished.load(Ordering::SeqCst) && Arc::strong_count(self) > 1
+	}
+}
+
+/// A sound that records how long it takes to process.
+struct ProfiledSound {
+	sound: Box<dyn Sound>,
+	measurements: Arc<SoundMeasurements>,
+}
+
+impl ProfiledSound {
+	fn record(&self, start_time: Instant) {
+		let num_recorded = self.measurements.num_recorded.load(Ordering::SeqCst);
+		self.measurements
+			.measurements
+			.record(num_recorded, start_time.elapsed());
+		self.measurements
+			.num_recorded
+			.store(num_recorded + 1, Ordering::SeqCst);
+	}
+}
+
+impl Sound for ProfiledSound {
+	fn on_start_processing(&mut self) {
+		self.sound.on_start_processing();
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		let start_time = Instant::now();
+		self.sound.process(out, dt, info);
+		self.record(start_time);
+	}
+
+	fn process_virtual(&mut self, num_frames: usize, dt: f64, info: &Info) {
+		let start_time = Instant::now();
+		self.sound.process_virtual(num_frames, dt, info);
+		self.record(start_time);
+	}
+
+	fn finished(&self) -> bool {
+		let finished = self.sound.finished();
+		if finished {
+			self.measurements.finished.store(true, Ordering::SeqCst);
+		}
+		finished
+	}
+
+	fn on_stolen(&mut self) {
+		self.measurements.finished.store(true, Ordering::SeqCst);
+		self.sound.on_stolen();
+	}
+}
+
+/// The most recent processing times (in nanoseconds) of something.
+#[derive(Debug)]
+struct Measurements([AtomicU64; NUM_MEASUREMENTS]);
+
+impl Measurements {
+	#[must_use]
+	fn new() -> Self {
+		Self(std::array::from_fn(|_| AtomicU64::new(0)))
+	}
+
+	fn record(&self, index: u64, time: Duration) {
+		self.0[(index % NUM_MEASUREMENTS as u64) as usize]
+			.store(time.as_nanos() as u64, Ordering::SeqCst);
+	}
+
+	#[must_use]
+	fn processing_time(&self, num_recorded: u64) -> ProcessingTime {
+		let num_measurements = num_recorded.min(NUM_MEASUREMENTS as u64) as usize;
+		if num_measurements == 0 {
+			return ProcessingTime::default();
+		}
+		let mut total = 0;
+		let mut peak = 0;
+		for measurement in &self.0[..num_measurements] {
+			let measurement = measurement.load(Ordering::SeqCst);
+			total += measurement;
+			peak = peak.max(measurement);
+		}
+		ProcessingTime {
+			average: Duration::from_nanos(total / num_measurements as u64),
+			peak: Duration::from_nanos(peak),
+		}
+	}
+}
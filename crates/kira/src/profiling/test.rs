@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use crate::{
+	backend::offline::{OfflineBackend, OfflineBackendSettings, RenderLength},
+	effect::{delay::DelayBuilder, filter::FilterBuilder},
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::{SendTrackBuilder, TrackBuilder},
+	AudioManager, AudioManagerSettings, Frame, Tween,
+};
+
+use super::{Measurements, ProcessingTime, SoundId, TrackProfiler};
+
+fn create_manager(profiling: bool) -> AudioManager<OfflineBackend> {
+	AudioManager::<OfflineBackend>::new(AudioManagerSettings {
+		internal_buffer_size: 8,
+		profiling,
+		backend_settings: OfflineBackendSettings {
+			sample_rate: 100,
+			num_channels: 2,
+		},
+		..Default::default()
+	})
+	.unwrap()
+}
+
+fn sound() -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 100,
+		frames: vec![Frame::from_mono(0.5); 100].into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+fn render(manager: &mut AudioManager<OfflineBackend>, duration: Duration) {
+	let _ = manager
+		.backend_mut()
+		.render_to_vec(RenderLength::Duration(duration));
+}
+
+/// Tests that track handles don't return profiles when profiling
+/// is disabled.
+#[test]
+fn disabled_by_default() {
+	let mut manager = create_manager(false);
+	let track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let send_track = manager.add_send_track(SendTrackBuilder::new()).unwrap();
+	render(&mut manager, Duration::from_millis(100));
+	assert!(track.profile().is_none());
+	assert!(send_track.profile().is_none());
+	assert!(manager.main_track().profile().is_none());
+}
+
+/// Tests that every track reports processing times for itself, its
+/// sounds, and each of its effects.
+#[test]
+fn profiles_tracks_and_effects() {
+	let mut manager = create_manager(true);
+	let mut track = manager
+		.add_sub_track(
+			TrackBuilder::new()
+				.with_effect(FilterBuilder::new())
+				.with_effect(DelayBuilder::new()),
+		)
+		.unwrap();
+	let send_track = manager
+		.add_send_track(SendTrackBuilder::new().with_effect(FilterBuilder::new()))
+		.unwrap();
+	track.play(sound()).unwrap();
+	render(&mut manager, Duration::from_millis(500));
+
+	let profile = track.profile().unwrap();
+	assert_eq!(profile.effects.len(), 2);
+	assert!(profile.total.average > Duration::ZERO);
+	assert!(profile.total.peak >= profile.total.average);
+	assert!(profile.sounds.average > Duration::ZERO);
+	for effect in &profile.effects {
+		assert!(effect.average > Duration::ZERO);
+		assert!(effect.average <= profile.total.peak);
+	}
+
+	let profile = send_track.profile().unwrap();
+	assert_eq!(profile.effects.len(), 1);
+	assert!(profile.total.average > Duration::ZERO);
+	assert_eq!(profile.sounds, ProcessingTime::default());
+
+	let profile = manager.main_track().profile().unwrap();
+	assert!(profile.effects.is_empty());
+	assert!(profile.total.average > Duration::ZERO);
+}
+
+/// Tests that each sound playing on a track is reported separately
+/// until it finishes.
+#[test]
+fn profiles_each_sound() {
+	let mut manager = create_manager(true);
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	assert_eq!(track.last_sound_id(), None);
+	track.play(sound()).unwrap();
+	let first_id = track.last_sound_id().unwrap();
+	render(&mut manager, Duration::from_millis(500));
+	track.play(sound()).unwrap();
+	let second_id = track.last_sound_id().unwrap();
+	assert_ne!(first_id, second_id);
+	manager.main_track().play(sound()).unwrap();
+	let main_track_id = manager.main_track().last_sound_id().unwrap();
+	render(&mut manager, Duration::from_millis(200));
+
+	let profile = track.profile().unwrap();
+	let ids = profile
+		.each_sound
+		.iter()
+		.map(|sound| sound.id)
+		.collect::<Vec<_>>();
+	assert_eq!(ids, vec![first_id, second_id]);
+	for sound in &profile.each_sound {
+		assert!(sound.time.average > Duration::ZERO);
+		assert!(sound.time.average <= profile.sounds.peak);
+	}
+	let profile = manager.main_track().profile().unwrap();
+	assert_eq!(profile.each_sound.len(), 1);
+	assert_eq!(profile.each_sound[0].id, main_track_id);
+
+	// the first sound lasts 1 second
+	render(&mut manager, Duration::from_millis(500));
+	let profile = track.profile().unwrap();
+	let ids = profile
+		.each_sound
+		.iter()
+		.map(|sound| sound.id)
+		.collect::<Vec<_>>();
+	assert_eq!(ids, vec![second_id]);
+}
+
+/// Tests that sound IDs aren't reported when profiling is disabled.
+#[test]
+fn no_sound_ids_when_disabled() {
+	let mut manager = create_manager(false);
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	track.play(sound()).unwrap();
+	assert_eq!(track.last_sound_id(), None::<SoundId>);
+}
+
+/// Tests that sounds and effects on a paused track are reported
+/// as taking no time.
+#[test]
+fn paused_tracks_report_no_sound_or_effect_time() {
+	let mut manager = create_manager(true);
+	let mut track = manager
+		.add_sub_track(TrackBuilder::new().with_effect(FilterBuilder::new()))
+		.unwrap();
+	track.play(sound()).unwrap();
+	track.pause(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	// render more than 100 chunks so all of the measurements are
+	// from while the track was paused
+	render(&mut manager, Duration::from_secs(10));
+	let profile = track.profile().unwrap();
+	assert_eq!(profile.sounds, ProcessingTime::default());
+	assert_eq!(profile.effects, vec![ProcessingTime::default()]);
+}
+
+/// Tests that only the most recent measurements are used.
+#[test]
+fn uses_most_recent_measurements() {
+	let measurements = Measurements::new();
+	assert_eq!(measurements.processing_time(0), ProcessingTime::default());
+	for i in 0..150 {
+		let time = if i < 50 { 1000 } else { 10 };
+		measurements.record(i, Duration::from_nanos(time));
+	}
+	assert_eq!(
+		measurements.processing_time(150),
+		ProcessingTime {
+			average: Duration::from_nanos(10),
+			peak: Duration::from_nanos(10),
+		}
+	);
+}
+
+/// Tests that a profiler isn't created if profiling is disabled.
+#[test]
+fn no_profiler_when_disabled() {
+	assert!(TrackProfiler::new(false, 1).is_none());
+	assert!(TrackProfiler::new(true, 1).is_some());
+}
@@ -0,0 +1,31 @@
+/// A small, fast pseudo-random number generator (xorshift64*).
+///
+/// This is not suitable for anything security related, but it's
+/// cheap enough to use on the audio thread and produces the same
+/// numbers for the same seed on every platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Random(u64);
+
+impl Random {
+	#[must_use]
+	pub fn new(seed: u64) -> Self {
+		// the state must never be zero, so mix the seed with splitmix64
+		let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^= z >> 31;
+		Self(if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z })
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 ^= self.0 >> 12;
+		self.0 ^= self.0 << 25;
+		self.0 ^= self.0 >> 27;
+		self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+	}
+
+	/// Returns a random number from `0.0` (inclusive) to `1.0` (exclusive).
+	pub fn next_f32(&mut self) -> f32 {
+		(self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+	}
+}
@@ -2,7 +2,7 @@
 Sources of audio.
 
 Any type that implements [`SoundData`] can be played using
-[`AudioManager::play`](crate::AudioManager::play). Kira comes with two
+[`AudioManager::play`](crate::AudioManager::play). Kira comes with these
 [`SoundData`] implementations:
 
 - [`StaticSoundData`](static_sound::StaticSoundData), which loads an entire chunk of audio
@@ -11,29 +11,66 @@ Any type that implements [`SoundData`] can be played using
 - [`StreamingSoundData`](streaming::StreamingSoundData), which streams audio from a file or cursor
   (only available on desktop platforms). This is more appropriate for long sounds that you only
   play once at a time, like background music. Streaming sounds use less memory than static sounds.
+- [`InputSoundData`](input::InputSoundData), which plays audio captured from an input device,
+  like a microphone.
+- [`OscillatorSoundData`](oscillator::OscillatorSoundData), which generates sine, triangle, saw,
+  and pulse waves.
+- [`NoiseSoundData`](noise::NoiseSoundData), which generates white, pink, and brown noise.
+- [`GranularSoundData`](granular::GranularSoundData), which plays overlapping grains of a
+  [`StaticSoundData`](static_sound::StaticSoundData).
+- [`TrackerSoundData`](tracker::TrackerSoundData), which plays MOD and XM tracker modules.
+- [`QueueSoundData`](queue::QueueSoundData), which plays a sequence of static and streaming
+  sounds back-to-back with no gaps between them.
+- [`ContainerSoundData`](container::ContainerSoundData), which plays a different variation of
+  a [`StaticSoundData`](static_sound::StaticSoundData) each time it's played.
 
-These two sound types should cover most use cases, but if you need something else, you can
+To play notes using multisampled instruments, see [`Sampler`](sampler::Sampler).
+
+To smoothly transition from one playing sound to another, see [`crossfade`].
+
+These sound types should cover most use cases, but if you need something else, you can
 create your own types that implement the [`SoundData`] and [`Sound`] traits.
 */
 
+pub mod container;
+pub mod crossfade;
+#[cfg(feature = "symphonia")]
+mod embedded_cues;
 #[cfg(feature = "symphonia")]
 mod error;
+mod file_metadata;
+pub mod granular;
+pub mod input;
+mod marker;
+pub mod noise;
+pub mod oscillator;
 mod playback_position;
+pub mod queue;
+pub mod sampler;
 pub mod static_sound;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod streaming;
 #[cfg(feature = "symphonia")]
 mod symphonia;
+pub mod tracker;
 mod transport;
 
 use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
 
+#[cfg(feature = "symphonia")]
+pub use embedded_cues::*;
 #[cfg(feature = "symphonia")]
 pub use error::*;
+pub use file_metadata::*;
+pub use marker::*;
 pub use playback_position::*;
 
 use crate::{frame::Frame, info::Info};
 
+/// How many frames the default implementation of [`Sound::process_virtual`]
+/// processes at a time.
+const VIRTUAL_CHUNK_SIZE: usize = 128;
+
 /// A source of audio that is loaded, but not yet playing.
 pub trait SoundData {
 	/// Errors that can occur when starting the sound.
@@ -80,6 +117,27 @@ pub trait Sound: Send {
 		out[0]
 	}
 
+	/// Advances the sound by `num_frames` frames without producing
+	/// any audio.
+	///
+	/// This is called instead of [`process`](Sound::process) while the sound
+	/// is virtual (see [`VoiceLimit::virtualize`](crate::track::VoiceLimit::virtualize)
+	/// and [`TrackBuilder::virtualize_below`](crate::track::TrackBuilder::virtualize_below)).
+	/// The sound should keep track of time as if it were playing normally
+	/// so it can pick up from the right spot once it's audible again.
+	///
+	/// By default, this processes the sound normally and discards the output.
+	/// Sounds that can skip ahead more cheaply should override this.
+	fn process_virtual(&mut self, num_frames: usize, dt: f64, info: &Info) {
+		let mut out = [Frame::ZERO; VIRTUAL_CHUNK_SIZE];
+		let mut frames_remaining = num_frames;
+		while frames_remaining > 0 {
+			let chunk_size = frames_remaining.min(VIRTUAL_CHUNK_SIZE);
+			self.process(&mut out[..chunk_size], dt, info);
+			frames_remaining -= chunk_size;
+		}
+	}
+
 	/// Returns `true` if the sound is finished and can be unloaded.
 	///
 	/// For finite sounds, this will typically be when playback has reached the
@@ -87,6 +145,14 @@ pub trait Sound: Send {
 	/// handle for the sound is dropped.
 	#[must_use]
 	fn finished(&self) -> bool;
+
+	/// Called when the mixer track the sound is playing on stops the sound
+	/// to make room for other sounds (see [`VoiceLimit`](crate::track::VoiceLimit)).
+	///
+	/// The track fades the sound out on its own and removes the sound right
+	/// after this is called, so the sound only needs to update any state
+	/// that's visible from its handle.
+	fn on_stolen(&mut self) {}
 }
 
 /// The playback state of a sound.
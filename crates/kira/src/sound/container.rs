@@ -0,0 +1,56 @@
+/*!
+Plays a different variation of a sound each time.
+
+[`ContainerSoundData`] holds several
+[`StaticSoundData`](crate::sound::static_sound::StaticSoundData) variants.
+Each time the container is played, it picks one of the variants and
+optionally randomizes its volume, playback rate, and start position.
+This is useful for sounds that play often, like footsteps or gunshots,
+where hearing the exact same audio every time would be repetitive.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend, Decibels, PlaybackRate,
+	sound::{
+		container::{ContainerSoundData, ContainerSoundSettings, SelectionMode},
+		static_sound::StaticSoundData,
+	},
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut footsteps = ContainerSoundData::new([
+	StaticSoundData::from_file("footstep_1.ogg")?,
+	StaticSoundData::from_file("footstep_2.ogg")?,
+	StaticSoundData::from_file("footstep_3.ogg")?,
+])
+.with_settings(
+	ContainerSoundSettings::new()
+		.mode(SelectionMode::Shuffle)
+		.volume(Decibels(-3.0)..=Decibels::IDENTITY)
+		.playback_rate(PlaybackRate(0.95)..=PlaybackRate(1.05)),
+);
+// every time the player takes a step...
+manager.play(&mut footsteps)?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+Containers are played by mutable reference so they can keep track of
+which variants have been played. Each play returns a
+[`StaticSoundHandle`](crate::sound::static_sound::StaticSoundHandle)
+for the chosen variant.
+
+The choices a container makes only depend on its seed, so two containers
+with the same variants and settings play the same sequence of variations.
+This makes it possible to replay a recorded session deterministically.
+*/
+
+mod data;
+mod error;
+mod settings;
+
+pub use data::*;
+pub use error::*;
+pub use settings::*;
+
+#[cfg(test)]
+mod test;
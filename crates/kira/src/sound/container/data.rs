@@ -0,0 +1,184 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+	random::Random,
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundHandle},
+		Sound, SoundData,
+	},
+	Decibels, PlaybackRate,
+};
+
+use super::{ContainerSoundSettings, EmptyContainerError, SelectionMode};
+
+/// A collection of [`StaticSoundData`] variants that plays one
+/// of the variants each time it's played.
+#[derive(Debug, Clone)]
+pub struct ContainerSoundData {
+	variants: Vec<StaticSoundData>,
+	settings: ContainerSoundSettings,
+	random: Random,
+	/// The variants left to play in the current round of
+	/// [`SelectionMode::Shuffle`]. The next variant is at the end.
+	shuffled: Vec<usize>,
+	/// The next variant to play in [`SelectionMode::Sequential`].
+	next_sequential: usize,
+	/// The most recently played variant.
+	previous: Option<usize>,
+}
+
+impl ContainerSoundData {
+	/// Creates a new [`ContainerSoundData`] with the given variants and
+	/// the default settings.
+	#[must_use]
+	pub fn new(variants: impl IntoIterator<Item = StaticSoundData>) -> Self {
+		let settings = ContainerSoundSettings::new();
+		Self {
+			variants: variants.into_iter().collect(),
+			random: Random::new(settings.seed),
+			settings,
+			shuffled: vec![],
+			next_sequential: 0,
+			previous: None,
+		}
+	}
+
+	/// Sets the settings for the container.
+	///
+	/// This also resets the container as if it had never been played.
+	#[must_use = "This method consumes self and returns a modified ContainerSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: ContainerSoundSettings) -> Self {
+		let mut new = Self { settings, ..self };
+		new.reset();
+		new
+	}
+
+	/// Returns the settings for the container.
+	#[must_use]
+	pub fn settings(&self) -> &ContainerSoundSettings {
+		&self.settings
+	}
+
+	/// Returns the variants the container chooses from.
+	#[must_use]
+	pub fn variants(&self) -> &[StaticSoundData] {
+		&self.variants
+	}
+
+	/// Adds a variant to the container.
+	///
+	/// If the container is in the middle of a round of
+	/// [`SelectionMode::Shuffle`], a new round is started.
+	pub fn add_variant(&mut self, variant: StaticSoundData) {
+		self.variants.push(variant);
+		self.shuffled.clear();
+	}
+
+	/// Resets the container as if it had never been played.
+	///
+	/// After resetting, the container plays the same sequence of
+	/// variations it played when it was created.
+	pub fn reset(&mut self) {
+		self.random = Random::new(self.settings.seed);
+		self.shuffled.clear();
+		self.next_sequential = 0;
+		self.previous = None;
+	}
+
+	/// Chooses the next variant to play and randomizes its settings.
+	///
+	/// This is what the container does when it's played, so calling
+	/// this affects which variants are played afterward. Returns `None`
+	/// if the container has no variants.
+	#[must_use]
+	pub fn next_variant(&mut self) -> Option<StaticSoundData> {
+		let index = self.next_index()?;
+		let mut variant = self.variants[index].clone();
+		if let Some(volume) = &self.settings.volume {
+			let volume = random_in_range(
+				&mut self.random,
+				volume.start().0 as f64..=volume.end().0 as f64,
+			);
+			variant = variant.volume(Decibels(volume as f32));
+		}
+		if let Some(playback_rate) = &self.settings.playback_rate {
+			let playback_rate = random_in_range(
+				&mut self.random,
+				playback_rate.start().0..=playback_rate.end().0,
+			);
+			variant = variant.playback_rate(PlaybackRate(playback_rate));
+		}
+		if let Some(start_position) = &self.settings.start_position {
+			let start_position = random_in_range(&mut self.random, start_position.clone());
+			variant = variant.start_position(start_position);
+		}
+		Some(variant)
+	}
+
+	#[must_use]
+	fn next_index(&mut self) -> Option<usize> {
+		let num_variants = self.variants.len();
+		if num_variants == 0 {
+			return None;
+		}
+		let index = match self.settings.mode {
+			SelectionMode::Random => self.random_index(num_variants),
+			SelectionMode::Shuffle => {
+				if self.shuffled.is_empty() {
+					self.start_shuffle_round();
+				}
+				self.shuffled
+					.pop()
+					.expect("a shuffle round always has at least one variant")
+			}
+			SelectionMode::Sequential => {
+				let index = self.next_sequential % num_variants;
+				self.next_sequential = index + 1;
+				index
+			}
+		};
+		self.previous = Some(index);
+		Some(index)
+	}
+
+	fn start_shuffle_round(&mut self) {
+		let num_variants = self.variants.len();
+		self.shuffled.extend(0..num_variants);
+		// fisher-yates shuffle
+		for i in (1..num_variants).rev() {
+			let j = self.random_index(i + 1);
+			self.shuffled.swap(i, j);
+		}
+		// don't play the same variant twice in a row across rounds
+		let next = num_variants - 1;
+		if num_variants > 1 && Some(self.shuffled[next]) == self.previous {
+			let other = self.random_index(num_variants - 1);
+			self.shuffled.swap(next, other);
+		}
+	}
+
+	#[must_use]
+	fn random_index(&mut self, len: usize) -> usize {
+		(self.random.next_u64() % len as u64) as usize
+	}
+}
+
+impl SoundData for &mut ContainerSoundData {
+	type Error = EmptyContainerError;
+
+	type Handle = StaticSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let variant = self.next_variant().ok_or(EmptyContainerError)?;
+		Ok(variant
+			.into_sound()
+			.expect("static sounds can always be played"))
+	}
+}
+
+/// Returns a random number from `range.start()` to `range.end()`.
+#[must_use]
+fn random_in_range(random: &mut Random, range: RangeInclusive<f64>) -> f64 {
+	range.start() + (range.end() - range.start()) * random.next_f32() as f64
+}
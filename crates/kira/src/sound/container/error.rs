@@ -0,0 +1,13 @@
+use std::fmt::Display;
+
+/// An error that occurs when playing a container with no variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmptyContainerError;
+
+impl Display for EmptyContainerError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Could not play the container because it has no variants")
+	}
+}
+
+impl std::error::Error for EmptyContainerError {}
@@ -0,0 +1,119 @@
+use std::ops::RangeInclusive;
+
+use crate::{Decibels, PlaybackRate};
+
+/// How a [`ContainerSoundData`](super::ContainerSoundData) chooses
+/// which variant to play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SelectionMode {
+	/// Picks any variant at random. The same variant may be
+	/// picked several times in a row.
+	Random,
+	/// Plays every variant once in a random order before any
+	/// variant is played again. The last variant of one round
+	/// is never the first variant of the next round.
+	#[default]
+	Shuffle,
+	/// Plays the variants in the order they were added, starting
+	/// over from the first variant after the last one.
+	Sequential,
+}
+
+/// Settings for a container sound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerSoundSettings {
+	/// How the container chooses which variant to play.
+	pub mode: SelectionMode,
+	/// The range the volume of each play is randomly chosen from.
+	///
+	/// If this is `None`, each variant uses the volume from
+	/// its own settings.
+	pub volume: Option<RangeInclusive<Decibels>>,
+	/// The range the playback rate of each play is randomly
+	/// chosen from.
+	///
+	/// If this is `None`, each variant uses the playback rate
+	/// from its own settings.
+	pub playback_rate: Option<RangeInclusive<PlaybackRate>>,
+	/// The range the start position of each play is randomly
+	/// chosen from (in seconds).
+	///
+	/// If this is `None`, each variant uses the start position
+	/// from its own settings.
+	pub start_position: Option<RangeInclusive<f64>>,
+	/// The seed for the random number generator used to pick
+	/// variants and randomize them.
+	///
+	/// Containers with the same variants, settings, and seed
+	/// play the same sequence of variations.
+	pub seed: u64,
+}
+
+impl ContainerSoundSettings {
+	/// Creates a new [`ContainerSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			mode: SelectionMode::default(),
+			volume: None,
+			playback_rate: None,
+			start_position: None,
+			seed: 0,
+		}
+	}
+
+	/// Sets how the container chooses which variant to play.
+	#[must_use = "This method consumes self and returns a modified ContainerSoundSettings, so the return value should be used"]
+	pub fn mode(self, mode: SelectionMode) -> Self {
+		Self { mode, ..self }
+	}
+
+	/// Sets the range the volume of each play is randomly chosen from.
+	#[must_use = "This method consumes self and returns a modified ContainerSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Option<RangeInclusive<Decibels>>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the range the playback rate of each play is randomly chosen from.
+
+	Changing the playback rate will change both the speed
+	and the pitch of the sound.
+	*/
+	#[must_use = "This method consumes self and returns a modified ContainerSoundSettings, so the return value should be used"]
+	pub fn playback_rate(
+		self,
+		playback_rate: impl Into<Option<RangeInclusive<PlaybackRate>>>,
+	) -> Self {
+		Self {
+			playback_rate: playback_rate.into(),
+			..self
+		}
+	}
+
+	/// Sets the range the start position of each play is randomly
+	/// chosen from (in seconds).
+	#[must_use = "This method consumes self and returns a modified ContainerSoundSettings, so the return value should be used"]
+	pub fn start_position(self, start_position: impl Into<Option<RangeInclusive<f64>>>) -> Self {
+		Self {
+			start_position: start_position.into(),
+			..self
+		}
+	}
+
+	/// Sets the seed for the random number generator used to pick
+	/// variants and randomize them.
+	#[must_use = "This method consumes self and returns a modified ContainerSoundSettings, so the return value should be used"]
+	pub fn seed(self, seed: u64) -> Self {
+		Self { seed, ..self }
+	}
+}
+
+impl Default for ContainerSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
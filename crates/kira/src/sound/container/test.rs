@@ -0,0 +1,155 @@
+use crate::{
+	frame::Frame,
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		PlaybackPosition, SoundData,
+	},
+	Decibels, PlaybackRate, Value,
+};
+
+use super::{ContainerSoundData, ContainerSoundSettings, EmptyContainerError, SelectionMode};
+
+/// Creates a container whose variants can be told apart by their
+/// first frame.
+fn container(num_variants: usize, settings: ContainerSoundSettings) -> ContainerSoundData {
+	ContainerSoundData::new((0..num_variants).map(|i| StaticSoundData {
+		sample_rate: 1,
+		frames: vec![Frame::from_mono(i as f32)].into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}))
+	.with_settings(settings)
+}
+
+/// Plays the container and returns which variants were chosen.
+fn play(container: &mut ContainerSoundData, num_plays: usize) -> Vec<usize> {
+	(0..num_plays)
+		.map(|_| container.next_variant().unwrap().frames[0].left as usize)
+		.collect()
+}
+
+/// Tests that sequential containers play their variants in order.
+#[test]
+fn sequential() {
+	let mut container = container(
+		3,
+		ContainerSoundSettings::new().mode(SelectionMode::Sequential),
+	);
+	assert_eq!(play(&mut container, 7), [0, 1, 2, 0, 1, 2, 0]);
+}
+
+/// Tests that shuffled containers play every variant once per round
+/// and never play the same variant twice in a row.
+#[test]
+fn shuffle() {
+	for seed in 0..100 {
+		let mut container = container(
+			4,
+			ContainerSoundSettings::new()
+				.mode(SelectionMode::Shuffle)
+				.seed(seed),
+		);
+		let plays = play(&mut container, 40);
+		for round in plays.chunks(4) {
+			let mut round = round.to_vec();
+			round.sort();
+			assert_eq!(round, [0, 1, 2, 3]);
+		}
+		assert!(plays.windows(2).all(|pair| pair[0] != pair[1]));
+	}
+
+	// a container with one variant just plays that variant
+	let mut container = container(1, ContainerSoundSettings::new());
+	assert_eq!(play(&mut container, 3), [0, 0, 0]);
+}
+
+/// Tests that random containers eventually play every variant.
+#[test]
+fn random() {
+	let mut container = container(4, ContainerSoundSettings::new().mode(SelectionMode::Random));
+	let plays = play(&mut container, 100);
+	for variant in 0..4 {
+		assert!(plays.contains(&variant));
+	}
+}
+
+/// Tests that containers with the same seed make the same choices,
+/// and that resetting a container starts the sequence over.
+#[test]
+fn deterministic() {
+	let settings = ContainerSoundSettings::new()
+		.mode(SelectionMode::Random)
+		.volume(Decibels(-6.0)..=Decibels::IDENTITY)
+		.seed(42);
+	let mut a = container(8, settings.clone());
+	let mut b = container(8, settings.clone());
+	let from_a = (0..20).map(|_| a.next_variant()).collect::<Vec<_>>();
+	let from_b = (0..20).map(|_| b.next_variant()).collect::<Vec<_>>();
+	assert_eq!(from_a, from_b);
+
+	a.reset();
+	assert_eq!(a.next_variant(), from_a[0]);
+
+	let mut c = container(8, settings.seed(43));
+	let from_c = (0..20).map(|_| c.next_variant()).collect::<Vec<_>>();
+	assert_ne!(from_a, from_c);
+}
+
+/// Tests that the volume, playback rate, and start position of each
+/// play are chosen from the given ranges.
+#[test]
+fn randomizes_settings() {
+	let mut randomized = container(
+		2,
+		ContainerSoundSettings::new()
+			.volume(Decibels(-6.0)..=Decibels(-3.0))
+			.playback_rate(PlaybackRate(0.5)..=PlaybackRate(2.0))
+			.start_position(0.25..=0.75),
+	);
+	let mut volumes = vec![];
+	for _ in 0..50 {
+		let settings = randomized.next_variant().unwrap().settings;
+		let Value::Fixed(volume) = settings.volume else {
+			panic!("volume should be fixed");
+		};
+		let Value::Fixed(playback_rate) = settings.playback_rate else {
+			panic!("playback rate should be fixed");
+		};
+		let PlaybackPosition::Seconds(start_position) = settings.start_position else {
+			panic!("start position should be in seconds");
+		};
+		assert!((-6.0..=-3.0).contains(&volume.0));
+		assert!((0.5..=2.0).contains(&playback_rate.0));
+		assert!((0.25..=0.75).contains(&start_position));
+		volumes.push(volume.0);
+	}
+	assert!(volumes.windows(2).any(|pair| pair[0] != pair[1]));
+
+	// without ranges, the variants' own settings are used
+	let mut container = container(2, ContainerSoundSettings::new());
+	assert_eq!(
+		container.next_variant().unwrap().settings,
+		StaticSoundSettings::new()
+	);
+}
+
+/// Tests that playing an empty container fails.
+#[test]
+fn empty() {
+	let mut container = ContainerSoundData::new([]);
+	assert!(container.next_variant().is_none());
+	assert_eq!(
+		(&mut container).into_sound().err(),
+		Some(EmptyContainerError)
+	);
+
+	container.add_variant(StaticSoundData {
+		sample_rate: 1,
+		frames: vec![Frame::ZERO].into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	});
+	assert!((&mut container).into_sound().is_ok());
+}
@@ -0,0 +1,58 @@
+/*!
+Smoothly transitions from one sound to another.
+
+[`AudioManager::crossfade`](crate::AudioManager::crossfade) fades out a
+playing sound while fading in a new one. Both sounds follow equal-power
+curves, so the overall loudness stays steady during the transition. The
+incoming sound starts on the same sample that the fade begins, and the
+outgoing sound is stopped once the crossfade is finished.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	clock::ClockSpeed,
+	sound::{
+		crossfade::CrossfadeSettings,
+		static_sound::StaticSoundData,
+	},
+};
+use std::time::Duration;
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let clock = manager.add_clock(ClockSpeed::TicksPerMinute(120.0))?;
+let mut calm = manager.play(StaticSoundData::from_file("calm.ogg")?)?;
+// when a battle starts, crossfade to the battle music on the next beat
+let battle = manager.crossfade(
+	&mut calm,
+	StaticSoundData::from_file("battle.ogg")?,
+	CrossfadeSettings::new()
+		.start_time(clock.time() + 1)
+		.duration(Duration::from_secs(2)),
+)?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+The crossfade is applied on top of each sound's own volume, so the
+sounds can still be made quieter or louder with their handles while
+they're fading. The crossfade takes the place of the incoming sound's
+fade-in tween, and pausing or resuming either sound during the
+crossfade cancels the crossfade for that sound.
+
+Each crossfade uses two modulators, which are removed automatically
+once the crossfade is finished.
+*/
+
+mod curve;
+mod handle;
+mod incoming;
+mod outgoing;
+mod settings;
+
+pub(crate) use curve::*;
+pub use handle::*;
+pub use incoming::*;
+pub use outgoing::*;
+pub use settings::*;
+
+#[cfg(test)]
+mod test;
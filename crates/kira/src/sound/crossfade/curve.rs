@@ -0,0 +1,151 @@
+use std::{
+	f64::consts::FRAC_PI_2,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use crate::{
+	info::{Info, WhenToStart},
+	modulator::{Modulator, ModulatorBuilder, ModulatorId},
+	Decibels, Easing, Mapping, StartTime, Value,
+};
+
+use super::CrossfadeSettings;
+
+/// Maps the output of an [`EqualPowerCurve`] directly to decibels.
+const CURVE_MAPPING: Mapping<Decibels> = Mapping {
+	input_range: (Decibels::SILENCE.0 as f64, -Decibels::SILENCE.0 as f64),
+	output_range: (Decibels::SILENCE, Decibels(-Decibels::SILENCE.0)),
+	easing: Easing::Linear,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CurveDirection {
+	FadeIn,
+	FadeOut,
+}
+
+/// A modulator that outputs the volume (in decibels) of one side of an
+/// equal-power crossfade.
+pub(crate) struct EqualPowerCurve {
+	direction: CurveDirection,
+	start_time: StartTime,
+	duration: Duration,
+	time: f64,
+	shared: Arc<CurveShared>,
+}
+
+impl EqualPowerCurve {
+	/// Returns how far along the crossfade is, from `0.0` to `1.0`.
+	#[must_use]
+	fn progress(&self) -> f64 {
+		if self.duration.is_zero() {
+			return 1.0;
+		}
+		(self.time / self.duration.as_secs_f64()).min(1.0)
+	}
+}
+
+impl Modulator for EqualPowerCurve {
+	fn update(&mut self, dt: f64, info: &Info) {
+		if self.shared.finished() {
+			return;
+		}
+		let started = match &mut self.start_time {
+			StartTime::Immediate => true,
+			StartTime::Delayed(time_remaining) => {
+				if time_remaining.is_zero() {
+					true
+				} else {
+					*time_remaining = time_remaining.saturating_sub(Duration::from_secs_f64(dt));
+					false
+				}
+			}
+			StartTime::ClockTime(clock_time) => match info.when_to_start(*clock_time) {
+				WhenToStart::Now => true,
+				WhenToStart::Later => false,
+				WhenToStart::Never => {
+					self.shared.finished.store(true, Ordering::SeqCst);
+					false
+				}
+			},
+		};
+		if !started {
+			return;
+		}
+		self.time += dt;
+		if self.progress() >= 1.0 {
+			self.shared.finished.store(true, Ordering::SeqCst);
+		}
+	}
+
+	fn value(&self) -> f64 {
+		let progress = self.progress();
+		match self.direction {
+			CurveDirection::FadeIn => amplitude_to_decibels((progress * FRAC_PI_2).sin()),
+			CurveDirection::FadeOut => amplitude_to_decibels((progress * FRAC_PI_2).cos()),
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.shared.finished()
+	}
+}
+
+pub(crate) struct EqualPowerCurveBuilder {
+	pub direction: CurveDirection,
+	pub settings: CrossfadeSettings,
+}
+
+impl ModulatorBuilder for EqualPowerCurveBuilder {
+	type Handle = EqualPowerCurveHandle;
+
+	fn build(self, id: ModulatorId) -> (Box<dyn Modulator>, Self::Handle) {
+		let shared = Arc::new(CurveShared {
+			finished: AtomicBool::new(false),
+		});
+		(
+			Box::new(EqualPowerCurve {
+				direction: self.direction,
+				start_time: self.settings.start_time,
+				duration: self.settings.duration,
+				time: 0.0,
+				shared: shared.clone(),
+			}),
+			EqualPowerCurveHandle { id, shared },
+		)
+	}
+}
+
+pub(crate) struct EqualPowerCurveHandle {
+	id: ModulatorId,
+	pub shared: Arc<CurveShared>,
+}
+
+impl EqualPowerCurveHandle {
+	/// Returns a volume that follows the curve.
+	#[must_use]
+	pub fn volume(&self) -> Value<Decibels> {
+		Value::from_modulator(self.id, CURVE_MAPPING)
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct CurveShared {
+	finished: AtomicBool,
+}
+
+impl CurveShared {
+	#[must_use]
+	pub fn finished(&self) -> bool {
+		self.finished.load(Ordering::SeqCst)
+	}
+}
+
+#[must_use]
+fn amplitude_to_decibels(amplitude: f64) -> f64 {
+	(20.0 * amplitude.log10()).max(Decibels::SILENCE.0 as f64)
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use super::curve::CurveShared;
+
+/// Controls a crossfade and the sound that's fading in.
+#[derive(Debug)]
+pub struct CrossfadeHandle<H> {
+	pub(crate) incoming: H,
+	pub(crate) shared: Arc<CurveShared>,
+}
+
+impl<H> CrossfadeHandle<H> {
+	/// Returns `true` if the crossfade is finished and the outgoing
+	/// sound has been stopped.
+	#[must_use]
+	pub fn finished(&self) -> bool {
+		self.shared.finished()
+	}
+
+	/// Returns a reference to the handle of the sound that's fading in.
+	#[must_use]
+	pub fn incoming(&self) -> &H {
+		&self.incoming
+	}
+
+	/// Returns a mutable reference to the handle of the sound
+	/// that's fading in.
+	#[must_use]
+	pub fn incoming_mut(&mut self) -> &mut H {
+		&mut self.incoming
+	}
+
+	/// Returns the handle of the sound that's fading in.
+	///
+	/// The crossfade continues after this handle is dropped.
+	#[must_use]
+	pub fn into_incoming(self) -> H {
+		self.incoming
+	}
+}
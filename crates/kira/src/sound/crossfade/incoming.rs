@@ -0,0 +1,64 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sound::streaming::StreamingSoundData;
+use crate::{
+	sound::{static_sound::StaticSoundData, Sound, SoundData},
+	Decibels, StartTime, Value,
+};
+
+/// Sound data that can be faded in by a crossfade.
+pub trait CrossfadeIncoming: SoundData {
+	/// Converts the data into a sound that starts playing when the
+	/// crossfade starts and fades in along the given volume.
+	///
+	/// The crossfade is applied on top of the sound's own volume.
+	#[allow(clippy::type_complexity)]
+	fn into_crossfading_sound(
+		self,
+		volume: Value<Decibels>,
+		start_time: StartTime,
+	) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error>;
+}
+
+impl CrossfadeIncoming for StaticSoundData {
+	fn into_crossfading_sound(
+		self,
+		volume: Value<Decibels>,
+		start_time: StartTime,
+	) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (mut sound, handle) = self.start_time(start_time).split();
+		sound.crossfade_in(volume);
+		Ok((Box::new(sound), handle))
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<Error: Send + 'static> CrossfadeIncoming for StreamingSoundData<Error> {
+	fn into_crossfading_sound(
+		self,
+		volume: Value<Decibels>,
+		start_time: StartTime,
+	) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (mut sound, handle, scheduler) = self.start_time(start_time).split()?;
+		scheduler.start();
+		sound.crossfade_in(volume);
+		Ok((Box::new(sound), handle))
+	}
+}
+
+/// The sound that's faded in by a crossfade.
+pub(crate) struct IncomingSoundData<D> {
+	pub data: D,
+	pub volume: Value<Decibels>,
+	pub start_time: StartTime,
+}
+
+impl<D: CrossfadeIncoming> SoundData for IncomingSoundData<D> {
+	type Error = D::Error;
+
+	type Handle = D::Handle;
+
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		self.data
+			.into_crossfading_sound(self.volume, self.start_time)
+	}
+}
@@ -0,0 +1,26 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sound::streaming::StreamingSoundHandle;
+use crate::{sound::static_sound::StaticSoundHandle, Decibels, Value};
+
+use super::CrossfadeSettings;
+
+/// A handle to a playing sound that can be faded out by a crossfade.
+pub trait CrossfadeOutgoing {
+	/// Fades the sound out along the given volume, on top of the
+	/// sound's own volume, and stops the sound when the crossfade
+	/// is finished.
+	fn crossfade_out(&mut self, volume: Value<Decibels>, settings: CrossfadeSettings);
+}
+
+impl CrossfadeOutgoing for StaticSoundHandle {
+	fn crossfade_out(&mut self, volume: Value<Decibels>, settings: CrossfadeSettings) {
+		self.fade_out_and_stop(volume, settings.start_time);
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<Error> CrossfadeOutgoing for StreamingSoundHandle<Error> {
+	fn crossfade_out(&mut self, volume: Value<Decibels>, settings: CrossfadeSettings) {
+		self.fade_out_and_stop(volume, settings.start_time);
+	}
+}
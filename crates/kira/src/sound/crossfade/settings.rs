@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use crate::StartTime;
+
+/// Settings for a crossfade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossfadeSettings {
+	/// When the crossfade should start.
+	///
+	/// Use a [`ClockTime`](crate::clock::ClockTime) to start the
+	/// crossfade on a beat.
+	pub start_time: StartTime,
+	/// How long the crossfade takes.
+	pub duration: Duration,
+}
+
+impl CrossfadeSettings {
+	/// Creates a new [`CrossfadeSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			duration: Duration::from_secs(1),
+		}
+	}
+
+	/// Sets when the crossfade should start.
+	#[must_use = "This method consumes self and returns a modified CrossfadeSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets how long the crossfade takes.
+	#[must_use = "This method consumes self and returns a modified CrossfadeSettings, so the return value should be used"]
+	pub fn duration(self, duration: Duration) -> Self {
+		Self { duration, ..self }
+	}
+}
+
+impl Default for CrossfadeSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
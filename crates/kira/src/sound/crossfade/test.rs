@@ -0,0 +1,192 @@
+use std::{f64::consts::FRAC_PI_2, time::Duration};
+
+use crate::{
+	clock::ClockTime,
+	frame::Frame,
+	info::MockInfoBuilder,
+	modulator::{Modulator, ModulatorBuilder},
+	sound::{static_sound::StaticSoundData, PlaybackState, SoundData},
+	Decibels, Panning, Parameter, StartTime,
+};
+
+use super::{
+	CrossfadeOutgoing, CrossfadeSettings, CurveDirection, EqualPowerCurveBuilder,
+	EqualPowerCurveHandle, IncomingSoundData,
+};
+
+const DT: f64 = 0.01;
+
+fn curve(
+	direction: CurveDirection,
+	settings: CrossfadeSettings,
+) -> (Box<dyn Modulator>, EqualPowerCurveHandle) {
+	let id = MockInfoBuilder::new().add_modulator(0.0);
+	EqualPowerCurveBuilder {
+		direction,
+		settings,
+	}
+	.build(id)
+}
+
+/// Returns the amplitude a sound would have with its volume linked to
+/// the given curve.
+fn amplitude(curve: &dyn Modulator, handle: &EqualPowerCurveHandle) -> f64 {
+	let mut builder = MockInfoBuilder::new();
+	builder.add_modulator(curve.value());
+	let info = builder.build();
+	let mut volume = Parameter::new(handle.volume(), Decibels::IDENTITY);
+	volume.update(0.0, &info);
+	volume.value().as_amplitude() as f64
+}
+
+/// Tests that the combined power of the outgoing and incoming sounds
+/// stays the same throughout the crossfade.
+#[test]
+fn keeps_power_constant() {
+	let settings = CrossfadeSettings::new().duration(Duration::from_secs(1));
+	let (mut fade_out, fade_out_handle) = curve(CurveDirection::FadeOut, settings);
+	let (mut fade_in, fade_in_handle) = curve(CurveDirection::FadeIn, settings);
+	let info = MockInfoBuilder::new().build();
+	for i in 0..=100 {
+		let progress = i as f64 * DT;
+		let outgoing = amplitude(&*fade_out, &fade_out_handle);
+		let incoming = amplitude(&*fade_in, &fade_in_handle);
+		assert!(
+			(outgoing.powi(2) + incoming.powi(2) - 1.0).abs() < 0.01,
+			"power at {progress} was {}",
+			outgoing.powi(2) + incoming.powi(2)
+		);
+		assert!((incoming - (progress * FRAC_PI_2).sin()).abs() < 0.01);
+		fade_out.update(DT, &info);
+		fade_in.update(DT, &info);
+	}
+	assert!(fade_out.finished());
+	assert!(fade_in.finished());
+	assert!(fade_out_handle.shared.finished());
+	assert!(fade_in_handle.shared.finished());
+}
+
+/// Tests that the crossfade doesn't progress until its start time.
+#[test]
+fn waits_for_start_time() {
+	let (mut fade_in, _handle) = curve(
+		CurveDirection::FadeIn,
+		CrossfadeSettings::new()
+			.start_time(Duration::from_secs(1))
+			.duration(Duration::from_secs(1)),
+	);
+	let info = MockInfoBuilder::new().build();
+	fade_in.update(0.5, &info);
+	assert_eq!(fade_in.value(), Decibels::SILENCE.0 as f64);
+	fade_in.update(0.5, &info);
+	assert_eq!(fade_in.value(), Decibels::SILENCE.0 as f64);
+	fade_in.update(1.0, &info);
+	assert_eq!(fade_in.value(), 0.0);
+	assert!(fade_in.finished());
+
+	let clock_info = |ticks| {
+		let mut builder = MockInfoBuilder::new();
+		let id = builder.add_clock(true, ticks, 0.0);
+		(id, builder.build())
+	};
+	let (clock, _) = clock_info(0);
+	let (mut fade_in, _handle) = curve(
+		CurveDirection::FadeIn,
+		CrossfadeSettings::new()
+			.start_time(ClockTime {
+				clock,
+				ticks: 2,
+				fraction: 0.0,
+			})
+			.duration(Duration::from_secs(1)),
+	);
+	fade_in.update(1.0, &clock_info(1).1);
+	assert_eq!(fade_in.value(), Decibels::SILENCE.0 as f64);
+	fade_in.update(1.0, &clock_info(2).1);
+	assert!(fade_in.finished());
+
+	// a crossfade waiting on a clock that no longer exists never starts
+	let (mut fade_in, _handle) = curve(
+		CurveDirection::FadeIn,
+		CrossfadeSettings::new().start_time(StartTime::ClockTime(ClockTime {
+			clock,
+			ticks: 2,
+			fraction: 0.0,
+		})),
+	);
+	fade_in.update(1.0, &MockInfoBuilder::new().build());
+	assert!(fade_in.finished());
+	assert_eq!(fade_in.value(), Decibels::SILENCE.0 as f64);
+}
+
+/// Returns a looping sound with every sample set to `1.0`
+/// and a volume of -6 dB.
+fn looping_sound() -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 100,
+		frames: vec![Frame::from_mono(1.0); 10].into(),
+		settings: Default::default(),
+		slice: None,
+		metadata: None,
+	}
+	.loop_region(..)
+	.volume(Decibels(-6.0))
+}
+
+/// Tests that the outgoing sound follows the fade out curve on top
+/// of its own volume and stops once the crossfade is finished.
+#[test]
+fn stops_outgoing_sound() {
+	let (mut sound, mut handle) = looping_sound().into_sound().unwrap();
+	let settings = CrossfadeSettings::new().duration(Duration::from_secs(1));
+	let (mut fade_out, fade_out_handle) = curve(CurveDirection::FadeOut, settings);
+	handle.crossfade_out(fade_out_handle.volume(), settings);
+	for i in 1..=100 {
+		let progress = i as f64 * DT;
+		// like the renderer, update the modulator before processing the sound
+		fade_out.update(DT, &MockInfoBuilder::new().build());
+		let mut builder = MockInfoBuilder::new();
+		builder.add_modulator(fade_out.value());
+		let info = builder.build();
+		sound.on_start_processing();
+		let frame = sound.process_one(DT, &info);
+		let expected = (progress * FRAC_PI_2).cos() as f32 * Decibels(-6.0).as_amplitude();
+		let actual = frame.left / Frame::from_mono(1.0).panned(Panning::CENTER).left;
+		assert!(
+			(actual - expected).abs() < 0.02,
+			"expected {expected} at {progress}, got {actual}"
+		);
+	}
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+	assert!(sound.finished());
+}
+
+/// Tests that the incoming sound follows the fade in curve on top
+/// of its own volume.
+#[test]
+fn fades_in_incoming_sound() {
+	let settings = CrossfadeSettings::new().duration(Duration::from_secs(1));
+	let (mut fade_in, fade_in_handle) = curve(CurveDirection::FadeIn, settings);
+	let (mut sound, _handle) = IncomingSoundData {
+		data: looping_sound(),
+		volume: fade_in_handle.volume(),
+		start_time: settings.start_time,
+	}
+	.into_sound()
+	.unwrap();
+	for i in 1..=100 {
+		let progress = i as f64 * DT;
+		fade_in.update(DT, &MockInfoBuilder::new().build());
+		let mut builder = MockInfoBuilder::new();
+		builder.add_modulator(fade_in.value());
+		let info = builder.build();
+		sound.on_start_processing();
+		let frame = sound.process_one(DT, &info);
+		let expected = (progress * FRAC_PI_2).sin() as f32 * Decibels(-6.0).as_amplitude();
+		let actual = frame.left / Frame::from_mono(1.0).panned(Panning::CENTER).left;
+		assert!(
+			(actual - expected).abs() < 0.02,
+			"expected {expected} at {progress}, got {actual}"
+		);
+	}
+}
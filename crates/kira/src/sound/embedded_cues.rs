@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	collections::HashMap,
+	io::{Cursor, ErrorKind, Read, SeekFrom},
+};
+
+use symphonia::core::{
+	io::{MediaSource, MediaSourceStream},
+	meta::Tag,
+};
+
+use super::{EndPosition, FromFileError, Marker, PlaybackPosition, Region};
+
+/**
+Loop points and markers embedded in an audio file.
+
+Kira reads:
+- The first loop of the `smpl` chunk and the cue points of the `cue `
+  chunk of WAV files. Cue points are named using their `labl` labels
+  if they have one.
+- `LOOPSTART` and `LOOPLENGTH` comments (in samples) of Ogg, FLAC,
+  and other files with Vorbis comments or similar tags. If there's
+  a `LOOPSTART` comment but no `LOOPLENGTH` comment, the loop
+  continues to the end of the audio.
+
+Cues can be applied to static and streaming sounds using their
+`with_cues` methods, or read while loading a file using
+`from_file_with_cues`.
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
+pub struct EmbeddedCues {
+	/// The portion of the audio that should be looped.
+	pub loop_region: Option<Region>,
+	/// Named positions in the audio.
+	pub markers: Vec<Marker>,
+}
+
+impl EmbeddedCues {
+	/// Reads the loop points and markers embedded in an audio file.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "symphonia", not(wasm32)))))]
+	pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, FromFileError> {
+		Self::from_media_source(std::fs::File::open(path)?)
+	}
+
+	/// Reads the loop points and markers embedded in a cursor wrapping
+	/// audio file data.
+	pub fn from_cursor<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: Cursor<T>,
+	) -> Result<Self, FromFileError> {
+		Self::from_media_source(cursor)
+	}
+
+	/// Reads the loop points and markers embedded in a type that implements
+	/// Symphonia's [`MediaSource`] trait.
+	pub fn from_media_source(
+		mut media_source: impl MediaSource + 'static,
+	) -> Result<Self, FromFileError> {
+		let mut header = [0; 12];
+		let is_wav = match media_source.read_exact(&mut header) {
+			Ok(()) => &header[0..4] == b"RIFF" && &header[8..12] == b"WAVE",
+			Err(error) if error.kind() == ErrorKind::UnexpectedEof => false,
+			Err(error) => return Err(error.into()),
+		};
+		if is_wav {
+			return read_riff_chunks(&mut media_source);
+		}
+		media_source.seek(SeekFrom::Start(0))?;
+		Self::from_tags(Box::new(media_source))
+	}
+
+	fn from_tags(media_source: Box<dyn MediaSource>) -> Result<Self, FromFileError> {
+		let probe = symphonia::default::get_probe();
+		let mss = MediaSourceStream::new(media_source, Default::default());
+		let mut probe_result = probe.format(
+			&Default::default(),
+			mss,
+			&Default::default(),
+			&Default::default(),
+		)?;
+		let mut loop_region = None;
+		if let Some(mut metadata) = probe_result.metadata.get() {
+			if let Some(revision) = metadata.skip_to_latest() {
+				loop_region = loop_region_from_tags(revision.tags());
+			}
+		}
+		if loop_region.is_none() {
+			if let Some(revision) = probe_result.format.metadata().skip_to_latest() {
+				loop_region = loop_region_from_tags(revision.tags());
+			}
+		}
+		Ok(Self {
+			loop_region,
+			markers: vec![],
+		})
+	}
+}
+
+/// Reads the loop region from `LOOPSTART` and `LOOPLENGTH` tags.
+#[must_use]
+fn loop_region_from_tags(tags: &[Tag]) -> Option<Region> {
+	let tag_value = |key: &str| -> Option<usize> {
+		tags.iter()
+			.find(|tag| tag.key.eq_ignore_ascii_case(key))
+			.and_then(|tag| tag.value.to_string().trim().parse().ok())
+	};
+	let loop_start = tag_value("LOOPSTART")?;
+	let loop_end = match tag_value("LOOPLENGTH") {
+		Some(0) | None => EndPosition::EndOfAudio,
+		// loops that end past the largest possible position are ignored
+		Some(loop_length) => EndPosition::Custom(PlaybackPosition::Samples(
+			loop_start.checked_add(loop_length)?,
+		)),
+	};
+	Some(Region {
+		start: PlaybackPosition::Samples(loop_start),
+		end: loop_end,
+	})
+}
+
+/// Reads cues from the chunks of a RIFF file, starting after the
+/// `RIFF` header.
+///
+/// Files that end partway through a chunk are tolerated, since the
+/// audio itself may still be playable.
+fn read_riff_chunks(reader: &mut impl MediaSource) -> Result<EmbeddedCues, FromFileError> {
+	let mut cue_points = vec![];
+	let mut labels = HashMap::new();
+	let mut loop_region = None;
+	loop {
+		let mut chunk_header = [0; 8];
+		match reader.read_exact(&mut chunk_header) {
+			Ok(()) => {}
+			Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+			Err(error) => return Err(error.into()),
+		}
+		let chunk_id = &chunk_header[0..4];
+		let chunk_size = u32_at(&chunk_header, 4) as u64;
+		// chunks are padded to an even number of bytes
+		let padded_chunk_size = chunk_size + chunk_size % 2;
+		if !matches!(chunk_id, b"cue " | b"smpl" | b"LIST") {
+			reader.seek(SeekFrom::Current(padded_chunk_size as i64))?;
+			continue;
+		}
+		// the chunk size comes from the file, so the buffer grows as
+		// data is read instead of being allocated up front
+		let mut chunk = vec![];
+		reader
+			.by_ref()
+			.take(padded_chunk_size)
+			.read_to_end(&mut chunk)?;
+		if (chunk.len() as u64) < chunk_size {
+			break;
+		}
+		chunk.truncate(chunk_size as usize);
+		match chunk_id {
+			b"cue " => cue_points = read_cue_chunk(&chunk),
+			b"smpl" => loop_region = read_smpl_chunk(&chunk),
+			_ => read_list_chunk(&chunk, &mut labels),
+		}
+	}
+	cue_points.sort_by_key(|(_, position)| *position);
+	Ok(EmbeddedCues {
+		loop_region,
+		markers: cue_points
+			.into_iter()
+			.map(|(id, position)| {
+				let name = labels.remove(&id).unwrap_or_else(|| format!("cue {id}"));
+				Marker::new(name, PlaybackPosition::Samples(position))
+			})
+			.collect(),
+	})
+}
+
+/// Returns the ID and sample offset of each cue point in a `cue ` chunk.
+#[must_use]
+fn read_cue_chunk(chunk: &[u8]) -> Vec<(u32, usize)> {
+	const CUE_POINT_SIZE: usize = 24;
+	if chunk.len() < 4 {
+		return vec![];
+	}
+	chunk[4..]
+		.chunks_exact(CUE_POINT_SIZE)
+		.take(u32_at(chunk, 0) as usize)
+		.map(|cue_point| (u32_at(cue_point, 0), u32_at(cue_point, 20) as usize))
+		.collect()
+}
+
+/// Returns the region of the first loop in a `smpl` chunk.
+#[must_use]
+fn read_smpl_chunk(chunk: &[u8]) -> Option<Region> {
+	const HEADER_SIZE: usize = 36;
+	const LOOP_SIZE: usize = 24;
+	let num_loops = u32_at(chunk.get(..HEADER_SIZE)?, 28);
+	if num_loops == 0 {
+		return None;
+	}
+	let first_loop = chunk.get(HEADER_SIZE..HEADER_SIZE + LOOP_SIZE)?;
+	let loop_start = u32_at(first_loop, 8) as usize;
+	// the loop end is the last frame of the loop, but regions
+	// don't include their end
+	let loop_end = (u32_at(first_loop, 12) as usize).checked_add(1)?;
+	Some(Region {
+		start: PlaybackPosition::Samples(loop_start),
+		end: EndPosition::Custom(PlaybackPosition::Samples(loop_end)),
+	})
+}
+
+/// Reads the cue point labels from an `adtl` list chunk.
+fn read_list_chunk(chunk: &[u8], labels: &mut HashMap<u32, String>) {
+	if chunk.get(..4) != Some(b"adtl") {
+		return;
+	}
+	let mut sub_chunks = &chunk[4..];
+	while sub_chunks.len() >= 8 {
+		let sub_chunk_size = u32_at(sub_chunks, 4) as usize;
+		// sizes too big to add up are treated like any other
+		// sub-chunk that runs past the end of the list
+		let Some(data) = 8usize
+			.checked_add(sub_chunk_size)
+			.and_then(|end| sub_chunks.get(8..end))
+		else {
+			break;
+		};
+		if &sub_chunks[..4] == b"labl" && data.len() >= 4 {
+			let text = &data[4..];
+			let text = &text[..text
+				.iter()
+				.position(|byte| *byte == 0)
+				.unwrap_or(text.len())];
+			labels.insert(u32_at(data, 0), String::from_utf8_lossy(text).into_owned());
+		}
+		sub_chunks = (8 + sub_chunk_size)
+			.checked_add(sub_chunk_size % 2)
+			.and_then(|padded_size| sub_chunks.get(padded_size..))
+			.unwrap_or_default();
+	}
+}
+
+/// Reads a little-endian `u32` from `bytes` at `offset`.
+#[must_use]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+	u32::from_le_bytes(
+		bytes[offset..offset + 4]
+			.try_into()
+			.expect("the slice should be 4 bytes long"),
+	)
+}
@@ -0,0 +1,195 @@
+use std::io::Cursor;
+
+use symphonia::core::meta::{Tag, Value};
+
+use crate::sound::{
+	static_sound::StaticSoundData, streaming::StreamingSoundData, EndPosition, Marker,
+	PlaybackPosition, Region, ReplayGain,
+};
+
+use super::{loop_region_from_tags, EmbeddedCues};
+
+const NUM_FRAMES: u32 = 100;
+
+/// Creates a RIFF chunk with the given ID and data.
+fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+	let mut chunk = id.to_vec();
+	chunk.extend((data.len() as u32).to_le_bytes());
+	chunk.extend(data);
+	if data.len() % 2 == 1 {
+		chunk.push(0);
+	}
+	chunk
+}
+
+fn u32s(values: &[u32]) -> Vec<u8> {
+	values
+		.iter()
+		.flat_map(|value| value.to_le_bytes())
+		.collect()
+}
+
+/// Creates a silent 16-bit mono WAV file with the given extra chunks
+/// after the audio data.
+fn wav(extra_chunks: &[Vec<u8>]) -> Vec<u8> {
+	wav_with_header_chunks(&[], extra_chunks)
+}
+
+/// Creates a silent 16-bit mono WAV file with the given extra chunks
+/// before and after the audio data.
+fn wav_with_header_chunks(header_chunks: &[Vec<u8>], extra_chunks: &[Vec<u8>]) -> Vec<u8> {
+	let mut fmt = vec![];
+	fmt.extend(1u16.to_le_bytes()); // PCM
+	fmt.extend(1u16.to_le_bytes()); // mono
+	fmt.extend(u32s(&[44100, 88200]));
+	fmt.extend(2u16.to_le_bytes()); // block align
+	fmt.extend(16u16.to_le_bytes()); // bits per sample
+	let mut body = b"WAVE".to_vec();
+	body.extend(chunk(b"fmt ", &fmt));
+	for header_chunk in header_chunks {
+		body.extend(header_chunk);
+	}
+	body.extend(chunk(b"data", &vec![0; NUM_FRAMES as usize * 2]));
+	for extra_chunk in extra_chunks {
+		body.extend(extra_chunk);
+	}
+	chunk(b"RIFF", &body)
+}
+
+/// Tests that loop points and labeled cue points are read from WAV files.
+#[test]
+fn reads_wav_cues() {
+	let mut cue = u32s(&[2]);
+	cue.extend(u32s(&[1, 0]));
+	cue.extend(b"data");
+	cue.extend(u32s(&[0, 0, 50]));
+	cue.extend(u32s(&[2, 0]));
+	cue.extend(b"data");
+	cue.extend(u32s(&[0, 0, 10]));
+
+	let mut label = u32s(&[1]);
+	label.extend(b"chorus\0");
+	let mut adtl = b"adtl".to_vec();
+	adtl.extend(chunk(b"labl", &label));
+
+	let mut smpl = u32s(&[0, 0, 22675, 60, 0, 0, 0, 1, 0]);
+	smpl.extend(u32s(&[0, 0, 20, 79, 0, 0]));
+
+	let wav = wav(&[
+		chunk(b"cue ", &cue),
+		chunk(b"LIST", &adtl),
+		chunk(b"smpl", &smpl),
+	]);
+	let cues = EmbeddedCues::from_cursor(Cursor::new(wav.clone())).unwrap();
+	assert_eq!(
+		cues,
+		EmbeddedCues {
+			loop_region: Some(Region {
+				start: PlaybackPosition::Samples(20),
+				end: EndPosition::Custom(PlaybackPosition::Samples(80)),
+			}),
+			markers: vec![
+				Marker::new("cue 2", PlaybackPosition::Samples(10)),
+				Marker::new("chorus", PlaybackPosition::Samples(50)),
+			],
+		}
+	);
+
+	// the extra chunks don't stop the audio from loading
+	let data = StaticSoundData::from_cursor(Cursor::new(wav))
+		.unwrap()
+		.with_cues(cues.clone());
+	assert_eq!(data.num_frames(), NUM_FRAMES as usize);
+	assert_eq!(data.settings.loop_region, cues.loop_region);
+	assert_eq!(*data.settings.markers, *cues.markers);
+}
+
+/// Tests that WAV files without cues have no loop region or markers.
+#[test]
+fn reads_wav_without_cues() {
+	let cues = EmbeddedCues::from_cursor(Cursor::new(wav(&[]))).unwrap();
+	assert_eq!(cues, EmbeddedCues::default());
+}
+
+/// Tests that chunks that claim to be longer than the rest of the
+/// file are ignored.
+#[test]
+fn ignores_truncated_chunks() {
+	let mut truncated_cue = b"cue ".to_vec();
+	truncated_cue.extend(u32::MAX.to_le_bytes());
+	truncated_cue.extend(u32s(&[1]));
+	let cues = EmbeddedCues::from_cursor(Cursor::new(wav(&[truncated_cue]))).unwrap();
+	assert_eq!(cues, EmbeddedCues::default());
+
+	// labels before a truncated label are still read
+	let mut cue = u32s(&[1, 1, 0]);
+	cue.extend(b"data");
+	cue.extend(u32s(&[0, 0, 50]));
+	let mut label = u32s(&[1]);
+	label.extend(b"chorus\0");
+	let mut adtl = b"adtl".to_vec();
+	adtl.extend(chunk(b"labl", &label));
+	adtl.extend(b"labl");
+	adtl.extend(u32::MAX.to_le_bytes());
+	adtl.extend(u32s(&[1]));
+	let wav = wav(&[chunk(b"cue ", &cue), chunk(b"LIST", &adtl)]);
+	let cues = EmbeddedCues::from_cursor(Cursor::new(wav)).unwrap();
+	assert_eq!(
+		cues.markers,
+		vec![Marker::new("chorus", PlaybackPosition::Samples(50))]
+	);
+}
+
+/// Tests that loop regions are read from `LOOPSTART` and `LOOPLENGTH` tags.
+#[test]
+fn reads_loop_tags() {
+	let tag = |key: &str, value: &str| Tag::new(None, key, Value::String(value.to_string()));
+	assert_eq!(
+		loop_region_from_tags(&[tag("LOOPSTART", "1000"), tag("looplength", "500")]),
+		Some(Region {
+			start: PlaybackPosition::Samples(1000),
+			end: EndPosition::Custom(PlaybackPosition::Samples(1500)),
+		})
+	);
+	assert_eq!(
+		loop_region_from_tags(&[tag("LOOPSTART", "1000")]),
+		Some(Region {
+			start: PlaybackPosition::Samples(1000),
+			end: EndPosition::EndOfAudio,
+		})
+	);
+	assert_eq!(loop_region_from_tags(&[tag("LOOPLENGTH", "500")]), None);
+	assert_eq!(
+		loop_region_from_tags(&[
+			tag("LOOPSTART", &usize::MAX.to_string()),
+			tag("LOOPLENGTH", "1")
+		]),
+		None
+	);
+	assert_eq!(loop_region_from_tags(&[tag("LOOPSTART", "soon")]), None);
+}
+
+/// Tests that file metadata is kept on sound data loaded from files.
+#[test]
+fn reads_file_metadata() {
+	let mut info = b"INFO".to_vec();
+	info.extend(chunk(b"INAM", b"Theme\0"));
+	// Symphonia only reads tags that come before the audio data
+	let wav = wav_with_header_chunks(&[chunk(b"LIST", &info)], &[]);
+
+	let metadata = StaticSoundData::from_cursor(Cursor::new(wav.clone()))
+		.unwrap()
+		.metadata
+		.unwrap();
+	assert_eq!(metadata.title.as_deref(), Some("Theme"));
+	assert_eq!(metadata.num_channels, Some(1));
+	assert_eq!(metadata.bits_per_sample, Some(16));
+	assert!(metadata.codec.as_deref().unwrap().starts_with("pcm"));
+	assert_eq!(metadata.replay_gain, ReplayGain::default());
+
+	let streaming_metadata = StreamingSoundData::from_cursor(Cursor::new(wav))
+		.unwrap()
+		.metadata
+		.unwrap();
+	assert_eq!(streaming_metadata, metadata);
+}
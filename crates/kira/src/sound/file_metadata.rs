@@ -0,0 +1,46 @@
+use crate::Decibels;
+
+/// Information about the audio file a sound was loaded from.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FileMetadata {
+	/// The title of the track, if the file is tagged with one.
+	pub title: Option<String>,
+	/// The artist of the track, if the file is tagged with one.
+	pub artist: Option<String>,
+	/// The album the track is from, if the file is tagged with one.
+	pub album: Option<String>,
+	/// The ReplayGain values embedded in the file.
+	pub replay_gain: ReplayGain,
+	/// The number of channels in the file.
+	///
+	/// Kira mixes every sound in stereo, so mono files are
+	/// played in both channels.
+	pub num_channels: Option<usize>,
+	/// The number of bits per sample in the file. This is only
+	/// known for uncompressed and losslessly compressed audio.
+	pub bits_per_sample: Option<u32>,
+	/// A short name for the codec the audio was encoded with,
+	/// like `"vorbis"`, `"flac"`, or `"pcm_s16le"`.
+	pub codec: Option<String>,
+	/// Every tag in the file as a key-value pair, including the
+	/// ones that have their own fields.
+	///
+	/// The keys are the ones used by the file format, so the same
+	/// kind of tag may have different keys in different formats.
+	pub tags: Vec<(String, String)>,
+}
+
+/// ReplayGain values, which describe how much to change the volume
+/// of audio so that different tracks sound equally loud.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ReplayGain {
+	/// The volume adjustment for the track on its own.
+	pub track_gain: Option<Decibels>,
+	/// The peak amplitude of the track, where `1.0` is full scale.
+	pub track_peak: Option<f32>,
+	/// The volume adjustment for the track when it's played as
+	/// part of its album.
+	pub album_gain: Option<Decibels>,
+	/// The peak amplitude of the album, where `1.0` is full scale.
+	pub album_peak: Option<f32>,
+}
@@ -0,0 +1,68 @@
+/*!
+Plays clouds of overlapping grains of audio.
+
+[`GranularSoundData`] chops a [`StaticSoundData`](crate::sound::static_sound::StaticSoundData)
+into short, overlapping grains and plays them back continuously. Changing
+the position, size, and density of the grains over time turns existing
+audio into evolving textures, and holding the position still "freezes"
+a moment of the audio in place.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+	sound::{
+		granular::{GranularSoundData, GranularSoundSettings},
+		static_sound::StaticSoundData,
+	},
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let source = StaticSoundData::from_file("sound.ogg")?;
+let mut cloud = manager.play(GranularSoundData::new(source).with_settings(
+	GranularSoundSettings::new()
+		.grain_size(0.08)
+		.density(40.0)
+		.position(1.5)
+		.position_jitter(0.05)
+		.pitch_spread(0.2),
+))?;
+// later...
+cloud.set_position(3.0, Tween::default());
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+Like any other [`Value`](crate::Value), the grain settings can be linked
+to modulators.
+
+Each grain is faded in and out, but grains are not normalized, so
+overlapping many grains at once makes the sound louder. Since the sound
+never ends on its own, it keeps playing until it's stopped or its handle
+is dropped.
+*/
+
+mod data;
+mod handle;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use handle::*;
+pub use settings::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
+	StartTime,
+};
+
+command_writers_and_readers! {
+	set_grain_size: ValueChangeCommand<f64>,
+	set_density: ValueChangeCommand<f64>,
+	set_position: ValueChangeCommand<f64>,
+	set_position_jitter: ValueChangeCommand<f64>,
+	set_pitch_spread: ValueChangeCommand<f64>,
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	pause: Tween,
+	resume: (StartTime, Tween),
+	stop: Tween,
+}
@@ -0,0 +1,60 @@
+use crate::sound::{static_sound::StaticSoundData, Sound, SoundData};
+
+use super::{
+	command_writers_and_readers, sound::GranularSound, GranularSoundHandle, GranularSoundSettings,
+};
+
+/// A sound that plays overlapping grains of a [`StaticSoundData`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GranularSoundData {
+	/// The audio to take grains from.
+	///
+	/// If [`StaticSoundData::slice`] is set, grains are only taken
+	/// from the slice. The source's own settings are ignored.
+	pub source: StaticSoundData,
+	/// Settings for the sound.
+	pub settings: GranularSoundSettings,
+}
+
+impl GranularSoundData {
+	/// Creates a new [`GranularSoundData`] with the default settings.
+	#[must_use]
+	pub fn new(source: StaticSoundData) -> Self {
+		Self {
+			source,
+			settings: GranularSoundSettings::new(),
+		}
+	}
+
+	/// Sets the settings for the sound.
+	#[must_use = "This method consumes self and returns a modified GranularSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: GranularSoundSettings) -> Self {
+		Self { settings, ..self }
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (GranularSound, GranularSoundHandle) {
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let sound = GranularSound::new(self, command_readers);
+		let shared = sound.shared();
+		(
+			sound,
+			GranularSoundHandle {
+				command_writers,
+				shared,
+			},
+		)
+	}
+}
+
+impl SoundData for GranularSoundData {
+	type Error = ();
+
+	type Handle = GranularSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
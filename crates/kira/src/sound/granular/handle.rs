@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::{
+	command::handle_param_setters, sound::PlaybackState, Decibels, Panning, StartTime, Tween,
+};
+
+use super::{sound::Shared, CommandWriters};
+
+/// Controls a granular sound.
+///
+/// When the handle is dropped, the sound will stop playing.
+#[derive(Debug)]
+pub struct GranularSoundHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) shared: Arc<Shared>,
+}
+
+impl GranularSoundHandle {
+	/// Returns the current playback state of the sound.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state.load()
+	}
+
+	handle_param_setters! {
+		/// Sets how long each new grain lasts (in seconds).
+		grain_size: f64,
+
+		/// Sets how many grains start every second.
+		///
+		/// At most one grain starts per frame, so densities higher
+		/// than the sample rate are treated as the sample rate.
+		density: f64,
+
+		/// Sets where in the source audio new grains start (in seconds).
+		position: f64,
+
+		/// Sets the maximum random offset of each new grain's start
+		/// position (in seconds).
+		position_jitter: f64,
+
+		/// Sets the maximum random change in pitch of each new grain
+		/// (in semitones).
+		pitch_spread: f64,
+
+		/// Sets the volume of the sound.
+		volume: Decibels,
+
+		/// Sets the panning of the sound, where `-1.0` is hard left,
+		/// `0.0` is center, and `1.0` is hard right.
+		panning: Panning,
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers.pause.write(tween)
+	}
+
+	/// Resumes playback and fades in the sound from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.resume_at(StartTime::Immediate, tween)
+	}
+
+	/// Resumes playback at the given start time and fades in
+	/// the sound from silence with the given tween.
+	pub fn resume_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.resume.write((start_time, tween))
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sound is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers.stop.write(tween)
+	}
+}
+
+impl Drop for GranularSoundHandle {
+	fn drop(&mut self) {
+		self.shared.state.mark_removed();
+	}
+}
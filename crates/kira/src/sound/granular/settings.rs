@@ -0,0 +1,157 @@
+use crate::{Decibels, Panning, StartTime, Tween, Value};
+
+/// Settings for a granular sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GranularSoundSettings {
+	/// When the sound should start playing.
+	pub start_time: StartTime,
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+	/// How long each grain lasts (in seconds).
+	pub grain_size: Value<f64>,
+	/// How many grains start every second.
+	///
+	/// At most one grain starts per frame, so densities higher
+	/// than the sample rate are treated as the sample rate.
+	pub density: Value<f64>,
+	/// Where in the source audio new grains start (in seconds).
+	pub position: Value<f64>,
+	/// The maximum random offset of each grain's start
+	/// position (in seconds).
+	pub position_jitter: Value<f64>,
+	/// The maximum random change in pitch of each grain
+	/// (in semitones).
+	pub pitch_spread: Value<f64>,
+	/// The seed for the random number generator used for jitter
+	/// and pitch spread.
+	///
+	/// Sounds with the same source, settings, and seed produce
+	/// the same audio.
+	pub seed: u64,
+}
+
+impl GranularSoundSettings {
+	/// Creates a new [`GranularSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			fade_in_tween: None,
+			grain_size: Value::Fixed(0.1),
+			density: Value::Fixed(20.0),
+			position: Value::Fixed(0.0),
+			position_jitter: Value::Fixed(0.0),
+			pitch_spread: Value::Fixed(0.0),
+			seed: 0,
+		}
+	}
+
+	/// Sets when the sound should start playing.
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the panning of the sound, where -1.0 is hard left
+	and 1.0 is hard right.
+	*/
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+
+	/// Sets how long each grain lasts (in seconds).
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn grain_size(self, grain_size: impl Into<Value<f64>>) -> Self {
+		Self {
+			grain_size: grain_size.into(),
+			..self
+		}
+	}
+
+	/// Sets how many grains start every second.
+	///
+	/// At most one grain starts per frame, so densities higher
+	/// than the sample rate are treated as the sample rate.
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn density(self, density: impl Into<Value<f64>>) -> Self {
+		Self {
+			density: density.into(),
+			..self
+		}
+	}
+
+	/// Sets where in the source audio new grains start (in seconds).
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn position(self, position: impl Into<Value<f64>>) -> Self {
+		Self {
+			position: position.into(),
+			..self
+		}
+	}
+
+	/// Sets the maximum random offset of each grain's start
+	/// position (in seconds).
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn position_jitter(self, position_jitter: impl Into<Value<f64>>) -> Self {
+		Self {
+			position_jitter: position_jitter.into(),
+			..self
+		}
+	}
+
+	/// Sets the maximum random change in pitch of each grain
+	/// (in semitones).
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn pitch_spread(self, pitch_spread: impl Into<Value<f64>>) -> Self {
+		Self {
+			pitch_spread: pitch_spread.into(),
+			..self
+		}
+	}
+
+	/// Sets the seed for the random number generator used for
+	/// jitter and pitch spread.
+	#[must_use = "This method consumes self and returns a modified GranularSoundSettings, so the return value should be used"]
+	pub fn seed(self, seed: u64) -> Self {
+		Self { seed, ..self }
+	}
+}
+
+impl Default for GranularSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
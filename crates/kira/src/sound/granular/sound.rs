@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod test;
+
+use std::{f64::consts::PI, sync::Arc};
+
+use crate::{
+	command::read_commands_into_parameters,
+	frame::{interpolate_frame, Frame},
+	info::Info,
+	playback_state_manager::{PlaybackStateManager, SharedPlaybackState},
+	random::Random,
+	sound::{static_sound::StaticSoundData, PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime,
+};
+
+use super::{CommandReaders, GranularSoundData};
+
+/// The maximum number of grains that can play at the same time.
+///
+/// New grains are skipped while this many grains are playing.
+const MAX_GRAINS: usize = 128;
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	pub(super) state: SharedPlaybackState,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: SharedPlaybackState::new(),
+		}
+	}
+}
+
+/// A short piece of the source audio that's currently playing.
+#[derive(Debug, Clone, Copy)]
+struct Grain {
+	/// The position in the source audio (in frames).
+	position: f64,
+	/// How many source frames to advance per second.
+	frames_per_second: f64,
+	/// How long the grain has been playing (in seconds).
+	time: f64,
+	/// How long the grain lasts (in seconds).
+	duration: f64,
+}
+
+impl Grain {
+	/// Returns the volume of the grain's fade in and out.
+	#[must_use]
+	fn envelope(&self) -> f64 {
+		// a Hann window
+		(PI * self.time / self.duration).sin().powi(2)
+	}
+}
+
+pub(crate) struct GranularSound {
+	command_readers: CommandReaders,
+	source: StaticSoundData,
+	grain_size: Parameter,
+	density: Parameter,
+	position: Parameter,
+	position_jitter: Parameter,
+	pitch_spread: Parameter,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	start_time: StartTime,
+	playback_state_manager: PlaybackStateManager,
+	grains: [Option<Grain>; MAX_GRAINS],
+	/// How long until the next grain starts (in seconds).
+	time_until_next_grain: f64,
+	random: Random,
+	shared: Arc<Shared>,
+}
+
+impl GranularSound {
+	#[must_use]
+	pub fn new(data: GranularSoundData, command_readers: CommandReaders) -> Self {
+		let GranularSoundData { source, settings } = data;
+		Self {
+			command_readers,
+			source,
+			grain_size: Parameter::new(settings.grain_size, 0.1),
+			density: Parameter::new(settings.density, 20.0),
+			position: Parameter::new(settings.position, 0.0),
+			position_jitter: Parameter::new(settings.position_jitter, 0.0),
+			pitch_spread: Parameter::new(settings.pitch_spread, 0.0),
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			start_time: settings.start_time,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			grains: [None; MAX_GRAINS],
+			time_until_next_grain: 0.0,
+			random: Random::new(settings.seed),
+			shared: Arc::new(Shared::new()),
+		}
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+
+	/// Starts a new grain using the current grain settings.
+	fn start_grain(&mut self, time_in_chunk: f64) {
+		let duration = self.grain_size.interpolated_value(time_in_chunk);
+		if duration <= 0.0 {
+			return;
+		}
+		let Some(slot) = self.grains.iter_mut().find(|grain| grain.is_none()) else {
+			return;
+		};
+		let sample_rate = self.source.sample_rate as f64;
+		let jitter = self.position_jitter.interpolated_value(time_in_chunk)
+			* (self.random.next_f32() as f64 * 2.0 - 1.0);
+		let position = ((self.position.interpolated_value(time_in_chunk) + jitter) * sample_rate)
+			.clamp(0.0, self.source.num_frames() as f64);
+		let semitones = self.pitch_spread.interpolated_value(time_in_chunk)
+			* (self.random.next_f32() as f64 * 2.0 - 1.0);
+		*slot = Some(Grain {
+			position,
+			frames_per_second: sample_rate * 2.0f64.powf(semitones / 12.0),
+			time: 0.0,
+			duration,
+		});
+	}
+
+	/// Returns the frame of the source audio at a fractional position.
+	#[must_use]
+	fn source_frame(&self, position: f64) -> Frame {
+		let index = position as usize;
+		let frame_at_index = |index: Option<usize>| {
+			index
+				.and_then(|index| self.source.frame_at_index(index))
+				.unwrap_or(Frame::ZERO)
+		};
+		interpolate_frame(
+			frame_at_index(index.checked_sub(1)),
+			frame_at_index(Some(index)),
+			frame_at_index(Some(index + 1)),
+			frame_at_index(Some(index + 2)),
+			position.fract() as f32,
+		)
+	}
+}
+
+impl Sound for GranularSound {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(
+			self,
+			grain_size,
+			density,
+			position,
+			position_jitter,
+			pitch_spread,
+			volume,
+			panning
+		);
+		self.playback_state_manager.read_commands(
+			&mut self.command_readers.pause,
+			&mut self.command_readers.resume,
+			&mut self.command_readers.stop,
+			&self.shared.state,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		// update parameters
+		self.grain_size.update(dt * out.len() as f64, info);
+		self.density.update(dt * out.len() as f64, info);
+		self.position.update(dt * out.len() as f64, info);
+		self.position_jitter.update(dt * out.len() as f64, info);
+		self.pitch_spread.update(dt * out.len() as f64, info);
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+
+		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+
+			// start new grains. at most one grain starts per frame,
+			// so the density can't be higher than the sample rate
+			let density = self.density.interpolated_value(time_in_chunk).min(1.0 / dt);
+			if density > 0.0 {
+				while self.time_until_next_grain <= 0.0 {
+					self.start_grain(time_in_chunk);
+					self.time_until_next_grain += 1.0 / density;
+				}
+			} else {
+				self.time_until_next_grain = self.time_until_next_grain.max(0.0);
+			}
+			self.time_until_next_grain -= dt;
+
+			// mix the grains that are playing
+			let mut mixed = Frame::ZERO;
+			for index in 0..MAX_GRAINS {
+				let Some(mut grain) = self.grains[index] else {
+					continue;
+				};
+				mixed += self.source_frame(grain.position) * grain.envelope() as f32;
+				grain.position += grain.frames_per_second * dt;
+				grain.time += dt;
+				self.grains[index] = if grain.time < grain.duration {
+					Some(grain)
+				} else {
+					None
+				};
+			}
+			*frame = (mixed * fade_volume * volume).panned(panning);
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished(&self.shared.state)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.state.store(PlaybackState::Stopped);
+	}
+}
@@ -0,0 +1,117 @@
+use crate::{
+	info::MockInfoBuilder,
+	sound::{
+		granular::{GranularSoundData, GranularSoundSettings},
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		Sound,
+	},
+	Frame,
+};
+
+/// Returns 1 second of audio at 100Hz that's silent except
+/// for the frames in `loud_frames`.
+fn source(loud_frames: std::ops::Range<usize>) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 100,
+		frames: (0..100)
+			.map(|i| {
+				if loud_frames.contains(&i) {
+					Frame::from_mono(1.0)
+				} else {
+					Frame::ZERO
+				}
+			})
+			.collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+fn render(data: GranularSoundData, num_frames: usize) -> Vec<f32> {
+	let (mut sound, _handle) = data.split();
+	let info = MockInfoBuilder::new().build();
+	(0..num_frames)
+		.map(|_| sound.process_one(0.001, &info).left)
+		.collect()
+}
+
+/// Tests that grains are taken from the given position
+/// in the source audio.
+#[test]
+fn position() {
+	let settings = GranularSoundSettings::new().grain_size(0.05);
+	let loud = render(
+		GranularSoundData::new(source(40..70)).with_settings(settings.position(0.5)),
+		500,
+	);
+	assert!(loud.iter().any(|sample| sample.abs() > 0.1));
+	let silent = render(
+		GranularSoundData::new(source(40..70)).with_settings(settings.position(0.1)),
+		500,
+	);
+	assert!(silent.iter().all(|sample| sample.abs() < 0.0001));
+}
+
+/// Tests that grains last for the grain size and start as often
+/// as the density says.
+#[test]
+fn grain_size_and_density() {
+	let output = render(
+		GranularSoundData::new(source(0..100))
+			.with_settings(GranularSoundSettings::new().grain_size(0.05).density(10.0)),
+		300,
+	);
+	for grain_start in [0, 100, 200] {
+		// each grain fades in and out
+		assert!(output[grain_start].abs() < 0.01);
+		assert!(output[grain_start + 25].abs() > 0.5);
+		// and there's silence until the next grain
+		assert!(output[grain_start + 50..grain_start + 100]
+			.iter()
+			.all(|sample| sample.abs() < 0.0001));
+	}
+}
+
+/// Tests that densities higher than the sample rate
+/// start at most one grain per frame.
+#[test]
+fn density_higher_than_sample_rate() {
+	let output = render(
+		GranularSoundData::new(source(0..100))
+			.with_settings(GranularSoundSettings::new().density(f64::INFINITY)),
+		10,
+	);
+	assert!(output.iter().all(|sample| sample.is_finite()));
+	let output = render(
+		GranularSoundData::new(source(0..100))
+			.with_settings(GranularSoundSettings::new().density(1.0e12)),
+		10,
+	);
+	assert!(output.iter().all(|sample| sample.is_finite()));
+}
+
+/// Tests that the same seed always produces the same output,
+/// and different seeds produce different output.
+#[test]
+fn seeded() {
+	let data = GranularSoundData::new(StaticSoundData {
+		sample_rate: 100,
+		frames: (0..100)
+			.map(|i| Frame::from_mono((i as f32 * 0.7).sin()))
+			.collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	})
+	.with_settings(
+		GranularSoundSettings::new()
+			.position(0.5)
+			.position_jitter(0.2)
+			.pitch_spread(3.0),
+	);
+	let output = render(data.clone(), 500);
+	assert_eq!(render(data.clone(), 500), output);
+	let reseeded = render(data.clone().with_settings(data.settings.seed(1)), 500);
+	assert_ne!(reseeded, output);
+}
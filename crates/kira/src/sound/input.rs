@@ -0,0 +1,50 @@
+/*!
+Plays audio captured from input devices, like microphones.
+
+To play captured audio, start an [`InputBackend`](crate::backend::input::InputBackend)
+and pass its [`InputStream`](crate::backend::input::InputStream) to
+[`InputSoundData::new`]. Playing the sound on a track will apply the
+track's effects to the captured audio.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	backend::{cpal::CpalInputBackend, input::InputBackend},
+	effect::reverb::ReverbBuilder,
+	sound::input::InputSoundData,
+	track::TrackBuilder,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut track = manager.add_sub_track(TrackBuilder::new().with_effect(ReverbBuilder::new()))?;
+let (input_backend, input_stream) = CpalInputBackend::start(Default::default())?;
+let input_sound = track.play(InputSoundData::new(input_stream))?;
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+Captured audio is buffered before it's played to smooth out differences
+in timing between the input device and the output device. The amount
+of buffering can be set using [`InputSoundSettings::latency`].
+*/
+
+mod data;
+mod handle;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use handle::*;
+pub use settings::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
+	StartTime,
+};
+
+command_writers_and_readers! {
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	pause: Tween,
+	resume: (StartTime, Tween),
+	stop: Tween,
+}
@@ -0,0 +1,69 @@
+use std::fmt::{Debug, Formatter};
+
+use crate::{
+	backend::input::InputStream,
+	sound::{Sound, SoundData},
+};
+
+use super::{command_writers_and_readers, sound::InputSound, InputSoundHandle, InputSoundSettings};
+
+/// A sound that plays audio captured by an
+/// [`InputBackend`](crate::backend::input::InputBackend).
+pub struct InputSoundData {
+	/// The stream of captured audio.
+	pub stream: InputStream,
+	/// Settings for the sound.
+	pub settings: InputSoundSettings,
+}
+
+impl InputSoundData {
+	/// Creates a new [`InputSoundData`] with the default settings.
+	#[must_use]
+	pub fn new(stream: InputStream) -> Self {
+		Self {
+			stream,
+			settings: InputSoundSettings::new(),
+		}
+	}
+
+	/// Sets the settings for the sound.
+	#[must_use = "This method consumes self and returns a modified InputSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: InputSoundSettings) -> Self {
+		Self { settings, ..self }
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (InputSound, InputSoundHandle) {
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let sound = InputSound::new(self, command_readers);
+		let shared = sound.shared();
+		(
+			sound,
+			InputSoundHandle {
+				command_writers,
+				shared,
+			},
+		)
+	}
+}
+
+impl SoundData for InputSoundData {
+	type Error = ();
+
+	type Handle = InputSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
+
+impl Debug for InputSoundData {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("InputSoundData")
+			.field("sample_rate", &self.stream.sample_rate())
+			.field("settings", &self.settings)
+			.finish()
+	}
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::{
+	command::handle_param_setters, sound::PlaybackState, Decibels, Panning, StartTime, Tween,
+};
+
+use super::{sound::Shared, CommandWriters};
+
+/// Controls an input sound.
+///
+/// When the handle is dropped, the sound will stop playing.
+#[derive(Debug)]
+pub struct InputSoundHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) shared: Arc<Shared>,
+}
+
+impl InputSoundHandle {
+	/// Returns the current playback state of the sound.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state.load()
+	}
+
+	/// Returns the number of times the sound has run out of
+	/// captured audio to play.
+	///
+	/// If this number keeps increasing, consider increasing the
+	/// [`latency`](super::InputSoundSettings::latency) of the sound.
+	#[must_use]
+	pub fn num_underruns(&self) -> u64 {
+		self.shared.num_underruns()
+	}
+
+	handle_param_setters! {
+		/// Sets the volume of the sound.
+		volume: Decibels,
+
+		/// Sets the panning of the sound, where `-1.0` is hard left,
+		/// `0.0` is center, and `1.0` is hard right.
+		panning: Panning,
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback.
+	///
+	/// Audio captured while the sound is paused is discarded.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers.pause.write(tween)
+	}
+
+	/// Resumes playback and fades in the sound from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.resume_at(StartTime::Immediate, tween)
+	}
+
+	/// Resumes playback at the given start time and fades in
+	/// the sound from silence with the given tween.
+	pub fn resume_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.resume.write((start_time, tween))
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sound is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers.stop.write(tween)
+	}
+}
+
+impl Drop for InputSoundHandle {
+	fn drop(&mut self) {
+		self.shared.state.mark_removed();
+	}
+}
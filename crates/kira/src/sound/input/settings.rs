@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use crate::{Decibels, Panning, StartTime, Tween, Value};
+
+/// Settings for an input sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputSoundSettings {
+	/// When the sound should start playing.
+	pub start_time: StartTime,
+	/// How much captured audio to buffer before playing it.
+	///
+	/// Lower values reduce the delay between audio being captured and
+	/// played, but make it more likely that the sound runs out of captured
+	/// audio to play, which causes gaps in the output.
+	pub latency: Duration,
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl InputSoundSettings {
+	/// Creates a new [`InputSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			latency: Duration::from_millis(20),
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets when the sound should start playing.
+	#[must_use = "This method consumes self and returns a modified InputSoundSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets how much captured audio to buffer before playing it.
+	#[must_use = "This method consumes self and returns a modified InputSoundSettings, so the return value should be used"]
+	pub fn latency(self, latency: Duration) -> Self {
+		Self { latency, ..self }
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified InputSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the panning of the sound, where -1.0 is hard left
+	and 1.0 is hard right.
+	*/
+	#[must_use = "This method consumes self and returns a modified InputSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified InputSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for InputSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
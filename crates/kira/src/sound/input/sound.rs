@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::{
+	atomic::{AtomicU64, Ordering},
+	Arc,
+};
+
+use crate::{
+	backend::input::{InputStream, InputStreamShared},
+	command::read_commands_into_parameters,
+	frame::{interpolate_frame, Frame},
+	info::Info,
+	playback_state_manager::{PlaybackStateManager, SharedPlaybackState},
+	sound::{PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime,
+};
+
+use ringbuf::{ Cons, HeapRb as RingBuffer, consumer::Consumer as _, traits::Observer as _ };
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use super::{CommandReaders, InputSoundData};
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	pub(super) state: SharedPlaybackState,
+	num_underruns: AtomicU64,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: SharedPlaybackState::new(),
+			num_underruns: AtomicU64::new(0),
+		}
+	}
+
+	#[must_use]
+	pub fn num_underruns(&self) -> u64 {
+		self.num_underruns.load(Ordering::SeqCst)
+	}
+}
+
+pub(crate) struct InputSound {
+	command_readers: CommandReaders,
+	frame_consumer: Consumer<Frame>,
+	stream_shared: Arc<InputStreamShared>,
+	sample_rate: u32,
+	latency: f64,
+	start_time: StartTime,
+	playback_state_manager: PlaybackStateManager,
+	/// Whether we're waiting for enough audio to be captured
+	/// before we start playing it.
+	buffering: bool,
+	/// The last 4 captured frames used for interpolation.
+	frames: [Frame; 4],
+	fractional_position: f64,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	shared: Arc<Shared>,
+}
+
+impl InputSound {
+	#[must_use]
+	pub fn new(data: InputSoundData, command_readers: CommandReaders) -> Self {
+		let InputSoundData {
+			stream: InputStream { consumer, shared },
+			settings,
+		} = data;
+		Self {
+			command_readers,
+			frame_consumer: consumer,
+			sample_rate: shared.sample_rate(),
+			stream_shared: shared,
+			latency: settings.latency.as_secs_f64(),
+			start_time: settings.start_time,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			buffering: true,
+			frames: [Frame::ZERO; 4],
+			fractional_position: 0.0,
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			shared: Arc::new(Shared::new()),
+		}
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+
+	#[must_use]
+	fn latency_in_frames(&self) -> usize {
+		((self.latency * self.sample_rate as f64).ceil() as usize).max(1)
+	}
+
+	/// Discards captured audio that's too old to play, which keeps the delay
+	/// between capturing and playing audio from growing if the input device
+	/// runs slightly faster than the output device.
+	fn discard_stale_frames(&mut self) {
+		let latency_in_frames = self.latency_in_frames();
+		let num_buffered_frames = self.frame_consumer.occupied_len();
+		if num_buffered_frames > latency_in_frames * 2 {
+			self.frame_consumer
+				.skip(num_buffered_frames - latency_in_frames);
+		}
+	}
+
+	/// Moves the interpolation window forward by one captured frame.
+	///
+	/// Returns `false` if there are no more captured frames to play.
+	#[must_use]
+	fn advance(&mut self) -> bool {
+		let Some(frame) = self.frame_consumer.try_pop() else {
+			return false;
+		};
+		self.frames.copy_within(1.., 0);
+		self.frames[3] = frame;
+		true
+	}
+}
+
+impl Sound for InputSound {
+	fn on_start_processing(&mut self) {
+		self.sample_rate = self.stream_shared.sample_rate();
+		read_commands_into_parameters!(self, volume, panning);
+		self.playback_state_manager.read_commands(
+			&mut self.command_readers.pause,
+			&mut self.command_readers.resume,
+			&mut self.command_readers.stop,
+			&self.shared.state,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		// update parameters
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+
+		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			// captured audio isn't played while the sound is paused
+			// or waiting to start
+			self.buffering = true;
+			self.frame_consumer.skip(self.frame_consumer.occupied_len());
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		self.discard_stale_frames();
+		if self.buffering {
+			if self.frame_consumer.occupied_len() < self.latency_in_frames() {
+				out.fill(Frame::ZERO);
+				return;
+			}
+			self.buffering = false;
+		}
+
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			if self.buffering {
+				*frame = Frame::ZERO;
+				continue;
+			}
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+			let interpolated_out = interpolate_frame(
+				self.frames[0],
+				self.frames[1],
+				self.frames[2],
+				self.frames[3],
+				self.fractional_position as f32,
+			);
+			*frame = (interpolated_out * fade_volume * volume).panned(panning);
+			self.fractional_position += self.sample_rate as f64 * dt;
+			while self.fractional_position >= 1.0 {
+				self.fractional_position -= 1.0;
+				if !self.advance() {
+					// we ran out of captured audio, so output silence until
+					// enough audio is buffered again
+					self.shared.num_underruns.fetch_add(1, Ordering::SeqCst);
+					self.buffering = true;
+					self.fractional_position = 0.0;
+					break;
+				}
+			}
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished(&self.shared.state)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.state.store(PlaybackState::Stopped);
+	}
+}
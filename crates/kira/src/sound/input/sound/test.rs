@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use ringbuf::traits::Observer as _;
+
+use crate::{
+	backend::input::{
+		mock::{MockInputBackend, MockInputBackendSettings},
+		InputBackend,
+	},
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{
+		input::{InputSoundData, InputSoundSettings},
+		PlaybackState, Sound,
+	},
+	Panning, Tween,
+};
+
+fn mock_input(sample_rate: u32) -> (MockInputBackend, InputSoundData) {
+	let (backend, stream) = MockInputBackend::start(MockInputBackendSettings {
+		sample_rate,
+		..Default::default()
+	})
+	.unwrap();
+	(backend, InputSoundData::new(stream))
+}
+
+fn frames(values: impl IntoIterator<Item = f32>) -> Vec<Frame> {
+	values.into_iter().map(Frame::from_mono).collect()
+}
+
+/// Tests that an `InputSound` plays captured frames and counts underruns
+/// when it runs out of captured audio.
+#[test]
+fn plays_captured_audio() {
+	let (mut backend, data) = mock_input(1);
+	let (mut sound, handle) = data
+		.with_settings(InputSoundSettings::new().latency(Duration::from_secs(5)))
+		.split();
+	backend.push_frames(&frames([1.0, 2.0, 3.0, 4.0, 5.0]));
+	let info = MockInfoBuilder::new().build();
+	let mut out = vec![];
+	for _ in 0..8 {
+		out.push(sound.process_one(1.0, &info));
+	}
+	// the first few frames are used to fill up the interpolation window
+	assert_eq!(
+		out,
+		frames([0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 0.0, 0.0])
+			.into_iter()
+			.map(|frame| frame.panned(Panning::CENTER))
+			.collect::<Vec<_>>()
+	);
+	assert_eq!(handle.num_underruns(), 1);
+}
+
+/// Tests that an `InputSound` resamples captured audio to the
+/// output sample rate.
+#[test]
+fn resamples_captured_audio() {
+	let (mut backend, data) = mock_input(2);
+	let (mut sound, _handle) = data
+		.with_settings(InputSoundSettings::new().latency(Duration::from_secs(5)))
+		.split();
+	backend.push_frames(&frames((1..=10).map(|i| i as f32)));
+	let info = MockInfoBuilder::new().build();
+	let mut out = vec![];
+	for _ in 0..6 {
+		out.push(sound.process_one(1.0, &info));
+	}
+	assert_eq!(
+		out,
+		frames([0.0, 0.0, 2.0, 4.0, 6.0, 8.0])
+			.into_iter()
+			.map(|frame| frame.panned(Panning::CENTER))
+			.collect::<Vec<_>>()
+	);
+}
+
+/// Tests that an `InputSound` waits for the configured amount of audio to
+/// be captured before playing it.
+#[test]
+fn waits_for_latency() {
+	let (mut backend, data) = mock_input(1);
+	let (mut sound, handle) = data
+		.with_settings(InputSoundSettings::new().latency(Duration::from_secs(4)))
+		.split();
+	let info = MockInfoBuilder::new().build();
+	backend.push_frames(&frames([1.0; 3]));
+	for _ in 0..10 {
+		assert_eq!(sound.process_one(1.0, &info), Frame::ZERO);
+	}
+	assert_eq!(sound.frame_consumer.occupied_len(), 3);
+	backend.push_frames(&frames([1.0]));
+	sound.process_one(1.0, &info);
+	assert_eq!(sound.frame_consumer.occupied_len(), 3);
+	assert_eq!(handle.num_underruns(), 0);
+}
+
+/// Tests that an `InputSound` discards old captured audio to keep
+/// the latency from growing.
+#[test]
+fn discards_stale_audio() {
+	let (mut backend, data) = mock_input(1);
+	let (mut sound, _handle) = data
+		.with_settings(InputSoundSettings::new().latency(Duration::from_secs(2)))
+		.split();
+	backend.push_frames(&frames((1..=10).map(|i| i as f32)));
+	sound.process_one(1.0, &MockInfoBuilder::new().build());
+	assert_eq!(sound.frames[3], Frame::from_mono(9.0));
+	assert_eq!(sound.frame_consumer.occupied_len(), 1);
+}
+
+/// Tests that an `InputSound` discards captured audio while paused.
+#[test]
+fn discards_audio_while_paused() {
+	let (mut backend, data) = mock_input(1);
+	let (mut sound, mut handle) = data
+		.with_settings(InputSoundSettings::new().latency(Duration::ZERO))
+		.split();
+	let info = MockInfoBuilder::new().build();
+	handle.pause(Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	});
+	sound.on_start_processing();
+	sound.process_one(1.0, &info);
+	assert_eq!(handle.state(), PlaybackState::Paused);
+	backend.push_frames(&frames([1.0; 5]));
+	assert_eq!(sound.process_one(1.0, &info), Frame::ZERO);
+	assert_eq!(sound.frame_consumer.occupied_len(), 0);
+}
+
+/// Tests that an `InputSound` finishes when it's stopped.
+#[test]
+fn stops() {
+	let (_backend, data) = mock_input(1);
+	let (mut sound, mut handle) = data.split();
+	let info = MockInfoBuilder::new().build();
+	handle.stop(Tween {
+		duration: Duration::from_secs(2),
+		..Default::default()
+	});
+	sound.on_start_processing();
+	assert_eq!(handle.state(), PlaybackState::Stopping);
+	assert!(!sound.finished());
+	for _ in 0..3 {
+		sound.process_one(1.0, &info);
+	}
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+	assert!(sound.finished());
+}
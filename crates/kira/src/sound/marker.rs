@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::Arc;
+
+use ringbuf::{producer::Producer as _, Cons, HeapRb as RingBuffer, Prod};
+type Producer<T> = Prod<Arc<RingBuffer<T>>>;
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use super::PlaybackPosition;
+
+/// How many events can be waiting to be read from a sound's handle.
+/// Events that happen while the queue is full are discarded.
+pub(crate) const EVENT_QUEUE_CAPACITY: usize = 64;
+
+/// A named position in a sound.
+///
+/// When playback reaches a marker, the sound reports a
+/// [`SoundEvent::MarkerReached`] event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+	/// The name of the marker.
+	pub name: Arc<str>,
+	/// The position of the marker in the sound.
+	pub position: PlaybackPosition,
+}
+
+impl Marker {
+	/// Creates a new [`Marker`].
+	#[must_use]
+	pub fn new(name: impl Into<Arc<str>>, position: impl Into<PlaybackPosition>) -> Self {
+		Self {
+			name: name.into(),
+			position: position.into(),
+		}
+	}
+}
+
+/// Something that happened while a sound was playing.
+///
+/// Static and streaming sounds report events to their handles,
+/// which can read them using `pop_event`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoundEvent {
+	/// Playback reached a marker.
+	MarkerReached(Marker),
+	/// Playback reached the end of the loop region and jumped back
+	/// to the start of the loop region.
+	Looped,
+	/// The sound stopped playing and can't be resumed.
+	Finished,
+}
+
+/// Reports [`SoundEvent`]s from the audio thread.
+pub(crate) struct SoundEventWriter {
+	/// The markers, sorted by the index of the frame they're at.
+	markers: Vec<(usize, Marker)>,
+	producer: Producer<SoundEvent>,
+	/// The index of the frame that's currently being heard, or `None`
+	/// if playback hasn't started yet.
+	current_frame: Option<usize>,
+	finished: bool,
+}
+
+impl SoundEventWriter {
+	#[must_use]
+	pub fn new(markers: &[Marker], sample_rate: u32) -> (Self, Consumer<SoundEvent>) {
+		let mut markers = markers
+			.iter()
+			.map(|marker| (marker.position.into_samples(sample_rate), marker.clone()))
+			.collect::<Vec<_>>();
+		markers.sort_by_key(|(index, _)| *index);
+		let ring_buffer = Arc::new(RingBuffer::new(EVENT_QUEUE_CAPACITY));
+		let writer = Self {
+			markers,
+			producer: Producer::new(ring_buffer.clone()),
+			current_frame: None,
+			finished: false,
+		};
+		(writer, Consumer::new(ring_buffer))
+	}
+
+	/// Reports that playback moved to the frame at `index`.
+	///
+	/// If `looped` is `true`, playback got to this frame by jumping
+	/// back to the start of the loop region.
+	pub fn update_position(&mut self, index: usize, looped: bool) {
+		if self.current_frame == Some(index) {
+			return;
+		}
+		self.current_frame = Some(index);
+		if looped {
+			self.push(SoundEvent::Looped);
+		}
+		let first_marker = self.markers.partition_point(|(i, _)| *i < index);
+		for (marker_index, marker) in &self.markers[first_marker..] {
+			if *marker_index != index {
+				break;
+			}
+			self.producer
+				.try_push(SoundEvent::MarkerReached(marker.clone()))
+				.ok();
+		}
+	}
+
+	/// Reports that the sound finished. Only the first call
+	/// has any effect.
+	pub fn finish(&mut self) {
+		if !self.finished {
+			self.finished = true;
+			self.push(SoundEvent::Finished);
+		}
+	}
+
+	fn push(&mut self, event: SoundEvent) {
+		// if the handle isn't reading events, there's nothing
+		// useful to do with new ones
+		self.producer.try_push(event).ok();
+	}
+}
@@ -0,0 +1,60 @@
+use ringbuf::consumer::Consumer as _;
+
+use super::{Marker, SoundEvent, SoundEventWriter, EVENT_QUEUE_CAPACITY};
+
+/// Tests that markers are reported when playback moves onto
+/// them, in order, and only once per visit.
+#[test]
+fn reports_markers() {
+	let (mut writer, mut consumer) = SoundEventWriter::new(
+		&[
+			Marker::new("b", 2.0),
+			Marker::new("a", 1.0),
+			Marker::new("c", 2.0),
+		],
+		1,
+	);
+	for index in [0, 1, 1, 2] {
+		writer.update_position(index, false);
+	}
+	assert_eq!(
+		consumer.pop_iter().collect::<Vec<_>>(),
+		[
+			SoundEvent::MarkerReached(Marker::new("a", 1.0)),
+			SoundEvent::MarkerReached(Marker::new("b", 2.0)),
+			SoundEvent::MarkerReached(Marker::new("c", 2.0)),
+		]
+	);
+}
+
+/// Tests that loops are reported before the markers at the
+/// start of the loop, and that finishing is only reported once.
+#[test]
+fn reports_loops_and_finishing() {
+	let (mut writer, mut consumer) = SoundEventWriter::new(&[Marker::new("start", 0.0)], 1);
+	writer.update_position(1, false);
+	writer.update_position(0, true);
+	writer.finish();
+	writer.finish();
+	assert_eq!(
+		consumer.pop_iter().collect::<Vec<_>>(),
+		[
+			SoundEvent::Looped,
+			SoundEvent::MarkerReached(Marker::new("start", 0.0)),
+			SoundEvent::Finished,
+		]
+	);
+}
+
+/// Tests that events are discarded when the queue is full.
+#[test]
+fn discards_events_when_full() {
+	let (mut writer, mut consumer) = SoundEventWriter::new(&[], 1);
+	for index in 0..EVENT_QUEUE_CAPACITY * 2 {
+		writer.update_position(index, true);
+	}
+	writer.finish();
+	assert_eq!(consumer.pop_iter().count(), EVENT_QUEUE_CAPACITY);
+	writer.update_position(0, true);
+	assert_eq!(consumer.try_pop(), Some(SoundEvent::Looped));
+}
@@ -0,0 +1,50 @@
+/*!
+Generates noise procedurally.
+
+[`NoiseSoundData`] plays endless white, pink, or brown noise. This
+is useful for ambience like wind, rain, or radio static, especially
+when combined with [filters](crate::effect::filter) and
+[EQ filters](crate::effect::eq_filter) on a track.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+	sound::noise::{NoiseColor, NoiseSoundData},
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut wind = manager.play(NoiseSoundData::new(NoiseColor::Brown))?;
+// later...
+wind.stop(Tween::default());
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+The noise is generated from a seeded random number generator, so the
+same seed always produces the same noise. This keeps offline renders
+and tests deterministic.
+
+Since the sound never ends on its own, it keeps playing until it's
+stopped or its handle is dropped.
+*/
+
+mod data;
+mod handle;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use handle::*;
+pub use settings::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
+	StartTime,
+};
+
+command_writers_and_readers! {
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	pause: Tween,
+	resume: (StartTime, Tween),
+	stop: Tween,
+}
@@ -0,0 +1,68 @@
+use crate::sound::{Sound, SoundData};
+
+use super::{command_writers_and_readers, sound::NoiseSound, NoiseSoundHandle, NoiseSoundSettings};
+
+/// The spectrum of a noise sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoiseColor {
+	/// Noise with equal energy at every frequency.
+	White,
+	/// Noise whose energy falls off by 3dB per octave, which
+	/// sounds more balanced than white noise.
+	Pink,
+	/// Noise whose energy falls off by 6dB per octave, which
+	/// sounds deep and rumbly.
+	Brown,
+}
+
+/// A sound that generates noise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseSoundData {
+	/// The spectrum of the noise.
+	pub color: NoiseColor,
+	/// Settings for the sound.
+	pub settings: NoiseSoundSettings,
+}
+
+impl NoiseSoundData {
+	/// Creates a new [`NoiseSoundData`] with the default settings.
+	#[must_use]
+	pub fn new(color: NoiseColor) -> Self {
+		Self {
+			color,
+			settings: NoiseSoundSettings::new(),
+		}
+	}
+
+	/// Sets the settings for the sound.
+	#[must_use = "This method consumes self and returns a modified NoiseSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: NoiseSoundSettings) -> Self {
+		Self { settings, ..self }
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (NoiseSound, NoiseSoundHandle) {
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let sound = NoiseSound::new(self, command_readers);
+		let shared = sound.shared();
+		(
+			sound,
+			NoiseSoundHandle {
+				command_writers,
+				shared,
+			},
+		)
+	}
+}
+
+impl SoundData for NoiseSoundData {
+	type Error = ();
+
+	type Handle = NoiseSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
@@ -0,0 +1,84 @@
+use crate::{Decibels, Panning, StartTime, Tween, Value};
+
+/// Settings for a noise sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseSoundSettings {
+	/// When the sound should start playing.
+	pub start_time: StartTime,
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+	/// The seed for the random number generator.
+	///
+	/// Sounds with the same color and seed produce the same noise.
+	pub seed: u64,
+}
+
+impl NoiseSoundSettings {
+	/// Creates a new [`NoiseSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			fade_in_tween: None,
+			seed: 0,
+		}
+	}
+
+	/// Sets when the sound should start playing.
+	#[must_use = "This method consumes self and returns a modified NoiseSoundSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified NoiseSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the panning of the sound, where -1.0 is hard left
+	and 1.0 is hard right.
+	*/
+	#[must_use = "This method consumes self and returns a modified NoiseSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified NoiseSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+
+	/// Sets the seed for the random number generator.
+	#[must_use = "This method consumes self and returns a modified NoiseSoundSettings, so the return value should be used"]
+	pub fn seed(self, seed: u64) -> Self {
+		Self { seed, ..self }
+	}
+}
+
+impl Default for NoiseSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::Arc;
+
+use crate::{
+	command::read_commands_into_parameters,
+	frame::Frame,
+	info::Info,
+	playback_state_manager::{PlaybackStateManager, SharedPlaybackState},
+	random::Random,
+	sound::{PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime,
+};
+
+use super::{CommandReaders, NoiseColor, NoiseSoundData};
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	pub(super) state: SharedPlaybackState,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: SharedPlaybackState::new(),
+		}
+	}
+}
+
+pub(crate) struct NoiseSound {
+	command_readers: CommandReaders,
+	generator: NoiseGenerator,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	start_time: StartTime,
+	playback_state_manager: PlaybackStateManager,
+	shared: Arc<Shared>,
+}
+
+impl NoiseSound {
+	#[must_use]
+	pub fn new(data: NoiseSoundData, command_readers: CommandReaders) -> Self {
+		let NoiseSoundData { color, settings } = data;
+		Self {
+			command_readers,
+			generator: NoiseGenerator::new(color, settings.seed),
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			start_time: settings.start_time,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			shared: Arc::new(Shared::new()),
+		}
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+}
+
+impl Sound for NoiseSound {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, volume, panning);
+		self.playback_state_manager.read_commands(
+			&mut self.command_readers.pause,
+			&mut self.command_readers.resume,
+			&mut self.command_readers.stop,
+			&self.shared.state,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		// update parameters
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+
+		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+			let value = self.generator.next_value();
+			*frame = (Frame::from_mono(value) * fade_volume * volume).panned(panning);
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished(&self.shared.state)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.state.store(PlaybackState::Stopped);
+	}
+}
+
+/// Produces noise samples of a certain color.
+struct NoiseGenerator {
+	color: NoiseColor,
+	random: Random,
+	/// The states of the filters that shape white noise into
+	/// pink or brown noise.
+	filter_state: [f32; 7],
+}
+
+impl NoiseGenerator {
+	#[must_use]
+	fn new(color: NoiseColor, seed: u64) -> Self {
+		Self {
+			color,
+			random: Random::new(seed),
+			filter_state: [0.0; 7],
+		}
+	}
+
+	fn next_value(&mut self) -> f32 {
+		let white = self.random.next_f32() * 2.0 - 1.0;
+		match self.color {
+			NoiseColor::White => white,
+			// Paul Kellet's approximation of a -3dB/octave filter
+			NoiseColor::Pink => {
+				let b = &mut self.filter_state;
+				b[0] = 0.99886 * b[0] + white * 0.0555179;
+				b[1] = 0.99332 * b[1] + white * 0.0750759;
+				b[2] = 0.96900 * b[2] + white * 0.153852;
+				b[3] = 0.86650 * b[3] + white * 0.3104856;
+				b[4] = 0.55000 * b[4] + white * 0.5329522;
+				b[5] = -0.7616 * b[5] - white * 0.0168980;
+				let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+				b[6] = white * 0.115926;
+				pink * 0.11
+			}
+			// a leaky integrator, which is a -6dB/octave filter that
+			// doesn't drift away from 0
+			NoiseColor::Brown => {
+				let brown = &mut self.filter_state[0];
+				*brown = (*brown + white * 0.02) / 1.02;
+				*brown * 3.5
+			}
+		}
+	}
+}
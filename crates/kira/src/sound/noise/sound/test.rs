@@ -0,0 +1,65 @@
+use crate::{
+	info::MockInfoBuilder,
+	sound::{
+		noise::{NoiseColor, NoiseSoundData, NoiseSoundSettings},
+		Sound,
+	},
+};
+
+fn render(data: NoiseSoundData, num_frames: usize) -> Vec<f32> {
+	let (mut sound, _handle) = data.split();
+	let info = MockInfoBuilder::new().build();
+	(0..num_frames)
+		.map(|_| sound.process_one(1.0 / 44100.0, &info).left)
+		.collect()
+}
+
+/// Returns how much of the signal's power is in its
+/// sample-to-sample changes, which is higher for signals with
+/// more high-frequency content.
+fn high_frequency_ratio(signal: &[f32]) -> f32 {
+	let power = signal.iter().map(|sample| sample * sample).sum::<f32>();
+	let difference_power = signal
+		.windows(2)
+		.map(|pair| (pair[1] - pair[0]) * (pair[1] - pair[0]))
+		.sum::<f32>();
+	difference_power / power
+}
+
+/// Tests that the same seed always produces the same noise,
+/// and different seeds produce different noise.
+#[test]
+fn seeded() {
+	for color in [NoiseColor::White, NoiseColor::Pink, NoiseColor::Brown] {
+		let data = NoiseSoundData::new(color);
+		let noise = render(data, 1000);
+		assert_eq!(render(data, 1000), noise);
+		let reseeded = render(data.with_settings(NoiseSoundSettings::new().seed(1)), 1000);
+		assert_ne!(reseeded, noise);
+	}
+}
+
+/// Tests that the noise stays in a reasonable range and is centered on 0.
+#[test]
+fn range() {
+	for color in [NoiseColor::White, NoiseColor::Pink, NoiseColor::Brown] {
+		let noise = render(NoiseSoundData::new(color), 44100);
+		assert!(noise.iter().all(|sample| sample.abs() <= 1.0));
+		let mean = noise.iter().sum::<f32>() / noise.len() as f32;
+		assert!(mean.abs() < 0.1, "{:?} noise has a mean of {}", color, mean);
+		let rms =
+			(noise.iter().map(|sample| sample * sample).sum::<f32>() / noise.len() as f32).sqrt();
+		assert!(rms > 0.05, "{:?} noise has an RMS of {}", color, rms);
+	}
+}
+
+/// Tests that pink noise has less high-frequency content than white
+/// noise, and brown noise has less than pink noise.
+#[test]
+fn colors() {
+	let white = high_frequency_ratio(&render(NoiseSoundData::new(NoiseColor::White), 44100));
+	let pink = high_frequency_ratio(&render(NoiseSoundData::new(NoiseColor::Pink), 44100));
+	let brown = high_frequency_ratio(&render(NoiseSoundData::new(NoiseColor::Brown), 44100));
+	assert!(pink < white * 0.75);
+	assert!(brown < pink * 0.5);
+}
@@ -0,0 +1,53 @@
+/*!
+Generates tones procedurally.
+
+[`OscillatorSoundData`] plays an endless sine, triangle, saw, or pulse
+wave. This is useful for simple sound effects, like beeps for UI
+feedback, that don't need to be loaded from audio files.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend, Tween,
+	modulator::lfo::Waveform,
+	sound::oscillator::OscillatorSoundData,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut beep = manager.play(OscillatorSoundData::new(Waveform::Sine, 440.0))?;
+// later...
+beep.set_frequency(880.0, Tween::default());
+beep.stop(Tween::default());
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+The saw, pulse, and triangle waves are band-limited, so they don't
+produce aliasing artifacts at high frequencies.
+
+Since the sound never ends on its own, it keeps playing until it's
+stopped or its handle is dropped.
+*/
+
+mod data;
+mod handle;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use handle::*;
+pub use settings::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, modulator::lfo::Waveform,
+	tween::Tween, Decibels, Panning, StartTime,
+};
+
+command_writers_and_readers! {
+	set_waveform: Waveform,
+	set_frequency: ValueChangeCommand<f64>,
+	set_pulse_width: ValueChangeCommand<f64>,
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	pause: Tween,
+	resume: (StartTime, Tween),
+	stop: Tween,
+}
@@ -0,0 +1,68 @@
+use crate::{
+	modulator::lfo::Waveform,
+	sound::{Sound, SoundData},
+	Value,
+};
+
+use super::{
+	command_writers_and_readers, sound::OscillatorSound, OscillatorSoundHandle,
+	OscillatorSoundSettings,
+};
+
+/// A sound that generates a tone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OscillatorSoundData {
+	/// The shape of the tone.
+	///
+	/// The width of [`Waveform::Pulse`] is used as the pulse width
+	/// unless [`OscillatorSoundSettings::pulse_width`] is set.
+	pub waveform: Waveform,
+	/// The frequency of the tone (in Hz).
+	pub frequency: Value<f64>,
+	/// Settings for the sound.
+	pub settings: OscillatorSoundSettings,
+}
+
+impl OscillatorSoundData {
+	/// Creates a new [`OscillatorSoundData`] with the default settings.
+	#[must_use]
+	pub fn new(waveform: Waveform, frequency: impl Into<Value<f64>>) -> Self {
+		Self {
+			waveform,
+			frequency: frequency.into(),
+			settings: OscillatorSoundSettings::new(),
+		}
+	}
+
+	/// Sets the settings for the sound.
+	#[must_use = "This method consumes self and returns a modified OscillatorSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: OscillatorSoundSettings) -> Self {
+		Self { settings, ..self }
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (OscillatorSound, OscillatorSoundHandle) {
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let sound = OscillatorSound::new(self, command_readers);
+		let shared = sound.shared();
+		(
+			sound,
+			OscillatorSoundHandle {
+				command_writers,
+				shared,
+			},
+		)
+	}
+}
+
+impl SoundData for OscillatorSoundData {
+	type Error = ();
+
+	type Handle = OscillatorSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::{
+	command::handle_param_setters, modulator::lfo::Waveform, sound::PlaybackState, Decibels,
+	Panning, StartTime, Tween,
+};
+
+use super::{sound::Shared, CommandWriters};
+
+/// Controls an oscillator sound.
+///
+/// When the handle is dropped, the sound will stop playing.
+#[derive(Debug)]
+pub struct OscillatorSoundHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) shared: Arc<Shared>,
+}
+
+impl OscillatorSoundHandle {
+	/// Returns the current playback state of the sound.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state.load()
+	}
+
+	/// Sets the shape of the tone.
+	///
+	/// If the new waveform is a [`Waveform::Pulse`], the pulse width
+	/// immediately changes to its width, unless the pulse width was set
+	/// by [`OscillatorSoundSettings::pulse_width`](super::OscillatorSoundSettings::pulse_width)
+	/// or [`set_pulse_width`](Self::set_pulse_width).
+	pub fn set_waveform(&mut self, waveform: Waveform) {
+		self.command_writers.set_waveform.write(waveform)
+	}
+
+	handle_param_setters! {
+		/// Sets the frequency of the tone (in Hz).
+		frequency: f64,
+
+		/// Sets the ratio between how much time a pulse wave spends on
+		/// its high value vs. its low value.
+		///
+		/// This has no effect on other waveforms.
+		pulse_width: f64,
+
+		/// Sets the volume of the sound.
+		volume: Decibels,
+
+		/// Sets the panning of the sound, where `-1.0` is hard left,
+		/// `0.0` is center, and `1.0` is hard right.
+		panning: Panning,
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers.pause.write(tween)
+	}
+
+	/// Resumes playback and fades in the sound from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.resume_at(StartTime::Immediate, tween)
+	}
+
+	/// Resumes playback at the given start time and fades in
+	/// the sound from silence with the given tween.
+	pub fn resume_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.resume.write((start_time, tween))
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sound is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers.stop.write(tween)
+	}
+}
+
+impl Drop for OscillatorSoundHandle {
+	fn drop(&mut self) {
+		self.shared.state.mark_removed();
+	}
+}
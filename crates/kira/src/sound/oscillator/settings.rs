@@ -0,0 +1,91 @@
+use crate::{Decibels, Panning, StartTime, Tween, Value};
+
+/// Settings for an oscillator sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OscillatorSoundSettings {
+	/// When the sound should start playing.
+	pub start_time: StartTime,
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// The ratio between how much time a pulse wave spends on its
+	/// high value vs. its low value.
+	///
+	/// This should be a number between `0.0` and `1.0`. If this is `None`,
+	/// the width of the [`Waveform::Pulse`](crate::modulator::lfo::Waveform::Pulse)
+	/// is used. This has no effect on other waveforms.
+	pub pulse_width: Option<Value<f64>>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl OscillatorSoundSettings {
+	/// Creates a new [`OscillatorSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			pulse_width: None,
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets when the sound should start playing.
+	#[must_use = "This method consumes self and returns a modified OscillatorSoundSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified OscillatorSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the panning of the sound, where -1.0 is hard left
+	and 1.0 is hard right.
+	*/
+	#[must_use = "This method consumes self and returns a modified OscillatorSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the pulse width of the sound, which only affects
+	/// pulse waves.
+	#[must_use = "This method consumes self and returns a modified OscillatorSoundSettings, so the return value should be used"]
+	pub fn pulse_width(self, pulse_width: impl Into<Value<f64>>) -> Self {
+		Self {
+			pulse_width: Some(pulse_width.into()),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified OscillatorSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for OscillatorSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
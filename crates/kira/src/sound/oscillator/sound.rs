@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod test;
+
+use std::sync::Arc;
+
+use crate::{
+	command::{read_commands_into_parameters, ValueChangeCommand},
+	frame::Frame,
+	info::Info,
+	modulator::lfo::Waveform,
+	playback_state_manager::{PlaybackStateManager, SharedPlaybackState},
+	sound::{PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime, Value,
+};
+
+use super::{CommandReaders, OscillatorSoundData};
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	pub(super) state: SharedPlaybackState,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: SharedPlaybackState::new(),
+		}
+	}
+}
+
+pub(crate) struct OscillatorSound {
+	command_readers: CommandReaders,
+	waveform: Waveform,
+	frequency: Parameter,
+	pulse_width: Parameter,
+	/// Whether the pulse width comes from the width of the
+	/// [`Waveform::Pulse`] rather than the settings or the handle.
+	pulse_width_from_waveform: bool,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	start_time: StartTime,
+	playback_state_manager: PlaybackStateManager,
+	/// How far through the current cycle of the waveform the
+	/// oscillator is (from `0.0` to `1.0`).
+	phase: f64,
+	shared: Arc<Shared>,
+}
+
+impl OscillatorSound {
+	#[must_use]
+	pub fn new(data: OscillatorSoundData, command_readers: CommandReaders) -> Self {
+		let OscillatorSoundData {
+			waveform,
+			frequency,
+			settings,
+		} = data;
+		let pulse_width = settings
+			.pulse_width
+			.unwrap_or(Value::Fixed(pulse_width(waveform)));
+		Self {
+			command_readers,
+			waveform,
+			frequency: Parameter::new(frequency, 440.0),
+			pulse_width: Parameter::new(pulse_width, 0.5),
+			pulse_width_from_waveform: settings.pulse_width.is_none(),
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			start_time: settings.start_time,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			phase: 0.0,
+			shared: Arc::new(Shared::new()),
+		}
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+
+	fn set_waveform(&mut self, waveform: Waveform) {
+		self.waveform = waveform;
+		if let Waveform::Pulse { width } = waveform {
+			// don't override a pulse width that was set on purpose
+			if self.pulse_width_from_waveform {
+				self.pulse_width = Parameter::new(Value::Fixed(width), width);
+			}
+		}
+	}
+}
+
+impl Sound for OscillatorSound {
+	fn on_start_processing(&mut self) {
+		if let Some(waveform) = self.command_readers.set_waveform.read() {
+			self.set_waveform(waveform);
+		}
+		read_commands_into_parameters!(self, frequency, volume, panning);
+		if let Some(ValueChangeCommand { target, tween }) =
+			self.command_readers.set_pulse_width.read()
+		{
+			self.pulse_width.set(target, tween);
+			self.pulse_width_from_waveform = false;
+		}
+		self.playback_state_manager.read_commands(
+			&mut self.command_readers.pause,
+			&mut self.command_readers.resume,
+			&mut self.command_readers.stop,
+			&self.shared.state,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		// update parameters
+		self.frequency.update(dt * out.len() as f64, info);
+		self.pulse_width.update(dt * out.len() as f64, info);
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+
+		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+			let phase_increment = (self.frequency.interpolated_value(time_in_chunk) * dt).abs();
+			let pulse_width = self
+				.pulse_width
+				.interpolated_value(time_in_chunk)
+				.clamp(0.0, 1.0);
+			let value = band_limited_value(self.waveform, self.phase, phase_increment, pulse_width);
+			*frame = (Frame::from_mono(value as f32) * fade_volume * volume).panned(panning);
+			self.phase = (self.phase + phase_increment).fract();
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished(&self.shared.state)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.state.store(PlaybackState::Stopped);
+	}
+}
+
+#[must_use]
+fn pulse_width(waveform: Waveform) -> f64 {
+	match waveform {
+		Waveform::Pulse { width } => width,
+		_ => 0.5,
+	}
+}
+
+/// Returns the value of the waveform at the given phase with the
+/// discontinuities smoothed out to reduce aliasing.
+///
+/// `phase_increment` is how much the phase advances each frame.
+#[must_use]
+fn band_limited_value(
+	waveform: Waveform,
+	phase: f64,
+	phase_increment: f64,
+	pulse_width: f64,
+) -> f64 {
+	match waveform {
+		Waveform::Sine => waveform.value(phase),
+		// the value jumps down at phase 0.5
+		Waveform::Saw => waveform.value(phase) - poly_blep((phase + 0.5).fract(), phase_increment),
+		// the value jumps up at phase 0 and down at the pulse width
+		Waveform::Pulse { .. } => {
+			Waveform::Pulse { width: pulse_width }.value(phase) + poly_blep(phase, phase_increment)
+				- poly_blep((phase - pulse_width).rem_euclid(1.0), phase_increment)
+		}
+		// the slope changes from 4 to -4 at phase 0.25 and back at phase 0.75
+		Waveform::Triangle => {
+			waveform.value(phase)
+				+ 8.0
+					* phase_increment
+					* (poly_blamp((phase - 0.75).rem_euclid(1.0), phase_increment)
+						- poly_blamp((phase - 0.25).rem_euclid(1.0), phase_increment))
+		}
+	}
+}
+
+/// Returns the correction for a jump from `-1.0` to `1.0` at phase `0.0`
+/// using a polynomial approximation of a band-limited step.
+#[must_use]
+fn poly_blep(phase: f64, phase_increment: f64) -> f64 {
+	if phase_increment <= 0.0 {
+		return 0.0;
+	}
+	if phase < phase_increment {
+		let x = phase / phase_increment;
+		x + x - x * x - 1.0
+	} else if phase > 1.0 - phase_increment {
+		let x = (phase - 1.0) / phase_increment;
+		x * x + x + x + 1.0
+	} else {
+		0.0
+	}
+}
+
+/// Returns the correction for a corner at phase `0.0` where the slope
+/// of the waveform increases, using the integral of [`poly_blep`].
+///
+/// The result should be multiplied by the change in slope and
+/// `phase_increment`.
+#[must_use]
+fn poly_blamp(phase: f64, phase_increment: f64) -> f64 {
+	if phase_increment <= 0.0 {
+		return 0.0;
+	}
+	if phase < phase_increment {
+		let x = 1.0 - phase / phase_increment;
+		x * x * x / 6.0
+	} else if phase > 1.0 - phase_increment {
+		let x = (phase - 1.0) / phase_increment + 1.0;
+		x * x * x / 6.0
+	} else {
+		0.0
+	}
+}
@@ -0,0 +1,185 @@
+use std::{f64::consts::TAU, time::Duration};
+
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	modulator::lfo::Waveform,
+	sound::{
+		oscillator::{OscillatorSoundData, OscillatorSoundSettings},
+		Sound,
+	},
+	Easing, Mapping, Panning, Tween, Value,
+};
+
+use super::band_limited_value;
+
+/// Returns the fraction of the signal's energy that isn't in the
+/// harmonics of a waveform that completes `num_cycles` cycles.
+///
+/// Aliasing causes energy to show up between the harmonics.
+fn aliased_energy(signal: &[f64], num_cycles: usize) -> f64 {
+	let mut total_energy = 0.0;
+	let mut aliased_energy = 0.0;
+	for bin in 1..signal.len() / 2 {
+		let (mut real, mut imaginary) = (0.0, 0.0);
+		for (i, sample) in signal.iter().enumerate() {
+			let angle = TAU * (bin * i) as f64 / signal.len() as f64;
+			real += sample * angle.cos();
+			imaginary -= sample * angle.sin();
+		}
+		let energy = real * real + imaginary * imaginary;
+		total_energy += energy;
+		if bin % num_cycles != 0 {
+			aliased_energy += energy;
+		}
+	}
+	aliased_energy / total_energy
+}
+
+/// Tests that the oscillator produces a sine wave at the given frequency.
+#[test]
+fn plays_sine_wave() {
+	let (mut sound, _handle) = OscillatorSoundData::new(Waveform::Sine, 1.0).split();
+	let info = MockInfoBuilder::new().build();
+	for i in 0..100 {
+		let expected =
+			Frame::from_mono((TAU * i as f64 / 100.0).sin() as f32).panned(Panning::CENTER);
+		let frame = sound.process_one(0.01, &info);
+		assert!((frame.left - expected.left).abs() < 0.0001);
+		assert!((frame.right - expected.right).abs() < 0.0001);
+	}
+}
+
+/// Tests that the band-limited waveforms have much less aliasing
+/// than the naive versions.
+#[test]
+fn reduces_aliasing() {
+	// 73 cycles of a 730Hz wave at 8000Hz
+	let num_frames = 800;
+	let num_cycles = 73;
+	let phase_increment = num_cycles as f64 / num_frames as f64;
+	for waveform in [
+		Waveform::Saw,
+		Waveform::Pulse { width: 0.5 },
+		Waveform::Pulse { width: 0.3 },
+		Waveform::Triangle,
+	] {
+		let pulse_width = match waveform {
+			Waveform::Pulse { width } => width,
+			_ => 0.5,
+		};
+		let phases = (0..num_frames)
+			.map(|i| (i as f64 * phase_increment).fract())
+			.collect::<Vec<_>>();
+		let naive = phases
+			.iter()
+			.map(|phase| waveform.value(*phase))
+			.collect::<Vec<_>>();
+		let band_limited = phases
+			.iter()
+			.map(|phase| band_limited_value(waveform, *phase, phase_increment, pulse_width))
+			.collect::<Vec<_>>();
+		let naive_aliasing = aliased_energy(&naive, num_cycles);
+		let band_limited_aliasing = aliased_energy(&band_limited, num_cycles);
+		assert!(
+			band_limited_aliasing < naive_aliasing * 0.1,
+			"{:?}: band-limited aliasing {} is not much lower than naive aliasing {}",
+			waveform,
+			band_limited_aliasing,
+			naive_aliasing
+		);
+	}
+}
+
+/// Tests that the width of a pulse wave can be set by the waveform
+/// or the pulse width setting.
+#[test]
+fn pulse_width() {
+	let info = MockInfoBuilder::new().build();
+	let count_high_frames = |data: OscillatorSoundData| {
+		let (mut sound, _handle) = data.split();
+		(0..1000)
+			.filter(|_| sound.process_one(0.001, &info).left > 0.0)
+			.count()
+	};
+	let num_high_frames = count_high_frames(OscillatorSoundData::new(
+		Waveform::Pulse { width: 0.25 },
+		1.0,
+	));
+	assert!(num_high_frames.abs_diff(250) <= 1);
+	let num_high_frames = count_high_frames(
+		OscillatorSoundData::new(Waveform::Pulse { width: 0.25 }, 1.0)
+			.with_settings(OscillatorSoundSettings::new().pulse_width(0.75)),
+	);
+	assert!(num_high_frames.abs_diff(750) <= 1);
+}
+
+/// Tests that changing the waveform only changes the pulse width
+/// if the pulse width wasn't set some other way.
+#[test]
+fn set_waveform_pulse_width() {
+	let mut info_builder = MockInfoBuilder::new();
+	let modulator = info_builder.add_modulator(0.75);
+	let info = info_builder.build();
+	let count_high_frames = |sound: &mut dyn Sound| {
+		(0..1000)
+			.filter(|_| sound.process_one(0.001, &info).left > 0.0)
+			.count()
+	};
+
+	// the width of the new waveform is used by default
+	let (mut sound, mut handle) = OscillatorSoundData::new(Waveform::Sine, 1.0).split();
+	handle.set_waveform(Waveform::Pulse { width: 0.25 });
+	sound.on_start_processing();
+	assert!(count_high_frames(&mut sound).abs_diff(250) <= 1);
+
+	// a pulse width linked to a modulator is kept
+	let (mut sound, mut handle) = OscillatorSoundData::new(Waveform::Sine, 1.0)
+		.with_settings(
+			OscillatorSoundSettings::new().pulse_width(Value::FromModulator {
+				id: modulator,
+				mapping: Mapping {
+					input_range: (0.0, 1.0),
+					output_range: (0.0, 1.0),
+					easing: Easing::Linear,
+				},
+			}),
+		)
+		.split();
+	handle.set_waveform(Waveform::Pulse { width: 0.25 });
+	sound.on_start_processing();
+	assert!(count_high_frames(&mut sound).abs_diff(750) <= 1);
+
+	// so is a pulse width set by the handle
+	let (mut sound, mut handle) = OscillatorSoundData::new(Waveform::Sine, 1.0).split();
+	handle.set_pulse_width(
+		0.75,
+		Tween {
+			duration: Duration::ZERO,
+			..Default::default()
+		},
+	);
+	sound.on_start_processing();
+	handle.set_waveform(Waveform::Pulse { width: 0.25 });
+	sound.on_start_processing();
+	assert!(count_high_frames(&mut sound).abs_diff(750) <= 1);
+}
+
+/// Tests that the frequency can be changed using the handle.
+#[test]
+fn set_frequency() {
+	let (mut sound, mut handle) = OscillatorSoundData::new(Waveform::Saw, 1.0).split();
+	let info = MockInfoBuilder::new().build();
+	handle.set_frequency(2.0, Tween::default());
+	sound.on_start_processing();
+	// a saw wave at 2Hz jumps from high to low every 0.5 seconds.
+	// the jump is smoothed out over a couple frames.
+	let values = (0..100)
+		.map(|_| sound.process_one(0.01, &info).left)
+		.collect::<Vec<_>>();
+	let num_wraps = values
+		.windows(3)
+		.filter(|frames| frames[0] > 0.5 && frames[2] < -0.5)
+		.count();
+	assert_eq!(num_wraps, 2);
+}
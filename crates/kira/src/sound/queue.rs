@@ -0,0 +1,73 @@
+/*!
+Plays a sequence of sounds back-to-back.
+
+[`QueueSoundData`] plays a list of [`QueueItem`]s one after another with
+no gap between them. Items can be made from [`StaticSoundData`](crate::sound::static_sound::StaticSoundData) and
+(on desktop platforms) [`StreamingSoundData`](crate::sound::streaming::StreamingSoundData),
+and they can have different sample rates.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	sound::{
+		queue::QueueSoundData,
+		static_sound::StaticSoundData,
+	},
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut queue = QueueSoundData::new();
+queue.append(StaticSoundData::from_file("intro.ogg")?);
+queue.append(StaticSoundData::from_file("loop.ogg")?.loop_region(..));
+let mut handle = manager.play(queue)?;
+// later, move on to the next song with a 2 second crossfade
+handle.append(StaticSoundData::from_file("outro.ogg")?)?;
+handle.skip(kira::Tween {
+	duration: std::time::Duration::from_secs(2),
+	..Default::default()
+});
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+## Transitions
+
+Each item starts on the frame right after the previous item's last frame,
+so sounds that are meant to be played in sequence (like an intro and a
+loop) line up exactly. The audio is resampled as a single stream, so there's
+no extra latency at the start of each item.
+
+An item with a loop region loops until [`QueueSoundHandle::skip`] is
+called or the item is removed. Skipping can optionally crossfade between
+the current item and the next one.
+
+## Item settings
+
+Items respect the slice, start position, and loop region of the sound
+data they're made from. Other settings, like the volume, playback rate,
+and start time, are ignored. Use the queue's own settings and handle
+to control the volume and panning of the whole queue.
+*/
+
+mod data;
+mod handle;
+mod item;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use handle::*;
+pub use item::*;
+pub use settings::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
+	StartTime,
+};
+
+command_writers_and_readers! {
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	pause: Tween,
+	resume: (StartTime, Tween),
+	stop: Tween,
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use ringbuf::{Cons, HeapRb as RingBuffer, Prod};
+type Producer<T> = Prod<Arc<RingBuffer<T>>>;
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use crate::sound::{Sound, SoundData};
+
+use super::{
+	command_writers_and_readers,
+	sound::{QueueCommand, QueueSound},
+	QueueItem, QueueItemId, QueueSoundHandle, QueueSoundSettings,
+};
+
+/// A sound that plays a sequence of items back-to-back.
+#[derive(Debug)]
+pub struct QueueSoundData {
+	items: Vec<(QueueItemId, QueueItem)>,
+	next_item_id: u64,
+	/// Settings for the sound.
+	pub settings: QueueSoundSettings,
+}
+
+impl QueueSoundData {
+	/// Creates a new, empty [`QueueSoundData`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			items: vec![],
+			next_item_id: 0,
+			settings: QueueSoundSettings::new(),
+		}
+	}
+
+	/// Sets the settings for the sound.
+	#[must_use = "This method consumes self and returns a modified QueueSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: QueueSoundSettings) -> Self {
+		Self { settings, ..self }
+	}
+
+	/// Adds an item to the end of the queue and returns its ID.
+	///
+	/// The ID can be used with [`QueueSoundHandle::remove`] and
+	/// [`QueueSoundHandle::current_item`] once the queue is playing.
+	pub fn append(&mut self, item: impl Into<QueueItem>) -> QueueItemId {
+		let id = QueueItemId(self.next_item_id);
+		self.next_item_id += 1;
+		self.items.push((id, item.into()));
+		id
+	}
+
+	/// Returns the number of items in the queue.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Returns `true` if the queue has no items.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (QueueSound, QueueSoundHandle) {
+		let capacity = self.settings.capacity.max(self.items.len()).max(1);
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let (item_producer, item_consumer) = ring_buffer(capacity);
+		let (queue_command_producer, queue_command_consumer) =
+			ring_buffer::<QueueCommand>(capacity);
+		let (unused_item_producer, unused_item_consumer) = ring_buffer(capacity);
+		let num_items = self.items.len();
+		let sound = QueueSound::new(
+			self.items,
+			capacity,
+			self.settings,
+			command_readers,
+			item_consumer,
+			queue_command_consumer,
+			unused_item_producer,
+		);
+		let shared = sound.shared();
+		(
+			sound,
+			QueueSoundHandle {
+				command_writers,
+				shared,
+				item_producer,
+				queue_command_producer,
+				unused_item_consumer,
+				capacity,
+				num_items,
+				next_item_id: self.next_item_id,
+			},
+		)
+	}
+}
+
+impl Default for QueueSoundData {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SoundData for QueueSoundData {
+	type Error = ();
+
+	type Handle = QueueSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
+
+#[must_use]
+fn ring_buffer<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+	let rb = Arc::new(RingBuffer::new(capacity));
+	(Producer::new(rb.clone()), Consumer::new(rb))
+}
@@ -0,0 +1,175 @@
+use std::{
+	fmt::{Debug, Formatter},
+	sync::Arc,
+};
+
+use ringbuf::{consumer::Consumer as _, producer::Producer as _, Cons, HeapRb as RingBuffer, Prod};
+type Producer<T> = Prod<Arc<RingBuffer<T>>>;
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use crate::{
+	command::handle_param_setters, sound::PlaybackState, Decibels, Panning, ResourceLimitReached,
+	StartTime, Tween,
+};
+
+use super::{
+	sound::{QueueCommand, Shared},
+	CommandWriters, QueueItem, QueueItemId,
+};
+
+/// Controls a queue sound.
+///
+/// When the handle is dropped, the sound will stop playing.
+pub struct QueueSoundHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) shared: Arc<Shared>,
+	pub(super) item_producer: Producer<(QueueItemId, QueueItem)>,
+	pub(super) queue_command_producer: Producer<QueueCommand>,
+	pub(super) unused_item_consumer: Consumer<(QueueItemId, QueueItem)>,
+	pub(super) capacity: usize,
+	/// The number of items that have been added to the queue and
+	/// haven't been sent back from the audio thread yet.
+	pub(super) num_items: usize,
+	pub(super) next_item_id: u64,
+}
+
+impl QueueSoundHandle {
+	/// Returns the current playback state of the sound.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state.load()
+	}
+
+	/// Returns the ID of the item that's currently playing, or `None`
+	/// if no item is playing.
+	///
+	/// While skipping with a crossfade, this is the item being faded in.
+	#[must_use]
+	pub fn current_item(&self) -> Option<QueueItemId> {
+		self.shared.current_item()
+	}
+
+	handle_param_setters! {
+		/// Sets the volume of the sound.
+		volume: Decibels,
+
+		/// Sets the panning of the sound, where `-1.0` is hard left,
+		/// `0.0` is center, and `1.0` is hard right.
+		panning: Panning,
+	}
+
+	/// Adds an item to the end of the queue and returns its ID.
+	///
+	/// Returns an error if the queue already holds as many items
+	/// as its [capacity](super::QueueSoundSettings::capacity).
+	pub fn append(
+		&mut self,
+		item: impl Into<QueueItem>,
+	) -> Result<QueueItemId, ResourceLimitReached> {
+		self.remove_unused_items();
+		if self.num_items >= self.capacity {
+			return Err(ResourceLimitReached);
+		}
+		let id = QueueItemId(self.next_item_id);
+		self.item_producer
+			.try_push((id, item.into()))
+			.map_err(|_| ResourceLimitReached)?;
+		self.next_item_id += 1;
+		self.num_items += 1;
+		Ok(id)
+	}
+
+	/// Removes an item from the queue.
+	///
+	/// If the item is currently playing, the queue moves on to
+	/// the next item right away. If the item has already finished
+	/// or doesn't exist, this does nothing.
+	pub fn remove(&mut self, id: QueueItemId) {
+		self.remove_unused_items();
+		self.queue_command_producer
+			.try_push(QueueCommand::Remove(id))
+			.ok();
+	}
+
+	/// Stops the current item and starts playing the next one.
+	///
+	/// If a tween is given, the two items are crossfaded over the
+	/// duration of the tween.
+	pub fn skip(&mut self, crossfade_tween: impl Into<Option<Tween>>) {
+		self.remove_unused_items();
+		self.queue_command_producer
+			.try_push(QueueCommand::Skip(crossfade_tween.into()))
+			.ok();
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers.pause.write(tween)
+	}
+
+	/// Resumes playback and fades in the sound from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.resume_at(StartTime::Immediate, tween)
+	}
+
+	/// Resumes playback at the given start time and fades in
+	/// the sound from silence with the given tween.
+	pub fn resume_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.resume.write((start_time, tween))
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sound is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers.stop.write(tween)
+	}
+
+	/// Drops the items the audio thread is done with, so their
+	/// memory isn't freed on the audio thread.
+	fn remove_unused_items(&mut self) {
+		while self.unused_item_consumer.try_pop().is_some() {
+			self.num_items -= 1;
+		}
+	}
+}
+
+impl Drop for QueueSoundHandle {
+	fn drop(&mut self) {
+		self.shared.state.mark_removed();
+	}
+}
+
+impl Debug for QueueSoundHandle {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("QueueSoundHandle")
+			.field("command_writers", &self.command_writers)
+			.field("shared", &self.shared)
+			.field("item_producer", &ProducerDebug)
+			.field("queue_command_producer", &ProducerDebug)
+			.field("unused_item_consumer", &ConsumerDebug)
+			.field("capacity", &self.capacity)
+			.field("num_items", &self.num_items)
+			.field("next_item_id", &self.next_item_id)
+			.finish()
+	}
+}
+
+struct ProducerDebug;
+
+impl Debug for ProducerDebug {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Producer").finish()
+	}
+}
+
+struct ConsumerDebug;
+
+impl Debug for ConsumerDebug {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Consumer").finish()
+	}
+}
@@ -0,0 +1,104 @@
+use std::fmt::{Debug, Formatter};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sound::streaming::{FrameStream, StreamingSoundData};
+use crate::{
+	frame::Frame,
+	sound::{
+		static_sound::{frame_at_index, num_frames, StaticSoundData},
+		transport::Transport,
+	},
+};
+
+/// A unique identifier for an item in a queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QueueItemId(pub(super) u64);
+
+/// A sound that can be played by a queue.
+///
+/// Static items can be created using `From<StaticSoundData>`. On desktop
+/// platforms, streaming items can be created using
+/// `TryFrom<StreamingSoundData>`, which starts decoding the audio
+/// in the background right away.
+pub struct QueueItem {
+	source: Source,
+}
+
+impl QueueItem {
+	/// Returns the sample rate of the audio (in Hz).
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		match &self.source {
+			Source::Static { data, .. } => data.sample_rate,
+			#[cfg(not(target_arch = "wasm32"))]
+			Source::Streaming(frame_stream) => frame_stream.sample_rate(),
+		}
+	}
+
+	/// Returns the next frame of audio, or `None` if the item
+	/// is finished.
+	#[must_use]
+	pub(super) fn next_frame(&mut self) -> Option<Frame> {
+		match &mut self.source {
+			Source::Static { data, transport } => {
+				if !transport.playing {
+					return None;
+				}
+				let frame = frame_at_index(transport.position, &data.frames, data.slice)?;
+				transport.increment_position(num_frames(&data.frames, data.slice));
+				Some(frame)
+			}
+			#[cfg(not(target_arch = "wasm32"))]
+			Source::Streaming(frame_stream) => frame_stream.next_frame(),
+		}
+	}
+}
+
+impl From<StaticSoundData> for QueueItem {
+	fn from(data: StaticSoundData) -> Self {
+		let transport = Transport::new(
+			data.settings.start_position.into_samples(data.sample_rate),
+			data.settings.loop_region,
+			false,
+			data.sample_rate,
+			data.num_frames(),
+		);
+		Self {
+			source: Source::Static { data, transport },
+		}
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<Error: Send + 'static> TryFrom<StreamingSoundData<Error>> for QueueItem {
+	type Error = Error;
+
+	fn try_from(data: StreamingSoundData<Error>) -> Result<Self, Self::Error> {
+		Ok(Self {
+			source: Source::Streaming(data.into_frame_stream()?),
+		})
+	}
+}
+
+impl Debug for QueueItem {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let kind = match &self.source {
+			Source::Static { .. } => "Static",
+			#[cfg(not(target_arch = "wasm32"))]
+			Source::Streaming(_) => "Streaming",
+		};
+		f.debug_struct("QueueItem")
+			.field("kind", &kind)
+			.field("sample_rate", &self.sample_rate())
+			.finish()
+	}
+}
+
+enum Source {
+	Static {
+		data: StaticSoundData,
+		transport: Transport,
+	},
+	#[cfg(not(target_arch = "wasm32"))]
+	Streaming(FrameStream),
+}
@@ -0,0 +1,100 @@
+use crate::{Decibels, Panning, StartTime, Tween, Value};
+
+/// Settings for a queue sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueueSoundSettings {
+	/// When the sound should start playing.
+	pub start_time: StartTime,
+	/// The maximum number of items the queue can hold at once.
+	///
+	/// If the queue was created with more items than this,
+	/// the capacity is raised to fit them.
+	pub capacity: usize,
+	/// Whether the sound should stop once it runs out of items.
+	///
+	/// If this is `false`, the sound plays silence until more items
+	/// are appended or it's stopped manually.
+	pub stop_when_empty: bool,
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl QueueSoundSettings {
+	/// Creates a new [`QueueSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			capacity: 16,
+			stop_when_empty: true,
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets when the sound should start playing.
+	#[must_use = "This method consumes self and returns a modified QueueSoundSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets the maximum number of items the queue can hold at once.
+	#[must_use = "This method consumes self and returns a modified QueueSoundSettings, so the return value should be used"]
+	pub fn capacity(self, capacity: usize) -> Self {
+		Self { capacity, ..self }
+	}
+
+	/// Sets whether the sound should stop once it runs out of items.
+	#[must_use = "This method consumes self and returns a modified QueueSoundSettings, so the return value should be used"]
+	pub fn stop_when_empty(self, stop_when_empty: bool) -> Self {
+		Self {
+			stop_when_empty,
+			..self
+		}
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified QueueSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the panning of the sound, where -1.0 is hard left
+	and 1.0 is hard right.
+	*/
+	#[must_use = "This method consumes self and returns a modified QueueSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified QueueSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for QueueSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,388 @@
+#[cfg(test)]
+mod test;
+
+mod playhead;
+
+use std::{
+	collections::VecDeque,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+};
+
+use ringbuf::{consumer::Consumer as _, producer::Producer as _, Cons, HeapRb as RingBuffer, Prod};
+type Producer<T> = Prod<Arc<RingBuffer<T>>>;
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use crate::{
+	command::read_commands_into_parameters,
+	frame::Frame,
+	info::Info,
+	playback_state_manager::{PlaybackStateManager, SharedPlaybackState},
+	sound::{PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime, Tween, Value,
+};
+
+use super::{CommandReaders, QueueItem, QueueItemId, QueueSoundSettings};
+
+use playhead::{Playhead, SourceFrame};
+
+/// Changes to the queue that are sent from the handle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum QueueCommand {
+	Remove(QueueItemId),
+	Skip(Option<Tween>),
+}
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	pub(super) state: SharedPlaybackState,
+	/// The ID of the current item plus one, or `0` if no
+	/// item is playing.
+	current_item: AtomicU64,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: SharedPlaybackState::new(),
+			current_item: AtomicU64::new(0),
+		}
+	}
+
+	#[must_use]
+	pub fn current_item(&self) -> Option<QueueItemId> {
+		match self.current_item.load(Ordering::SeqCst) {
+			0 => None,
+			id => Some(QueueItemId(id - 1)),
+		}
+	}
+
+	pub fn set_current_item(&self, item: Option<QueueItemId>) {
+		self.current_item
+			.store(item.map_or(0, |QueueItemId(id)| id + 1), Ordering::SeqCst);
+	}
+}
+
+/// An item that's being faded out after a skip.
+struct FadeOut {
+	id: QueueItemId,
+	playhead: Playhead,
+	/// The item, or `None` if all of its frames were already
+	/// read into the playhead.
+	item: Option<(QueueItemId, QueueItem)>,
+	volume: Parameter<Decibels>,
+}
+
+pub(crate) struct QueueSound {
+	command_readers: CommandReaders,
+	item_consumer: Consumer<(QueueItemId, QueueItem)>,
+	queue_command_consumer: Consumer<QueueCommand>,
+	unused_item_producer: Producer<(QueueItemId, QueueItem)>,
+	/// The items that haven't been completely read yet.
+	items: VecDeque<(QueueItemId, QueueItem)>,
+	playhead: Playhead,
+	/// The volume of the current item, which fades in when
+	/// skipping with a crossfade.
+	item_volume: Parameter<Decibels>,
+	fade_out: Option<FadeOut>,
+	stop_when_empty: bool,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	start_time: StartTime,
+	playback_state_manager: PlaybackStateManager,
+	shared: Arc<Shared>,
+}
+
+impl QueueSound {
+	#[must_use]
+	pub fn new(
+		items: Vec<(QueueItemId, QueueItem)>,
+		capacity: usize,
+		settings: QueueSoundSettings,
+		command_readers: CommandReaders,
+		item_consumer: Consumer<(QueueItemId, QueueItem)>,
+		queue_command_consumer: Consumer<QueueCommand>,
+		unused_item_producer: Producer<(QueueItemId, QueueItem)>,
+	) -> Self {
+		let mut item_queue = VecDeque::with_capacity(capacity);
+		item_queue.extend(items);
+		let shared = Arc::new(Shared::new());
+		shared.set_current_item(item_queue.front().map(|(id, _)| *id));
+		Self {
+			command_readers,
+			item_consumer,
+			queue_command_consumer,
+			unused_item_producer,
+			items: item_queue,
+			playhead: Playhead::new(),
+			item_volume: Parameter::new(Value::Fixed(Decibels::IDENTITY), Decibels::IDENTITY),
+			fade_out: None,
+			stop_when_empty: settings.stop_when_empty,
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			start_time: settings.start_time,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			shared,
+		}
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+
+	/// Starts reading the first item into the playhead if nothing
+	/// is playing.
+	fn prime_playhead(&mut self) {
+		if self.playhead.is_idle() && !self.items.is_empty() {
+			self.playhead = Playhead::primed(&[], || {
+				next_source_frame(&mut self.items, &mut self.unused_item_producer)
+			});
+		}
+	}
+
+	fn skip(&mut self, crossfade_tween: Option<Tween>) {
+		self.prime_playhead();
+		let Some(id) = self.playhead.current_item() else {
+			return;
+		};
+		// if the current item still has frames left to read, take it
+		// out of the queue so the next item can start
+		let item = if self
+			.items
+			.front()
+			.is_some_and(|(front_id, _)| *front_id == id)
+		{
+			self.items.pop_front()
+		} else {
+			None
+		};
+		// the next item's first frames may already be in the interpolation
+		// window, so they're carried over to the new playhead
+		let mut carried = [SourceFrame::SILENCE; 2];
+		let mut num_carried = 0;
+		for frame in self.playhead.upcoming_frames() {
+			carried[num_carried] = frame;
+			num_carried += 1;
+		}
+		let playhead = Playhead::primed(&carried[..num_carried], || {
+			next_source_frame(&mut self.items, &mut self.unused_item_producer)
+		});
+		let previous_playhead = std::mem::replace(&mut self.playhead, playhead);
+		if let Some(FadeOut { item, .. }) = self.fade_out.take() {
+			self.discard(item);
+		}
+		match crossfade_tween {
+			Some(tween) => {
+				let mut volume =
+					Parameter::new(Value::Fixed(Decibels::IDENTITY), Decibels::IDENTITY);
+				volume.set(Value::Fixed(Decibels::SILENCE), tween);
+				self.fade_out = Some(FadeOut {
+					id,
+					playhead: previous_playhead,
+					item,
+					volume,
+				});
+				self.item_volume =
+					Parameter::new(Value::Fixed(Decibels::SILENCE), Decibels::SILENCE);
+				self.item_volume
+					.set(Value::Fixed(Decibels::IDENTITY), tween);
+			}
+			None => {
+				self.discard(item);
+				self.item_volume =
+					Parameter::new(Value::Fixed(Decibels::IDENTITY), Decibels::IDENTITY);
+			}
+		}
+		self.update_shared_current_item();
+	}
+
+	fn remove(&mut self, id: QueueItemId) {
+		if self.playhead.current_item() == Some(id) {
+			self.skip(None);
+			return;
+		}
+		if self
+			.fade_out
+			.as_ref()
+			.is_some_and(|fade_out| fade_out.id == id)
+		{
+			let item = self.fade_out.take().and_then(|fade_out| fade_out.item);
+			self.discard(item);
+			return;
+		}
+		if let Some(index) = self.items.iter().position(|(item_id, _)| *item_id == id) {
+			let item = self.items.remove(index);
+			self.discard(item);
+		}
+		// the item's first frames may already be in the interpolation window
+		self.playhead.remove_upcoming_frames(id, || {
+			next_source_frame(&mut self.items, &mut self.unused_item_producer)
+		});
+		self.update_shared_current_item();
+	}
+
+	/// Sends an item back to the handle so it isn't deallocated
+	/// on the audio thread.
+	fn discard(&mut self, item: Option<(QueueItemId, QueueItem)>) {
+		if let Some(item) = item {
+			self.unused_item_producer.try_push(item).ok();
+		}
+	}
+
+	fn update_shared_current_item(&mut self) {
+		let current_item = if self.playhead.is_idle() {
+			self.items.front().map(|(id, _)| *id)
+		} else {
+			self.playhead.current_item()
+		};
+		self.shared.set_current_item(current_item);
+	}
+}
+
+impl Sound for QueueSound {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, volume, panning);
+		while let Some(item) = self.item_consumer.try_pop() {
+			self.items.push_back(item);
+		}
+		while let Some(command) = self.queue_command_consumer.try_pop() {
+			match command {
+				QueueCommand::Remove(id) => self.remove(id),
+				QueueCommand::Skip(crossfade_tween) => self.skip(crossfade_tween),
+			}
+		}
+		self.update_shared_current_item();
+		self.playback_state_manager.read_commands(
+			&mut self.command_readers.pause,
+			&mut self.command_readers.resume,
+			&mut self.command_readers.stop,
+			&self.shared.state,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		// update parameters
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		self.item_volume.update(dt * out.len() as f64, info);
+		let fade_out_finished = self
+			.fade_out
+			.as_mut()
+			.is_some_and(|fade_out| fade_out.volume.update(dt * out.len() as f64, info));
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+
+		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+
+			self.prime_playhead();
+			let item_volume = self
+				.item_volume
+				.interpolated_value(time_in_chunk)
+				.as_amplitude();
+			let mut queue_out = self.playhead.output() * item_volume;
+			self.playhead.advance(dt, || {
+				next_source_frame(&mut self.items, &mut self.unused_item_producer)
+			});
+			if let Some(fade_out) = &mut self.fade_out {
+				let fade_out_volume = fade_out
+					.volume
+					.interpolated_value(time_in_chunk)
+					.as_amplitude();
+				if fade_out.playhead.current_item() == Some(fade_out.id) {
+					queue_out += fade_out.playhead.output() * fade_out_volume;
+				}
+				let item = &mut fade_out.item;
+				fade_out.playhead.advance(dt, || match item {
+					Some((id, item)) => source_frame(*id, item).unwrap_or(SourceFrame::SILENCE),
+					None => SourceFrame::SILENCE,
+				});
+			}
+
+			*frame = (queue_out * fade_volume * volume).panned(panning);
+		}
+
+		if fade_out_finished
+			|| self
+				.fade_out
+				.as_ref()
+				.is_some_and(|fade_out| fade_out.playhead.current_item() != Some(fade_out.id))
+		{
+			let item = self.fade_out.take().and_then(|fade_out| fade_out.item);
+			self.discard(item);
+		}
+		self.update_shared_current_item();
+
+		if self.stop_when_empty
+			&& self.playhead.is_idle()
+			&& self.items.is_empty()
+			&& self.fade_out.is_none()
+		{
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished(&self.shared.state)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.state.store(PlaybackState::Stopped);
+	}
+}
+
+/// Reads the next frame from the front of the queue, sending items
+/// that have run out of frames back to the handle.
+#[must_use]
+fn next_source_frame(
+	items: &mut VecDeque<(QueueItemId, QueueItem)>,
+	unused_item_producer: &mut Producer<(QueueItemId, QueueItem)>,
+) -> SourceFrame {
+	while let Some((id, item)) = items.front_mut() {
+		if let Some(frame) = source_frame(*id, item) {
+			return frame;
+		}
+		if let Some(item) = items.pop_front() {
+			unused_item_producer.try_push(item).ok();
+		}
+	}
+	SourceFrame::SILENCE
+}
+
+#[must_use]
+fn source_frame(id: QueueItemId, item: &mut QueueItem) -> Option<SourceFrame> {
+	let sample_rate = item.sample_rate();
+	item.next_frame().map(|frame| SourceFrame {
+		frame,
+		item: Some(id),
+		sample_rate,
+	})
+}
@@ -0,0 +1,133 @@
+use crate::frame::{interpolate_frame, Frame};
+
+use super::super::QueueItemId;
+
+/// A frame of audio read from one of the queue's items.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct SourceFrame {
+	pub frame: Frame,
+	/// The item the frame came from, or `None` if the frame
+	/// is padding between items.
+	pub item: Option<QueueItemId>,
+	/// The sample rate of the item the frame came from (in Hz).
+	pub sample_rate: u32,
+}
+
+impl SourceFrame {
+	pub const SILENCE: Self = Self {
+		frame: Frame::ZERO,
+		item: None,
+		sample_rate: 0,
+	};
+}
+
+/// Resamples a sequence of frames from one or more items.
+///
+/// Frames from every item go through the same interpolation window,
+/// so there's no gap or added latency when one item ends and the
+/// next one starts.
+pub(super) struct Playhead {
+	/// The previous frame, the current frame, and the next two frames.
+	frames: [SourceFrame; 4],
+	fractional_position: f64,
+}
+
+impl Playhead {
+	/// Creates a playhead that isn't playing any items.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			frames: [SourceFrame::SILENCE; 4],
+			fractional_position: 0.0,
+		}
+	}
+
+	/// Creates a playhead whose current frame is the first of the
+	/// `carried` frames, or the first frame returned by `next_frame`
+	/// if there are no carried frames.
+	#[must_use]
+	pub fn primed(carried: &[SourceFrame], mut next_frame: impl FnMut() -> SourceFrame) -> Self {
+		let mut frames = [SourceFrame::SILENCE; 4];
+		let mut carried = carried.iter().copied();
+		for frame in &mut frames[1..] {
+			*frame = carried.next().unwrap_or_else(&mut next_frame);
+		}
+		Self {
+			frames,
+			fractional_position: 0.0,
+		}
+	}
+
+	/// Returns the item the current frame came from.
+	#[must_use]
+	pub fn current_item(&self) -> Option<QueueItemId> {
+		self.frames[1].item
+	}
+
+	/// Returns `true` if none of the frames in the interpolation
+	/// window came from an item.
+	#[must_use]
+	pub fn is_idle(&self) -> bool {
+		self.frames.iter().all(|frame| frame.item.is_none())
+	}
+
+	/// Returns the frames after the current frame that came from
+	/// a different item than the current frame.
+	pub fn upcoming_frames(&self) -> impl Iterator<Item = SourceFrame> + '_ {
+		let current_item = self.current_item();
+		self.frames[2..]
+			.iter()
+			.copied()
+			.filter(move |frame| frame.item.is_some() && frame.item != current_item)
+	}
+
+	/// Replaces the frames after the current frame that came from the
+	/// given item with frames from `next_frame`.
+	pub fn remove_upcoming_frames(
+		&mut self,
+		item: QueueItemId,
+		mut next_frame: impl FnMut() -> SourceFrame,
+	) {
+		let mut upcoming = [SourceFrame::SILENCE; 2];
+		let mut num_upcoming = 0;
+		for frame in &self.frames[2..] {
+			if frame.item != Some(item) {
+				upcoming[num_upcoming] = *frame;
+				num_upcoming += 1;
+			}
+		}
+		for (i, frame) in self.frames[2..].iter_mut().enumerate() {
+			*frame = if i < num_upcoming {
+				upcoming[i]
+			} else {
+				next_frame()
+			};
+		}
+	}
+
+	#[must_use]
+	pub fn output(&self) -> Frame {
+		interpolate_frame(
+			self.frames[0].frame,
+			self.frames[1].frame,
+			self.frames[2].frame,
+			self.frames[3].frame,
+			self.fractional_position as f32,
+		)
+	}
+
+	/// Moves the playhead forward by `dt` seconds, pulling in new frames
+	/// from `next_frame` as needed.
+	pub fn advance(&mut self, dt: f64, mut next_frame: impl FnMut() -> SourceFrame) {
+		// padding between items is played at the output sample rate
+		self.fractional_position += match self.frames[1].sample_rate {
+			0 => 1.0,
+			sample_rate => sample_rate as f64 * dt,
+		};
+		while self.fractional_position >= 1.0 {
+			self.fractional_position -= 1.0;
+			self.frames.copy_within(1.., 0);
+			self.frames[3] = next_frame();
+		}
+	}
+}
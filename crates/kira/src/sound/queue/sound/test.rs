@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{
+		queue::{QueueItem, QueueItemId, QueueSoundData, QueueSoundHandle, QueueSoundSettings},
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		PlaybackState, Sound,
+	},
+	Panning, ResourceLimitReached, Tween,
+};
+
+use super::QueueSound;
+
+const SAMPLE_RATE: u32 = 1000;
+const DT: f64 = 1.0 / SAMPLE_RATE as f64;
+
+/// Creates a static sound that plays one frame per output frame.
+fn static_sound(values: &[f32]) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: SAMPLE_RATE,
+		frames: values.iter().copied().map(Frame::from_mono).collect(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+fn play(items: Vec<QueueItem>, settings: QueueSoundSettings) -> (QueueSound, QueueSoundHandle) {
+	let mut data = QueueSoundData::new().with_settings(settings);
+	for item in items {
+		data.append(item);
+	}
+	data.split()
+}
+
+/// Renders frames and converts them back to mono values.
+fn render(sound: &mut QueueSound, num_frames: usize) -> Vec<f32> {
+	let info = MockInfoBuilder::new().build();
+	(0..num_frames)
+		.map(|_| {
+			sound.on_start_processing();
+			let frame = sound.process_one(DT, &info);
+			frame.left / Frame::from_mono(1.0).panned(Panning::CENTER).left
+		})
+		.collect()
+}
+
+/// Tests that items play one after another with no gap between them.
+#[test]
+fn plays_items_back_to_back() {
+	let (mut sound, handle) = play(
+		vec![
+			static_sound(&[1.0, 2.0, 3.0]).into(),
+			static_sound(&[4.0, 5.0]).into(),
+		],
+		QueueSoundSettings::new(),
+	);
+	assert_eq!(handle.current_item(), Some(QueueItemId(0)));
+	assert_eq!(render(&mut sound, 3), [1.0, 2.0, 3.0]);
+	assert_eq!(handle.current_item(), Some(QueueItemId(1)));
+	assert_eq!(render(&mut sound, 2), [4.0, 5.0]);
+	assert!(!sound.finished());
+	assert_eq!(render(&mut sound, 4), [0.0; 4]);
+	assert_eq!(handle.current_item(), None);
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+	assert!(sound.finished());
+}
+
+/// Tests that the slice, start position, and loop region of static
+/// items are respected.
+#[test]
+fn respects_item_settings() {
+	let (mut sound, _handle) = play(
+		vec![
+			static_sound(&[1.0, 2.0, 3.0, 4.0, 5.0])
+				.slice(0.001..0.004)
+				.into(),
+			static_sound(&[6.0, 7.0, 8.0]).start_position(0.002).into(),
+			static_sound(&[9.0, 10.0]).loop_region(..).into(),
+		],
+		QueueSoundSettings::new(),
+	);
+	assert_eq!(
+		render(&mut sound, 8),
+		[2.0, 3.0, 4.0, 8.0, 9.0, 10.0, 9.0, 10.0]
+	);
+}
+
+/// Tests that items can be appended and removed while the
+/// queue is playing.
+#[test]
+fn append_and_remove() {
+	let (mut sound, mut handle) = play(
+		vec![static_sound(&[1.0, 2.0]).into()],
+		QueueSoundSettings::new(),
+	);
+	let removed = handle.append(static_sound(&[3.0])).unwrap();
+	let kept = handle.append(static_sound(&[4.0, 5.0])).unwrap();
+	assert_eq!(removed, QueueItemId(1));
+	assert_eq!(kept, QueueItemId(2));
+	assert_eq!(render(&mut sound, 1), [1.0]);
+	handle.remove(removed);
+	assert_eq!(render(&mut sound, 3), [2.0, 4.0, 5.0]);
+
+	// removing the item that's playing moves on to the next one
+	let (mut sound, mut handle) = play(
+		vec![
+			static_sound(&[1.0, 2.0, 3.0]).into(),
+			static_sound(&[4.0]).into(),
+		],
+		QueueSoundSettings::new(),
+	);
+	assert_eq!(render(&mut sound, 1), [1.0]);
+	handle.remove(QueueItemId(0));
+	assert_eq!(render(&mut sound, 1), [4.0]);
+}
+
+/// Tests that items can't be appended past the queue's capacity until
+/// the audio thread is done with some of them.
+#[test]
+fn capacity() {
+	let (mut sound, mut handle) = play(
+		vec![static_sound(&[1.0]).into()],
+		QueueSoundSettings::new().capacity(2).stop_when_empty(false),
+	);
+	assert!(handle.append(static_sound(&[2.0])).is_ok());
+	assert_eq!(
+		handle.append(static_sound(&[3.0])),
+		Err(ResourceLimitReached)
+	);
+	render(&mut sound, 5);
+	assert!(handle.append(static_sound(&[3.0])).is_ok());
+	assert!(!sound.finished());
+}
+
+/// Tests that skipping moves on to the next item right away, or
+/// crossfades into it if a tween is given.
+#[test]
+fn skip() {
+	let (mut sound, mut handle) = play(
+		vec![
+			static_sound(&[1.0]).loop_region(..).into(),
+			static_sound(&[2.0, 3.0]).into(),
+		],
+		QueueSoundSettings::new(),
+	);
+	assert_eq!(render(&mut sound, 4), [1.0; 4]);
+	handle.skip(None);
+	assert_eq!(render(&mut sound, 2), [2.0, 3.0]);
+
+	let (mut sound, mut handle) = play(
+		vec![
+			static_sound(&[1.0]).loop_region(..).into(),
+			static_sound(&[-1.0]).loop_region(..).into(),
+		],
+		QueueSoundSettings::new(),
+	);
+	render(&mut sound, 4);
+	handle.skip(Tween {
+		duration: Duration::from_millis(10),
+		..Default::default()
+	});
+	let frames = render(&mut sound, 20);
+	assert_eq!(handle.current_item(), Some(QueueItemId(1)));
+	assert!(frames[..9]
+		.iter()
+		.all(|value| *value > -1.0 && *value < 1.0));
+	assert!(frames[10..].iter().all(|value| *value == -1.0));
+}
+
+/// Tests that items with different sample rates are resampled.
+#[test]
+fn resamples_items() {
+	let mut slow = static_sound(&[1.0, 1.0, 1.0, 1.0]);
+	slow.sample_rate = SAMPLE_RATE / 2;
+	let (mut sound, _handle) = play(
+		vec![slow.into(), static_sound(&[2.0]).into()],
+		QueueSoundSettings::new(),
+	);
+	let frames = render(&mut sound, 9);
+	assert_eq!(frames[8], 2.0);
+	assert!(frames[..8].iter().all(|value| *value != 2.0));
+}
+
+/// Tests that streaming items play after static items with no gap.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn streaming_items() {
+	use crate::sound::streaming::{mock::MockDecoder, StreamingSoundData};
+
+	let streaming = StreamingSoundData::from_decoder(MockDecoder::new(vec![
+		Frame::from_mono(3.0),
+		Frame::from_mono(4.0),
+		Frame::from_mono(5.0),
+	]));
+	let mut queue = QueueSoundData::new();
+	queue.append(StaticSoundData {
+		sample_rate: 1,
+		..static_sound(&[1.0, 2.0])
+	});
+	queue.append(QueueItem::try_from(streaming).unwrap());
+	let (mut sound, _handle) = queue.split();
+	// give the decoding thread time to fill its buffer
+	std::thread::sleep(Duration::from_millis(100));
+	let info = MockInfoBuilder::new().build();
+	for i in 1..=5 {
+		sound.on_start_processing();
+		assert_eq!(
+			sound.process_one(1.0, &info),
+			Frame::from_mono(i as f32).panned(Panning::CENTER)
+		);
+	}
+}
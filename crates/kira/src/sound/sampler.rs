@@ -0,0 +1,128 @@
+/*!
+Plays notes using multisampled instruments.
+
+A [`Sampler`] is a set of [`SamplerZone`]s, each of which maps a range of
+keys and velocities to a [`StaticSoundData`](crate::sound::static_sound::StaticSoundData). [`Sampler::note`] picks the
+zone for a note and returns a [`SamplerNote`], which can be played like
+any other sound. The sample is repitched to match the key, and when the
+note is [released](SamplerNoteHandle::release), it fades out using the
+zone's release time.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	sound::sampler::Sampler,
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let piano = Sampler::from_sfz_file("piano/piano.sfz")?;
+if let Some(note) = piano.note(60, 100) {
+	let mut note = manager.play(note)?;
+	// later...
+	note.release();
+}
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+## SFZ support
+
+Samplers can be loaded from [SFZ](https://sfzformat.com) files using
+[`Sampler::from_sfz_file`] or [`Sampler::from_sfz_str`]. The
+`<control>`, `<global>`, `<master>`, `<group>`, and `<region>` headers
+are supported, along with these opcodes:
+
+- `sample`, `default_path`
+- `key`, `lokey`, `hikey`, `pitch_keycenter`, `lovel`, `hivel`
+- `transpose`, `tune`
+- `volume`, `pan`, `amp_veltrack`
+- `loop_mode`, `loop_start`, `loop_end`
+- `ampeg_release`
+
+Keys can be given as MIDI note numbers or as note names like `c#4`,
+where `c4` is middle C (60). Other headers and opcodes are ignored.
+Preprocessor directives like `#include` and `#define` are not supported.
+
+If more than one zone matches a note, the first one is used.
+*/
+
+#[cfg(test)]
+mod test;
+
+mod note;
+mod sfz;
+mod zone;
+
+pub use note::*;
+pub use sfz::*;
+pub use zone::*;
+
+use crate::{Decibels, Semitones, Tween};
+
+/// An instrument that plays notes using samples.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Sampler {
+	zones: Vec<SamplerZone>,
+}
+
+impl Sampler {
+	/// Creates a new [`Sampler`] with no zones.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a zone to the sampler.
+	pub fn add_zone(&mut self, zone: SamplerZone) {
+		self.zones.push(zone);
+	}
+
+	/// Returns the sampler's zones.
+	#[must_use]
+	pub fn zones(&self) -> &[SamplerZone] {
+		&self.zones
+	}
+
+	/// Returns the zone that should play a note with the given
+	/// key and velocity, if there is one.
+	#[must_use]
+	pub fn zone(&self, key: u8, velocity: u8) -> Option<&SamplerZone> {
+		self.zones
+			.iter()
+			.find(|zone| zone.keys.contains(&key) && zone.velocities.contains(&velocity))
+	}
+
+	/// Returns a sound that plays a note with the given key
+	/// and velocity (both from `0` to `127`).
+	///
+	/// Returns `None` if no zone matches the note or the velocity is 0.
+	#[must_use]
+	pub fn note(&self, key: u8, velocity: u8) -> Option<SamplerNote> {
+		if velocity == 0 {
+			return None;
+		}
+		let zone = self.zone(key, velocity)?;
+		let pitch = Semitones(key as f64 - zone.root_key as f64) + zone.tune;
+		let velocity_amplitude = 1.0 - zone.velocity_tracking
+			+ zone.velocity_tracking * (velocity as f32 / 127.0).powi(2);
+		let volume = Decibels(zone.volume.0 + 20.0 * velocity_amplitude.log10());
+		let data = zone
+			.data
+			.playback_rate(pitch)
+			.volume(volume)
+			.panning(zone.panning);
+		let data = match zone.loop_mode {
+			SamplerLoopMode::Continuous | SamplerLoopMode::Sustain => {
+				data.loop_region(zone.loop_region.unwrap_or_default())
+			}
+			SamplerLoopMode::NoLoop | SamplerLoopMode::OneShot => data.loop_region(None),
+		};
+		Some(SamplerNote {
+			data,
+			loop_mode: zone.loop_mode,
+			release_tween: Tween {
+				duration: zone.release,
+				..Default::default()
+			},
+		})
+	}
+}
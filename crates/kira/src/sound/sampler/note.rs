@@ -0,0 +1,102 @@
+use crate::{
+	sound::{
+		static_sound::{StaticSoundData, StaticSoundHandle},
+		PlaybackState, Sound, SoundData,
+	},
+	Decibels, Panning, Tween, Value,
+};
+
+use super::SamplerLoopMode;
+
+/// A note played by a [`Sampler`](super::Sampler).
+///
+/// These are created by [`Sampler::note`](super::Sampler::note).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplerNote {
+	/// The zone's sample with the note's pitch, volume, panning,
+	/// and loop region applied.
+	pub data: StaticSoundData,
+	/// How the sample loops.
+	pub loop_mode: SamplerLoopMode,
+	/// The tween used to fade out the note when it's released.
+	pub release_tween: Tween,
+}
+
+impl SoundData for SamplerNote {
+	type Error = ();
+
+	type Handle = SamplerNoteHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.data.into_sound()?;
+		Ok((
+			sound,
+			SamplerNoteHandle {
+				handle,
+				loop_mode: self.loop_mode,
+				release_tween: self.release_tween,
+			},
+		))
+	}
+}
+
+/// Controls a note played by a [`Sampler`](super::Sampler).
+///
+/// Dropping this handle does not stop the note.
+#[derive(Debug)]
+pub struct SamplerNoteHandle {
+	handle: StaticSoundHandle,
+	loop_mode: SamplerLoopMode,
+	release_tween: Tween,
+}
+
+impl SamplerNoteHandle {
+	/// Returns the current playback state of the note.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.handle.state()
+	}
+
+	/// Returns the current playback position of the note's sample
+	/// (in seconds).
+	#[must_use]
+	pub fn position(&self) -> f64 {
+		self.handle.position()
+	}
+
+	/// Sets the volume of the note.
+	pub fn set_volume(&mut self, volume: impl Into<Value<Decibels>>, tween: Tween) {
+		self.handle.set_volume(volume, tween)
+	}
+
+	/// Sets the panning of the note, where `-1.0` is hard left,
+	/// `0.0` is center, and `1.0` is hard right.
+	pub fn set_panning(&mut self, panning: impl Into<Value<Panning>>, tween: Tween) {
+		self.handle.set_panning(panning, tween)
+	}
+
+	/// Releases the note, fading it out over the zone's release time.
+	///
+	/// If the zone's loop mode is [`SamplerLoopMode::Sustain`], the
+	/// sample stops looping. If the loop mode is [`SamplerLoopMode::OneShot`],
+	/// this has no effect.
+	pub fn release(&mut self) {
+		match self.loop_mode {
+			SamplerLoopMode::OneShot => {}
+			SamplerLoopMode::Sustain => {
+				self.handle.set_loop_region(None);
+				self.handle.stop(self.release_tween);
+			}
+			SamplerLoopMode::NoLoop | SamplerLoopMode::Continuous => {
+				self.handle.stop(self.release_tween);
+			}
+		}
+	}
+
+	/// Fades out the note to silence with the given tween and then
+	/// stops playback, regardless of the loop mode.
+	pub fn stop(&mut self, tween: Tween) {
+		self.handle.stop(tween)
+	}
+}
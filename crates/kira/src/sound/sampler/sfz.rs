@@ -0,0 +1,476 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	collections::HashMap,
+	fmt::Display,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+use crate::{
+	sound::{static_sound::StaticSoundData, EndPosition, PlaybackPosition, Region},
+	Decibels, Panning, Semitones,
+};
+
+use super::{Sampler, SamplerLoopMode, SamplerZone};
+
+/// Errors that can occur when loading an SFZ file.
+#[derive(Debug)]
+pub enum SfzError {
+	/// An opcode has a value that couldn't be parsed.
+	InvalidValue {
+		/// The line the opcode is on (starting from 1).
+		line: usize,
+		/// The name of the opcode.
+		opcode: String,
+		/// The value of the opcode.
+		value: String,
+	},
+	/// The file contains text that isn't a header, an opcode,
+	/// or a comment.
+	UnexpectedText {
+		/// The line the text is on (starting from 1).
+		line: usize,
+		/// The unexpected text.
+		text: String,
+	},
+	/// The file uses a preprocessor directive like `#include`,
+	/// which isn't supported.
+	UnsupportedDirective {
+		/// The line the directive is on (starting from 1).
+		line: usize,
+		/// The directive.
+		directive: String,
+	},
+	/// A region doesn't have a `sample` opcode.
+	MissingSample {
+		/// The line the region starts on (starting from 1).
+		line: usize,
+	},
+	/// A sample couldn't be loaded.
+	SampleLoadError {
+		/// The path of the sample, relative to the SFZ file.
+		path: PathBuf,
+		/// The error that occurred when loading the sample.
+		error: Box<dyn std::error::Error + Send + Sync>,
+	},
+	/// An error occurred while reading the SFZ file from the filesystem.
+	IoError(std::io::Error),
+}
+
+impl Display for SfzError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SfzError::InvalidValue {
+				line,
+				opcode,
+				value,
+			} => write!(
+				f,
+				"Invalid value '{}' for opcode '{}' on line {}",
+				value, opcode, line
+			),
+			SfzError::UnexpectedText { line, text } => {
+				write!(f, "Unexpected text '{}' on line {}", text, line)
+			}
+			SfzError::UnsupportedDirective { line, directive } => write!(
+				f,
+				"The directive '{}' on line {} is not supported",
+				directive, line
+			),
+			SfzError::MissingSample { line } => {
+				write!(f, "The region on line {} does not have a sample", line)
+			}
+			SfzError::SampleLoadError { path, error } => {
+				write!(f, "Could not load the sample {}: {}", path.display(), error)
+			}
+			SfzError::IoError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl std::error::Error for SfzError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			SfzError::SampleLoadError { error, .. } => Some(error.as_ref()),
+			SfzError::IoError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<std::io::Error> for SfzError {
+	fn from(v: std::io::Error) -> Self {
+		Self::IoError(v)
+	}
+}
+
+impl Sampler {
+	/// Loads a [`Sampler`] from an SFZ file.
+	///
+	/// Samples are loaded relative to the directory the SFZ file is in.
+	/// See the [module docs](crate::sound::sampler#sfz-support) for which
+	/// parts of the SFZ format are supported.
+	#[cfg(all(feature = "symphonia", not(target_arch = "wasm32")))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "symphonia", not(wasm32)))))]
+	pub fn from_sfz_file(path: impl AsRef<Path>) -> Result<Self, SfzError> {
+		let path = path.as_ref();
+		let sfz = std::fs::read_to_string(path)?;
+		let directory = path.parent().unwrap_or(Path::new(""));
+		Self::from_sfz_str(&sfz, |sample| {
+			StaticSoundData::from_file(directory.join(sample))
+		})
+	}
+
+	/// Loads a [`Sampler`] from the contents of an SFZ file.
+	///
+	/// `load_sample` is called with the path of each sample (relative
+	/// to the SFZ file). Each sample is only loaded once, even if
+	/// multiple regions use it.
+	pub fn from_sfz_str<E: Into<Box<dyn std::error::Error + Send + Sync>>>(
+		sfz: &str,
+		mut load_sample: impl FnMut(&Path) -> Result<StaticSoundData, E>,
+	) -> Result<Self, SfzError> {
+		let mut samples = HashMap::<PathBuf, StaticSoundData>::new();
+		let mut sampler = Sampler::new();
+		for region in parse(sfz)? {
+			let data = match samples.get(&region.sample) {
+				Some(data) => data.clone(),
+				None => {
+					let data =
+						load_sample(&region.sample).map_err(|error| SfzError::SampleLoadError {
+							path: region.sample.clone(),
+							error: error.into(),
+						})?;
+					samples.insert(region.sample.clone(), data.clone());
+					data
+				}
+			};
+			sampler.add_zone(region.into_zone(data));
+		}
+		Ok(sampler)
+	}
+}
+
+/// The level of the SFZ file opcodes are defined at. Opcodes
+/// at lower levels override the ones at higher levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Header {
+	Control,
+	Global,
+	Master,
+	Group,
+	Region,
+	/// A header we don't support, whose opcodes are ignored.
+	Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Opcode {
+	name: String,
+	value: String,
+	line: usize,
+}
+
+/// A `<region>` with all of the opcodes it inherits applied.
+#[derive(Debug, Clone, PartialEq)]
+struct ParsedRegion {
+	sample: PathBuf,
+	zone: SamplerZone,
+}
+
+impl ParsedRegion {
+	#[must_use]
+	fn into_zone(self, data: StaticSoundData) -> SamplerZone {
+		SamplerZone { data, ..self.zone }
+	}
+}
+
+#[derive(Debug, Default)]
+struct Parser {
+	control: Vec<Opcode>,
+	global: Vec<Opcode>,
+	master: Vec<Opcode>,
+	group: Vec<Opcode>,
+	/// The opcodes of the current region and the line it starts on.
+	region: Option<(Vec<Opcode>, usize)>,
+	current_header: Option<Header>,
+	regions: Vec<ParsedRegion>,
+}
+
+impl Parser {
+	fn start_header(&mut self, name: &str, line: usize) -> Result<(), SfzError> {
+		self.finish_region()?;
+		let header = match name {
+			"control" => Header::Control,
+			"global" => Header::Global,
+			"master" => Header::Master,
+			"group" => Header::Group,
+			"region" => Header::Region,
+			_ => Header::Unsupported,
+		};
+		match header {
+			Header::Control => self.control.clear(),
+			Header::Global => {
+				self.global.clear();
+				self.master.clear();
+				self.group.clear();
+			}
+			Header::Master => {
+				self.master.clear();
+				self.group.clear();
+			}
+			Header::Group => self.group.clear(),
+			Header::Region => self.region = Some((vec![], line)),
+			Header::Unsupported => {}
+		}
+		self.current_header = Some(header);
+		Ok(())
+	}
+
+	fn add_opcode(&mut self, opcode: Opcode) -> Result<(), SfzError> {
+		let opcodes = match self.current_header {
+			Some(Header::Control) => &mut self.control,
+			Some(Header::Global) => &mut self.global,
+			Some(Header::Master) => &mut self.master,
+			Some(Header::Group) => &mut self.group,
+			Some(Header::Region) => match &mut self.region {
+				Some((opcodes, _)) => opcodes,
+				None => return Ok(()),
+			},
+			Some(Header::Unsupported) => return Ok(()),
+			None => {
+				return Err(SfzError::UnexpectedText {
+					line: opcode.line,
+					text: format!("{}={}", opcode.name, opcode.value),
+				})
+			}
+		};
+		opcodes.push(opcode);
+		Ok(())
+	}
+
+	fn finish_region(&mut self) -> Result<(), SfzError> {
+		let Some((region_opcodes, line)) = self.region.take() else {
+			return Ok(());
+		};
+		let mut default_path = String::new();
+		for opcode in &self.control {
+			if opcode.name == "default_path" {
+				default_path.clone_from(&opcode.value);
+			}
+		}
+		let mut sample = None;
+		let mut zone = SamplerZone::new(
+			StaticSoundData {
+				sample_rate: 1,
+				frames: vec![].into(),
+				settings: Default::default(),
+				slice: None,
+				metadata: None,
+			},
+			60,
+		);
+		let mut transpose: i32 = 0;
+		let mut tune_cents: i32 = 0;
+		let mut loop_mode = None;
+		let mut loop_start: Option<usize> = None;
+		let mut loop_end: Option<usize> = None;
+		let opcodes = self
+			.global
+			.iter()
+			.chain(&self.master)
+			.chain(&self.group)
+			.chain(&region_opcodes);
+		for opcode in opcodes {
+			let invalid_value = || SfzError::InvalidValue {
+				line: opcode.line,
+				opcode: opcode.name.clone(),
+				value: opcode.value.clone(),
+			};
+			let value = opcode.value.as_str();
+			match opcode.name.as_str() {
+				"sample" => sample = Some(format!("{}{}", default_path, value).replace('\\', "/")),
+				"key" => {
+					let key = parse_key(value).ok_or_else(invalid_value)?;
+					zone.keys = key..=key;
+					zone.root_key = key;
+				}
+				"lokey" => {
+					zone.keys = parse_key(value).ok_or_else(invalid_value)?..=*zone.keys.end()
+				}
+				"hikey" => {
+					zone.keys = *zone.keys.start()..=parse_key(value).ok_or_else(invalid_value)?
+				}
+				"pitch_keycenter" => zone.root_key = parse_key(value).ok_or_else(invalid_value)?,
+				"lovel" => {
+					zone.velocities =
+						parse_number(value).ok_or_else(invalid_value)?..=*zone.velocities.end()
+				}
+				"hivel" => {
+					zone.velocities =
+						*zone.velocities.start()..=parse_number(value).ok_or_else(invalid_value)?
+				}
+				"transpose" => transpose = parse_number(value).ok_or_else(invalid_value)?,
+				"tune" => tune_cents = parse_number(value).ok_or_else(invalid_value)?,
+				"volume" => zone.volume = Decibels(parse_number(value).ok_or_else(invalid_value)?),
+				"pan" => {
+					let pan: f32 = parse_number(value).ok_or_else(invalid_value)?;
+					zone.panning = Panning(pan / 100.0);
+				}
+				"amp_veltrack" => {
+					let velocity_tracking: f32 = parse_number(value).ok_or_else(invalid_value)?;
+					zone.velocity_tracking = velocity_tracking / 100.0;
+				}
+				"loop_mode" | "loopmode" => {
+					loop_mode = Some(match value {
+						"no_loop" => SamplerLoopMode::NoLoop,
+						"one_shot" => SamplerLoopMode::OneShot,
+						"loop_continuous" => SamplerLoopMode::Continuous,
+						"loop_sustain" => SamplerLoopMode::Sustain,
+						_ => return Err(invalid_value()),
+					})
+				}
+				"loop_start" | "loopstart" => {
+					loop_start = Some(parse_number(value).ok_or_else(invalid_value)?)
+				}
+				"loop_end" | "loopend" => {
+					loop_end = Some(parse_number(value).ok_or_else(invalid_value)?)
+				}
+				"ampeg_release" => {
+					let release: f64 = parse_number(value).ok_or_else(invalid_value)?;
+					zone.release =
+						Duration::try_from_secs_f64(release).map_err(|_| invalid_value())?;
+				}
+				_ => {}
+			}
+		}
+		zone.tune = Semitones(transpose as f64 + tune_cents as f64 / 100.0);
+		// loop points only make a sample loop if the loop mode isn't set
+		let has_loop_points = loop_start.is_some() || loop_end.is_some();
+		zone.loop_mode = loop_mode.unwrap_or(if has_loop_points {
+			SamplerLoopMode::Continuous
+		} else {
+			SamplerLoopMode::NoLoop
+		});
+		if has_loop_points {
+			zone.loop_region = Some(Region {
+				start: PlaybackPosition::Samples(loop_start.unwrap_or(0)),
+				// the loop end in SFZ files is inclusive
+				end: match loop_end {
+					Some(loop_end) => EndPosition::Custom(PlaybackPosition::Samples(loop_end + 1)),
+					None => EndPosition::EndOfAudio,
+				},
+			});
+		}
+		let sample = sample.ok_or(SfzError::MissingSample { line })?;
+		self.regions.push(ParsedRegion {
+			sample: sample.into(),
+			zone,
+		});
+		Ok(())
+	}
+}
+
+/// Parses the regions in an SFZ file.
+fn parse(sfz: &str) -> Result<Vec<ParsedRegion>, SfzError> {
+	let mut parser = Parser::default();
+	for (line_index, line) in sfz.lines().enumerate() {
+		let line_number = line_index + 1;
+		let line = match line.find("//") {
+			Some(comment_start) => &line[..comment_start],
+			None => line,
+		};
+		// opcode values can have spaces in them (like sample paths), so
+		// an opcode isn't added until the next opcode or header starts
+		let mut pending_opcode: Option<Opcode> = None;
+		let mut words = line
+			.split_whitespace()
+			.map(str::to_string)
+			.collect::<Vec<_>>();
+		words.reverse();
+		while let Some(word) = words.pop() {
+			if let Some(header) = word.strip_prefix('<') {
+				if let Some(opcode) = pending_opcode.take() {
+					parser.add_opcode(opcode)?;
+				}
+				let Some((name, rest)) = header.split_once('>') else {
+					return Err(SfzError::UnexpectedText {
+						line: line_number,
+						text: word,
+					});
+				};
+				parser.start_header(name, line_number)?;
+				if !rest.is_empty() {
+					words.push(rest.to_string());
+				}
+			} else if word.starts_with('#') {
+				return Err(SfzError::UnsupportedDirective {
+					line: line_number,
+					directive: word,
+				});
+			} else if let Some((name, value)) = word.split_once('=') {
+				if let Some(opcode) = pending_opcode.take() {
+					parser.add_opcode(opcode)?;
+				}
+				pending_opcode = Some(Opcode {
+					name: name.to_string(),
+					value: value.to_string(),
+					line: line_number,
+				});
+			} else if let Some(opcode) = &mut pending_opcode {
+				opcode.value.push(' ');
+				opcode.value.push_str(&word);
+			} else {
+				return Err(SfzError::UnexpectedText {
+					line: line_number,
+					text: word,
+				});
+			}
+		}
+		if let Some(opcode) = pending_opcode {
+			parser.add_opcode(opcode)?;
+		}
+	}
+	parser.finish_region()?;
+	Ok(parser.regions)
+}
+
+#[must_use]
+fn parse_number<T: std::str::FromStr>(value: &str) -> Option<T> {
+	value.parse().ok()
+}
+
+/// Parses a MIDI note number or a note name like `c#4`,
+/// where `c4` is 60.
+#[must_use]
+fn parse_key(value: &str) -> Option<u8> {
+	if let Ok(key) = value.parse::<u8>() {
+		return (key <= 127).then_some(key);
+	}
+	let value = value.to_ascii_lowercase();
+	let mut chars = value.chars();
+	let mut semitone: i32 = match chars.next()? {
+		'c' => 0,
+		'd' => 2,
+		'e' => 4,
+		'f' => 5,
+		'g' => 7,
+		'a' => 9,
+		'b' => 11,
+		_ => return None,
+	};
+	let rest = chars.as_str();
+	let octave = if let Some(octave) = rest.strip_prefix('#') {
+		semitone += 1;
+		octave
+	} else if let Some(octave) = rest.strip_prefix('b').filter(|octave| !octave.is_empty()) {
+		semitone -= 1;
+		octave
+	} else {
+		rest
+	};
+	let key = (octave.parse::<i32>().ok()? + 1) * 12 + semitone;
+	u8::try_from(key).ok().filter(|key| *key <= 127)
+}
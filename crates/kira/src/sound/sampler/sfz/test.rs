@@ -0,0 +1,155 @@
+use std::{convert::Infallible, path::Path, time::Duration};
+
+use crate::{
+	sound::{
+		sampler::{Sampler, SamplerLoopMode, SfzError},
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		EndPosition, PlaybackPosition, Region,
+	},
+	Decibels, Frame, Panning, Semitones,
+};
+
+use super::parse_key;
+
+/// Returns a sample whose length identifies the path it was loaded from.
+fn load_sample(path: &Path) -> Result<StaticSoundData, Infallible> {
+	Ok(StaticSoundData {
+		sample_rate: 100,
+		frames: vec![Frame::ZERO; path.as_os_str().len()].into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	})
+}
+
+/// Tests that regions inherit opcodes from the headers above them.
+#[test]
+fn inherits_opcodes() {
+	let sampler = Sampler::from_sfz_str(
+		"
+		<control> default_path=samples/
+		<global> volume=-6 ampeg_release=0.5
+		<group> lokey=0 hikey=59 // the lower half of the keyboard
+		<region> sample=low.wav pitch_keycenter=48
+		<region> sample=low 2.wav pitch_keycenter=50 volume=-3
+		<group> lokey=60 hikey=127
+		<region> sample=high.wav pitch_keycenter=72
+		",
+		load_sample,
+	)
+	.unwrap();
+	let zones = sampler.zones();
+	assert_eq!(zones.len(), 3);
+	assert_eq!(zones[0].data.frames.len(), "samples/low.wav".len());
+	assert_eq!(zones[0].keys, 0..=59);
+	assert_eq!(zones[0].root_key, 48);
+	assert_eq!(zones[0].volume, Decibels(-6.0));
+	assert_eq!(zones[0].release, Duration::from_secs_f64(0.5));
+	assert_eq!(zones[1].data.frames.len(), "samples/low 2.wav".len());
+	assert_eq!(zones[1].volume, Decibels(-3.0));
+	assert_eq!(zones[2].keys, 60..=127);
+	assert_eq!(zones[2].root_key, 72);
+	assert_eq!(zones[2].volume, Decibels(-6.0));
+}
+
+/// Tests that the supported opcodes are applied to zones.
+#[test]
+fn opcodes() {
+	let sampler = Sampler::from_sfz_str(
+		"<region>sample=a.wav key=c#4 lovel=64 hivel=100 transpose=-12 tune=50 pan=-50 amp_veltrack=25
+		loop_mode=loop_sustain loop_start=10 loop_end=19",
+		load_sample,
+	)
+	.unwrap();
+	let zone = &sampler.zones()[0];
+	assert_eq!(zone.keys, 61..=61);
+	assert_eq!(zone.root_key, 61);
+	assert_eq!(zone.velocities, 64..=100);
+	assert_eq!(zone.tune, Semitones(-11.5));
+	assert_eq!(zone.panning, Panning(-0.5));
+	assert_eq!(zone.velocity_tracking, 0.25);
+	assert_eq!(zone.loop_mode, SamplerLoopMode::Sustain);
+	assert_eq!(
+		zone.loop_region,
+		Some(Region {
+			start: PlaybackPosition::Samples(10),
+			end: EndPosition::Custom(PlaybackPosition::Samples(20)),
+		})
+	);
+}
+
+/// Tests that loop points make a sample loop if the loop mode isn't set.
+#[test]
+fn loop_points_imply_looping() {
+	let sampler = Sampler::from_sfz_str(
+		"<region> sample=a.wav loop_start=10
+		<region> sample=b.wav",
+		load_sample,
+	)
+	.unwrap();
+	assert_eq!(sampler.zones()[0].loop_mode, SamplerLoopMode::Continuous);
+	assert_eq!(sampler.zones()[1].loop_mode, SamplerLoopMode::NoLoop);
+}
+
+/// Tests that each sample is only loaded once.
+#[test]
+fn loads_samples_once() {
+	let mut num_loads = 0;
+	let sampler = Sampler::from_sfz_str(
+		"<region> sample=a.wav key=60 <region> sample=a.wav key=62",
+		|path| {
+			num_loads += 1;
+			load_sample(path)
+		},
+	)
+	.unwrap();
+	assert_eq!(num_loads, 1);
+	assert_eq!(sampler.zones().len(), 2);
+}
+
+/// Tests that unsupported headers and opcodes are ignored.
+#[test]
+fn ignores_unsupported_features() {
+	let sampler = Sampler::from_sfz_str(
+		"<curve> curve_index=7 v000=0 v127=1
+		<region> sample=a.wav fil_type=lpf_2p cutoff=500",
+		load_sample,
+	)
+	.unwrap();
+	assert_eq!(sampler.zones().len(), 1);
+}
+
+/// Tests that invalid files produce errors.
+#[test]
+fn errors() {
+	assert!(matches!(
+		Sampler::from_sfz_str("<region> sample=a.wav\n<region> lokey=x", load_sample),
+		Err(SfzError::InvalidValue { line: 2, .. })
+	));
+	assert!(matches!(
+		Sampler::from_sfz_str("<region> key=60", load_sample),
+		Err(SfzError::MissingSample { line: 1 })
+	));
+	assert!(matches!(
+		Sampler::from_sfz_str("#include \"other.sfz\"", load_sample),
+		Err(SfzError::UnsupportedDirective { line: 1, .. })
+	));
+	assert!(matches!(
+		Sampler::from_sfz_str("<region> sample=a.wav", |_| Err("missing")),
+		Err(SfzError::SampleLoadError { .. })
+	));
+}
+
+/// Tests that keys can be parsed from numbers and note names.
+#[test]
+fn parses_keys() {
+	assert_eq!(parse_key("60"), Some(60));
+	assert_eq!(parse_key("c4"), Some(60));
+	assert_eq!(parse_key("C#4"), Some(61));
+	assert_eq!(parse_key("db4"), Some(61));
+	assert_eq!(parse_key("b3"), Some(59));
+	assert_eq!(parse_key("c-1"), Some(0));
+	assert_eq!(parse_key("g9"), Some(127));
+	assert_eq!(parse_key("128"), None);
+	assert_eq!(parse_key("h4"), None);
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use crate::{
+	backend::mock::MockBackend,
+	sound::{
+		sampler::{Sampler, SamplerLoopMode, SamplerZone},
+		static_sound::{StaticSoundData, StaticSoundSettings},
+		PlaybackState,
+	},
+	AudioManager, AudioManagerSettings, Frame, PlaybackRate, Value,
+};
+
+fn sample() -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 100,
+		frames: vec![Frame::from_mono(0.5); 100].into(),
+		settings: StaticSoundSettings::new(),
+		slice: None,
+		metadata: None,
+	}
+}
+
+/// Tests that notes are played by the first zone that matches
+/// their key and velocity.
+#[test]
+fn selects_zones() {
+	let mut sampler = Sampler::new();
+	sampler.add_zone(
+		SamplerZone::new(sample(), 48)
+			.keys(0..=59)
+			.velocities(1..=63),
+	);
+	sampler.add_zone(SamplerZone::new(sample(), 48).keys(0..=59));
+	sampler.add_zone(SamplerZone::new(sample(), 72).keys(60..=84));
+	assert_eq!(sampler.zone(50, 40), Some(&sampler.zones()[0]));
+	assert_eq!(sampler.zone(50, 100), Some(&sampler.zones()[1]));
+	assert_eq!(sampler.zone(70, 100), Some(&sampler.zones()[2]));
+	assert_eq!(sampler.zone(100, 100), None);
+	assert!(sampler.note(50, 0).is_none());
+}
+
+/// Tests that notes are repitched relative to the zone's root key.
+#[test]
+fn repitches_notes() {
+	let mut sampler = Sampler::new();
+	sampler.add_zone(SamplerZone::new(sample(), 60));
+	let playback_rate = |key| sampler.note(key, 127).unwrap().data.settings.playback_rate;
+	assert_eq!(playback_rate(60), Value::Fixed(PlaybackRate(1.0)));
+	assert_eq!(playback_rate(72), Value::Fixed(PlaybackRate(2.0)));
+	assert_eq!(playback_rate(48), Value::Fixed(PlaybackRate(0.5)));
+}
+
+/// Tests that quieter notes are played more quietly, depending on the
+/// zone's velocity tracking.
+#[test]
+fn velocity() {
+	let volume = |velocity_tracking, velocity| {
+		let mut sampler = Sampler::new();
+		sampler.add_zone(SamplerZone::new(sample(), 60).velocity_tracking(velocity_tracking));
+		match sampler.note(60, velocity).unwrap().data.settings.volume {
+			Value::Fixed(volume) => volume.as_amplitude(),
+			_ => panic!("expected a fixed volume"),
+		}
+	};
+	assert!((volume(1.0, 127) - 1.0).abs() < 0.0001);
+	assert!(volume(1.0, 64) < volume(0.5, 64));
+	assert!((volume(0.0, 64) - 1.0).abs() < 0.0001);
+}
+
+/// Tests that releasing a note fades it out over the zone's release time
+/// and stops a sustain loop.
+#[test]
+fn release() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	let mut sampler = Sampler::new();
+	sampler.add_zone(
+		SamplerZone::new(sample(), 60)
+			.loop_mode(SamplerLoopMode::Sustain, ..)
+			.release(Duration::from_secs(1)),
+	);
+	let note = sampler.note(60, 127).unwrap();
+	assert!(note.data.settings.loop_region.is_some());
+	let mut note = manager.play(note).unwrap();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(note.state(), PlaybackState::Playing);
+	note.release();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(note.state(), PlaybackState::Stopping);
+}
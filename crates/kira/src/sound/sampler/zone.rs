@@ -0,0 +1,146 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use crate::{
+	sound::{static_sound::StaticSoundData, IntoOptionalRegion, Region},
+	Decibels, Panning, Semitones,
+};
+
+/// How a [`SamplerZone`]'s sample loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SamplerLoopMode {
+	/// The sample plays once. Releasing the note fades it out.
+	#[default]
+	NoLoop,
+	/// The sample plays once, all the way through. Releasing the
+	/// note has no effect.
+	OneShot,
+	/// The sample loops for as long as it plays, even after the note
+	/// is released.
+	Continuous,
+	/// The sample loops until the note is released, and then plays
+	/// to the end while fading out.
+	Sustain,
+}
+
+/// A sample that plays for a range of keys and velocities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplerZone {
+	/// The audio to play.
+	pub data: StaticSoundData,
+	/// The keys this zone plays for (from `0` to `127`).
+	pub keys: RangeInclusive<u8>,
+	/// The velocities this zone plays for (from `1` to `127`).
+	pub velocities: RangeInclusive<u8>,
+	/// The key at which the sample plays at its original pitch.
+	pub root_key: u8,
+	/// An extra change in pitch applied to every note.
+	pub tune: Semitones,
+	/// The volume of the zone.
+	pub volume: Decibels,
+	/// The panning of the zone, where `-1.0` is hard left,
+	/// `0.0` is center, and `1.0` is hard right.
+	pub panning: Panning,
+	/// How much the velocity of a note affects its volume, from
+	/// `0.0` (not at all) to `1.0` (quiet notes are silent).
+	pub velocity_tracking: f32,
+	/// How the sample loops.
+	pub loop_mode: SamplerLoopMode,
+	/// The portion of the sample to loop. If this is `None`,
+	/// the whole sample loops.
+	///
+	/// This has no effect unless the loop mode is [`SamplerLoopMode::Continuous`]
+	/// or [`SamplerLoopMode::Sustain`].
+	pub loop_region: Option<Region>,
+	/// How long notes take to fade out after they're released.
+	pub release: Duration,
+}
+
+impl SamplerZone {
+	/// Creates a new [`SamplerZone`] that plays for every key and velocity.
+	#[must_use]
+	pub fn new(data: StaticSoundData, root_key: u8) -> Self {
+		Self {
+			data,
+			keys: 0..=127,
+			velocities: 1..=127,
+			root_key,
+			tune: Semitones(0.0),
+			volume: Decibels::IDENTITY,
+			panning: Panning::CENTER,
+			velocity_tracking: 1.0,
+			loop_mode: SamplerLoopMode::NoLoop,
+			loop_region: None,
+			release: Duration::from_millis(10),
+		}
+	}
+
+	/// Sets the keys this zone plays for.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn keys(self, keys: RangeInclusive<u8>) -> Self {
+		Self { keys, ..self }
+	}
+
+	/// Sets the velocities this zone plays for.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn velocities(self, velocities: RangeInclusive<u8>) -> Self {
+		Self { velocities, ..self }
+	}
+
+	/// Sets an extra change in pitch applied to every note.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn tune(self, tune: impl Into<Semitones>) -> Self {
+		Self {
+			tune: tune.into(),
+			..self
+		}
+	}
+
+	/// Sets the volume of the zone.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Decibels>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/// Sets the panning of the zone, where `-1.0` is hard left
+	/// and `1.0` is hard right.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Panning>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets how much the velocity of a note affects its volume, from
+	/// `0.0` (not at all) to `1.0` (quiet notes are silent).
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn velocity_tracking(self, velocity_tracking: f32) -> Self {
+		Self {
+			velocity_tracking,
+			..self
+		}
+	}
+
+	/// Sets how the sample loops and which portion of it loops.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn loop_mode(
+		self,
+		loop_mode: SamplerLoopMode,
+		loop_region: impl IntoOptionalRegion,
+	) -> Self {
+		Self {
+			loop_mode,
+			loop_region: loop_region.into_optional_region(),
+			..self
+		}
+	}
+
+	/// Sets how long notes take to fade out after they're released.
+	#[must_use = "This method consumes self and returns a modified SamplerZone, so the return value should be used"]
+	pub fn release(self, release: Duration) -> Self {
+		Self { release, ..self }
+	}
+}
@@ -31,7 +31,7 @@ pub use settings::*;
 
 use crate::{
 	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
-	PlaybackRate, StartTime,
+	PlaybackRate, StartTime, Value,
 };
 
 use super::Region;
@@ -44,6 +44,7 @@ command_writers_and_readers! {
 	pause: Tween,
 	resume: (StartTime, Tween),
 	stop: Tween,
+	crossfade_out: (Value<Decibels>, StartTime),
 	seek_by: f64,
 	seek_to: f64,
 }
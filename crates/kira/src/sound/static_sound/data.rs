@@ -12,7 +12,10 @@ use std::{
 
 use crate::{
 	frame::Frame,
-	sound::{EndPosition, IntoOptionalRegion, PlaybackPosition, Region, Sound, SoundData},
+	sound::{
+		EndPosition, FileMetadata, IntoOptionalRegion, Marker, PlaybackPosition, Region, Sound,
+		SoundData, SoundEventWriter,
+	},
 	Tween,
 	Decibels, Panning, PlaybackRate, StartTime, Value,
 };
@@ -44,6 +47,9 @@ pub struct StaticSoundData {
 	audio.
 	*/
 	pub slice: Option<(usize, usize)>,
+	/// Information about the audio file the sound was loaded from,
+	/// or `None` if it wasn't loaded from a file.
+	pub metadata: Option<Arc<FileMetadata>>,
 }
 
 impl StaticSoundData {
@@ -286,6 +292,23 @@ impl StaticSoundData {
 		new
 	}
 
+	/// Adds a named position to the sound. The sound's handle reports
+	/// when playback reaches the marker.
+	///
+	/// This returns a clone of the [`StaticSoundData`] with the added marker.
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn marker(&self, name: impl Into<Arc<str>>, position: impl Into<PlaybackPosition>) -> Self {
+		let mut new = self.clone();
+		new.settings.markers = new
+			.settings
+			.markers
+			.iter()
+			.cloned()
+			.chain([Marker::new(name, position)])
+			.collect();
+		new
+	}
+
 	/// Returns a cheap clone of the `StaticSoundData` with the specified settings.
 	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
 	pub fn with_settings(&self, settings: StaticSoundSettings) -> Self {
@@ -350,6 +373,7 @@ impl StaticSoundData {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::default(),
 		slice: None,
+		metadata: None,
 	};
 	let sliced = sound.slice(3.0..6.0);
 	assert_eq!(sliced.num_frames(), 3);
@@ -371,15 +395,18 @@ impl StaticSoundData {
 		new
 	}
 
-	pub(super) fn split(self) -> (StaticSound, StaticSoundHandle) {
+	pub(crate) fn split(self) -> (StaticSound, StaticSoundHandle) {
 		let (command_writers, command_readers) = command_writers_and_readers();
-		let sound = StaticSound::new(self, command_readers);
+		let (event_writer, event_consumer) =
+			SoundEventWriter::new(&self.settings.markers, self.sample_rate);
+		let sound = StaticSound::new(self, command_readers, event_writer);
 		let shared = sound.shared();
 		(
 			sound,
 			StaticSoundHandle {
 				command_writers,
 				shared,
+				event_consumer,
 			},
 		)
 	}
@@ -408,6 +435,8 @@ impl Debug for StaticSoundData {
 				},
 			)
 			.field("settings", &self.settings)
+			.field("slice", &self.slice)
+			.field("metadata", &self.metadata)
 			.finish()
 	}
 }
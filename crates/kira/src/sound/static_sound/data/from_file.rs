@@ -1,9 +1,11 @@
-use std::io::Cursor;
+use std::{io::Cursor, sync::Arc};
 
 use symphonia::core::io::{MediaSource, MediaSourceStream};
 
 use crate::sound::{
-	static_sound::StaticSoundSettings, symphonia::load_frames_from_buffer_ref, FromFileError,
+	static_sound::StaticSoundSettings,
+	symphonia::{load_frames_from_buffer_ref, read_file_metadata},
+	EmbeddedCues, FromFileError,
 };
 
 use super::StaticSoundData;
@@ -16,6 +18,18 @@ impl StaticSoundData {
 		Self::from_media_source(std::fs::File::open(path)?)
 	}
 
+	/// Loads an audio file into a [`StaticSoundData`] that loops and reports
+	/// markers using the loop points and cue markers embedded in the file.
+	///
+	/// See [`EmbeddedCues`] for the kinds of cues that are supported.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "symphonia", not(wasm32)))))]
+	pub fn from_file_with_cues(path: impl AsRef<std::path::Path>) -> Result<Self, FromFileError> {
+		let path = path.as_ref();
+		let cues = EmbeddedCues::from_file(path)?;
+		Ok(Self::from_file(path)?.with_cues(cues))
+	}
+
 	/// Loads a cursor wrapping audio file data into a [`StaticSoundData`].
 	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
 	pub fn from_cursor<T: AsRef<[u8]> + Send + Sync + 'static>(
@@ -33,18 +47,39 @@ impl StaticSoundData {
 		Self::from_boxed_media_source(Box::new(media_source))
 	}
 
+	/// Sets the loop region of the sound to the loop region in the given cues
+	/// (if there is one) and adds the cues' markers to the sound.
+	///
+	/// This returns a clone of the [`StaticSoundData`] with the modified settings.
+	#[cfg_attr(docsrs, doc(cfg(feature = "symphonia")))]
+	#[must_use = "This method returns a modified StaticSoundData and does not mutate the original value"]
+	pub fn with_cues(&self, cues: EmbeddedCues) -> Self {
+		let mut new = self.clone();
+		if let Some(loop_region) = cues.loop_region {
+			new.settings.loop_region = Some(loop_region);
+		}
+		new.settings.markers = new
+			.settings
+			.markers
+			.iter()
+			.cloned()
+			.chain(cues.markers)
+			.collect();
+		new
+	}
+
 	fn from_boxed_media_source(media_source: Box<dyn MediaSource>) -> Result<Self, FromFileError> {
 		let codecs = symphonia::default::get_codecs();
 		let probe = symphonia::default::get_probe();
 		let mss = MediaSourceStream::new(media_source, Default::default());
-		let mut format_reader = probe
-			.format(
-				&Default::default(),
-				mss,
-				&Default::default(),
-				&Default::default(),
-			)?
-			.format;
+		let mut probe_result = probe.format(
+			&Default::default(),
+			mss,
+			&Default::default(),
+			&Default::default(),
+		)?;
+		let metadata = read_file_metadata(&mut probe_result);
+		let mut format_reader = probe_result.format;
 		let codec_params = &format_reader
 			.default_track()
 			.ok_or(FromFileError::NoDefaultTrack)?
@@ -76,6 +111,7 @@ impl StaticSoundData {
 			frames: frames.into(),
 			settings: StaticSoundSettings::default(),
 			slice: None,
+			metadata: Some(Arc::new(metadata)),
 		})
 	}
 }
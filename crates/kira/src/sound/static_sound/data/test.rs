@@ -11,6 +11,7 @@ fn duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(4));
 }
@@ -22,6 +23,7 @@ fn unsliced_duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: Some((2, 3)),
+		metadata: None,
 	};
 	assert_eq!(static_sound.unsliced_duration(), Duration::from_secs(4));
 }
@@ -33,6 +35,7 @@ fn sliced_duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(4));
 
@@ -41,6 +44,7 @@ fn sliced_duration() {
 		frames: Arc::new([Frame::from_mono(0.0); 4]),
 		settings: Default::default(),
 		slice: Some((2, 3)),
+		metadata: None,
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(1));
 }
@@ -52,6 +56,7 @@ fn slice() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	}
 	.slice(3.0..6.0);
 	for i in 0..3 {
@@ -1,18 +1,25 @@
-use std::sync::Arc;
+use std::{
+	fmt::{Debug, Formatter},
+	sync::Arc,
+	time::Instant,
+};
 
 use crate::{
 	command::handle_param_setters,
-	sound::{IntoOptionalRegion, PlaybackState},
-	Decibels, Panning, PlaybackRate, StartTime, Tween,
+	sound::{IntoOptionalRegion, PlaybackState, SoundEvent},
+	Decibels, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 
+use ringbuf::{consumer::Consumer as _, Cons, HeapRb as RingBuffer};
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
 use super::{sound::Shared, CommandWriters};
 
 /// Controls a static sound.
-#[derive(Debug)]
 pub struct StaticSoundHandle {
 	pub(super) command_writers: CommandWriters,
 	pub(super) shared: Arc<Shared>,
+	pub(super) event_consumer: Consumer<SoundEvent>,
 }
 
 impl StaticSoundHandle {
@@ -28,6 +35,23 @@ impl StaticSoundHandle {
 		self.shared.position()
 	}
 
+	/**
+	Returns when the audio at the position returned by
+	[`position`](Self::position) will be heard, or when it was heard
+	if that has already happened.
+
+	This accounts for the output latency. To find when another
+	position will be heard, divide the distance to that position by
+	the playback rate and add it to this instant.
+
+	Returns `None` if the backend doesn't report its output latency
+	or the sound hasn't been processed yet.
+	*/
+	#[must_use]
+	pub fn position_playback_instant(&self) -> Option<Instant> {
+		self.shared.position_playback_instant()
+	}
+
 	handle_param_setters! {
 		/**
 		Sets the volume of the sound.
@@ -311,6 +335,15 @@ impl StaticSoundHandle {
 		self.command_writers.stop.write(tween)
 	}
 
+	/// Fades out the sound along the outgoing side of a crossfade
+	/// that starts at `start_time`, and stops playback once the sound
+	/// has faded to silence.
+	pub(crate) fn fade_out_and_stop(&mut self, volume: Value<Decibels>, start_time: StartTime) {
+		self.command_writers
+			.crossfade_out
+			.write((volume, start_time))
+	}
+
 	/// Sets the playback position to the specified time in seconds.
 	pub fn seek_to(&mut self, position: f64) {
 		self.command_writers.seek_to.write(position)
@@ -320,4 +353,59 @@ impl StaticSoundHandle {
 	pub fn seek_by(&mut self, amount: f64) {
 		self.command_writers.seek_by.write(amount)
 	}
+
+	/**
+	Returns the oldest event reported by the sound that hasn't
+	been read yet, if any.
+
+	The sound reports when playback reaches one of its
+	[`Marker`](crate::sound::Marker)s, when it loops, and when it
+	finishes. Up to 64 events can be waiting to be read. Events that
+	happen while the queue is full are discarded.
+
+	# Examples
+
+	```no_run
+	# use kira::{
+	# 	AudioManager, AudioManagerSettings, DefaultBackend,
+	# 	sound::static_sound::StaticSoundData,
+	# };
+	# let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+	# fn shake_camera() {}
+	use kira::sound::SoundEvent;
+
+	let mut sound = manager.play(StaticSoundData::from_file("door.ogg")?.marker("slam", 0.8))?;
+	// later, once per game frame:
+	while let Some(event) = sound.pop_event() {
+		if let SoundEvent::MarkerReached(marker) = event {
+			if &*marker.name == "slam" {
+				shake_camera();
+			}
+		}
+	}
+	# Result::<(), Box<dyn std::error::Error>>::Ok(())
+	```
+	*/
+	#[must_use]
+	pub fn pop_event(&mut self) -> Option<SoundEvent> {
+		self.event_consumer.try_pop()
+	}
+}
+
+impl Debug for StaticSoundHandle {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("StaticSoundHandle")
+			.field("command_writers", &self.command_writers)
+			.field("shared", &self.shared)
+			.field("event_consumer", &ConsumerDebug)
+			.finish()
+	}
+}
+
+struct ConsumerDebug;
+
+impl Debug for ConsumerDebug {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Consumer").finish()
+	}
 }
@@ -1,11 +1,13 @@
+use std::sync::Arc;
+
 use crate::{
-	sound::{IntoOptionalRegion, PlaybackPosition, Region},
+	sound::{IntoOptionalRegion, Marker, PlaybackPosition, Region},
 	Tween,
 	Decibels, Panning, PlaybackRate, StartTime, Value,
 };
 
 /// Settings for a static sound.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StaticSoundSettings {
 	/// When the sound should start playing.
 	pub start_time: StartTime,
@@ -27,6 +29,12 @@ pub struct StaticSoundSettings {
 	pub panning: Value<Panning>,
 	/// An optional fade-in from silence.
 	pub fade_in_tween: Option<Tween>,
+	/// Named positions in the sound. The sound's handle reports
+	/// when playback reaches each marker.
+	///
+	/// The markers are shared between clones of the settings, so
+	/// cloning the settings doesn't copy them.
+	pub markers: Arc<[Marker]>,
 }
 
 impl StaticSoundSettings {
@@ -42,6 +50,7 @@ impl StaticSoundSettings {
 			playback_rate: Value::Fixed(PlaybackRate(1.0)),
 			panning: Value::Fixed(Panning::CENTER),
 			fade_in_tween: None,
+			markers: Arc::new([]),
 		}
 	}
 
@@ -121,6 +130,23 @@ impl StaticSoundSettings {
 			..self
 		}
 	}
+
+	/// Adds a named position to the sound. The sound's handle reports
+	/// when playback reaches the marker.
+	#[must_use = "This method consumes self and returns a modified StaticSoundSettings, so the return value should be used"]
+	pub fn marker(
+		mut self,
+		name: impl Into<Arc<str>>,
+		position: impl Into<PlaybackPosition>,
+	) -> Self {
+		self.markers = self
+			.markers
+			.iter()
+			.cloned()
+			.chain([Marker::new(name, position)])
+			.collect();
+		self
+	}
 }
 
 impl Default for StaticSoundSettings {
@@ -3,9 +3,12 @@ mod resampler;
 #[cfg(test)]
 mod test;
 
-use std::sync::{
-	atomic::{AtomicU64, AtomicU8, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicU64, AtomicU8, Ordering},
+		Arc,
+	},
+	time::Instant,
 };
 
 use crate::{
@@ -13,16 +16,16 @@ use crate::{
 	frame::Frame,
 	info::Info,
 	playback_state_manager::PlaybackStateManager,
-	sound::{transport::Transport, PlaybackState, Sound},
-	Tween,
-	Decibels, Panning, Parameter, PlaybackRate, StartTime,
+	playback_timing::AtomicInstant,
+	sound::{transport::Transport, PlaybackState, Sound, SoundEventWriter},
+	Decibels, Panning, Parameter, PlaybackRate, StartTime, Tween, Value,
 };
 
 use self::resampler::Resampler;
 
 use super::{data::StaticSoundData, frame_at_index, num_frames, CommandReaders};
 
-pub(super) struct StaticSound {
+pub(crate) struct StaticSound {
 	command_readers: CommandReaders,
 	sample_rate: u32,
 	frames: Arc<[Frame]>,
@@ -36,13 +39,23 @@ pub(super) struct StaticSound {
 	volume: Parameter<Decibels>,
 	playback_rate: Parameter<PlaybackRate>,
 	panning: Parameter<Panning>,
+	/// When the audio at the end of the last processed chunk will be heard.
+	playback_nanos: Option<i64>,
 	shared: Arc<Shared>,
+	event_writer: SoundEventWriter,
+	/// Whether the transport looped after the most recent frame
+	/// was pushed to the resampler.
+	looped: bool,
 }
 
 impl StaticSound {
 	#[must_use]
-	pub fn new(data: StaticSoundData, command_readers: CommandReaders) -> Self {
-		let settings = data.settings;
+	pub fn new(
+		data: StaticSoundData,
+		command_readers: CommandReaders,
+		event_writer: SoundEventWriter,
+	) -> Self {
+		let settings = &data.settings;
 		let transport = Transport::new(
 			data.settings.start_position.into_samples(data.sample_rate),
 			data.settings.loop_region,
@@ -66,10 +79,14 @@ impl StaticSound {
 			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
 			playback_rate: Parameter::new(settings.playback_rate, PlaybackRate(1.0)),
 			panning: Parameter::new(settings.panning, Panning::CENTER),
+			playback_nanos: None,
 			shared: Arc::new(Shared {
 				state: AtomicU8::new(PlaybackState::Playing as u8),
 				position: AtomicU64::new(position.to_bits()),
+				position_playback_instant: AtomicInstant::unknown(),
 			}),
+			event_writer,
+			looped: false,
 		};
 		// fill the resample buffer with 3 samples so playback can
 		// start immediately
@@ -79,13 +96,21 @@ impl StaticSound {
 		sound
 	}
 
+	/// Makes the sound fade in along the incoming side of a crossfade.
+	pub(crate) fn crossfade_in(&mut self, volume: Value<Decibels>) {
+		self.playback_state_manager.crossfade_in(volume);
+	}
+
 	pub(super) fn shared(&self) -> Arc<Shared> {
 		self.shared.clone()
 	}
 
 	fn update_shared_playback_state(&mut self) {
-		self.shared
-			.set_state(self.playback_state_manager.playback_state());
+		let playback_state = self.playback_state_manager.playback_state();
+		self.shared.set_state(playback_state);
+		if playback_state == PlaybackState::Stopped {
+			self.event_writer.finish();
+		}
 	}
 
 	fn pause(&mut self, fade_out_tween: Tween) {
@@ -116,12 +141,12 @@ impl StaticSound {
 	/// Updates the current frame index by 1 and pushes a new sample to the resampler.
 	fn update_position(&mut self) {
 		self.push_frame_to_resampler();
-		if self.is_playing_backwards() {
-			self.transport.decrement_position();
+		self.looped = if self.is_playing_backwards() {
+			self.transport.decrement_position()
 		} else {
 			self.transport
-				.increment_position(num_frames(&self.frames, self.slice));
-		}
+				.increment_position(num_frames(&self.frames, self.slice))
+		};
 		if !self.transport.playing && self.resampler.empty() {
 			self.playback_state_manager.mark_as_stopped();
 			self.update_shared_playback_state();
@@ -131,6 +156,7 @@ impl StaticSound {
 	fn seek_to_index(&mut self, index: usize) {
 		self.transport
 			.seek_to(index, num_frames(&self.frames, self.slice));
+		self.looped = false;
 		// if the sound is playing, push a frame to the resample buffer
 		// to make sure it doesn't get skipped
 		if self.playback_state_manager.playback_state().is_advancing() {
@@ -142,7 +168,9 @@ impl StaticSound {
 		let frame = self.transport.playing.then(|| {
 			frame_at_index(self.transport.position, &self.frames, self.slice).unwrap_or_default()
 		});
-		self.resampler.push_frame(frame, self.transport.position);
+		self.resampler
+			.push_frame(frame, self.transport.position, self.looped);
+		self.looped = false;
 	}
 
 	fn seek_by(&mut self, amount: f64) {
@@ -157,6 +185,54 @@ impl StaticSound {
 		self.seek_to_index(index);
 	}
 
+	/// Updates the parameters and playback state for the next
+	/// `num_frames` frames. Returns `true` if playback should
+	/// advance during those frames.
+	#[must_use]
+	fn update(&mut self, num_frames: usize, dt: f64, info: &Info) -> bool {
+		self.playback_nanos = info.playback_nanos();
+
+		// update parameters
+		self.volume.update(dt * num_frames as f64, info);
+		self.playback_rate.update(dt * num_frames as f64, info);
+		self.panning.update(dt * num_frames as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * num_frames as f64, info);
+		if changed_playback_state {
+			self.update_shared_playback_state();
+		}
+
+		let will_never_start = self.start_time.update(dt * num_frames as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.update_shared_playback_state();
+		}
+		if self.start_time != StartTime::Immediate {
+			return false;
+		}
+
+		self.playback_state_manager.playback_state().is_advancing()
+	}
+
+	/// Moves the playback position forward by one output frame.
+	fn advance(&mut self, playback_rate: PlaybackRate, dt: f64) {
+		self.fractional_position += self.sample_rate as f64 * playback_rate.0.abs() * dt;
+		while self.fractional_position >= 1.0 {
+			self.fractional_position -= 1.0;
+			self.update_position();
+			self.report_position();
+		}
+	}
+
+	/// Reports events for the frame that's currently being heard.
+	fn report_position(&mut self) {
+		self.event_writer.update_position(
+			self.resampler.current_frame_index(),
+			self.resampler.current_frame_looped(),
+		);
+	}
+
 	fn read_commands(&mut self) {
 		read_commands_into_parameters!(self, volume, playback_rate, panning);
 		if let Some(loop_region) = self.command_readers.set_loop_region.read() {
@@ -175,6 +251,11 @@ impl StaticSound {
 		if let Some(tween) = self.command_readers.stop.read() {
 			self.stop(tween);
 		}
+		if let Some((volume, start_time)) = self.command_readers.crossfade_out.read() {
+			self.playback_state_manager
+				.crossfade_out(volume, start_time);
+			self.update_shared_playback_state();
+		}
 		if let Some(amount) = self.command_readers.seek_by.read() {
 			self.seek_by(amount);
 		}
@@ -191,35 +272,18 @@ impl Sound for StaticSound {
 			(last_played_frame_position as f64 / self.sample_rate as f64).to_bits(),
 			Ordering::SeqCst,
 		);
+		self.shared
+			.position_playback_instant
+			.store_nanos(self.playback_nanos);
 		self.read_commands();
 	}
 
 	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
-		// update parameters
-		self.volume.update(dt * out.len() as f64, info);
-		self.playback_rate.update(dt * out.len() as f64, info);
-		self.panning.update(dt * out.len() as f64, info);
-		let changed_playback_state = self
-			.playback_state_manager
-			.update(dt * out.len() as f64, info);
-		if changed_playback_state {
-			self.update_shared_playback_state();
-		}
-
-		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
-		if will_never_start {
-			self.playback_state_manager.mark_as_stopped();
-			self.update_shared_playback_state();
-		}
-		if self.start_time != StartTime::Immediate {
-			out.fill(Frame::ZERO);
-			return;
-		}
-
-		if !self.playback_state_manager.playback_state().is_advancing() {
+		if !self.update(out.len(), dt, info) {
 			out.fill(Frame::ZERO);
 			return;
 		}
+		self.report_position();
 
 		// play back audio
 		let num_frames = out.len();
@@ -233,24 +297,38 @@ impl Sound for StaticSound {
 			let panning = self.panning.interpolated_value(time_in_chunk);
 			let playback_rate = self.playback_rate.interpolated_value(time_in_chunk);
 			let resampler_out = self.resampler.get(self.fractional_position as f32);
-			self.fractional_position += self.sample_rate as f64 * playback_rate.0.abs() * dt;
-			while self.fractional_position >= 1.0 {
-				self.fractional_position -= 1.0;
-				self.update_position();
-			}
+			self.advance(playback_rate, dt);
 			*frame = (resampler_out * fade_volume * volume).panned(panning);
 		}
 	}
 
+	fn process_virtual(&mut self, num_frames: usize, dt: f64, info: &Info) {
+		if !self.update(num_frames, dt, info) {
+			return;
+		}
+		self.report_position();
+		for i in 0..num_frames {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let playback_rate = self.playback_rate.interpolated_value(time_in_chunk);
+			self.advance(playback_rate, dt);
+		}
+	}
+
 	fn finished(&self) -> bool {
 		self.playback_state_manager.playback_state() == PlaybackState::Stopped
 	}
+
+	fn on_stolen(&mut self) {
+		self.shared.set_state(PlaybackState::Stopped);
+		self.event_writer.finish();
+	}
 }
 
 #[derive(Debug)]
 pub(super) struct Shared {
 	state: AtomicU8,
 	position: AtomicU64,
+	position_playback_instant: AtomicInstant,
 }
 
 impl Shared {
@@ -274,4 +352,8 @@ impl Shared {
 	pub fn position(&self) -> f64 {
 		f64::from_bits(self.position.load(Ordering::SeqCst))
 	}
+
+	pub fn position_playback_instant(&self) -> Option<Instant> {
+		self.position_playback_instant.load()
+	}
 }
@@ -7,6 +7,9 @@ struct RecentFrame {
 	/// The current frame index of the source sound at the
 	/// time this frame was pushed to the resampler.
 	frame_index: usize,
+	/// Whether playback got to this frame by jumping to the
+	/// other end of the loop region.
+	looped: bool,
 }
 
 pub(super) struct Resampler {
@@ -21,12 +24,13 @@ impl Resampler {
 			frames: [RecentFrame {
 				frame: Frame::ZERO,
 				frame_index: starting_frame_index,
+				looped: false,
 			}; 4],
 			time_until_empty: 0,
 		}
 	}
 
-	pub fn push_frame(&mut self, frame: Option<Frame>, sample_index: usize) {
+	pub fn push_frame(&mut self, frame: Option<Frame>, sample_index: usize, looped: bool) {
 		if frame.is_some() {
 			self.time_until_empty = 4;
 		} else {
@@ -37,6 +41,7 @@ impl Resampler {
 		self.frames[self.frames.len() - 1] = RecentFrame {
 			frame,
 			frame_index: sample_index,
+			looped,
 		};
 	}
 
@@ -64,6 +69,13 @@ impl Resampler {
 		self.frames[1].frame_index
 	}
 
+	/// Returns whether playback got to the frame returned by
+	/// [`current_frame_index`](Self::current_frame_index) by looping.
+	#[must_use]
+	pub fn current_frame_looped(&self) -> bool {
+		self.frames[1].looped
+	}
+
 	#[must_use]
 	pub fn empty(&self) -> bool {
 		self.time_until_empty == 0
@@ -6,7 +6,7 @@ use crate::{
 	info::MockInfoBuilder,
 	sound::{
 		static_sound::{StaticSoundData, StaticSoundSettings},
-		PlaybackState, Sound,
+		Marker, PlaybackState, Sound, SoundEvent,
 	},
 	test_helpers::expect_frame_soon,
 	Decibels, Panning, StartTime, Tween,
@@ -24,6 +24,7 @@ fn plays_all_samples() {
 		]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -63,6 +64,7 @@ fn reports_playback_state() {
 		frames: Arc::new([Frame::from_mono(0.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -85,6 +87,7 @@ fn reports_playback_position() {
 		frames: Arc::new([Frame::from_mono(0.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -108,6 +111,7 @@ fn pauses_and_resumes_with_fades() {
 		frames: Arc::new([Frame::from_mono(1.0); 100]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -218,6 +222,7 @@ fn stops_with_fade_out() {
 		frames: Arc::new([Frame::from_mono(1.0); 100]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -287,6 +292,7 @@ fn waits_for_start_time() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -358,6 +364,7 @@ fn stops_if_waiting_on_missing_clock() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -391,6 +398,7 @@ fn continues_when_clock_stops() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -422,6 +430,7 @@ fn immediate_playback_state_change_with_start_time() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -465,6 +474,7 @@ fn resume_at() {
 		frames: (1..100).map(|_| Frame::from_mono(1.0)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -515,6 +525,7 @@ fn start_position() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().start_position(3.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle) = data.split();
 
@@ -538,6 +549,7 @@ fn out_of_bounds_start_position() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().start_position(15.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 	sound.process_one(1.0, &MockInfoBuilder::new().build());
@@ -553,6 +565,7 @@ fn loops_forward() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().loop_region(Some((3.0..6.0).into())),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -590,6 +603,7 @@ fn volume() {
 		frames: Arc::new([Frame::from_mono(1.0); 10]),
 		settings: StaticSoundSettings::new().volume(-6.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -608,6 +622,7 @@ fn set_volume() {
 		frames: Arc::new([Frame::from_mono(1.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -638,6 +653,7 @@ fn panning() {
 		frames: Arc::new([Frame::from_mono(1.0); 10]),
 		settings: StaticSoundSettings::new().panning(Panning::LEFT),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -656,6 +672,7 @@ fn set_panning() {
 		frames: Arc::new([Frame::from_mono(1.0); 10]),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -683,6 +700,7 @@ fn playback_rate() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().playback_rate(2.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -706,6 +724,7 @@ fn set_playback_rate() {
 		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 
@@ -754,6 +773,7 @@ fn interpolates_samples() {
 		]),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -779,6 +799,7 @@ fn interpolates_samples_when_looping() {
 		frames: Arc::new([Frame::from_mono(10.0), Frame::from_mono(9.0)]),
 		settings: StaticSoundSettings::new().loop_region(Some((..).into())),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 	sound.process_one(1.5, &MockInfoBuilder::new().build());
@@ -796,6 +817,7 @@ fn seek_to() {
 		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 	handle.seek_to(15.0);
@@ -811,6 +833,7 @@ fn seek_by() {
 		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().start_position(10.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle) = data.split();
 	handle.seek_by(5.0);
@@ -829,6 +852,7 @@ fn reverse() {
 		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
 		settings: StaticSoundSettings::new().reverse(true),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _) = data.split();
 
@@ -839,3 +863,77 @@ fn reverse() {
 		);
 	}
 }
+
+/// Tests that processing a sound virtually advances its playback
+/// position as if it was processed normally.
+#[test]
+fn process_virtual_advances_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new().playback_rate(2.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut real, real_handle) = data.clone().split();
+	let (mut virtual_sound, virtual_handle) = data.split();
+	let info = MockInfoBuilder::new().build();
+	for _ in 0..10 {
+		real.process_one(1.0, &info);
+	}
+	virtual_sound.process_virtual(10, 1.0, &info);
+	assert_eq!(virtual_handle.position(), real_handle.position());
+	for _ in 0..10 {
+		assert_eq!(
+			virtual_sound.process_one(1.0, &info),
+			real.process_one(1.0, &info)
+		);
+	}
+}
+
+/// Tests that a `StaticSound` reports when playback reaches its markers,
+/// when it loops, and when it finishes.
+#[test]
+fn reports_events() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: (0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		settings: StaticSoundSettings::new()
+			.loop_region(Some((4.0..6.0).into()))
+			.marker("start", 0.0)
+			.marker("two", 2.0)
+			.marker("five", 5.0)
+			.marker("seven", 7.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle) = data.split();
+	let info = MockInfoBuilder::new().build();
+	assert_eq!(handle.pop_event(), None);
+
+	let mut events = vec![];
+	for _ in 0..8 {
+		sound.process_one(1.0, &info);
+		events.extend(std::iter::from_fn(|| handle.pop_event()));
+	}
+	assert_eq!(
+		events,
+		[
+			SoundEvent::MarkerReached(Marker::new("start", 0.0)),
+			SoundEvent::MarkerReached(Marker::new("two", 2.0)),
+			SoundEvent::MarkerReached(Marker::new("five", 5.0)),
+			SoundEvent::Looped,
+			SoundEvent::MarkerReached(Marker::new("five", 5.0)),
+			SoundEvent::Looped,
+		]
+	);
+
+	handle.stop(Tween::default());
+	for _ in 0..20 {
+		sound.on_start_processing();
+		sound.process_one(0.001, &info);
+	}
+	assert!(sound.finished());
+	assert_eq!(handle.pop_event(), Some(SoundEvent::Finished));
+	assert_eq!(handle.pop_event(), None);
+}
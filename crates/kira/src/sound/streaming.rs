@@ -33,9 +33,11 @@ pub use decoder::*;
 pub use handle::*;
 pub use settings::*;
 
+pub(crate) use sound::frame_stream::FrameStream;
+
 use crate::{
 	command::{command_writer_and_reader, CommandReader, CommandWriter, ValueChangeCommand},
-	Decibels, Panning, PlaybackRate, StartTime, Tween,
+	Decibels, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 
 use super::Region;
@@ -49,6 +51,7 @@ pub(crate) struct CommandWriters {
 	pause: CommandWriter<Tween>,
 	resume: CommandWriter<(StartTime, Tween)>,
 	stop: CommandWriter<Tween>,
+	crossfade_out: CommandWriter<(Value<Decibels>, StartTime)>,
 	seek_by: CommandWriter<f64>,
 	seek_to: CommandWriter<f64>,
 }
@@ -60,6 +63,7 @@ pub(crate) struct CommandReaders {
 	pause: CommandReader<Tween>,
 	resume: CommandReader<(StartTime, Tween)>,
 	stop: CommandReader<Tween>,
+	crossfade_out: CommandReader<(Value<Decibels>, StartTime)>,
 }
 
 #[derive(Debug)]
@@ -82,6 +86,7 @@ fn command_writers_and_readers() -> (
 	let (pause_writer, pause_reader) = command_writer_and_reader();
 	let (resume_writer, resume_reader) = command_writer_and_reader();
 	let (stop_writer, stop_reader) = command_writer_and_reader();
+	let (crossfade_out_writer, crossfade_out_reader) = command_writer_and_reader();
 	let (seek_by_writer, seek_by_reader) = command_writer_and_reader();
 	let (seek_to_writer, seek_to_reader) = command_writer_and_reader();
 	(
@@ -93,6 +98,7 @@ fn command_writers_and_readers() -> (
 			pause: pause_writer,
 			resume: resume_writer,
 			stop: stop_writer,
+			crossfade_out: crossfade_out_writer,
 			seek_by: seek_by_writer,
 			seek_to: seek_to_writer,
 		},
@@ -103,6 +109,7 @@ fn command_writers_and_readers() -> (
 			pause: pause_reader,
 			resume: resume_reader,
 			stop: stop_reader,
+			crossfade_out: crossfade_out_reader,
 		},
 		DecodeSchedulerCommandReaders {
 			set_loop_region: set_loop_region_reader,
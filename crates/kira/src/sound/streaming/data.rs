@@ -3,7 +3,10 @@ mod test;
 
 use std::{sync::Arc, time::Duration};
 
-use crate::sound::{EndPosition, IntoOptionalRegion, PlaybackPosition, Region, SoundData};
+use crate::sound::{
+	EndPosition, FileMetadata, IntoOptionalRegion, Marker, PlaybackPosition, Region, SoundData,
+	SoundEventWriter,
+};
 use crate::{Tween, Value};
 use crate::{Decibels, Panning, PlaybackRate, StartTime};
 
@@ -12,7 +15,9 @@ type Producer<T> = Prod<Arc<RingBuffer<T>>>;
 type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
 
 use super::sound::Shared;
-use super::{command_writers_and_readers, StreamingSoundHandle, StreamingSoundSettings};
+use super::{
+	command_writers_and_readers, FrameStream, StreamingSoundHandle, StreamingSoundSettings,
+};
 
 use super::{
 	decoder::Decoder,
@@ -36,6 +41,9 @@ pub struct StreamingSoundData<Error: Send + 'static> {
 	portion of audio.
 	*/
 	pub slice: Option<(usize, usize)>,
+	/// Information about the audio file the sound is streamed from,
+	/// or `None` if it isn't streamed from a file.
+	pub metadata: Option<Arc<FileMetadata>>,
 }
 
 impl<Error: Send> StreamingSoundData<Error> {
@@ -46,6 +54,7 @@ impl<Error: Send> StreamingSoundData<Error> {
 			decoder: Box::new(decoder),
 			settings: StreamingSoundSettings::default(),
 			slice: None,
+			metadata: None,
 		}
 	}
 
@@ -265,6 +274,24 @@ impl<Error: Send> StreamingSoundData<Error> {
 		self
 	}
 
+	/// Adds a named position to the sound. The sound's handle reports
+	/// when playback reaches the marker.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn marker(
+		mut self,
+		name: impl Into<Arc<str>>,
+		position: impl Into<PlaybackPosition>,
+	) -> Self {
+		self.settings.markers = self
+			.settings
+			.markers
+			.iter()
+			.cloned()
+			.chain([Marker::new(name, position)])
+			.collect();
+		self
+	}
+
 	/// Returns the `StreamingSoundData` with the specified settings.
 	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
 	pub fn with_settings(mut self, settings: StreamingSoundSettings) -> Self {
@@ -331,9 +358,9 @@ impl StreamingSoundData<crate::sound::FromFileError> {
 
 		use super::symphonia::SymphoniaDecoder;
 
-		Ok(Self::from_decoder(SymphoniaDecoder::new(Box::new(
-			File::open(path)?,
-		))?))
+		Ok(Self::from_symphonia_decoder(SymphoniaDecoder::new(
+			Box::new(File::open(path)?),
+		)?))
 	}
 
 	/// Creates a [`StreamingSoundData`] for a cursor wrapping audio file data.
@@ -342,7 +369,9 @@ impl StreamingSoundData<crate::sound::FromFileError> {
 	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
 		use super::symphonia::SymphoniaDecoder;
 
-		Ok(Self::from_decoder(SymphoniaDecoder::new(Box::new(cursor))?))
+		Ok(Self::from_symphonia_decoder(SymphoniaDecoder::new(
+			Box::new(cursor),
+		)?))
 	}
 
 	/// Creates a [`StreamingSoundData`] for a type that implements Symphonia's
@@ -352,9 +381,47 @@ impl StreamingSoundData<crate::sound::FromFileError> {
 	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
 		use super::symphonia::SymphoniaDecoder;
 
-		Ok(Self::from_decoder(SymphoniaDecoder::new(Box::new(
-			media_source,
-		))?))
+		Ok(Self::from_symphonia_decoder(SymphoniaDecoder::new(
+			Box::new(media_source),
+		)?))
+	}
+
+	fn from_symphonia_decoder(decoder: super::symphonia::SymphoniaDecoder) -> Self {
+		let metadata = decoder.metadata();
+		Self {
+			metadata: Some(metadata),
+			..Self::from_decoder(decoder)
+		}
+	}
+
+	/// Creates a [`StreamingSoundData`] for an audio file that loops and
+	/// reports markers using the loop points and cue markers embedded in the file.
+	///
+	/// See [`EmbeddedCues`](crate::sound::EmbeddedCues) for the kinds of
+	/// cues that are supported.
+	pub fn from_file_with_cues(
+		path: impl AsRef<std::path::Path>,
+	) -> Result<StreamingSoundData<crate::sound::FromFileError>, crate::sound::FromFileError> {
+		let path = path.as_ref();
+		let cues = crate::sound::EmbeddedCues::from_file(path)?;
+		Ok(Self::from_file(path)?.with_cues(cues))
+	}
+
+	/// Sets the loop region of the sound to the loop region in the given cues
+	/// (if there is one) and adds the cues' markers to the sound.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundData, so the return value should be used"]
+	pub fn with_cues(mut self, cues: crate::sound::EmbeddedCues) -> Self {
+		if let Some(loop_region) = cues.loop_region {
+			self.settings.loop_region = Some(loop_region);
+		}
+		self.settings.markers = self
+			.settings
+			.markers
+			.iter()
+			.cloned()
+			.chain(cues.markers)
+			.collect();
+		self
 	}
 }
 
@@ -377,11 +444,13 @@ impl<Error: Send + 'static> StreamingSoundData<Error> {
 		let error_consumer = Consumer::new(rb);
 
 		let sample_rate = self.decoder.sample_rate();
+		let (event_writer, event_consumer) =
+			SoundEventWriter::new(&self.settings.markers, sample_rate);
 		let shared = Arc::new(Shared::new());
 		let (scheduler, frame_consumer) = DecodeScheduler::new(
 			self.decoder,
 			self.slice,
-			self.settings,
+			&self.settings,
 			shared.clone(),
 			decode_scheduler_command_readers,
 			error_producer,
@@ -392,15 +461,42 @@ impl<Error: Send + 'static> StreamingSoundData<Error> {
 			shared.clone(),
 			frame_consumer,
 			command_readers,
+			event_writer,
 			&scheduler,
 		);
 		let handle = StreamingSoundHandle {
 			shared,
 			command_writers,
 			error_consumer,
+			event_consumer,
 		};
 		Ok((sound, handle, scheduler))
 	}
+
+	/// Starts decoding the sound on a background thread without
+	/// creating a [`StreamingSound`] to play it.
+	///
+	/// The slice, start position, and loop region are respected, but the
+	/// other settings are not. Decoding errors end the stream early.
+	pub(crate) fn into_frame_stream(self) -> Result<FrameStream, Error> {
+		let (_, _, decode_scheduler_command_readers) = command_writers_and_readers();
+
+		let rb = Arc::new(RingBuffer::new(ERROR_BUFFER_CAPACITY));
+		let error_producer = Producer::new(rb);
+
+		let sample_rate = self.decoder.sample_rate();
+		let shared = Arc::new(Shared::new());
+		let (scheduler, frame_consumer) = DecodeScheduler::new(
+			self.decoder,
+			self.slice,
+			&self.settings,
+			shared.clone(),
+			decode_scheduler_command_readers,
+			error_producer,
+		)?;
+		scheduler.start();
+		Ok(FrameStream::new(sample_rate, frame_consumer, shared))
+	}
 }
 
 impl<Error: Send + 'static> SoundData for StreamingSoundData<Error> {
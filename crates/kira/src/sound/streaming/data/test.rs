@@ -11,6 +11,7 @@ fn duration() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.5); 4])),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	assert_eq!(sound.duration(), Duration::from_secs(4));
 }
@@ -21,6 +22,7 @@ fn unsliced_duration() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.5); 4])),
 		settings: Default::default(),
 		slice: Some((2, 3)),
+		metadata: None,
 	};
 	assert_eq!(sound.unsliced_duration(), Duration::from_secs(4));
 }
@@ -31,6 +33,7 @@ fn sliced_duration() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.5); 4])),
 		settings: Default::default(),
 		slice: Some((2, 3)),
+		metadata: None,
 	};
 	assert_eq!(sound.duration(), Duration::from_secs(1));
 }
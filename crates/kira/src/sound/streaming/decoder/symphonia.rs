@@ -1,8 +1,11 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, sync::Arc};
 
 use crate::{
 	frame::Frame,
-	sound::{symphonia::load_frames_from_buffer_ref, FromFileError},
+	sound::{
+		symphonia::{load_frames_from_buffer_ref, read_file_metadata},
+		FileMetadata, FromFileError,
+	},
 };
 use symphonia::core::{
 	codecs::Decoder,
@@ -17,6 +20,7 @@ pub(crate) struct SymphoniaDecoder {
 	sample_rate: u32,
 	num_frames: usize,
 	track_id: u32,
+	metadata: Arc<FileMetadata>,
 }
 
 impl SymphoniaDecoder {
@@ -24,14 +28,14 @@ impl SymphoniaDecoder {
 		let codecs = symphonia::default::get_codecs();
 		let probe = symphonia::default::get_probe();
 		let mss = MediaSourceStream::new(media_source, Default::default());
-		let format_reader = probe
-			.format(
-				&Hint::default(),
-				mss,
-				&Default::default(),
-				&Default::default(),
-			)?
-			.format;
+		let mut probe_result = probe.format(
+			&Hint::default(),
+			mss,
+			&Default::default(),
+			&Default::default(),
+		)?;
+		let metadata = read_file_metadata(&mut probe_result);
+		let format_reader = probe_result.format;
 		let default_track = format_reader
 			.default_track()
 			.ok_or(FromFileError::NoDefaultTrack)?;
@@ -53,8 +57,14 @@ impl SymphoniaDecoder {
 			sample_rate,
 			num_frames,
 			track_id,
+			metadata: Arc::new(metadata),
 		})
 	}
+
+	#[must_use]
+	pub(crate) fn metadata(&self) -> Arc<FileMetadata> {
+		self.metadata.clone()
+	}
 }
 
 impl super::Decoder for SymphoniaDecoder {
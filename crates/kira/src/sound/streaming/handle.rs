@@ -1,12 +1,13 @@
 use std::{
 	fmt::{Debug, Formatter},
 	sync::Arc,
+	time::Instant,
 };
 
 use crate::{
 	command::handle_param_setters,
-	sound::{IntoOptionalRegion, PlaybackState},
-	Decibels, Panning, PlaybackRate, StartTime, Tween,
+	sound::{IntoOptionalRegion, PlaybackState, SoundEvent},
+	Decibels, Panning, PlaybackRate, StartTime, Tween, Value,
 };
 
 use ringbuf::{ Cons, HeapRb as RingBuffer, consumer::Consumer as _ };
@@ -19,6 +20,7 @@ pub struct StreamingSoundHandle<Error> {
 	pub(super) shared: Arc<Shared>,
 	pub(super) command_writers: CommandWriters,
 	pub(super) error_consumer: Consumer<Error>,
+	pub(super) event_consumer: Consumer<SoundEvent>,
 }
 
 impl<Error> StreamingSoundHandle<Error> {
@@ -34,6 +36,23 @@ impl<Error> StreamingSoundHandle<Error> {
 		self.shared.position()
 	}
 
+	/**
+	Returns when the audio at the position returned by
+	[`position`](Self::position) will be heard, or when it was heard
+	if that has already happened.
+
+	This accounts for the output latency. To find when another
+	position will be heard, divide the distance to that position by
+	the playback rate and add it to this instant.
+
+	Returns `None` if the backend doesn't report its output latency
+	or the sound hasn't been processed yet.
+	*/
+	#[must_use]
+	pub fn position_playback_instant(&self) -> Option<Instant> {
+		self.shared.position_playback_instant()
+	}
+
 	handle_param_setters! {
 		/**
 		Sets the volume of the sound.
@@ -317,6 +336,15 @@ impl<Error> StreamingSoundHandle<Error> {
 		self.command_writers.stop.write(tween)
 	}
 
+	/// Fades out the sound along the outgoing side of a crossfade
+	/// that starts at `start_time`, and stops playback once the sound
+	/// has faded to silence.
+	pub(crate) fn fade_out_and_stop(&mut self, volume: Value<Decibels>, start_time: StartTime) {
+		self.command_writers
+			.crossfade_out
+			.write((volume, start_time))
+	}
+
 	/// Sets the playback position to the specified time in seconds.
 	pub fn seek_to(&mut self, position: f64) {
 		self.command_writers.seek_to.write(position)
@@ -332,6 +360,20 @@ impl<Error> StreamingSoundHandle<Error> {
 	pub fn pop_error(&mut self) -> Option<Error> {
 		self.error_consumer.try_pop()
 	}
+
+	/**
+	Returns the oldest event reported by the sound that hasn't
+	been read yet, if any.
+
+	The sound reports when playback reaches one of its
+	[`Marker`](crate::sound::Marker)s, when it loops, and when it
+	finishes. Up to 64 events can be waiting to be read. Events that
+	happen while the queue is full are discarded.
+	*/
+	#[must_use]
+	pub fn pop_event(&mut self) -> Option<SoundEvent> {
+		self.event_consumer.try_pop()
+	}
 }
 
 impl<Error: Debug> Debug for StreamingSoundHandle<Error> {
@@ -340,6 +382,7 @@ impl<Error: Debug> Debug for StreamingSoundHandle<Error> {
 			.field("shared", &self.shared)
 			.field("command_writers", &self.command_writers)
 			.field("error_consumer", &ConsumerDebug)
+			.field("event_consumer", &ConsumerDebug)
 			.finish()
 	}
 }
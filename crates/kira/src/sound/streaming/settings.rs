@@ -1,11 +1,13 @@
+use std::sync::Arc;
+
 use crate::{
-	sound::{IntoOptionalRegion, PlaybackPosition, Region},
+	sound::{IntoOptionalRegion, Marker, PlaybackPosition, Region},
 	Tween,
 	Decibels, Panning, PlaybackRate, StartTime, Value,
 };
 
 /// Settings for a streaming sound.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StreamingSoundSettings {
 	/// When the sound should start playing.
 	pub start_time: StartTime,
@@ -25,6 +27,12 @@ pub struct StreamingSoundSettings {
 	pub panning: Value<Panning>,
 	/// An optional fade-in from silence.
 	pub fade_in_tween: Option<Tween>,
+	/// Named positions in the sound. The sound's handle reports
+	/// when playback reaches each marker.
+	///
+	/// The markers are shared between clones of the settings, so
+	/// cloning the settings doesn't copy them.
+	pub markers: Arc<[Marker]>,
 }
 
 impl StreamingSoundSettings {
@@ -39,6 +47,7 @@ impl StreamingSoundSettings {
 			playback_rate: Value::Fixed(PlaybackRate(1.0)),
 			panning: Value::Fixed(Panning::CENTER),
 			fade_in_tween: None,
+			markers: Arc::new([]),
 		}
 	}
 
@@ -112,6 +121,23 @@ impl StreamingSoundSettings {
 			..self
 		}
 	}
+
+	/// Adds a named position to the sound. The sound's handle reports
+	/// when playback reaches the marker.
+	#[must_use = "This method consumes self and returns a modified StreamingSoundSettings, so the return value should be used"]
+	pub fn marker(
+		mut self,
+		name: impl Into<Arc<str>>,
+		position: impl Into<PlaybackPosition>,
+	) -> Self {
+		self.markers = self
+			.markers
+			.iter()
+			.cloned()
+			.chain([Marker::new(name, position)])
+			.collect();
+		self
+	}
 }
 
 impl Default for StreamingSoundSettings {
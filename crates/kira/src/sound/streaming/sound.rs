@@ -1,11 +1,15 @@
 pub(crate) mod decode_scheduler;
+pub(crate) mod frame_stream;
 
 #[cfg(test)]
 mod test;
 
-use std::sync::{
-	atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+		Arc,
+	},
+	time::Instant,
 };
 
 use crate::{
@@ -13,9 +17,9 @@ use crate::{
 	frame::{interpolate_frame, Frame},
 	info::Info,
 	playback_state_manager::PlaybackStateManager,
-	sound::{PlaybackState, Sound},
-	Tween,
-	Decibels, Panning, Parameter, PlaybackRate, StartTime,
+	playback_timing::AtomicInstant,
+	sound::{PlaybackState, Sound, SoundEventWriter},
+	Decibels, Panning, Parameter, PlaybackRate, StartTime, Tween, Value,
 };
 
 use ringbuf::{ Cons, HeapRb as RingBuffer, consumer::Consumer as _, traits::Observer };
@@ -29,6 +33,7 @@ use self::decode_scheduler::DecodeScheduler;
 pub(crate) struct Shared {
 	state: AtomicU8,
 	position: AtomicU64,
+	position_playback_instant: AtomicInstant,
 	reached_end: AtomicBool,
 	encountered_error: AtomicBool,
 }
@@ -38,6 +43,7 @@ impl Shared {
 	pub fn new() -> Self {
 		Self {
 			position: AtomicU64::new(0.0f64.to_bits()),
+			position_playback_instant: AtomicInstant::unknown(),
 			state: AtomicU8::new(PlaybackState::Playing as u8),
 			reached_end: AtomicBool::new(false),
 			encountered_error: AtomicBool::new(false),
@@ -67,6 +73,11 @@ impl Shared {
 		f64::from_bits(self.position.load(Ordering::SeqCst))
 	}
 
+	#[must_use]
+	pub fn position_playback_instant(&self) -> Option<Instant> {
+		self.position_playback_instant.load()
+	}
+
 	#[must_use]
 	pub fn reached_end(&self) -> bool {
 		self.reached_end.load(Ordering::SeqCst)
@@ -89,7 +100,10 @@ pub(crate) struct StreamingSound {
 	volume: Parameter<Decibels>,
 	playback_rate: Parameter<PlaybackRate>,
 	panning: Parameter<Panning>,
+	/// When the audio at the end of the last processed chunk will be heard.
+	playback_nanos: Option<i64>,
 	shared: Arc<Shared>,
+	event_writer: SoundEventWriter,
 }
 
 impl StreamingSound {
@@ -100,6 +114,7 @@ impl StreamingSound {
 		shared: Arc<Shared>,
 		frame_consumer: Consumer<TimestampedFrame>,
 		command_readers: CommandReaders,
+		event_writer: SoundEventWriter,
 		scheduler: &DecodeScheduler<Error>,
 	) -> Self {
 		let current_frame = scheduler.current_frame();
@@ -118,23 +133,47 @@ impl StreamingSound {
 			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
 			playback_rate: Parameter::new(settings.playback_rate, PlaybackRate(1.0)),
 			panning: Parameter::new(settings.panning, Panning::CENTER),
+			playback_nanos: None,
 			shared,
+			event_writer,
 		}
 	}
 
+	/// Makes the sound fade in along the incoming side of a crossfade.
+	pub(crate) fn crossfade_in(&mut self, volume: Value<Decibels>) {
+		self.playback_state_manager.crossfade_in(volume);
+	}
+
 	fn update_shared_playback_state(&mut self) {
-		self.shared
-			.set_state(self.playback_state_manager.playback_state());
+		let playback_state = self.playback_state_manager.playback_state();
+		self.shared.set_state(playback_state);
+		if playback_state == PlaybackState::Stopped {
+			self.event_writer.finish();
+		}
 	}
 
-	fn update_current_frame(&mut self) {
+	/// Returns the frame that's currently being heard, if it's
+	/// been decoded.
+	#[must_use]
+	fn heard_frame(&self) -> Option<TimestampedFrame> {
 		let (a, b) = self.frame_consumer.as_slices();
 		let a = &a[..4.min(a.len())];
 		let b = &b[..4.min(b.len())];
 
 		let mut iter = a.iter().chain(b.iter());
-		if let Some(TimestampedFrame { index, .. }) = iter.nth(1) {
-			self.current_frame = *index;
+		iter.nth(1).copied()
+	}
+
+	fn update_current_frame(&mut self) {
+		if let Some(TimestampedFrame { index, .. }) = self.heard_frame() {
+			self.current_frame = index;
+		}
+	}
+
+	/// Reports events for the frame that's currently being heard.
+	fn report_position(&mut self) {
+		if let Some(TimestampedFrame { index, looped, .. }) = self.heard_frame() {
+			self.event_writer.update_position(index, looped);
 		}
 	}
 
@@ -183,69 +222,98 @@ impl StreamingSound {
 		self.update_shared_playback_state();
 	}
 
-	fn read_commands(&mut self) {
-		read_commands_into_parameters!(self, volume, playback_rate, panning);
-		if let Some(tween) = self.command_readers.pause.read() {
-			self.pause(tween);
-		}
-		if let Some((start_time, tween)) = self.command_readers.resume.read() {
-			self.resume(start_time, tween);
-		}
-		if let Some(tween) = self.command_readers.stop.read() {
-			self.stop(tween);
-		}
-	}
-}
-
-impl Sound for StreamingSound {
-	fn on_start_processing(&mut self) {
-		self.update_current_frame();
-		self.shared
-			.position
-			.store(self.position().to_bits(), Ordering::SeqCst);
-		self.read_commands();
-	}
-
-	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+	/// Updates the parameters and playback state for the next
+	/// `num_frames` frames. Returns `true` if playback should
+	/// advance during those frames.
+	#[must_use]
+	fn update(&mut self, num_frames: usize, dt: f64, info: &Info) -> bool {
+		self.playback_nanos = info.playback_nanos();
 		if self.shared.encountered_error() {
 			self.playback_state_manager.mark_as_stopped();
 			self.update_shared_playback_state();
-			out.fill(Frame::ZERO);
-			return;
+			return false;
 		}
 
 		// update parameters
-		self.volume.update(dt * out.len() as f64, info);
-		self.playback_rate.update(dt * out.len() as f64, info);
-		self.panning.update(dt * out.len() as f64, info);
+		self.volume.update(dt * num_frames as f64, info);
+		self.playback_rate.update(dt * num_frames as f64, info);
+		self.panning.update(dt * num_frames as f64, info);
 		let changed_playback_state = self
 			.playback_state_manager
-			.update(dt * out.len() as f64, info);
+			.update(dt * num_frames as f64, info);
 		if changed_playback_state {
 			self.update_shared_playback_state();
 		}
 
-		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		let will_never_start = self.start_time.update(dt * num_frames as f64, info);
 		if will_never_start {
 			self.playback_state_manager.mark_as_stopped();
 			self.update_shared_playback_state();
 		}
 		if self.start_time != StartTime::Immediate {
-			out.fill(Frame::ZERO);
-			return;
+			return false;
 		}
 
 		if !self.playback_state_manager.playback_state().is_advancing() {
-			out.fill(Frame::ZERO);
-			return;
+			return false;
 		}
 		// pause playback while waiting for audio data. the first frame
 		// in the ringbuffer is the previous frame, so we need to make
 		// sure there's at least 2 before we continue playing.
-		if self.frame_consumer.occupied_len() < 2 && !self.shared.reached_end() {
+		self.frame_consumer.occupied_len() >= 2 || self.shared.reached_end()
+	}
+
+	/// Moves the playback position forward by one output frame.
+	fn advance(&mut self, playback_rate: PlaybackRate, dt: f64) {
+		self.fractional_position += self.sample_rate as f64 * playback_rate.0.max(0.0) * dt;
+		while self.fractional_position >= 1.0 {
+			self.fractional_position -= 1.0;
+			self.frame_consumer.try_pop();
+			self.report_position();
+		}
+		if self.shared.reached_end() && self.frame_consumer.is_empty() {
+			self.playback_state_manager.mark_as_stopped();
+			self.update_shared_playback_state();
+		}
+	}
+
+	fn read_commands(&mut self) {
+		read_commands_into_parameters!(self, volume, playback_rate, panning);
+		if let Some(tween) = self.command_readers.pause.read() {
+			self.pause(tween);
+		}
+		if let Some((start_time, tween)) = self.command_readers.resume.read() {
+			self.resume(start_time, tween);
+		}
+		if let Some(tween) = self.command_readers.stop.read() {
+			self.stop(tween);
+		}
+		if let Some((volume, start_time)) = self.command_readers.crossfade_out.read() {
+			self.playback_state_manager
+				.crossfade_out(volume, start_time);
+			self.update_shared_playback_state();
+		}
+	}
+}
+
+impl Sound for StreamingSound {
+	fn on_start_processing(&mut self) {
+		self.update_current_frame();
+		self.shared
+			.position
+			.store(self.position().to_bits(), Ordering::SeqCst);
+		self.shared
+			.position_playback_instant
+			.store_nanos(self.playback_nanos);
+		self.read_commands();
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		if !self.update(out.len(), dt, info) {
 			out.fill(Frame::ZERO);
 			return;
 		}
+		self.report_position();
 
 		let num_frames = out.len();
 		for (i, frame) in out.iter_mut().enumerate() {
@@ -265,26 +333,38 @@ impl Sound for StreamingSound {
 				next_frames[3],
 				self.fractional_position as f32,
 			);
-			self.fractional_position += self.sample_rate as f64 * playback_rate.0.max(0.0) * dt;
-			while self.fractional_position >= 1.0 {
-				self.fractional_position -= 1.0;
-				self.frame_consumer.try_pop();
-			}
-			if self.shared.reached_end() && self.frame_consumer.is_empty() {
-				self.playback_state_manager.mark_as_stopped();
-				self.update_shared_playback_state();
-			}
+			self.advance(playback_rate, dt);
 			*frame = (interpolated_out * fade_volume * volume).panned(panning);
 		}
 	}
 
+	fn process_virtual(&mut self, num_frames: usize, dt: f64, info: &Info) {
+		if !self.update(num_frames, dt, info) {
+			return;
+		}
+		self.report_position();
+		for i in 0..num_frames {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let playback_rate = self.playback_rate.interpolated_value(time_in_chunk);
+			self.advance(playback_rate, dt);
+		}
+	}
+
 	fn finished(&self) -> bool {
 		self.playback_state_manager.playback_state() == PlaybackState::Stopped
 	}
+
+	fn on_stolen(&mut self) {
+		self.shared.set_state(PlaybackState::Stopped);
+		self.event_writer.finish();
+	}
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct TimestampedFrame {
 	frame: Frame,
 	index: usize,
+	/// Whether playback got to this frame by jumping back to
+	/// the start of the loop region.
+	looped: bool,
 }
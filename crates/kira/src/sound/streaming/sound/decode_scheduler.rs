@@ -39,13 +39,16 @@ pub(crate) struct DecodeScheduler<Error: Send + 'static> {
 	frame_producer: Producer<TimestampedFrame>,
 	error_producer: Producer<Error>,
 	shared: Arc<Shared>,
+	/// Whether playback jumped back to the start of the loop region
+	/// after the most recently pushed frame.
+	looped: bool,
 }
 
 impl<Error: Send + 'static> DecodeScheduler<Error> {
 	pub(crate) fn new(
 		mut decoder: Box<dyn Decoder<Error = Error>>,
 		slice: Option<(usize, usize)>,
-		settings: StreamingSoundSettings,
+		settings: &StreamingSoundSettings,
 		shared: Arc<Shared>,
 		command_readers: DecodeSchedulerCommandReaders,
 		error_producer: Producer<Error>,
@@ -61,6 +64,7 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 			.try_push(TimestampedFrame {
 				frame: Frame::ZERO,
 				index: 0,
+				looped: false,
 			})
 			.expect("The frame producer shouldn't be full because we just created it");
 		let sample_rate = decoder.sample_rate();
@@ -89,6 +93,7 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 			frame_producer,
 			error_producer,
 			shared,
+			looped: false,
 		};
 		Ok((scheduler, frame_consumer))
 	}
@@ -139,9 +144,10 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 			.try_push(TimestampedFrame {
 				frame,
 				index: self.transport.position,
+				looped: self.looped,
 			})
 			.expect("could not push frame to frame producer");
-		self.transport.increment_position(self.num_frames);
+		self.looped = self.transport.increment_position(self.num_frames);
 		if !self.transport.playing {
 			self.shared.reached_end.store(true, Ordering::SeqCst);
 			return Ok(NextStep::End);
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{frame::Frame, sound::PlaybackState};
+
+use ringbuf::{consumer::Consumer as _, Cons, HeapRb as RingBuffer};
+type Consumer<T> = Cons<Arc<RingBuffer<T>>>;
+
+use super::{Shared, TimestampedFrame};
+
+/// Frames decoded from a streaming sound on a background thread,
+/// for sounds that do their own playback.
+///
+/// The decoding thread stops when the [`FrameStream`] is dropped.
+pub(crate) struct FrameStream {
+	sample_rate: u32,
+	frame_consumer: Consumer<TimestampedFrame>,
+	shared: Arc<Shared>,
+}
+
+impl FrameStream {
+	#[must_use]
+	pub fn new(
+		sample_rate: u32,
+		mut frame_consumer: Consumer<TimestampedFrame>,
+		shared: Arc<Shared>,
+	) -> Self {
+		// the decode scheduler pre-seeds the ringbuffer with a silent
+		// "previous" frame, which we don't need
+		frame_consumer.try_pop();
+		Self {
+			sample_rate,
+			frame_consumer,
+			shared,
+		}
+	}
+
+	#[must_use]
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Returns the next decoded frame, or `None` if the end of the audio
+	/// was reached or the decoder encountered an error.
+	///
+	/// If the decoding thread hasn't caught up, this returns silence.
+	#[must_use]
+	pub fn next_frame(&mut self) -> Option<Frame> {
+		if self.shared.encountered_error() {
+			return None;
+		}
+		// the decode scheduler pushes the last frame before marking the
+		// end as reached, so this has to be checked before popping
+		let reached_end = self.shared.reached_end();
+		match self.frame_consumer.try_pop() {
+			Some(TimestampedFrame { frame, .. }) => Some(frame),
+			None if reached_end => None,
+			None => Some(Frame::ZERO),
+		}
+	}
+}
+
+impl Drop for FrameStream {
+	fn drop(&mut self) {
+		self.shared.set_state(PlaybackState::Stopped);
+	}
+}
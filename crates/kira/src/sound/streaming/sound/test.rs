@@ -6,7 +6,7 @@ use crate::{
 	info::MockInfoBuilder,
 	sound::{
 		streaming::{decoder::mock::MockDecoder, StreamingSoundData, StreamingSoundSettings},
-		PlaybackState, Sound,
+		Marker, PlaybackState, Sound, SoundEvent,
 	},
 	test_helpers::expect_frame_soon,
 	Decibels, Panning, StartTime, Tween,
@@ -25,6 +25,7 @@ fn plays_all_samples() {
 		])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -67,6 +68,7 @@ fn waits_for_samples() {
 		)),
 		settings: StreamingSoundSettings::default(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle, mut scheduler) = data.split().unwrap();
 
@@ -110,6 +112,7 @@ fn reports_playback_state() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.0); 10])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -132,6 +135,7 @@ fn reports_playback_position() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(0.0); 10])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -152,6 +156,7 @@ fn pauses_and_resumes_with_fades() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -263,6 +268,7 @@ fn stops_with_fade_out() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -335,6 +341,7 @@ fn waits_for_start_time() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -408,6 +415,7 @@ fn stops_if_waiting_on_missing_clock() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -443,6 +451,7 @@ fn continues_when_clock_stops() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -476,6 +485,7 @@ fn immediate_playback_state_change_with_start_time() {
 			fraction: 0.0,
 		}),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -521,6 +531,7 @@ fn resume_at() {
 		)),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -573,6 +584,7 @@ fn start_position() {
 		)),
 		settings: StreamingSoundSettings::new().start_position(3.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -598,6 +610,7 @@ fn loops_forward() {
 		)),
 		settings: StreamingSoundSettings::new().loop_region(Some((3.0..6.0).into())),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -635,6 +648,7 @@ fn volume() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 10])),
 		settings: StreamingSoundSettings::new().volume(-6.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -653,6 +667,7 @@ fn set_volume() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -683,6 +698,7 @@ fn panning() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 10])),
 		settings: StreamingSoundSettings::new().panning(Panning::LEFT),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -701,6 +717,7 @@ fn set_panning() {
 		decoder: Box::new(MockDecoder::new(vec![Frame::from_mono(1.0); 100])),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -730,6 +747,7 @@ fn playback_rate() {
 		)),
 		settings: StreamingSoundSettings::new().playback_rate(2.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -755,6 +773,7 @@ fn set_playback_rate() {
 		)),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -803,6 +822,7 @@ fn interpolates_samples() {
 		])),
 		settings: Default::default(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -831,6 +851,7 @@ fn interpolates_samples_when_looping() {
 		])),
 		settings: StreamingSoundSettings::new().loop_region(Some((..).into())),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, _, mut scheduler) = data.split().unwrap();
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
@@ -851,6 +872,7 @@ fn seek_to() {
 		)),
 		settings: StreamingSoundSettings::new(),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 
@@ -869,6 +891,7 @@ fn seek_by() {
 		)),
 		settings: StreamingSoundSettings::new().start_position(10.0),
 		slice: None,
+		metadata: None,
 	};
 	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
 	handle.seek_by(5.0);
@@ -876,3 +899,52 @@ fn seek_by() {
 	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
 	expect_frame_soon(Frame::from_mono(20.0).panned(Panning::CENTER), &mut sound);
 }
+
+/// Tests that a `StreamingSound` reports when playback reaches its markers,
+/// when it loops, and when it finishes.
+#[test]
+fn reports_events() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(0..10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new()
+			.loop_region(Some((4.0..6.0).into()))
+			.marker("start", 0.0)
+			.marker("two", 2.0)
+			.marker("five", 5.0)
+			.marker("seven", 7.0),
+		slice: None,
+		metadata: None,
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	let info = MockInfoBuilder::new().build();
+	assert_eq!(handle.pop_event(), None);
+
+	let mut events = vec![];
+	for _ in 0..8 {
+		sound.process_one(1.0, &info);
+		events.extend(std::iter::from_fn(|| handle.pop_event()));
+	}
+	assert_eq!(
+		events,
+		[
+			SoundEvent::MarkerReached(Marker::new("start", 0.0)),
+			SoundEvent::MarkerReached(Marker::new("two", 2.0)),
+			SoundEvent::MarkerReached(Marker::new("five", 5.0)),
+			SoundEvent::Looped,
+			SoundEvent::MarkerReached(Marker::new("five", 5.0)),
+			SoundEvent::Looped,
+		]
+	);
+
+	handle.stop(Tween::default());
+	for _ in 0..20 {
+		sound.on_start_processing();
+		sound.process_one(0.001, &info);
+	}
+	assert!(sound.finished());
+	assert_eq!(handle.pop_event(), Some(SoundEvent::Finished));
+	assert_eq!(handle.pop_event(), None);
+}
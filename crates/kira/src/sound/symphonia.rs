@@ -1,12 +1,14 @@
 use symphonia::core::{
 	audio::{AudioBuffer, AudioBufferRef, Signal},
 	conv::{FromSample, IntoSample},
+	meta::{StandardTagKey, Tag, Value},
+	probe::ProbeResult,
 	sample::Sample,
 };
 
-use crate::frame::Frame;
+use crate::{frame::Frame, Decibels};
 
-use super::FromFileError;
+use super::{FileMetadata, FromFileError, ReplayGain};
 
 pub fn load_frames_from_buffer_ref(buffer: &AudioBufferRef) -> Result<Vec<Frame>, FromFileError> {
 	match buffer {
@@ -44,3 +46,72 @@ where
 		_ => Err(FromFileError::UnsupportedChannelConfiguration),
 	}
 }
+
+/// Collects the tags and codec information of the default track
+/// of a probed file.
+#[must_use]
+pub fn read_file_metadata(probe_result: &mut ProbeResult) -> FileMetadata {
+	let mut tags = vec![];
+	if let Some(mut metadata) = probe_result.metadata.get() {
+		if let Some(revision) = metadata.skip_to_latest() {
+			tags.extend_from_slice(revision.tags());
+		}
+	}
+	if let Some(revision) = probe_result.format.metadata().skip_to_latest() {
+		tags.extend_from_slice(revision.tags());
+	}
+	let tag_value = |key: StandardTagKey| -> Option<String> {
+		tags.iter()
+			.find(|tag| tag.std_key == Some(key))
+			.map(|tag| tag_value_to_string(&tag.value))
+	};
+	let mut metadata = FileMetadata {
+		title: tag_value(StandardTagKey::TrackTitle),
+		artist: tag_value(StandardTagKey::Artist),
+		album: tag_value(StandardTagKey::Album),
+		replay_gain: ReplayGain {
+			track_gain: tag_value(StandardTagKey::ReplayGainTrackGain)
+				.and_then(|value| parse_replay_gain_value(&value))
+				.map(Decibels),
+			track_peak: tag_value(StandardTagKey::ReplayGainTrackPeak)
+				.and_then(|value| parse_replay_gain_value(&value)),
+			album_gain: tag_value(StandardTagKey::ReplayGainAlbumGain)
+				.and_then(|value| parse_replay_gain_value(&value))
+				.map(Decibels),
+			album_peak: tag_value(StandardTagKey::ReplayGainAlbumPeak)
+				.and_then(|value| parse_replay_gain_value(&value)),
+		},
+		tags: tags
+			.iter()
+			.map(|Tag { key, value, .. }| (key.clone(), tag_value_to_string(value)))
+			.collect(),
+		..Default::default()
+	};
+	if let Some(track) = probe_result.format.default_track() {
+		let codec_params = &track.codec_params;
+		metadata.num_channels = codec_params.channels.map(|channels| channels.count());
+		metadata.bits_per_sample = codec_params.bits_per_sample;
+		metadata.codec = symphonia::default::get_codecs()
+			.get_codec(codec_params.codec)
+			.map(|codec| codec.short_name.to_string());
+	}
+	metadata
+}
+
+/// Converts a tag value to a string, removing the null terminators
+/// some formats (like RIFF INFO chunks) store with text.
+#[must_use]
+fn tag_value_to_string(value: &Value) -> String {
+	value.to_string().trim_end_matches('\0').to_string()
+}
+
+/// Parses ReplayGain tag values like `"-6.50 dB"` and `"0.988"`.
+#[must_use]
+fn parse_replay_gain_value(value: &str) -> Option<f32> {
+	let value = value.trim();
+	let value = value
+		.strip_suffix("dB")
+		.or_else(|| value.strip_suffix("db"))
+		.unwrap_or(value);
+	value.trim().parse().ok()
+}
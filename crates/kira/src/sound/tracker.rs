@@ -0,0 +1,79 @@
+/*!
+Plays tracker music modules.
+
+[`TrackerSoundData`] plays a [`TrackerModule`] loaded from a MOD or XM
+file. The module's patterns are played in the order given by its order
+list, and the samples are mixed by kira, so the music can be sent to
+tracks and effects like any other sound.
+
+```no_run
+use kira::{
+	AudioManager, AudioManagerSettings, DefaultBackend,
+	sound::tracker::{TrackerSoundData, TrackerSoundSettings},
+};
+
+let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+let mut music = manager.play(
+	TrackerSoundData::from_file("song.xm")?
+		.with_settings(TrackerSoundSettings::new().looping(true)),
+)?;
+// when the player enters a new area...
+music.queue_order(8);
+# Result::<(), Box<dyn std::error::Error>>::Ok(())
+```
+
+## Adaptive music
+
+[`TrackerSoundHandle::position`] reports which order and row is
+playing. [`TrackerSoundHandle::jump_to_order`] moves playback to another
+order right away, while [`TrackerSoundHandle::queue_order`] waits until
+the current pattern is finished, which keeps transitions in time with
+the music.
+
+## Format support
+
+Both ProTracker-style MOD files (with 4 to 32 channels) and FastTracker 2
+XM files are supported. S3M and IT modules are not supported yet.
+
+Supported features include:
+
+- Amiga and linear frequency tables
+- Sample loops, including ping-pong loops in XM files
+- Volume and panning envelopes and fadeout in XM instruments
+- The XM volume column
+- Most effects, including arpeggio, portamento, vibrato, tremolo,
+  volume and panning slides, sample offset, position jumps, pattern
+  breaks, pattern loops, note cuts and delays, retriggers, pattern delays,
+  and speed and tempo changes
+
+Instrument auto-vibrato, tremor, and the less common extended effects
+are ignored.
+*/
+
+mod data;
+mod error;
+mod handle;
+mod module;
+mod settings;
+mod sound;
+
+pub use data::*;
+pub use error::*;
+pub use handle::*;
+pub use module::*;
+pub use settings::*;
+
+use crate::{
+	command::ValueChangeCommand, command_writers_and_readers, tween::Tween, Decibels, Panning,
+	StartTime,
+};
+
+command_writers_and_readers! {
+	set_volume: ValueChangeCommand<Decibels>,
+	set_panning: ValueChangeCommand<Panning>,
+	jump_to_order: usize,
+	queue_order: usize,
+	pause: Tween,
+	resume: (StartTime, Tween),
+	stop: Tween,
+}
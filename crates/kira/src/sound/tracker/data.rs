@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use crate::sound::{Sound, SoundData};
+
+use super::{
+	command_writers_and_readers, sound::TrackerSound, TrackerModule, TrackerModuleError,
+	TrackerSoundHandle, TrackerSoundSettings,
+};
+
+/// A sound that plays a tracker module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerSoundData {
+	/// The module to play.
+	///
+	/// The module is shared, so it can be played multiple
+	/// times without copying its samples.
+	pub module: Arc<TrackerModule>,
+	/// Settings for the sound.
+	pub settings: TrackerSoundSettings,
+}
+
+impl TrackerSoundData {
+	/// Creates a new [`TrackerSoundData`] with the default settings.
+	#[must_use]
+	pub fn new(module: impl Into<Arc<TrackerModule>>) -> Self {
+		Self {
+			module: module.into(),
+			settings: TrackerSoundSettings::new(),
+		}
+	}
+
+	/// Loads a MOD or XM file into a [`TrackerSoundData`] with the
+	/// default settings.
+	pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, TrackerModuleError> {
+		Ok(Self::new(TrackerModule::from_file(path)?))
+	}
+
+	/// Sets the settings for the sound.
+	#[must_use = "This method consumes self and returns a modified TrackerSoundData, so the return value should be used"]
+	pub fn with_settings(self, settings: TrackerSoundSettings) -> Self {
+		Self { settings, ..self }
+	}
+
+	#[must_use]
+	pub(super) fn split(self) -> (TrackerSound, TrackerSoundHandle) {
+		let (command_writers, command_readers) = command_writers_and_readers();
+		let sound = TrackerSound::new(self, command_readers);
+		let shared = sound.shared();
+		(
+			sound,
+			TrackerSoundHandle {
+				command_writers,
+				shared,
+			},
+		)
+	}
+}
+
+impl SoundData for TrackerSoundData {
+	type Error = ();
+
+	type Handle = TrackerSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (sound, handle) = self.split();
+		Ok((Box::new(sound), handle))
+	}
+}
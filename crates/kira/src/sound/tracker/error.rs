@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+/// Errors that can occur when loading a tracker module.
+#[derive(Debug)]
+pub enum TrackerModuleError {
+	/// The data isn't a MOD or XM module, or it's a version of the
+	/// format that isn't supported.
+	UnsupportedFormat,
+	/// The module's header contains invalid values.
+	InvalidHeader,
+	/// A pattern contains invalid data. The pattern is given
+	/// by its index (starting from `0`).
+	InvalidPattern(usize),
+	/// An instrument contains invalid data. The instrument is
+	/// given by its number (starting from `1`).
+	InvalidInstrument(usize),
+	/// The file ended in the middle of the header, a pattern,
+	/// or an instrument.
+	UnexpectedEndOfFile,
+	/// An error occurred while reading the file from the filesystem.
+	IoError(std::io::Error),
+}
+
+impl Display for TrackerModuleError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			TrackerModuleError::UnsupportedFormat => {
+				f.write_str("The data is not a supported tracker module")
+			}
+			TrackerModuleError::InvalidHeader => f.write_str("The module's header is invalid"),
+			TrackerModuleError::InvalidPattern(pattern) => {
+				write!(f, "Pattern {} is invalid", pattern)
+			}
+			TrackerModuleError::InvalidInstrument(instrument) => {
+				write!(f, "Instrument {} is invalid", instrument)
+			}
+			TrackerModuleError::UnexpectedEndOfFile => f.write_str("The module ended unexpectedly"),
+			TrackerModuleError::IoError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl std::error::Error for TrackerModuleError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			TrackerModuleError::IoError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<std::io::Error> for TrackerModuleError {
+	fn from(v: std::io::Error) -> Self {
+		Self::IoError(v)
+	}
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::{
+	command::handle_param_setters, sound::PlaybackState, Decibels, Panning, StartTime, Tween,
+};
+
+use super::{sound::Shared, CommandWriters};
+
+/// A position in a tracker module's song.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TrackerPosition {
+	/// The position in the module's order list.
+	pub order: usize,
+	/// The row of the pattern being played.
+	pub row: usize,
+}
+
+/// Controls a tracker sound.
+///
+/// When the handle is dropped, the sound will stop playing.
+#[derive(Debug)]
+pub struct TrackerSoundHandle {
+	pub(super) command_writers: CommandWriters,
+	pub(super) shared: Arc<Shared>,
+}
+
+impl TrackerSoundHandle {
+	/// Returns the current playback state of the sound.
+	#[must_use]
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state.load()
+	}
+
+	/// Returns the order and row that's currently playing.
+	#[must_use]
+	pub fn position(&self) -> TrackerPosition {
+		self.shared.position()
+	}
+
+	handle_param_setters! {
+		/// Sets the volume of the sound.
+		volume: Decibels,
+
+		/// Sets the panning of the sound, where `-1.0` is hard left,
+		/// `0.0` is center, and `1.0` is hard right.
+		panning: Panning,
+	}
+
+	/// Starts playing the given order from its first row.
+	///
+	/// If the order doesn't exist, this does nothing.
+	pub fn jump_to_order(&mut self, order: usize) {
+		self.command_writers.jump_to_order.write(order)
+	}
+
+	/// Plays the given order once the current pattern is finished,
+	/// instead of the order that would normally come next.
+	///
+	/// If the order doesn't exist, this does nothing.
+	pub fn queue_order(&mut self, order: usize) {
+		self.command_writers.queue_order.write(order)
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// pauses playback.
+	pub fn pause(&mut self, tween: Tween) {
+		self.command_writers.pause.write(tween)
+	}
+
+	/// Resumes playback and fades in the sound from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) {
+		self.resume_at(StartTime::Immediate, tween)
+	}
+
+	/// Resumes playback at the given start time and fades in
+	/// the sound from silence with the given tween.
+	pub fn resume_at(&mut self, start_time: StartTime, tween: Tween) {
+		self.command_writers.resume.write((start_time, tween))
+	}
+
+	/// Fades out the sound to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sound is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) {
+		self.command_writers.stop.write(tween)
+	}
+}
+
+impl Drop for TrackerSoundHandle {
+	fn drop(&mut self) {
+		self.shared.state.mark_removed();
+	}
+}
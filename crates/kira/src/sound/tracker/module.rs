@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod test;
+
+mod mod_format;
+mod xm;
+
+use super::TrackerModuleError;
+
+/// The file formats tracker modules can be loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackerFormat {
+	/// A ProTracker-style MOD file.
+	Mod,
+	/// A FastTracker 2 XM file.
+	Xm,
+}
+
+/// The patterns, order list, and instruments of a tracker module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerModule {
+	pub(super) name: String,
+	pub(super) format: TrackerFormat,
+	pub(super) num_channels: usize,
+	/// The index of the pattern to play for each position in the song.
+	pub(super) orders: Vec<usize>,
+	/// The order to go back to when the song loops.
+	pub(super) restart_position: usize,
+	pub(super) patterns: Vec<Pattern>,
+	pub(super) instruments: Vec<Instrument>,
+	/// The number of ticks per row at the start of the song.
+	pub(super) initial_speed: u32,
+	/// The tempo at the start of the song (in BPM).
+	pub(super) initial_tempo: u32,
+	/// Whether pitches are calculated using the linear frequency table
+	/// instead of Amiga periods.
+	pub(super) linear_frequencies: bool,
+	/// The initial panning of each channel, where `0` is hard left
+	/// and `255` is hard right.
+	pub(super) channel_panning: Vec<u8>,
+}
+
+impl TrackerModule {
+	/// Loads a MOD or XM file.
+	///
+	/// S3M and IT files are not supported.
+	pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, TrackerModuleError> {
+		Self::from_bytes(&std::fs::read(path)?)
+	}
+
+	/// Parses the contents of a MOD or XM file.
+	///
+	/// The format is detected from the contents of the file. S3M and
+	/// IT files are not supported.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, TrackerModuleError> {
+		if bytes.starts_with(xm::SIGNATURE) {
+			return xm::parse(bytes);
+		}
+		if let Some(num_channels) = bytes
+			.get(mod_format::SIGNATURE_POSITION..mod_format::SIGNATURE_POSITION + 4)
+			.and_then(mod_format::num_channels)
+		{
+			return mod_format::parse(bytes, num_channels);
+		}
+		Err(TrackerModuleError::UnsupportedFormat)
+	}
+
+	/// Returns the name of the song.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the format the module was loaded from.
+	#[must_use]
+	pub fn format(&self) -> TrackerFormat {
+		self.format
+	}
+
+	/// Returns the number of channels in the module.
+	#[must_use]
+	pub fn num_channels(&self) -> usize {
+		self.num_channels
+	}
+
+	/// Returns the number of positions in the module's order list.
+	#[must_use]
+	pub fn num_orders(&self) -> usize {
+		self.orders.len()
+	}
+
+	/// Returns the number of rows in the pattern played at the
+	/// given order, or `None` if the order doesn't exist.
+	#[must_use]
+	pub fn num_rows(&self, order: usize) -> Option<usize> {
+		self.orders
+			.get(order)
+			.map(|pattern| self.patterns[*pattern].num_rows)
+	}
+}
+
+/// A grid of notes with one column per channel.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Pattern {
+	pub num_rows: usize,
+	/// The cells of the pattern, stored row by row.
+	pub cells: Vec<Cell>,
+}
+
+impl Pattern {
+	#[must_use]
+	pub fn empty(num_rows: usize, num_channels: usize) -> Self {
+		Self {
+			num_rows,
+			cells: vec![Cell::default(); num_rows * num_channels],
+		}
+	}
+}
+
+/// What a channel should do on a row of a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) struct Cell {
+	pub note: Note,
+	/// The number of the instrument to play (starting from `1`),
+	/// or `0` if the cell doesn't have an instrument.
+	pub instrument: u8,
+	/// The XM volume column, or `0` if the cell doesn't have one.
+	pub volume: u8,
+	/// The effect command, using the XM numbering.
+	pub effect: u8,
+	pub param: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum Note {
+	#[default]
+	None,
+	/// Starts a note, where `0` is C-0 and `48` is C-4.
+	On(u8),
+	/// Releases the current note.
+	Off,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Instrument {
+	pub samples: Vec<Sample>,
+	/// The index of the sample to play for each note.
+	pub sample_map: [u8; 96],
+	pub volume_envelope: Option<Envelope>,
+	pub panning_envelope: Option<Envelope>,
+	/// How much the volume decreases each tick after the note is
+	/// released (out of `65536`).
+	pub fadeout: u16,
+}
+
+impl Instrument {
+	/// Creates an instrument that plays the same sample for
+	/// every note.
+	#[must_use]
+	pub fn from_sample(sample: Sample) -> Self {
+		Self {
+			samples: vec![sample],
+			sample_map: [0; 96],
+			volume_envelope: None,
+			panning_envelope: None,
+			fadeout: 0,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Sample {
+	pub frames: Vec<f32>,
+	/// The start and end of the loop (in frames).
+	pub loop_region: Option<(usize, usize)>,
+	/// Whether the loop should alternate between playing forward
+	/// and backward.
+	pub ping_pong: bool,
+	/// The default volume (from `0` to `64`).
+	pub volume: u8,
+	/// The default panning (from `0` to `255`).
+	pub panning: u8,
+	/// The tuning of the sample (in 1/128ths of a semitone).
+	pub finetune: i8,
+	/// The number of semitones to transpose notes by.
+	pub relative_note: i8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Envelope {
+	/// The tick and value (from `0` to `64`) of each point.
+	pub points: Vec<(u16, u16)>,
+	/// The point to hold at until the note is released.
+	pub sustain_point: Option<usize>,
+	/// The first and last points of the loop.
+	pub loop_points: Option<(usize, usize)>,
+}
+
+impl Envelope {
+	/// Returns the value of the envelope (from `0.0` to `1.0`)
+	/// at the given tick.
+	#[must_use]
+	pub fn value(&self, tick: u16) -> f32 {
+		let Some(next_point_index) = self.points.iter().position(|(t, _)| *t > tick) else {
+			return self
+				.points
+				.last()
+				.map_or(1.0, |(_, value)| *value as f32 / 64.0);
+		};
+		if next_point_index == 0 {
+			return self.points[0].1 as f32 / 64.0;
+		}
+		let (previous_tick, previous_value) = self.points[next_point_index - 1];
+		let (next_tick, next_value) = self.points[next_point_index];
+		let amount = (tick - previous_tick) as f32 / (next_tick - previous_tick) as f32;
+		(previous_value as f32 + (next_value as f32 - previous_value as f32) * amount) / 64.0
+	}
+
+	/// Returns the tick after the given tick, taking the sustain point
+	/// and loop into account.
+	#[must_use]
+	pub fn next_tick(&self, tick: u16, key_on: bool) -> u16 {
+		if key_on {
+			if let Some(sustain_point) = self.sustain_point {
+				if tick == self.points[sustain_point].0 {
+					return tick;
+				}
+			}
+		}
+		let tick = tick.saturating_add(1);
+		if let Some((loop_start, loop_end)) = self.loop_points {
+			if tick >= self.points[loop_end].0 {
+				return self.points[loop_start].0;
+			}
+		}
+		tick
+	}
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	position: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], TrackerModuleError> {
+		let bytes = self
+			.bytes
+			.get(self.position..self.position + length)
+			.ok_or(TrackerModuleError::UnexpectedEndOfFile)?;
+		self.position += length;
+		Ok(bytes)
+	}
+
+	fn read_u8(&mut self) -> Result<u8, TrackerModuleError> {
+		Ok(self.read_bytes(1)?[0])
+	}
+
+	fn read_u16_be(&mut self) -> Result<u16, TrackerModuleError> {
+		let bytes = self.read_bytes(2)?;
+		Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+	}
+
+	fn read_u16_le(&mut self) -> Result<u16, TrackerModuleError> {
+		let bytes = self.read_bytes(2)?;
+		Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+	}
+
+	fn read_u32_le(&mut self) -> Result<u32, TrackerModuleError> {
+		let bytes = self.read_bytes(4)?;
+		Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+	}
+}
+
+/// Reads a string that's padded with zeroes or spaces.
+#[must_use]
+fn read_name(bytes: &[u8]) -> String {
+	let end = bytes
+		.iter()
+		.position(|byte| *byte == 0)
+		.unwrap_or(bytes.len());
+	String::from_utf8_lossy(&bytes[..end])
+		.trim_end()
+		.to_string()
+}
@@ -0,0 +1,143 @@
+use crate::sound::tracker::TrackerModuleError;
+
+use super::{
+	read_name, Cell, Instrument, Note, Pattern, Reader, Sample, TrackerFormat, TrackerModule,
+};
+
+/// Where the signature that identifies a MOD file is.
+pub(super) const SIGNATURE_POSITION: usize = 1080;
+
+const NUM_SAMPLES: usize = 31;
+const NUM_ROWS: usize = 64;
+
+/// The Amiga period of C-4, which plays a sample at 8363Hz.
+const C4_PERIOD: f64 = 428.0;
+
+/// Returns the number of channels of a MOD file with the given
+/// signature, or `None` if the signature isn't a known MOD signature.
+#[must_use]
+pub(super) fn num_channels(signature: &[u8]) -> Option<usize> {
+	match signature {
+		b"M.K." | b"M!K!" | b"FLT4" | b"4CHN" => Some(4),
+		b"FLT8" | b"OCTA" | b"CD81" => Some(8),
+		[digit, b'C', b'H', b'N'] if digit.is_ascii_digit() => Some((digit - b'0') as usize),
+		[tens, ones, b'C', b'H'] if tens.is_ascii_digit() && ones.is_ascii_digit() => {
+			Some(((tens - b'0') * 10 + (ones - b'0')) as usize)
+		}
+		_ => None,
+	}
+	.filter(|num_channels| (1..=32).contains(num_channels))
+}
+
+pub(super) fn parse(
+	bytes: &[u8],
+	num_channels: usize,
+) -> Result<TrackerModule, TrackerModuleError> {
+	let mut reader = Reader { bytes, position: 0 };
+	let name = read_name(reader.read_bytes(20)?);
+
+	let mut sample_headers = vec![];
+	for _ in 0..NUM_SAMPLES {
+		reader.read_bytes(22)?;
+		let length = reader.read_u16_be()? as usize * 2;
+		// the finetune is a signed 4-bit number in 1/8ths of a semitone
+		let finetune = ((reader.read_u8()? << 4) as i8 >> 4) * 16;
+		let volume = reader.read_u8()?.min(64);
+		let loop_start = reader.read_u16_be()? as usize * 2;
+		let loop_length = reader.read_u16_be()? as usize * 2;
+		sample_headers.push((length, finetune, volume, loop_start, loop_length));
+	}
+
+	let song_length = reader.read_u8()? as usize;
+	let restart_position = reader.read_u8()? as usize;
+	let order_table = reader.read_bytes(128)?;
+	if song_length == 0 || song_length > 128 {
+		return Err(TrackerModuleError::InvalidHeader);
+	}
+	// every pattern in the order table is stored, even the ones
+	// past the end of the song
+	let num_patterns = *order_table.iter().max().unwrap() as usize + 1;
+	reader.read_bytes(4)?;
+
+	let mut patterns = vec![];
+	for _ in 0..num_patterns {
+		let mut pattern = Pattern::empty(NUM_ROWS, num_channels);
+		for cell in &mut pattern.cells {
+			let bytes = reader.read_bytes(4)?;
+			let period = (((bytes[0] & 0x0F) as u16) << 8) | bytes[1] as u16;
+			*cell = Cell {
+				note: note_from_period(period),
+				instrument: (bytes[0] & 0xF0) | (bytes[2] >> 4),
+				volume: 0,
+				effect: bytes[2] & 0x0F,
+				param: bytes[3],
+			};
+		}
+		patterns.push(pattern);
+	}
+
+	let mut instruments = vec![];
+	for (length, finetune, volume, loop_start, loop_length) in sample_headers {
+		// some MOD files are cut off in the middle of the sample data,
+		// so missing frames are treated as silence
+		let data = &bytes[reader.position.min(bytes.len())..];
+		let data = &data[..length.min(data.len())];
+		reader.position += length;
+		let frames = data
+			.iter()
+			.map(|byte| *byte as i8 as f32 / 128.0)
+			.collect::<Vec<_>>();
+		// a loop of one word or less means the sample doesn't loop
+		let loop_region = if loop_length > 2 && loop_start < frames.len() {
+			Some((loop_start, (loop_start + loop_length).min(frames.len())))
+		} else {
+			None
+		};
+		instruments.push(Instrument::from_sample(Sample {
+			frames,
+			loop_region,
+			ping_pong: false,
+			volume,
+			panning: 128,
+			finetune,
+			relative_note: 0,
+		}));
+	}
+
+	Ok(TrackerModule {
+		name,
+		format: TrackerFormat::Mod,
+		num_channels,
+		orders: order_table[..song_length]
+			.iter()
+			.map(|pattern| *pattern as usize)
+			.collect(),
+		restart_position: if restart_position < song_length {
+			restart_position
+		} else {
+			0
+		},
+		patterns,
+		instruments,
+		initial_speed: 6,
+		initial_tempo: 125,
+		linear_frequencies: false,
+		// the Amiga plays channels hard left, right, right, left. the
+		// panning is narrowed so it's more comfortable on headphones.
+		channel_panning: (0..num_channels)
+			.map(|channel| match channel % 4 {
+				0 | 3 => 64,
+				_ => 191,
+			})
+			.collect(),
+	})
+}
+
+#[must_use]
+fn note_from_period(period: u16) -> Note {
+	if period == 0 {
+		return Note::None;
+	}
+	let note = 48.0 + 12.0 * (C4_PERIOD / period as f64).log2();
+	Note::On(note.round().clamp(0.0, 95.0) as u8)
+}
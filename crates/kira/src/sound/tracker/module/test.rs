@@ -0,0 +1,251 @@
+use crate::{
+	sound::tracker::{TrackerFormat, TrackerModule, TrackerModuleError},
+	test_helpers::{mod_file, ModCell, ModSample},
+};
+
+use super::{mod_format, Cell, Envelope, Note};
+
+/// Builds an XM file with one two-row pattern and one instrument
+/// with a looping sample and a volume envelope.
+fn xm_file() -> Vec<u8> {
+	let mut bytes = vec![];
+	bytes.extend_from_slice(b"Extended Module: ");
+	let mut name = b"xm test".to_vec();
+	name.resize(20, 0);
+	bytes.extend_from_slice(&name);
+	bytes.push(0x1A);
+	bytes.extend_from_slice(&[0; 20]);
+	bytes.extend_from_slice(&0x0104u16.to_le_bytes());
+	// header
+	bytes.extend_from_slice(&276u32.to_le_bytes());
+	for value in [2u16, 1, 2, 1, 1, 1, 3, 140] {
+		bytes.extend_from_slice(&value.to_le_bytes());
+	}
+	let mut order_table = vec![0, 0];
+	order_table.resize(256, 0);
+	bytes.extend_from_slice(&order_table);
+	// pattern
+	let pattern_data = [
+		// C-4, instrument 1, volume 0x40, set volume to 0x20
+		49, 1, 0x40, 0x0C, 0x20, // empty
+		0x80, // key off
+		0x81, 97, // empty
+		0x80,
+	];
+	bytes.extend_from_slice(&9u32.to_le_bytes());
+	bytes.push(0);
+	bytes.extend_from_slice(&2u16.to_le_bytes());
+	bytes.extend_from_slice(&(pattern_data.len() as u16).to_le_bytes());
+	bytes.extend_from_slice(&pattern_data);
+	// instrument
+	let mut instrument = vec![];
+	instrument.extend_from_slice(&263u32.to_le_bytes());
+	instrument.extend_from_slice(&[0; 23]);
+	instrument.extend_from_slice(&1u16.to_le_bytes());
+	instrument.extend_from_slice(&40u32.to_le_bytes());
+	instrument.extend_from_slice(&[0; 96]);
+	let mut volume_points = vec![];
+	for value in [0u16, 64, 10, 32, 20, 0] {
+		volume_points.extend_from_slice(&value.to_le_bytes());
+	}
+	volume_points.resize(48, 0);
+	instrument.extend_from_slice(&volume_points);
+	instrument.extend_from_slice(&[0; 48]);
+	// 3 volume points with a sustain point at point 1
+	instrument.extend_from_slice(&[3, 0, 1, 0, 0, 0, 0, 0, 0b011, 0]);
+	instrument.extend_from_slice(&[0; 4]);
+	instrument.extend_from_slice(&1024u16.to_le_bytes());
+	instrument.resize(263, 0);
+	bytes.extend_from_slice(&instrument);
+	// sample header
+	for value in [4u32, 1, 2] {
+		bytes.extend_from_slice(&value.to_le_bytes());
+	}
+	// volume 48, finetune -16, forward loop, panning 200, relative note 12
+	bytes.extend_from_slice(&[48, 0xF0, 1, 200, 12, 0]);
+	bytes.extend_from_slice(&[0; 22]);
+	// sample data, stored as deltas of 10, 20, -10, 0
+	bytes.extend_from_slice(&[10, 10, (-30i8) as u8, 10]);
+	bytes
+}
+
+/// Tests that the order list, patterns, and samples are read
+/// from MOD files.
+#[test]
+fn reads_mod_files() {
+	let module = TrackerModule::from_bytes(&mod_file(
+		&[
+			ModSample {
+				frames: &[0, 64, 127, -128],
+				loop_region: None,
+			},
+			ModSample {
+				frames: &[1, 2, 3, 4, 5, 6],
+				loop_region: Some((2, 6)),
+			},
+		],
+		&[1, 0, 1],
+		&[
+			&[],
+			&[ModCell {
+				row: 3,
+				channel: 2,
+				sample: 2,
+				period: 214,
+				effect: 0x0A,
+				param: 0x04,
+			}],
+		],
+	))
+	.unwrap();
+	assert_eq!(module.name(), "test");
+	assert_eq!(module.format(), TrackerFormat::Mod);
+	assert_eq!(module.num_channels(), 4);
+	assert_eq!(module.num_orders(), 3);
+	assert_eq!(module.num_rows(2), Some(64));
+	assert_eq!(module.num_rows(3), None);
+	assert_eq!(module.orders, vec![1, 0, 1]);
+	assert_eq!(module.patterns.len(), 2);
+	assert_eq!(module.patterns[0].cells, vec![Cell::default(); 64 * 4]);
+	assert_eq!(
+		module.patterns[1].cells[3 * 4 + 2],
+		Cell {
+			note: Note::On(60),
+			instrument: 2,
+			volume: 0,
+			effect: 0x0A,
+			param: 0x04,
+		}
+	);
+	assert_eq!(module.instruments.len(), 31);
+	assert_eq!(
+		module.instruments[0].samples[0].frames,
+		vec![0.0, 0.5, 127.0 / 128.0, -1.0]
+	);
+	assert_eq!(module.instruments[0].samples[0].loop_region, None);
+	assert_eq!(module.instruments[1].samples[0].loop_region, Some((2, 6)));
+	assert!(!module.linear_frequencies);
+}
+
+/// Tests that the number of channels is read from the signature
+/// of MOD files.
+#[test]
+fn mod_signatures() {
+	assert_eq!(mod_format::num_channels(b"M.K."), Some(4));
+	assert_eq!(mod_format::num_channels(b"6CHN"), Some(6));
+	assert_eq!(mod_format::num_channels(b"FLT8"), Some(8));
+	assert_eq!(mod_format::num_channels(b"16CH"), Some(16));
+	assert_eq!(mod_format::num_channels(b"0CHN"), None);
+	assert_eq!(mod_format::num_channels(b"99CH"), None);
+	assert_eq!(mod_format::num_channels(b"SCRM"), None);
+}
+
+/// Tests that patterns, instruments, and envelopes are read
+/// from XM files.
+#[test]
+fn reads_xm_files() {
+	let module = TrackerModule::from_bytes(&xm_file()).unwrap();
+	assert_eq!(module.name(), "xm test");
+	assert_eq!(module.format(), TrackerFormat::Xm);
+	assert_eq!(module.num_channels(), 2);
+	assert_eq!(module.orders, vec![0, 0]);
+	assert_eq!(module.restart_position, 1);
+	assert_eq!(module.initial_speed, 3);
+	assert_eq!(module.initial_tempo, 140);
+	assert!(module.linear_frequencies);
+	assert_eq!(
+		module.patterns[0].cells,
+		vec![
+			Cell {
+				note: Note::On(48),
+				instrument: 1,
+				volume: 0x40,
+				effect: 0x0C,
+				param: 0x20,
+			},
+			Cell::default(),
+			Cell {
+				note: Note::Off,
+				..Default::default()
+			},
+			Cell::default(),
+		]
+	);
+	let instrument = &module.instruments[0];
+	assert_eq!(instrument.fadeout, 1024);
+	assert_eq!(
+		instrument.volume_envelope,
+		Some(Envelope {
+			points: vec![(0, 64), (10, 32), (20, 0)],
+			sustain_point: Some(1),
+			loop_points: None,
+		})
+	);
+	assert_eq!(instrument.panning_envelope, None);
+	let sample = &instrument.samples[0];
+	assert_eq!(
+		sample.frames,
+		vec![10.0 / 128.0, 20.0 / 128.0, -10.0 / 128.0, 0.0]
+	);
+	assert_eq!(sample.loop_region, Some((1, 3)));
+	assert!(!sample.ping_pong);
+	assert_eq!(sample.volume, 48);
+	assert_eq!(sample.finetune, -16);
+	assert_eq!(sample.panning, 200);
+	assert_eq!(sample.relative_note, 12);
+}
+
+/// Tests that envelopes interpolate between points, hold at the
+/// sustain point until the note is released, and loop.
+#[test]
+fn envelopes() {
+	let envelope = Envelope {
+		points: vec![(0, 64), (10, 32), (20, 0)],
+		sustain_point: Some(1),
+		loop_points: None,
+	};
+	assert_eq!(envelope.value(0), 1.0);
+	assert_eq!(envelope.value(5), 0.75);
+	assert_eq!(envelope.value(15), 0.25);
+	assert_eq!(envelope.value(100), 0.0);
+	assert_eq!(envelope.next_tick(5, true), 6);
+	assert_eq!(envelope.next_tick(10, true), 10);
+	assert_eq!(envelope.next_tick(10, false), 11);
+
+	let envelope = Envelope {
+		points: vec![(0, 0), (4, 64), (8, 0)],
+		sustain_point: None,
+		loop_points: Some((1, 2)),
+	};
+	assert_eq!(envelope.next_tick(6, true), 7);
+	assert_eq!(envelope.next_tick(7, true), 4);
+}
+
+/// Tests that unsupported and invalid modules are rejected.
+#[test]
+fn errors() {
+	assert!(matches!(
+		TrackerModule::from_bytes(b"not a module"),
+		Err(TrackerModuleError::UnsupportedFormat)
+	));
+	let mut bytes = mod_file(&[], &[0], &[&[]]);
+	bytes.truncate(1500);
+	assert!(matches!(
+		TrackerModule::from_bytes(&bytes),
+		Err(TrackerModuleError::UnexpectedEndOfFile)
+	));
+	let mut bytes = xm_file();
+	// set the number of channels to 0
+	bytes[68] = 0;
+	assert!(matches!(
+		TrackerModule::from_bytes(&bytes),
+		Err(TrackerModuleError::InvalidHeader)
+	));
+	let mut bytes = xm_file();
+	// set the version to 1.03
+	bytes[58] = 0x03;
+	assert!(matches!(
+		TrackerModule::from_bytes(&bytes),
+		Err(TrackerModuleError::UnsupportedFormat)
+	));
+}
@@ -0,0 +1,331 @@
+use crate::sound::tracker::TrackerModuleError;
+
+use super::{
+	read_name, Cell, Envelope, Instrument, Note, Pattern, Reader, Sample, TrackerFormat,
+	TrackerModule,
+};
+
+pub(super) const SIGNATURE: &[u8] = b"Extended Module: ";
+
+/// The only version of the XM format that's supported (1.04).
+const VERSION: u16 = 0x0104;
+
+const MAX_CHANNELS: usize = 64;
+const MAX_ROWS: usize = 256;
+const MAX_ENVELOPE_POINTS: usize = 12;
+const KEY_OFF: u8 = 97;
+
+pub(super) fn parse(bytes: &[u8]) -> Result<TrackerModule, TrackerModuleError> {
+	let mut reader = Reader {
+		bytes,
+		position: SIGNATURE.len(),
+	};
+	let name = read_name(reader.read_bytes(20)?);
+	reader.read_bytes(21)?;
+	if reader.read_u16_le()? != VERSION {
+		return Err(TrackerModuleError::UnsupportedFormat);
+	}
+	let header_start = reader.position;
+	let header_size = reader.read_u32_le()? as usize;
+	let song_length = reader.read_u16_le()? as usize;
+	let restart_position = reader.read_u16_le()? as usize;
+	let num_channels = reader.read_u16_le()? as usize;
+	let num_patterns = reader.read_u16_le()? as usize;
+	let num_instruments = reader.read_u16_le()? as usize;
+	let flags = reader.read_u16_le()?;
+	let initial_speed = reader.read_u16_le()? as u32;
+	let initial_tempo = reader.read_u16_le()? as u32;
+	let order_table = reader.read_bytes(256)?;
+	if song_length == 0
+		|| song_length > 256
+		|| num_channels == 0
+		|| num_channels > MAX_CHANNELS
+		|| initial_speed == 0
+		|| initial_tempo == 0
+	{
+		return Err(TrackerModuleError::InvalidHeader);
+	}
+	reader.position = header_start + header_size;
+
+	let mut patterns = vec![];
+	for pattern_index in 0..num_patterns {
+		patterns.push(read_pattern(&mut reader, num_channels, pattern_index)?);
+	}
+	let orders = order_table[..song_length]
+		.iter()
+		.map(|pattern| *pattern as usize)
+		.collect::<Vec<_>>();
+	// orders can refer to patterns that aren't stored in the file,
+	// which are played as empty patterns
+	while orders.iter().any(|pattern| *pattern >= patterns.len()) {
+		patterns.push(Pattern::empty(64, num_channels));
+	}
+
+	let mut instruments = vec![];
+	for instrument_index in 0..num_instruments {
+		instruments.push(read_instrument(&mut reader, instrument_index)?);
+	}
+
+	Ok(TrackerModule {
+		name,
+		format: TrackerFormat::Xm,
+		num_channels,
+		orders,
+		restart_position: if restart_position < song_length {
+			restart_position
+		} else {
+			0
+		},
+		patterns,
+		instruments,
+		initial_speed,
+		initial_tempo,
+		linear_frequencies: flags & 1 != 0,
+		channel_panning: vec![128; num_channels],
+	})
+}
+
+fn read_pattern(
+	reader: &mut Reader,
+	num_channels: usize,
+	pattern_index: usize,
+) -> Result<Pattern, TrackerModuleError> {
+	let pattern_start = reader.position;
+	let header_length = reader.read_u32_le()? as usize;
+	reader.read_u8()?;
+	let num_rows = reader.read_u16_le()? as usize;
+	let packed_size = reader.read_u16_le()? as usize;
+	if num_rows == 0 || num_rows > MAX_ROWS {
+		return Err(TrackerModuleError::InvalidPattern(pattern_index));
+	}
+	reader.position = pattern_start + header_length;
+	let mut pattern = Pattern::empty(num_rows, num_channels);
+	// patterns without any data are empty
+	if packed_size == 0 {
+		return Ok(pattern);
+	}
+	let mut data = Reader {
+		bytes: reader.read_bytes(packed_size)?,
+		position: 0,
+	};
+	for cell in &mut pattern.cells {
+		let first_byte = data
+			.read_u8()
+			.map_err(|_| TrackerModuleError::InvalidPattern(pattern_index))?;
+		// if the highest bit is set, the other bits say which fields
+		// are stored. otherwise, the byte is the note and every
+		// field is stored.
+		let (flags, note) = if first_byte & 0x80 != 0 {
+			(first_byte, None)
+		} else {
+			(0x1F, Some(first_byte))
+		};
+		let mut read_field = |flag: u8| {
+			if flags & flag == 0 {
+				return Ok(0);
+			}
+			data.read_u8()
+				.map_err(|_| TrackerModuleError::InvalidPattern(pattern_index))
+		};
+		let note = match note {
+			Some(note) => note,
+			None => read_field(0x01)?,
+		};
+		*cell = Cell {
+			note: match note {
+				0 => Note::None,
+				KEY_OFF => Note::Off,
+				note if note < KEY_OFF => Note::On(note - 1),
+				_ => return Err(TrackerModuleError::InvalidPattern(pattern_index)),
+			},
+			instrument: read_field(0x02)?,
+			volume: read_field(0x04)?,
+			effect: read_field(0x08)?,
+			param: read_field(0x10)?,
+		};
+	}
+	Ok(pattern)
+}
+
+fn read_instrument(
+	reader: &mut Reader,
+	instrument_index: usize,
+) -> Result<Instrument, TrackerModuleError> {
+	let instrument_start = reader.position;
+	let instrument_size = reader.read_u32_le()? as usize;
+	reader.read_bytes(23)?;
+	let num_samples = reader.read_u16_le()? as usize;
+	if num_samples == 0 {
+		reader.position = instrument_start + instrument_size;
+		return Ok(Instrument {
+			samples: vec![],
+			sample_map: [0; 96],
+			volume_envelope: None,
+			panning_envelope: None,
+			fadeout: 0,
+		});
+	}
+	let sample_header_size = reader.read_u32_le()? as usize;
+	let sample_map: [u8; 96] = reader.read_bytes(96)?.try_into().unwrap();
+	let volume_points = reader.read_bytes(MAX_ENVELOPE_POINTS * 4)?;
+	let panning_points = reader.read_bytes(MAX_ENVELOPE_POINTS * 4)?;
+	let num_volume_points = reader.read_u8()? as usize;
+	let num_panning_points = reader.read_u8()? as usize;
+	let volume_sustain_point = reader.read_u8()? as usize;
+	let volume_loop_points = (reader.read_u8()? as usize, reader.read_u8()? as usize);
+	let panning_sustain_point = reader.read_u8()? as usize;
+	let panning_loop_points = (reader.read_u8()? as usize, reader.read_u8()? as usize);
+	let volume_flags = reader.read_u8()?;
+	let panning_flags = reader.read_u8()?;
+	reader.read_bytes(4)?;
+	let fadeout = reader.read_u16_le()?;
+	let volume_envelope = read_envelope(
+		volume_points,
+		num_volume_points,
+		volume_sustain_point,
+		volume_loop_points,
+		volume_flags,
+	)
+	.map_err(|_| TrackerModuleError::InvalidInstrument(instrument_index + 1))?;
+	let panning_envelope = read_envelope(
+		panning_points,
+		num_panning_points,
+		panning_sustain_point,
+		panning_loop_points,
+		panning_flags,
+	)
+	.map_err(|_| TrackerModuleError::InvalidInstrument(instrument_index + 1))?;
+	reader.position = instrument_start + instrument_size;
+
+	let mut sample_headers = vec![];
+	for _ in 0..num_samples {
+		let header_start = reader.position;
+		let header = SampleHeader {
+			length: reader.read_u32_le()? as usize,
+			loop_start: reader.read_u32_le()? as usize,
+			loop_length: reader.read_u32_le()? as usize,
+			volume: reader.read_u8()?.min(64),
+			finetune: reader.read_u8()? as i8,
+			sample_type: reader.read_u8()?,
+			panning: reader.read_u8()?,
+			relative_note: reader.read_u8()? as i8,
+		};
+		reader.position = header_start + sample_header_size;
+		sample_headers.push(header);
+	}
+
+	let mut samples = vec![];
+	for header in sample_headers {
+		let sixteen_bit = header.sample_type & 0x10 != 0;
+		let data = reader.read_bytes(header.length)?;
+		// samples are stored as the difference between each
+		// frame and the previous frame
+		let frames = if sixteen_bit {
+			let mut value = 0i16;
+			data.chunks_exact(2)
+				.map(|bytes| {
+					value = value.wrapping_add(i16::from_le_bytes([bytes[0], bytes[1]]));
+					value as f32 / 32768.0
+				})
+				.collect::<Vec<_>>()
+		} else {
+			let mut value = 0i8;
+			data.iter()
+				.map(|byte| {
+					value = value.wrapping_add(*byte as i8);
+					value as f32 / 128.0
+				})
+				.collect::<Vec<_>>()
+		};
+		let bytes_per_frame = if sixteen_bit { 2 } else { 1 };
+		let loop_start = header.loop_start / bytes_per_frame;
+		let loop_end = (loop_start + header.loop_length / bytes_per_frame).min(frames.len());
+		let loop_type = header.sample_type & 0x03;
+		samples.push(Sample {
+			frames,
+			loop_region: if loop_type != 0 && loop_start < loop_end {
+				Some((loop_start, loop_end))
+			} else {
+				None
+			},
+			ping_pong: loop_type == 2,
+			volume: header.volume,
+			panning: header.panning,
+			finetune: header.finetune,
+			relative_note: header.relative_note,
+		});
+	}
+
+	if sample_map
+		.iter()
+		.any(|sample| *sample as usize >= samples.len())
+	{
+		return Err(TrackerModuleError::InvalidInstrument(instrument_index + 1));
+	}
+	Ok(Instrument {
+		samples,
+		sample_map,
+		volume_envelope,
+		panning_envelope,
+		fadeout,
+	})
+}
+
+/// The information about a sample that's stored before the
+/// sample data.
+struct SampleHeader {
+	/// The length of the sample data (in bytes).
+	length: usize,
+	/// The start of the loop (in bytes).
+	loop_start: usize,
+	/// The length of the loop (in bytes).
+	loop_length: usize,
+	volume: u8,
+	finetune: i8,
+	/// The loop type and bit depth of the sample.
+	sample_type: u8,
+	panning: u8,
+	relative_note: i8,
+}
+
+/// Reads an envelope, returning `Ok(None)` if the envelope is
+/// turned off and `Err(())` if its points are invalid.
+fn read_envelope(
+	points: &[u8],
+	num_points: usize,
+	sustain_point: usize,
+	loop_points: (usize, usize),
+	flags: u8,
+) -> Result<Option<Envelope>, ()> {
+	if flags & 0x01 == 0 {
+		return Ok(None);
+	}
+	if num_points == 0 || num_points > MAX_ENVELOPE_POINTS {
+		return Err(());
+	}
+	let points = points
+		.chunks_exact(4)
+		.take(num_points)
+		.map(|bytes| {
+			(
+				u16::from_le_bytes([bytes[0], bytes[1]]),
+				u16::from_le_bytes([bytes[2], bytes[3]]).min(64),
+			)
+		})
+		.collect::<Vec<_>>();
+	if points.windows(2).any(|points| points[1].0 <= points[0].0) {
+		return Err(());
+	}
+	let sustain_point = (flags & 0x02 != 0).then_some(sustain_point);
+	let loop_points = (flags & 0x04 != 0).then_some(loop_points);
+	if sustain_point.is_some_and(|point| point >= num_points)
+		|| loop_points.is_some_and(|(start, end)| start > end || end >= num_points)
+	{
+		return Err(());
+	}
+	Ok(Some(Envelope {
+		points,
+		sustain_point,
+		loop_points,
+	}))
+}
@@ -0,0 +1,98 @@
+use crate::{Decibels, Panning, StartTime, Tween, Value};
+
+/// Settings for a tracker sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackerSoundSettings {
+	/// When the sound should start playing.
+	pub start_time: StartTime,
+	/// The order of the module to start playing from.
+	pub start_order: usize,
+	/// Whether the song should start over when it reaches the end.
+	///
+	/// When the song loops, it goes back to the module's restart
+	/// position or to wherever a position jump at the end of the
+	/// song leads.
+	pub looping: bool,
+	/// The volume of the sound.
+	pub volume: Value<Decibels>,
+	/// The panning of the sound, where 0 is hard left
+	/// and 1 is hard right.
+	pub panning: Value<Panning>,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl TrackerSoundSettings {
+	/// Creates a new [`TrackerSoundSettings`] with the default settings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			start_time: StartTime::default(),
+			start_order: 0,
+			looping: false,
+			volume: Value::Fixed(Decibels::IDENTITY),
+			panning: Value::Fixed(Panning::CENTER),
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets when the sound should start playing.
+	#[must_use = "This method consumes self and returns a modified TrackerSoundSettings, so the return value should be used"]
+	pub fn start_time(self, start_time: impl Into<StartTime>) -> Self {
+		Self {
+			start_time: start_time.into(),
+			..self
+		}
+	}
+
+	/// Sets the order of the module to start playing from.
+	#[must_use = "This method consumes self and returns a modified TrackerSoundSettings, so the return value should be used"]
+	pub fn start_order(self, start_order: usize) -> Self {
+		Self {
+			start_order,
+			..self
+		}
+	}
+
+	/// Sets whether the song should start over when it reaches the end.
+	#[must_use = "This method consumes self and returns a modified TrackerSoundSettings, so the return value should be used"]
+	pub fn looping(self, looping: bool) -> Self {
+		Self { looping, ..self }
+	}
+
+	/// Sets the volume of the sound.
+	#[must_use = "This method consumes self and returns a modified TrackerSoundSettings, so the return value should be used"]
+	pub fn volume(self, volume: impl Into<Value<Decibels>>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/**
+	Sets the panning of the sound, where -1.0 is hard left
+	and 1.0 is hard right.
+	*/
+	#[must_use = "This method consumes self and returns a modified TrackerSoundSettings, so the return value should be used"]
+	pub fn panning(self, panning: impl Into<Value<Panning>>) -> Self {
+		Self {
+			panning: panning.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sound from silence.
+	#[must_use = "This method consumes self and returns a modified TrackerSoundSettings, so the return value should be used"]
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for TrackerSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod test;
+
+mod player;
+
+use std::sync::{
+	atomic::{AtomicU64, Ordering},
+	Arc,
+};
+
+use crate::{
+	command::read_commands_into_parameters,
+	frame::Frame,
+	info::Info,
+	playback_state_manager::{PlaybackStateManager, SharedPlaybackState},
+	sound::{PlaybackState, Sound},
+	Decibels, Panning, Parameter, StartTime,
+};
+
+use super::{CommandReaders, TrackerPosition, TrackerSoundData};
+
+use player::Player;
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+	pub(super) state: SharedPlaybackState,
+	/// The current order and row, stored in the upper and
+	/// lower 32 bits.
+	position: AtomicU64,
+}
+
+impl Shared {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			state: SharedPlaybackState::new(),
+			position: AtomicU64::new(0),
+		}
+	}
+
+	#[must_use]
+	pub fn position(&self) -> TrackerPosition {
+		let position = self.position.load(Ordering::SeqCst);
+		TrackerPosition {
+			order: (position >> 32) as usize,
+			row: (position & 0xFFFF_FFFF) as usize,
+		}
+	}
+
+	pub fn set_position(&self, position: TrackerPosition) {
+		self.position.store(
+			((position.order as u64) << 32) | position.row as u64,
+			Ordering::SeqCst,
+		);
+	}
+}
+
+pub(crate) struct TrackerSound {
+	command_readers: CommandReaders,
+	player: Player,
+	volume: Parameter<Decibels>,
+	panning: Parameter<Panning>,
+	start_time: StartTime,
+	playback_state_manager: PlaybackStateManager,
+	shared: Arc<Shared>,
+}
+
+impl TrackerSound {
+	#[must_use]
+	pub fn new(data: TrackerSoundData, command_readers: CommandReaders) -> Self {
+		let TrackerSoundData { module, settings } = data;
+		let player = Player::new(module, settings.start_order, settings.looping);
+		let shared = Arc::new(Shared::new());
+		shared.set_position(player.position());
+		Self {
+			command_readers,
+			player,
+			volume: Parameter::new(settings.volume, Decibels::IDENTITY),
+			panning: Parameter::new(settings.panning, Panning::CENTER),
+			start_time: settings.start_time,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			shared,
+		}
+	}
+
+	#[must_use]
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+}
+
+impl Sound for TrackerSound {
+	fn on_start_processing(&mut self) {
+		read_commands_into_parameters!(self, volume, panning);
+		if let Some(order) = self.command_readers.jump_to_order.read() {
+			self.player.jump_to_order(order);
+			self.shared.set_position(self.player.position());
+		}
+		if let Some(order) = self.command_readers.queue_order.read() {
+			self.player.queue_order(order);
+		}
+		self.playback_state_manager.read_commands(
+			&mut self.command_readers.pause,
+			&mut self.command_readers.resume,
+			&mut self.command_readers.stop,
+			&self.shared.state,
+		);
+	}
+
+	fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		// update parameters
+		self.volume.update(dt * out.len() as f64, info);
+		self.panning.update(dt * out.len() as f64, info);
+		let changed_playback_state = self
+			.playback_state_manager
+			.update(dt * out.len() as f64, info);
+		if changed_playback_state {
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+
+		let will_never_start = self.start_time.update(dt * out.len() as f64, info);
+		if will_never_start {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+		if self.start_time != StartTime::Immediate
+			|| !self.playback_state_manager.playback_state().is_advancing()
+		{
+			out.fill(Frame::ZERO);
+			return;
+		}
+
+		let num_frames = out.len();
+		for (i, frame) in out.iter_mut().enumerate() {
+			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
+			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
+			let fade_volume = self
+				.playback_state_manager
+				.interpolated_fade_volume(time_in_chunk)
+				.as_amplitude();
+			let panning = self.panning.interpolated_value(time_in_chunk);
+			*frame = (self.player.next_frame(dt) * fade_volume * volume).panned(panning);
+		}
+		self.shared.set_position(self.player.position());
+
+		if self.player.finished() {
+			self.playback_state_manager.mark_as_stopped();
+			self.playback_state_manager.share_state(&self.shared.state);
+		}
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished(&self.shared.state)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.state.store(PlaybackState::Stopped);
+	}
+}
@@ -0,0 +1,835 @@
+use std::{f64::consts::TAU, sync::Arc};
+
+use crate::{
+	frame::Frame,
+	sound::tracker::{
+		module::{Cell, Instrument, Note, Sample, TrackerFormat, TrackerModule},
+		TrackerPosition,
+	},
+	Panning,
+};
+
+const ARPEGGIO: u8 = 0x00;
+const PORTAMENTO_UP: u8 = 0x01;
+const PORTAMENTO_DOWN: u8 = 0x02;
+const TONE_PORTAMENTO: u8 = 0x03;
+const VIBRATO: u8 = 0x04;
+const TONE_PORTAMENTO_AND_VOLUME_SLIDE: u8 = 0x05;
+const VIBRATO_AND_VOLUME_SLIDE: u8 = 0x06;
+const TREMOLO: u8 = 0x07;
+const SET_PANNING: u8 = 0x08;
+const SAMPLE_OFFSET: u8 = 0x09;
+const VOLUME_SLIDE: u8 = 0x0A;
+const POSITION_JUMP: u8 = 0x0B;
+const SET_VOLUME: u8 = 0x0C;
+const PATTERN_BREAK: u8 = 0x0D;
+const EXTENDED: u8 = 0x0E;
+const SET_SPEED: u8 = 0x0F;
+const SET_GLOBAL_VOLUME: u8 = 0x10;
+const GLOBAL_VOLUME_SLIDE: u8 = 0x11;
+const KEY_OFF: u8 = 0x14;
+const PANNING_SLIDE: u8 = 0x19;
+const EXTRA_FINE_PORTAMENTO: u8 = 0x21;
+
+const FINE_PORTAMENTO_UP: u8 = 0x1;
+const FINE_PORTAMENTO_DOWN: u8 = 0x2;
+const PATTERN_LOOP: u8 = 0x6;
+const RETRIGGER: u8 = 0x9;
+const FINE_VOLUME_SLIDE_UP: u8 = 0xA;
+const FINE_VOLUME_SLIDE_DOWN: u8 = 0xB;
+const NOTE_CUT: u8 = 0xC;
+const NOTE_DELAY: u8 = 0xD;
+const PATTERN_DELAY: u8 = 0xE;
+
+const MAX_VOLUME: i32 = 64;
+const MAX_FADEOUT_VOLUME: i32 = 65536;
+
+/// Plays the patterns of a tracker module and mixes its channels.
+pub(super) struct Player {
+	module: Arc<TrackerModule>,
+	channels: Vec<Channel>,
+	looping: bool,
+	position: TrackerPosition,
+	/// The order to go to after the current row, set by position jumps.
+	jump_order: Option<usize>,
+	/// The row to go to after the current row, set by pattern breaks.
+	jump_row: Option<usize>,
+	/// The row of the current pattern to go back to, set by pattern loops.
+	loop_row: Option<usize>,
+	/// The order to play after the current pattern, set by the handle.
+	queued_order: Option<usize>,
+	/// Which orders have been played since the song started or looped.
+	///
+	/// Position jumps to an order that's already been played mean the
+	/// song is looping.
+	visited_orders: Vec<bool>,
+	/// The number of ticks per row.
+	speed: u32,
+	/// The tempo in BPM, where one beat is 24 ticks.
+	tempo: u32,
+	/// The current tick of the row.
+	tick: u32,
+	/// The number of extra times to repeat the current row.
+	pattern_delay: u32,
+	global_volume: i32,
+	/// How much time is left in the current tick (in seconds).
+	tick_time_remaining: f64,
+	finished: bool,
+}
+
+impl Player {
+	#[must_use]
+	pub fn new(module: Arc<TrackerModule>, start_order: usize, looping: bool) -> Self {
+		let start_order = if start_order < module.orders.len() {
+			start_order
+		} else {
+			0
+		};
+		let mut visited_orders = vec![false; module.orders.len()];
+		visited_orders[start_order] = true;
+		Self {
+			channels: module
+				.channel_panning
+				.iter()
+				.map(|panning| Channel::new(*panning))
+				.collect(),
+			looping,
+			position: TrackerPosition {
+				order: start_order,
+				row: 0,
+			},
+			jump_order: None,
+			jump_row: None,
+			loop_row: None,
+			queued_order: None,
+			visited_orders,
+			speed: module.initial_speed,
+			tempo: module.initial_tempo,
+			tick: 0,
+			pattern_delay: 0,
+			global_volume: MAX_VOLUME,
+			tick_time_remaining: 0.0,
+			finished: false,
+			module,
+		}
+	}
+
+	#[must_use]
+	pub fn position(&self) -> TrackerPosition {
+		self.position
+	}
+
+	#[must_use]
+	pub fn finished(&self) -> bool {
+		self.finished
+	}
+
+	pub fn jump_to_order(&mut self, order: usize) {
+		if order >= self.module.orders.len() {
+			return;
+		}
+		self.position = TrackerPosition { order, row: 0 };
+		self.jump_order = None;
+		self.jump_row = None;
+		self.loop_row = None;
+		self.tick = 0;
+		self.pattern_delay = 0;
+		self.tick_time_remaining = 0.0;
+		self.visited_orders.fill(false);
+		self.visited_orders[order] = true;
+		for channel in &mut self.channels {
+			channel.reset_pattern_loop();
+		}
+	}
+
+	pub fn queue_order(&mut self, order: usize) {
+		if order < self.module.orders.len() {
+			self.queued_order = Some(order);
+		}
+	}
+
+	/// Returns the next frame of audio.
+	#[must_use]
+	pub fn next_frame(&mut self, dt: f64) -> Frame {
+		if self.tick_time_remaining <= 0.0 {
+			self.process_tick();
+			// the tempo is measured in beats per minute, and there's 24
+			// ticks in a beat
+			self.tick_time_remaining += 2.5 / self.tempo as f64;
+		}
+		self.tick_time_remaining -= dt;
+		if self.finished {
+			return Frame::ZERO;
+		}
+		let module = &*self.module;
+		let mut out = Frame::ZERO;
+		for channel in &mut self.channels {
+			out += channel.next_frame(module, dt);
+		}
+		// keep modules with lots of channels from getting too loud
+		out / (module.num_channels as f32).sqrt()
+	}
+
+	fn process_tick(&mut self) {
+		if self.tick >= self.speed * (1 + self.pattern_delay) {
+			self.tick = 0;
+			self.pattern_delay = 0;
+			self.advance_row();
+		}
+		if self.finished {
+			return;
+		}
+		if self.tick == 0 {
+			self.start_row();
+		} else {
+			let tick = self.tick % self.speed;
+			let module = &*self.module;
+			for channel in &mut self.channels {
+				channel.process_tick(module, tick);
+				if channel.effect == GLOBAL_VOLUME_SLIDE {
+					let param = channel.global_volume_slide;
+					self.global_volume = (self.global_volume + (param >> 4) as i32
+						- (param & 0x0F) as i32)
+						.clamp(0, MAX_VOLUME);
+				}
+			}
+		}
+		let module = &*self.module;
+		for channel in &mut self.channels {
+			channel.update_output(module, self.global_volume);
+		}
+		self.tick += 1;
+	}
+
+	fn start_row(&mut self) {
+		let module = &*self.module;
+		let pattern = &module.patterns[module.orders[self.position.order]];
+		for (channel_index, channel) in self.channels.iter_mut().enumerate() {
+			let cell = pattern.cells[self.position.row * module.num_channels + channel_index];
+			channel.start_row(module, cell);
+			let (x, y) = (cell.param >> 4, cell.param & 0x0F);
+			match cell.effect {
+				POSITION_JUMP => self.jump_order = Some(cell.param as usize),
+				// the row is stored as a decimal number
+				PATTERN_BREAK => self.jump_row = Some((x * 10 + y) as usize),
+				SET_SPEED if cell.param == 0 => {}
+				SET_SPEED if cell.param < 32 => self.speed = cell.param as u32,
+				SET_SPEED => self.tempo = cell.param as u32,
+				SET_GLOBAL_VOLUME => self.global_volume = (cell.param as i32).min(MAX_VOLUME),
+				EXTENDED if x == PATTERN_LOOP => {
+					if y == 0 {
+						channel.loop_start_row = self.position.row;
+					} else if channel.loop_count == 0 {
+						channel.loop_count = y;
+						self.loop_row = Some(channel.loop_start_row);
+					} else {
+						channel.loop_count -= 1;
+						if channel.loop_count > 0 {
+							self.loop_row = Some(channel.loop_start_row);
+						}
+					}
+				}
+				EXTENDED if x == PATTERN_DELAY && self.pattern_delay == 0 => {
+					self.pattern_delay = y as u32;
+				}
+				_ => {}
+			}
+		}
+	}
+
+	fn advance_row(&mut self) {
+		let mut order = self.position.order;
+		let mut row = self.position.row + 1;
+		let mut changed_order = false;
+		if let Some(loop_row) = self.loop_row.take() {
+			row = loop_row;
+		} else if self.jump_order.is_some() || self.jump_row.is_some() {
+			order = self.jump_order.unwrap_or(order + 1);
+			row = self.jump_row.unwrap_or(0);
+			changed_order = true;
+		} else if row >= self.module.patterns[self.module.orders[order]].num_rows {
+			order += 1;
+			row = 0;
+			changed_order = true;
+		}
+		self.jump_order = None;
+		self.jump_row = None;
+		if changed_order {
+			if let Some(queued_order) = self.queued_order.take() {
+				// a queued order is a deliberate change in the song,
+				// like a jump, so it doesn't end the song even if that
+				// order has already been played
+				order = queued_order;
+				row = 0;
+				self.visited_orders.fill(false);
+			}
+			let song_ended = order >= self.module.orders.len() || self.visited_orders[order];
+			if song_ended {
+				if !self.looping {
+					self.finished = true;
+					return;
+				}
+				if order >= self.module.orders.len() {
+					order = self.module.restart_position;
+				}
+				self.visited_orders.fill(false);
+			}
+			self.visited_orders[order] = true;
+			for channel in &mut self.channels {
+				channel.reset_pattern_loop();
+			}
+		}
+		if row >= self.module.patterns[self.module.orders[order]].num_rows {
+			row = 0;
+		}
+		self.position = TrackerPosition { order, row };
+	}
+}
+
+/// One of the voices of a tracker module.
+struct Channel {
+	instrument: Option<usize>,
+	sample: Option<usize>,
+	playing: bool,
+	/// The position in the sample (in frames).
+	position: f64,
+	/// Whether a ping-pong loop is currently playing backward.
+	reverse: bool,
+	/// The note being played, including the sample's relative note
+	/// and finetune (in semitones).
+	note: f64,
+	/// The pitch of the note. Lower periods are higher pitches.
+	period: f64,
+	/// The period tone portamento slides toward.
+	target_period: f64,
+	/// The volume set by the pattern (from `0` to `64`).
+	volume: i32,
+	/// The panning set by the pattern (from `0` to `255`).
+	panning: i32,
+	/// Whether the note hasn't been released yet.
+	key_on: bool,
+	/// The volume of the fadeout after the note is released
+	/// (from `0` to `65536`).
+	fadeout_volume: i32,
+	volume_envelope_tick: u16,
+	panning_envelope_tick: u16,
+
+	effect: u8,
+	param: u8,
+	volume_command: u8,
+	/// A cell whose note is delayed by a note delay effect.
+	delayed_cell: Option<Cell>,
+	/// How far vibrato is moving the period on the current tick.
+	vibrato_offset: f64,
+	/// How far tremolo is moving the volume on the current tick.
+	tremolo_offset: i32,
+	/// How many semitones arpeggio is raising the note by on
+	/// the current tick.
+	arpeggio_offset: u8,
+
+	// effect parameters that are remembered when an effect
+	// is used with a parameter of `0`
+	portamento_up_speed: u8,
+	portamento_down_speed: u8,
+	tone_portamento_speed: u8,
+	volume_slide: u8,
+	fine_portamento_up_speed: u8,
+	fine_portamento_down_speed: u8,
+	extra_fine_portamento_up_speed: u8,
+	extra_fine_portamento_down_speed: u8,
+	fine_volume_slide_up: u8,
+	fine_volume_slide_down: u8,
+	global_volume_slide: u8,
+	panning_slide: u8,
+	sample_offset: u8,
+	vibrato_speed: u8,
+	vibrato_depth: u8,
+	vibrato_position: u8,
+	tremolo_speed: u8,
+	tremolo_depth: u8,
+	tremolo_position: u8,
+
+	loop_start_row: usize,
+	loop_count: u8,
+
+	// the values used for mixing, which are updated every tick
+	frequency: f64,
+	amplitude: f32,
+	output_panning: Panning,
+}
+
+impl Channel {
+	#[must_use]
+	fn new(panning: u8) -> Self {
+		Self {
+			instrument: None,
+			sample: None,
+			playing: false,
+			position: 0.0,
+			reverse: false,
+			note: 0.0,
+			period: 0.0,
+			target_period: 0.0,
+			volume: 0,
+			panning: panning as i32,
+			key_on: false,
+			fadeout_volume: MAX_FADEOUT_VOLUME,
+			volume_envelope_tick: 0,
+			panning_envelope_tick: 0,
+			effect: 0,
+			param: 0,
+			volume_command: 0,
+			delayed_cell: None,
+			vibrato_offset: 0.0,
+			tremolo_offset: 0,
+			arpeggio_offset: 0,
+			portamento_up_speed: 0,
+			portamento_down_speed: 0,
+			tone_portamento_speed: 0,
+			volume_slide: 0,
+			fine_portamento_up_speed: 0,
+			fine_portamento_down_speed: 0,
+			extra_fine_portamento_up_speed: 0,
+			extra_fine_portamento_down_speed: 0,
+			fine_volume_slide_up: 0,
+			fine_volume_slide_down: 0,
+			global_volume_slide: 0,
+			panning_slide: 0,
+			sample_offset: 0,
+			vibrato_speed: 0,
+			vibrato_depth: 0,
+			vibrato_position: 0,
+			tremolo_speed: 0,
+			tremolo_depth: 0,
+			tremolo_position: 0,
+			loop_start_row: 0,
+			loop_count: 0,
+			frequency: 0.0,
+			amplitude: 0.0,
+			output_panning: Panning::CENTER,
+		}
+	}
+
+	fn reset_pattern_loop(&mut self) {
+		self.loop_start_row = 0;
+		self.loop_count = 0;
+	}
+
+	#[must_use]
+	fn current_instrument<'a>(&self, module: &'a TrackerModule) -> Option<&'a Instrument> {
+		self.instrument
+			.and_then(|instrument| module.instruments.get(instrument))
+	}
+
+	#[must_use]
+	fn current_sample<'a>(&self, module: &'a TrackerModule) -> Option<&'a Sample> {
+		self.current_instrument(module)
+			.zip(self.sample)
+			.and_then(|(instrument, sample)| instrument.samples.get(sample))
+	}
+
+	/// Processes the first tick of a row.
+	fn start_row(&mut self, module: &TrackerModule, cell: Cell) {
+		self.effect = cell.effect;
+		self.param = cell.param;
+		self.volume_command = cell.volume;
+		self.vibrato_offset = 0.0;
+		self.tremolo_offset = 0;
+		self.arpeggio_offset = 0;
+		self.delayed_cell = None;
+		if cell.effect == EXTENDED && cell.param >> 4 == NOTE_DELAY && cell.param & 0x0F != 0 {
+			self.delayed_cell = Some(cell);
+			return;
+		}
+		self.trigger(module, cell);
+	}
+
+	/// Starts the note and processes the first tick of the effects
+	/// in a cell.
+	fn trigger(&mut self, module: &TrackerModule, cell: Cell) {
+		let uses_memory = module.format == TrackerFormat::Xm;
+		let (x, y) = (cell.param >> 4, cell.param & 0x0F);
+		if cell.effect == SAMPLE_OFFSET {
+			recall(&mut self.sample_offset, cell.param, true);
+		}
+		if cell.instrument != 0 {
+			self.instrument = Some(cell.instrument as usize - 1);
+		}
+		let tone_portamento = cell.effect == TONE_PORTAMENTO
+			|| cell.effect == TONE_PORTAMENTO_AND_VOLUME_SLIDE
+			|| cell.volume >> 4 == 0xF;
+		match cell.note {
+			Note::On(note) if tone_portamento && self.playing => {
+				if let Some(sample) = self.current_sample(module) {
+					self.target_period = period(module, note_with_tuning(note, sample));
+				}
+			}
+			Note::On(note) => self.start_note(module, note, cell.effect == SAMPLE_OFFSET),
+			Note::Off => self.release(module),
+			Note::None => {}
+		}
+		if cell.instrument != 0 {
+			if let Some(sample) = self.current_sample(module) {
+				self.volume = sample.volume as i32;
+				if module.format == TrackerFormat::Xm {
+					self.panning = sample.panning as i32;
+				}
+				if cell.note != Note::Off {
+					self.key_on = true;
+					self.fadeout_volume = MAX_FADEOUT_VOLUME;
+					self.volume_envelope_tick = 0;
+					self.panning_envelope_tick = 0;
+				}
+			}
+		}
+
+		let (command, value) = (cell.volume >> 4, (cell.volume & 0x0F));
+		match command {
+			0x1..=0x4 => self.volume = (cell.volume - 0x10) as i32,
+			0x5 => self.volume = MAX_VOLUME,
+			0x8 => self.slide_volume(-(value as i32)),
+			0x9 => self.slide_volume(value as i32),
+			0xA if value != 0 => self.vibrato_speed = value,
+			0xB if value != 0 => self.vibrato_depth = value,
+			0xC => self.panning = value as i32 * 17,
+			0xF if value != 0 => self.tone_portamento_speed = value << 4,
+			_ => {}
+		}
+
+		match cell.effect {
+			PORTAMENTO_UP => {
+				recall(&mut self.portamento_up_speed, cell.param, uses_memory);
+			}
+			PORTAMENTO_DOWN => {
+				recall(&mut self.portamento_down_speed, cell.param, uses_memory);
+			}
+			TONE_PORTAMENTO => {
+				recall(&mut self.tone_portamento_speed, cell.param, true);
+			}
+			VIBRATO => {
+				recall(&mut self.vibrato_speed, x, true);
+				recall(&mut self.vibrato_depth, y, true);
+			}
+			TREMOLO => {
+				recall(&mut self.tremolo_speed, x, true);
+				recall(&mut self.tremolo_depth, y, true);
+			}
+			TONE_PORTAMENTO_AND_VOLUME_SLIDE | VIBRATO_AND_VOLUME_SLIDE | VOLUME_SLIDE => {
+				recall(&mut self.volume_slide, cell.param, uses_memory);
+			}
+			SET_PANNING => self.panning = cell.param as i32,
+			SET_VOLUME => self.volume = (cell.param as i32).min(MAX_VOLUME),
+			EXTENDED => match x {
+				FINE_PORTAMENTO_UP => {
+					let speed = recall(&mut self.fine_portamento_up_speed, y, uses_memory);
+					self.slide_period(-(speed as f64) * 4.0);
+				}
+				FINE_PORTAMENTO_DOWN => {
+					let speed = recall(&mut self.fine_portamento_down_speed, y, uses_memory);
+					self.slide_period(speed as f64 * 4.0);
+				}
+				FINE_VOLUME_SLIDE_UP => {
+					let amount = recall(&mut self.fine_volume_slide_up, y, uses_memory);
+					self.slide_volume(amount as i32);
+				}
+				FINE_VOLUME_SLIDE_DOWN => {
+					let amount = recall(&mut self.fine_volume_slide_down, y, uses_memory);
+					self.slide_volume(-(amount as i32));
+				}
+				NOTE_CUT if y == 0 => self.volume = 0,
+				_ => {}
+			},
+			GLOBAL_VOLUME_SLIDE => {
+				recall(&mut self.global_volume_slide, cell.param, true);
+			}
+			KEY_OFF if cell.param == 0 => self.release(module),
+			PANNING_SLIDE => {
+				recall(&mut self.panning_slide, cell.param, true);
+			}
+			EXTRA_FINE_PORTAMENTO => match x {
+				1 => {
+					let speed = recall(&mut self.extra_fine_portamento_up_speed, y, true);
+					self.slide_period(-(speed as f64));
+				}
+				2 => {
+					let speed = recall(&mut self.extra_fine_portamento_down_speed, y, true);
+					self.slide_period(speed as f64);
+				}
+				_ => {}
+			},
+			_ => {}
+		}
+	}
+
+	fn start_note(&mut self, module: &TrackerModule, note: u8, use_sample_offset: bool) {
+		let Some(instrument) = self.current_instrument(module) else {
+			return;
+		};
+		let sample_index = instrument.sample_map[note.min(95) as usize] as usize;
+		let Some(sample) = instrument.samples.get(sample_index) else {
+			self.playing = false;
+			return;
+		};
+		self.sample = Some(sample_index);
+		self.note = note_with_tuning(note, sample);
+		self.period = period(module, self.note);
+		self.target_period = self.period;
+		self.position = if use_sample_offset {
+			self.sample_offset as f64 * 256.0
+		} else {
+			0.0
+		};
+		self.reverse = false;
+		self.playing = self.position < sample.frames.len() as f64;
+		self.key_on = true;
+		self.fadeout_volume = MAX_FADEOUT_VOLUME;
+		self.volume_envelope_tick = 0;
+		self.panning_envelope_tick = 0;
+		self.vibrato_position = 0;
+		self.tremolo_position = 0;
+	}
+
+	fn release(&mut self, module: &TrackerModule) {
+		self.key_on = false;
+		// without a volume envelope, there's nothing to fade out
+		if self
+			.current_instrument(module)
+			.is_some_and(|instrument| instrument.volume_envelope.is_none())
+		{
+			self.volume = 0;
+		}
+	}
+
+	/// Processes every tick of a row except the first one.
+	fn process_tick(&mut self, module: &TrackerModule, tick: u32) {
+		self.vibrato_offset = 0.0;
+		self.tremolo_offset = 0;
+		self.arpeggio_offset = 0;
+		let (x, y) = (self.param >> 4, self.param & 0x0F);
+
+		if let Some(cell) = self.delayed_cell {
+			if tick == y as u32 {
+				self.delayed_cell = None;
+				self.trigger(module, cell);
+			}
+		}
+
+		let value = self.volume_command & 0x0F;
+		match self.volume_command >> 4 {
+			0x6 => self.slide_volume(-(value as i32)),
+			0x7 => self.slide_volume(value as i32),
+			0xB => self.vibrato(),
+			0xD => self.panning = (self.panning - value as i32).max(0),
+			0xE => self.panning = (self.panning + value as i32).min(255),
+			0xF => self.tone_portamento(),
+			_ => {}
+		}
+
+		match self.effect {
+			ARPEGGIO if self.param != 0 => {
+				self.arpeggio_offset = match tick % 3 {
+					0 => 0,
+					1 => x,
+					_ => y,
+				};
+			}
+			PORTAMENTO_UP => self.slide_period(-(self.portamento_up_speed as f64) * 4.0),
+			PORTAMENTO_DOWN => self.slide_period(self.portamento_down_speed as f64 * 4.0),
+			TONE_PORTAMENTO => self.tone_portamento(),
+			VIBRATO => self.vibrato(),
+			TONE_PORTAMENTO_AND_VOLUME_SLIDE => {
+				self.tone_portamento();
+				self.volume_slide();
+			}
+			VIBRATO_AND_VOLUME_SLIDE => {
+				self.vibrato();
+				self.volume_slide();
+			}
+			TREMOLO => {
+				let amount = (TAU * self.tremolo_position as f64 / 64.0).sin();
+				self.tremolo_offset = (amount * self.tremolo_depth as f64 * 4.0) as i32;
+				self.tremolo_position = (self.tremolo_position + self.tremolo_speed) % 64;
+			}
+			VOLUME_SLIDE => self.volume_slide(),
+			EXTENDED => match x {
+				RETRIGGER if y != 0 && tick.is_multiple_of(y as u32) => {
+					self.position = 0.0;
+					self.reverse = false;
+					self.playing = self.current_sample(module).is_some();
+				}
+				NOTE_CUT if tick == y as u32 => self.volume = 0,
+				_ => {}
+			},
+			KEY_OFF if tick == self.param as u32 => self.release(module),
+			PANNING_SLIDE => {
+				let (right, left) = (self.panning_slide >> 4, self.panning_slide & 0x0F);
+				self.panning = if right != 0 {
+					(self.panning + right as i32).min(255)
+				} else {
+					(self.panning - left as i32).max(0)
+				};
+			}
+			_ => {}
+		}
+	}
+
+	fn slide_volume(&mut self, amount: i32) {
+		self.volume = (self.volume + amount).clamp(0, MAX_VOLUME);
+	}
+
+	fn volume_slide(&mut self) {
+		let (up, down) = (self.volume_slide >> 4, self.volume_slide & 0x0F);
+		if up != 0 {
+			self.slide_volume(up as i32);
+		} else {
+			self.slide_volume(-(down as i32));
+		}
+	}
+
+	fn slide_period(&mut self, amount: f64) {
+		if self.period > 0.0 {
+			self.period = (self.period + amount).clamp(1.0, 32000.0);
+		}
+	}
+
+	fn tone_portamento(&mut self) {
+		let speed = self.tone_portamento_speed as f64 * 4.0;
+		if self.period < self.target_period {
+			self.period = (self.period + speed).min(self.target_period);
+		} else {
+			self.period = (self.period - speed).max(self.target_period);
+		}
+	}
+
+	fn vibrato(&mut self) {
+		let amount = (TAU * self.vibrato_position as f64 / 64.0).sin();
+		self.vibrato_offset = amount * self.vibrato_depth as f64 * 8.0;
+		self.vibrato_position = (self.vibrato_position + self.vibrato_speed) % 64;
+	}
+
+	/// Updates the envelopes and the values used for mixing.
+	fn update_output(&mut self, module: &TrackerModule, global_volume: i32) {
+		let Some(instrument) = self.current_instrument(module) else {
+			return;
+		};
+		if !self.playing || self.period <= 0.0 {
+			return;
+		}
+		let mut volume = (self.volume + self.tremolo_offset).clamp(0, MAX_VOLUME) as f32 / 64.0;
+		let mut panning = self.panning as f32;
+		if let Some(envelope) = &instrument.volume_envelope {
+			volume *= envelope.value(self.volume_envelope_tick);
+			self.volume_envelope_tick = envelope.next_tick(self.volume_envelope_tick, self.key_on);
+			if !self.key_on {
+				self.fadeout_volume = (self.fadeout_volume - instrument.fadeout as i32).max(0);
+			}
+		}
+		if let Some(envelope) = &instrument.panning_envelope {
+			let amount = envelope.value(self.panning_envelope_tick) * 2.0 - 1.0;
+			panning += amount * (128.0 - (panning - 128.0).abs());
+			self.panning_envelope_tick =
+				envelope.next_tick(self.panning_envelope_tick, self.key_on);
+		}
+		self.amplitude = volume
+			* (self.fadeout_volume as f32 / MAX_FADEOUT_VOLUME as f32)
+			* (global_volume as f32 / MAX_VOLUME as f32);
+		self.output_panning = Panning((panning / 255.0).clamp(0.0, 1.0) * 2.0 - 1.0);
+		let period = (self.period + self.vibrato_offset).max(1.0);
+		self.frequency =
+			frequency(module, period) * 2.0f64.powf(self.arpeggio_offset as f64 / 12.0);
+	}
+
+	#[must_use]
+	fn next_frame(&mut self, module: &TrackerModule, dt: f64) -> Frame {
+		if !self.playing || self.amplitude == 0.0 {
+			return Frame::ZERO;
+		}
+		let Some(sample) = self.current_sample(module) else {
+			return Frame::ZERO;
+		};
+		let index = self.position as usize;
+		let Some(current) = sample.frames.get(index).copied() else {
+			self.playing = false;
+			return Frame::ZERO;
+		};
+		let next_index = match sample.loop_region {
+			Some((start, end)) if !sample.ping_pong && index + 1 >= end => start,
+			_ => index + 1,
+		};
+		let next = sample.frames.get(next_index).copied().unwrap_or(current);
+		let fraction = self.position.fract() as f32;
+		let value = current + (next - current) * fraction;
+
+		let step = self.frequency * dt;
+		if self.reverse {
+			self.position -= step;
+		} else {
+			self.position += step;
+		}
+		match sample.loop_region {
+			None => {
+				if self.position >= sample.frames.len() as f64 {
+					self.playing = false;
+				}
+			}
+			Some((start, end)) => {
+				let (start, end) = (start as f64, end as f64);
+				if sample.ping_pong {
+					loop {
+						if !self.reverse && self.position >= end {
+							self.position = (2.0 * end - self.position).max(start);
+							self.reverse = true;
+						} else if self.reverse && self.position < start {
+							self.position = (2.0 * start - self.position).min(end);
+							self.reverse = false;
+						} else {
+							break;
+						}
+					}
+				} else if self.position >= end {
+					self.position = start + (self.position - start) % (end - start);
+				}
+			}
+		}
+
+		Frame::from_mono(value * self.amplitude).panned(self.output_panning)
+	}
+}
+
+/// Stores a parameter so it can be used on later ticks, or returns
+/// the stored parameter if `param` is `0` and the format remembers
+/// parameters.
+fn recall(memory: &mut u8, param: u8, uses_memory: bool) -> u8 {
+	if param != 0 || !uses_memory {
+		*memory = param;
+	}
+	*memory
+}
+
+/// Returns the note with a sample's relative note and finetune
+/// applied (in semitones).
+#[must_use]
+fn note_with_tuning(note: u8, sample: &Sample) -> f64 {
+	note as f64 + sample.relative_note as f64 + sample.finetune as f64 / 128.0
+}
+
+#[must_use]
+fn period(module: &TrackerModule, note: f64) -> f64 {
+	if module.linear_frequencies {
+		7680.0 - note * 64.0
+	} else {
+		1712.0 * 2.0f64.powf((48.0 - note) / 12.0)
+	}
+}
+
+/// Returns the playback rate of a sample (in frames per second).
+///
+/// C-4 plays at 8363Hz.
+#[must_use]
+fn frequency(module: &TrackerModule, period: f64) -> f64 {
+	if module.linear_frequencies {
+		8363.0 * 2.0f64.powf((4608.0 - period) / 768.0)
+	} else {
+		8363.0 * 1712.0 / period
+	}
+}
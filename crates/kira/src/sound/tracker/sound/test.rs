@@ -0,0 +1,206 @@
+use crate::{
+	frame::Frame,
+	info::MockInfoBuilder,
+	sound::{
+		tracker::{
+			TrackerModule, TrackerPosition, TrackerSoundData, TrackerSoundHandle,
+			TrackerSoundSettings,
+		},
+		PlaybackState, Sound,
+	},
+	test_helpers::{mod_file, ModCell, ModSample},
+};
+
+use super::TrackerSound;
+
+const DT: f64 = 0.001;
+/// The number of frames in a row at the default speed and tempo.
+const ROW_FRAMES: usize = 120;
+/// The period of C-4.
+const C4: u16 = 428;
+/// The period of C-5.
+const C5: u16 = 214;
+
+/// Returns a cell on the first channel that plays the first sample.
+fn cell(row: usize, period: u16, effect: u8, param: u8) -> ModCell {
+	ModCell {
+		row,
+		channel: 0,
+		sample: if period == 0 { 0 } else { 1 },
+		period,
+		effect,
+		param,
+	}
+}
+
+/// Starts playing a module with a looping sample that always
+/// has a value of `0.5`.
+fn play(
+	orders: &[u8],
+	patterns: &[&[ModCell]],
+	settings: TrackerSoundSettings,
+) -> (TrackerSound, TrackerSoundHandle) {
+	let module = TrackerModule::from_bytes(&mod_file(
+		&[ModSample {
+			frames: &[64; 32],
+			loop_region: Some((0, 32)),
+		}],
+		orders,
+		patterns,
+	))
+	.unwrap();
+	TrackerSoundData::new(module)
+		.with_settings(settings)
+		.split()
+}
+
+fn render(sound: &mut TrackerSound, num_frames: usize, dt: f64) -> Vec<Frame> {
+	let info = MockInfoBuilder::new().build();
+	(0..num_frames)
+		.map(|_| {
+			sound.on_start_processing();
+			sound.process_one(dt, &info)
+		})
+		.collect()
+}
+
+/// Tests that notes play until the pattern changes their volume.
+#[test]
+fn plays_notes() {
+	let (mut sound, _handle) = play(
+		&[0],
+		&[&[cell(0, C4, 0, 0), cell(2, 0, 0x0C, 0)]],
+		TrackerSoundSettings::new(),
+	);
+	let frames = render(&mut sound, ROW_FRAMES * 3, DT);
+	assert!(frames[..ROW_FRAMES * 2 - 5]
+		.iter()
+		.all(|frame| frame.left > 0.0 && frame.right > 0.0));
+	// the first channel is panned to the left
+	assert!(frames[0].left > frames[0].right);
+	assert!(frames[ROW_FRAMES * 2 + 5..]
+		.iter()
+		.all(|frame| *frame == Frame::ZERO));
+}
+
+/// Tests that notes play the sample at the right speed.
+#[test]
+fn note_pitch() {
+	let render_note = |period: u16| {
+		let ramp = (0..100).map(|i| i as i8).collect::<Vec<_>>();
+		let module = TrackerModule::from_bytes(&mod_file(
+			&[ModSample {
+				frames: &ramp,
+				loop_region: None,
+			}],
+			&[0],
+			&[&[cell(0, period, 0, 0)]],
+		))
+		.unwrap();
+		let (mut sound, _handle) = TrackerSoundData::new(module).split();
+		// at 8363Hz, C-4 plays one frame of the sample per frame
+		render(&mut sound, 40, 1.0 / 8363.0)
+	};
+	let c4 = render_note(C4);
+	let c5 = render_note(C5);
+	for i in 1..40 {
+		assert!((c4[i].left - c4[1].left * i as f32).abs() < 0.0001);
+		assert!((c5[i].left - c4[i].left * 2.0).abs() < 0.0001);
+	}
+}
+
+/// Tests that the handle reports the current position, and that
+/// pattern breaks skip to the next order.
+#[test]
+fn position_and_pattern_breaks() {
+	let (mut sound, handle) = play(
+		&[0, 1],
+		&[&[cell(1, 0, 0x0D, 0x12)], &[]],
+		TrackerSoundSettings::new(),
+	);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 0 });
+	render(&mut sound, ROW_FRAMES / 2, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 0 });
+	render(&mut sound, ROW_FRAMES, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 1 });
+	// the pattern break goes to row 12 of the next order
+	render(&mut sound, ROW_FRAMES, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 1, row: 12 });
+}
+
+/// Tests that the sound finishes at the end of the song, or starts
+/// over if looping is enabled.
+#[test]
+fn song_end() {
+	// with a speed of 1, each row lasts 20 frames
+	let patterns: &[&[ModCell]] = &[&[cell(0, C4, 0x0F, 1)]];
+	let (mut sound, handle) = play(&[0], patterns, TrackerSoundSettings::new());
+	render(&mut sound, 64 * 20 - 10, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 63 });
+	assert!(!sound.finished());
+	render(&mut sound, 20, DT);
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+	assert!(sound.finished());
+
+	let (mut sound, handle) = play(&[0], patterns, TrackerSoundSettings::new().looping(true));
+	render(&mut sound, 64 * 20 + 10, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 0 });
+	assert!(!sound.finished());
+}
+
+/// Tests that a position jump to an order that's already been played
+/// counts as the end of the song.
+#[test]
+fn position_jumps() {
+	let patterns: &[&[ModCell]] = &[&[cell(0, C4, 0x0F, 1)], &[cell(0, 0, 0x0B, 0)]];
+	let (mut sound, handle) = play(&[0, 1], patterns, TrackerSoundSettings::new());
+	render(&mut sound, 64 * 20 + 10, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 1, row: 0 });
+	render(&mut sound, 20, DT);
+	assert!(sound.finished());
+
+	let (mut sound, handle) = play(&[0, 1], patterns, TrackerSoundSettings::new().looping(true));
+	render(&mut sound, 65 * 20 + 10, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 0 });
+	assert!(!sound.finished());
+}
+
+/// Tests that the handle can jump to an order right away or queue
+/// an order to play after the current pattern.
+#[test]
+fn jump_to_order_and_queue_order() {
+	let patterns: &[&[ModCell]] = &[&[cell(0, C4, 0x0F, 1)]];
+	let (mut sound, mut handle) = play(&[0, 0, 0], patterns, TrackerSoundSettings::new());
+	render(&mut sound, 30, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 1 });
+	handle.jump_to_order(2);
+	render(&mut sound, 10, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 2, row: 0 });
+	// orders that don't exist are ignored
+	handle.jump_to_order(3);
+	handle.queue_order(1);
+	render(&mut sound, 20, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 2, row: 1 });
+	// the queued order plays instead of ending the song
+	render(&mut sound, 63 * 20, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 1, row: 0 });
+	assert!(!sound.finished());
+}
+
+/// Tests that queueing an order that's already been played doesn't
+/// end the song.
+#[test]
+fn queue_played_order() {
+	let patterns: &[&[ModCell]] = &[&[cell(0, C4, 0x0F, 1)]];
+	let (mut sound, mut handle) = play(&[0, 0], patterns, TrackerSoundSettings::new());
+	render(&mut sound, 30, DT);
+	handle.queue_order(0);
+	render(&mut sound, 63 * 20, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 0, row: 0 });
+	assert!(!sound.finished());
+	// the song continues normally after the queued order
+	render(&mut sound, 64 * 20, DT);
+	assert_eq!(handle.position(), TrackerPosition { order: 1, row: 0 });
+	render(&mut sound, 64 * 20, DT);
+	assert!(sound.finished());
+}
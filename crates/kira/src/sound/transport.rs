@@ -55,28 +55,37 @@ impl Transport {
 		});
 	}
 
-	pub fn increment_position(&mut self, num_frames: usize) {
+	/// Moves the position forward by one frame. Returns `true` if
+	/// the position jumped back to the start of the loop region.
+	pub fn increment_position(&mut self, num_frames: usize) -> bool {
 		if !self.playing {
-			return;
+			return false;
 		}
 		self.position += 1;
+		let mut looped = false;
 		if let Some((loop_start, loop_end)) = self.loop_region {
 			while self.position >= loop_end {
 				self.position -= loop_end - loop_start;
+				looped = true;
 			}
 		}
 		if self.position >= num_frames {
 			self.playing = false;
 		}
+		looped
 	}
 
-	pub fn decrement_position(&mut self) {
+	/// Moves the position backward by one frame. Returns `true` if
+	/// the position jumped forward to the end of the loop region.
+	pub fn decrement_position(&mut self) -> bool {
 		if !self.playing {
-			return;
+			return false;
 		}
+		let mut looped = false;
 		if let Some((loop_start, loop_end)) = self.loop_region {
 			while self.position <= loop_start {
 				self.position += loop_end - loop_start;
+				looped = true;
 			}
 		}
 		if self.position == 0 {
@@ -84,6 +93,7 @@ impl Transport {
 		} else {
 			self.position -= 1;
 		}
+		looped
 	}
 
 	pub fn seek_to(&mut self, mut position: usize, num_frames: usize) {
@@ -71,6 +71,21 @@ fn loops_when_playing_backward() {
 	}
 }
 
+#[test]
+fn reports_loops() {
+	let mut transport = Transport {
+		position: 3,
+		loop_region: Some((2, 5)),
+		playing: true,
+	};
+	assert!(!transport.increment_position(10));
+	assert!(transport.increment_position(10));
+	assert_eq!(transport.position, 2);
+	assert!(transport.decrement_position());
+	assert_eq!(transport.position, 4);
+	assert!(!transport.decrement_position());
+}
+
 #[test]
 fn loop_wrapping() {
 	let mut transport = Transport {
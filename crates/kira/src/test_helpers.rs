@@ -15,3 +15,84 @@ pub fn expect_frame_soon(expected_frame: Frame, sound: &mut dyn Sound) {
 		expected_frame, NUM_SAMPLES_TO_WAIT, collected_samples
 	);
 }
+
+/// Builds a standard MIDI file with the given tracks.
+pub fn midi_file(ticks_per_quarter_note: u16, tracks: &[&[u8]]) -> Vec<u8> {
+	let mut bytes = vec![];
+	bytes.extend_from_slice(b"MThd");
+	bytes.extend_from_slice(&6u32.to_be_bytes());
+	bytes.extend_from_slice(&1u16.to_be_bytes());
+	bytes.extend_from_slice(&(tracks.len() as u16).to_be_bytes());
+	bytes.extend_from_slice(&ticks_per_quarter_note.to_be_bytes());
+	for track in tracks {
+		bytes.extend_from_slice(b"MTrk");
+		bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+		bytes.extend_from_slice(track);
+	}
+	bytes
+}
+
+/// A note in a pattern of a MOD file built by [`mod_file`].
+pub struct ModCell {
+	pub row: usize,
+	pub channel: usize,
+	/// The number of the sample to play (starting from `1`), or `0`.
+	pub sample: u8,
+	/// The Amiga period of the note, or `0` for no note.
+	pub period: u16,
+	pub effect: u8,
+	pub param: u8,
+}
+
+/// A sample of a MOD file built by [`mod_file`].
+#[derive(Clone, Copy)]
+pub struct ModSample<'a> {
+	/// The frames of the sample. There must be an even number of frames.
+	pub frames: &'a [i8],
+	/// The start and end of the loop (in frames).
+	pub loop_region: Option<(usize, usize)>,
+}
+
+/// Builds a 4-channel MOD file.
+pub fn mod_file(samples: &[ModSample], orders: &[u8], patterns: &[&[ModCell]]) -> Vec<u8> {
+	let mut bytes = vec![];
+	bytes.extend_from_slice(b"test");
+	bytes.resize(20, 0);
+	for i in 0..31 {
+		let ModSample {
+			frames,
+			loop_region,
+		} = samples.get(i).copied().unwrap_or(ModSample {
+			frames: &[],
+			loop_region: None,
+		});
+		bytes.extend_from_slice(&[0; 22]);
+		bytes.extend_from_slice(&((frames.len() / 2) as u16).to_be_bytes());
+		bytes.push(0);
+		bytes.push(64);
+		let (loop_start, loop_end) = loop_region.unwrap_or((0, 2));
+		bytes.extend_from_slice(&((loop_start / 2) as u16).to_be_bytes());
+		bytes.extend_from_slice(&(((loop_end - loop_start) / 2) as u16).to_be_bytes());
+	}
+	bytes.push(orders.len() as u8);
+	bytes.push(127);
+	let mut order_table = orders.to_vec();
+	order_table.resize(128, 0);
+	bytes.extend_from_slice(&order_table);
+	bytes.extend_from_slice(b"M.K.");
+	for pattern in patterns {
+		let mut pattern_bytes = vec![0; 64 * 4 * 4];
+		for cell in *pattern {
+			let index = (cell.row * 4 + cell.channel) * 4;
+			pattern_bytes[index] = (cell.sample & 0xF0) | (cell.period >> 8) as u8;
+			pattern_bytes[index + 1] = cell.period as u8;
+			pattern_bytes[index + 2] = (cell.sample << 4) | cell.effect;
+			pattern_bytes[index + 3] = cell.param;
+		}
+		bytes.extend_from_slice(&pattern_bytes);
+	}
+	for sample in samples {
+		bytes.extend(sample.frames.iter().map(|frame| *frame as u8));
+	}
+	bytes
+}
@@ -51,6 +51,17 @@ setting will affect all sounds being played on the track as well as all child tr
 You can pause all sounds (and child tracks) of a track using [`TrackHandle::pause`]
 and resume them using [`TrackHandle::resume`] or [`TrackHandle::resume_at`].
 
+To limit how many sounds can play on a track at the same time, use
+[`TrackBuilder::voice_limit`]. When the limit is reached, the track can stop
+the oldest sound, the quietest sound, or the sound with the lowest priority to
+make room for the new sound, or it can refuse to play the new sound.
+
+Sounds that can't be heard can be virtualized: they keep their place in time
+without outputting any audio, which is cheaper than playing them normally.
+Use [`VoiceLimit::virtualize`] to virtualize sounds over the voice limit instead
+of stopping them, and [`TrackBuilder::virtualize_below`] to virtualize every
+sound on a track while the track is too quiet to hear.
+
 ## Effects
 
 You can add effects to the track when creating it using
@@ -265,15 +276,27 @@ manager.add_spatial_sub_track(
 )?;
 # Result::<(), Box<dyn Error>>::Ok(())
 ```
+
+### Surround sound
+
+If the [`AudioManagerSettings::channel_layout`](crate::AudioManagerSettings::channel_layout)
+is a surround layout, spatial tracks are panned across all of the speakers in the layout
+(ignoring elevation) instead of just left and right. Since effects only process stereo
+audio, parent tracks and the main track downmix surround audio to stereo before applying
+their effects and then pan the processed audio back across the speakers. The stereo
+version of the track's output is still sent to send tracks.
 */
 
 mod main;
 mod send;
 mod sub;
+mod surround;
+mod voice;
 
 pub use main::*;
 pub use send::*;
 pub use sub::*;
+pub use voice::*;
 
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
@@ -4,22 +4,29 @@ mod handle;
 pub use builder::*;
 pub use handle::*;
 
+use std::time::Instant;
+
 use crate::{
 	backend::resources::ResourceStorage,
 	command::{CommandReader, ValueChangeCommand},
 	effect::Effect,
 	info::Info,
+	profiling::TrackProfiler,
 	sound::Sound,
-	Decibels, Frame, Parameter,
+	ChannelLayout, Decibels, Frame, Parameter,
 };
 
+use super::surround::SurroundEffects;
+
 pub(crate) struct MainTrack {
 	volume: Parameter<Decibels>,
 	set_volume_command_reader: CommandReader<ValueChangeCommand<Decibels>>,
 	sounds: ResourceStorage<Box<dyn Sound>>,
 	effects: Vec<Box<dyn Effect>>,
 	temp_buffer: Vec<Frame>,
+	surround_effects: SurroundEffects,
 	internal_buffer_size: usize,
+	profiler: Option<TrackProfiler>,
 }
 
 impl MainTrack {
@@ -35,6 +42,11 @@ impl MainTrack {
 		}
 	}
 
+	#[must_use]
+	pub fn has_sounds(&self) -> bool {
+		!self.sounds.is_empty()
+	}
+
 	pub fn on_start_processing(&mut self) {
 		self.volume
 			.read_command(&mut self.set_volume_command_reader);
@@ -47,8 +59,25 @@ impl MainTrack {
 		}
 	}
 
-	pub fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+	/// Returns the current time if profiling is enabled.
+	///
+	/// The mixer calls this before processing any other tracks, so the
+	/// main track's total time covers the whole mixer.
+	#[must_use]
+	pub fn start_profiling(&self) -> Option<Instant> {
+		TrackProfiler::start(&self.profiler)
+	}
+
+	pub fn process(
+		&mut self,
+		out: &mut [Frame],
+		surround_out: &mut [f32],
+		dt: f64,
+		info: &Info,
+		start_time: Option<Instant>,
+	) {
 		self.volume.update(dt * out.len() as f64, info);
+		let sounds_start_time = TrackProfiler::start(&self.profiler);
 		for (_, sound) in &mut self.sounds {
 			sound.process(&mut self.temp_buffer[..out.len()], dt, info);
 			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
@@ -56,14 +85,37 @@ impl MainTrack {
 			}
 			self.temp_buffer.fill(Frame::ZERO);
 		}
-		for effect in &mut self.effects {
-			effect.process(out, dt, info);
+		if let Some(profiler) = &mut self.profiler {
+			profiler.record_sounds(sounds_start_time);
 		}
 		let num_frames = out.len();
+		let num_surround_channels = surround_out.len() / num_frames.max(1);
+		if !self.effects.is_empty() {
+			let channel_layout =
+				ChannelLayout::from_num_channels(num_surround_channels as u16).unwrap_or_default();
+			self.surround_effects
+				.process(out, surround_out, channel_layout, |out| {
+					for (i, effect) in self.effects.iter_mut().enumerate() {
+						let effect_start_time = TrackProfiler::start(&self.profiler);
+						effect.process(out, dt, info);
+						if let Some(profiler) = &mut self.profiler {
+							profiler.record_effect(i, effect_start_time);
+						}
+					}
+				});
+		}
 		for (i, frame) in out.iter_mut().enumerate() {
 			let time_in_chunk = (i + 1) as f64 / num_frames as f64;
 			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
 			*frame *= volume;
+			for sample in
+				&mut surround_out[i * num_surround_channels..(i + 1) * num_surround_channels]
+			{
+				*sample *= volume;
+			}
+		}
+		if let Some(profiler) = &mut self.profiler {
+			profiler.record_total(start_time);
 		}
 	}
 }
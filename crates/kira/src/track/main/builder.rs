@@ -1,9 +1,12 @@
 use crate::{
-	backend::resources::ResourceStorage, command::command_writer_and_reader, effect::EffectBuilder,
+	backend::resources::ResourceStorage,
+	command::command_writer_and_reader,
+	effect::EffectBuilder,
+	profiling::{SoundProfilers, TrackProfiler},
 	Decibels, Frame, Parameter, Value,
 };
 
-use super::{Effect, MainTrack, MainTrackHandle};
+use super::{Effect, MainTrack, MainTrackHandle, SurroundEffects};
 
 /// Configures the main mixer track.
 pub struct MainTrackBuilder {
@@ -142,20 +145,30 @@ impl MainTrackBuilder {
 	}
 
 	#[must_use]
-	pub(crate) fn build(self, internal_buffer_size: usize) -> (MainTrack, MainTrackHandle) {
+	pub(crate) fn build(
+		self,
+		internal_buffer_size: usize,
+		profiling: bool,
+	) -> (MainTrack, MainTrackHandle) {
 		let (set_volume_command_writer, set_volume_command_reader) = command_writer_and_reader();
 		let (sounds, sound_controller) = ResourceStorage::new(self.sound_capacity);
+		let profiler = TrackProfiler::new(profiling, self.effects.len());
+		let profiler_shared = profiler.as_ref().map(TrackProfiler::shared);
 		let track = MainTrack {
 			volume: Parameter::new(self.volume, Decibels::IDENTITY),
 			set_volume_command_reader,
 			sounds,
 			effects: self.effects,
 			temp_buffer: vec![Frame::ZERO; internal_buffer_size],
+			surround_effects: SurroundEffects::new(),
 			internal_buffer_size,
+			profiler,
 		};
 		let handle = MainTrackHandle {
 			set_volume_command_writer,
 			sound_controller,
+			profiler_shared,
+			sound_profilers: SoundProfilers::new(profiling),
 		};
 		(track, handle)
 	}
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use crate::{
 	backend::resources::ResourceController,
 	command::{CommandWriter, ValueChangeCommand},
+	profiling::{SoundId, SoundProfilers, TrackProfile, TrackProfilerShared},
 	sound::{Sound, SoundData},
 	Decibels, PlaySoundError, Tween, Value,
 };
@@ -10,6 +13,8 @@ use crate::{
 pub struct MainTrackHandle {
 	pub(crate) set_volume_command_writer: CommandWriter<ValueChangeCommand<Decibels>>,
 	pub(crate) sound_controller: ResourceController<Box<dyn Sound>>,
+	pub(crate) profiler_shared: Option<Arc<TrackProfilerShared>>,
+	pub(crate) sound_profilers: Option<SoundProfilers>,
 }
 
 impl MainTrackHandle {
@@ -18,9 +23,12 @@ impl MainTrackHandle {
 		&mut self,
 		sound_data: D,
 	) -> Result<D::Handle, PlaySoundError<D::Error>> {
-		let (sound, handle) = sound_data
+		let (mut sound, handle) = sound_data
 			.into_sound()
 			.map_err(PlaySoundError::IntoSoundError)?;
+		if let Some(sound_profilers) = &mut self.sound_profilers {
+			sound = sound_profilers.add(sound);
+		}
 		self.sound_controller
 			.insert(sound)
 			.map_err(|_| PlaySoundError::SoundLimitReached)?;
@@ -46,4 +54,27 @@ impl MainTrackHandle {
 	pub fn num_sounds(&self) -> usize {
 		self.sound_controller.len()
 	}
+
+	/// Returns statistics about how long the track took to process recently.
+	///
+	/// Returns [`None`] if [profiling](crate::profiling) is disabled.
+	#[must_use]
+	pub fn profile(&self) -> Option<TrackProfile> {
+		self.profiler_shared
+			.as_ref()
+			.map(|shared| shared.profile(self.sound_profilers.as_ref()))
+	}
+
+	/// Returns the ID of the sound that was most recently played on
+	/// this track, which identifies the sound in the track's
+	/// [`profile`](Self::profile).
+	///
+	/// Returns [`None`] if [profiling](crate::profiling) is disabled
+	/// or no sounds have been played.
+	#[must_use]
+	pub fn last_sound_id(&self) -> Option<SoundId> {
+		self.sound_profilers
+			.as_ref()
+			.and_then(SoundProfilers::last_sound_id)
+	}
 }
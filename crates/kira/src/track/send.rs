@@ -11,6 +11,7 @@ use crate::{
 	command::{CommandReader, ValueChangeCommand},
 	effect::Effect,
 	info::Info,
+	profiling::TrackProfiler,
 	Decibels, Frame, Parameter,
 };
 
@@ -33,6 +34,7 @@ pub(crate) struct SendTrack {
 	effects: Vec<Box<dyn Effect>>,
 	input: Vec<Frame>,
 	internal_buffer_size: usize,
+	profiler: Option<TrackProfiler>,
 }
 
 impl SendTrack {
@@ -68,13 +70,18 @@ impl SendTrack {
 	}
 
 	pub fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info) {
+		let start_time = TrackProfiler::start(&self.profiler);
 		self.volume.update(dt * out.len() as f64, info);
 		for (out_frame, input_frame) in out.iter_mut().zip(self.input.iter().copied()) {
 			*out_frame += input_frame;
 		}
 		self.input.fill(Frame::ZERO);
-		for effect in &mut self.effects {
+		for (i, effect) in self.effects.iter_mut().enumerate() {
+			let effect_start_time = TrackProfiler::start(&self.profiler);
 			effect.process(out, dt, info);
+			if let Some(profiler) = &mut self.profiler {
+				profiler.record_effect(i, effect_start_time);
+			}
 		}
 		let num_frames = out.len();
 		for (i, frame) in out.iter_mut().enumerate() {
@@ -82,6 +89,9 @@ impl SendTrack {
 			let volume = self.volume.interpolated_value(time_in_chunk).as_amplitude();
 			*frame *= volume;
 		}
+		if let Some(profiler) = &mut self.profiler {
+			profiler.record_total(start_time);
+		}
 	}
 }
 
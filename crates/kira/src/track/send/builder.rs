@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use crate::{
-	command::command_writer_and_reader, effect::EffectBuilder, frame::Frame, Decibels, Parameter,
-	Value,
+	command::command_writer_and_reader, effect::EffectBuilder, frame::Frame,
+	profiling::TrackProfiler, Decibels, Parameter, Value,
 };
 
 use super::{Effect, SendTrack, SendTrackHandle, SendTrackId, TrackShared};
@@ -173,9 +173,12 @@ impl SendTrackBuilder {
 		self,
 		id: SendTrackId,
 		internal_buffer_size: usize,
+		profiling: bool,
 	) -> (SendTrack, SendTrackHandle) {
 		let (set_volume_command_writer, set_volume_command_reader) = command_writer_and_reader();
 		let shared = Arc::new(TrackShared::new());
+		let profiler = TrackProfiler::new(profiling, self.effects.len());
+		let profiler_shared = profiler.as_ref().map(TrackProfiler::shared);
 		let track = SendTrack {
 			shared: shared.clone(),
 			volume: Parameter::new(self.volume, Decibels::IDENTITY),
@@ -183,11 +186,13 @@ impl SendTrackBuilder {
 			effects: self.effects,
 			input: vec![Frame::ZERO; internal_buffer_size],
 			internal_buffer_size,
+			profiler,
 		};
 		let handle = SendTrackHandle {
 			id,
 			shared,
 			set_volume_command_writer,
+			profiler_shared,
 		};
 		(track, handle)
 	}
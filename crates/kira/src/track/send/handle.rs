@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use crate::{
 	command::{CommandWriter, ValueChangeCommand},
-	Tween,
-	Decibels, Value,
+	profiling::{TrackProfile, TrackProfilerShared},
+	Decibels, Tween, Value,
 };
 
 use super::{SendTrackId, TrackShared};
@@ -17,6 +17,7 @@ pub struct SendTrackHandle {
 	pub(crate) id: SendTrackId,
 	pub(crate) shared: Arc<TrackShared>,
 	pub(crate) set_volume_command_writer: CommandWriter<ValueChangeCommand<Decibels>>,
+	pub(crate) profiler_shared: Option<Arc<TrackProfilerShared>>,
 }
 
 impl SendTrackHandle {
@@ -32,6 +33,16 @@ impl SendTrackHandle {
 			tween,
 		})
 	}
+
+	/// Returns statistics about how long the track took to process recently.
+	///
+	/// Returns [`None`] if [profiling](crate::profiling) is disabled.
+	#[must_use]
+	pub fn profile(&self) -> Option<TrackProfile> {
+		self.profiler_shared
+			.as_ref()
+			.map(|shared| shared.profile(None))
+	}
 }
 
 impl Drop for SendTrackHandle {
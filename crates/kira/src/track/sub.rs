@@ -10,23 +10,25 @@ pub use spatial_handle::*;
 
 use std::{error::Error, f32::consts::FRAC_PI_8, fmt::Display, sync::Arc};
 
+use atomic_arena::Key;
 use glam::{Quat, Vec3};
 
 use crate::{
-	backend::resources::{
-		clocks::Clocks, listeners::Listeners, modulators::Modulators, ResourceStorage,
-	},
+	backend::resources::{clocks::Clocks, listeners::Listeners, ResourceStorage},
 	command::ValueChangeCommand,
 	command_writers_and_readers,
 	effect::Effect,
-	info::{Info, SpatialTrackInfo},
+	info::{Info, ModulatorValues, SpatialTrackInfo},
 	listener::ListenerId,
 	playback_state_manager::PlaybackStateManager,
-	sound::Sound,
-	Decibels, Easing, Frame, Parameter, StartTime, Tween, Tweenable,
+	profiling::TrackProfiler,
+	ChannelLayout, Decibels, Easing, Frame, Parameter, StartTime, Tween, Tweenable,
 };
 
-use super::{SendTrack, SendTrackId, SendTrackRoute, TrackShared};
+use super::{
+	surround::SurroundEffects, SendTrack, SendTrackId, SendTrackRoute, TrackShared, Voice,
+	VoiceController, VoiceLimit,
+};
 
 /// An error that's returned when trying to change the volume of a track route
 /// that did not exist originally.
@@ -45,7 +47,12 @@ pub(crate) struct Track {
 	shared: Arc<TrackShared>,
 	command_readers: CommandReaders,
 	volume: Parameter<Decibels>,
-	sounds: ResourceStorage<Box<dyn Sound>>,
+	sounds: ResourceStorage<Voice>,
+	voice_limit: Option<VoiceLimit>,
+	/// Scratch space for sorting the sounds when enforcing the voice limit.
+	voice_keys: Vec<Key>,
+	/// Sounds are virtualized while the track's volume is at or below this.
+	virtualize_below: Option<Decibels>,
 	sub_tracks: ResourceStorage<Track>,
 	effects: Vec<Box<dyn Effect>>,
 	sends: Vec<(SendTrackId, SendTrackRoute)>,
@@ -53,7 +60,15 @@ pub(crate) struct Track {
 	spatial_data: Option<SpatialData>,
 	playback_state_manager: PlaybackStateManager,
 	temp_buffer: Vec<Frame>,
+	/// Audio panned across the speakers of a surround [`ChannelLayout`]
+	/// by this track and its sub-tracks.
+	///
+	/// Empty if the mixer isn't using a surround layout.
+	surround_buffer: Vec<f32>,
+	surround_effects: SurroundEffects,
+	channel_layout: ChannelLayout,
 	internal_buffer_size: usize,
+	profiler: Option<TrackProfiler>,
 }
 
 impl Track {
@@ -111,11 +126,25 @@ impl Track {
 		}
 	}
 
+	/// Returns `true` if this track or any of its sub-tracks
+	/// is playing any sounds.
+	#[must_use]
+	pub fn has_sounds(&self) -> bool {
+		!self.sounds.is_empty()
+			|| self
+				.sub_tracks
+				.iter()
+				.any(|(_, sub_track)| sub_track.has_sounds())
+	}
+
 	pub fn on_start_processing(&mut self) {
 		self.read_commands();
-		self.sounds.remove_and_add(|sound| sound.finished());
-		for (_, sound) in &mut self.sounds {
-			sound.on_start_processing();
+		self.sounds.remove_and_add(|voice| voice.finished());
+		if let Some(voice_limit) = &self.voice_limit {
+			voice_limit.update_voices(&mut self.sounds, &mut self.voice_keys);
+		}
+		for (_, voice) in &mut self.sounds {
+			voice.on_start_processing();
 		}
 		self.sub_tracks
 			.remove_and_add(|sub_track| sub_track.should_be_removed());
@@ -127,17 +156,32 @@ impl Track {
 		}
 	}
 
+	/// Returns `true` if this track or any of its sub-tracks
+	/// outputs to send tracks.
+	#[must_use]
+	pub fn has_sends(&self) -> bool {
+		!self.sends.is_empty() || self.sub_tracks.iter().any(|(_, track)| track.has_sends())
+	}
+
+	/// Processes the track's sounds, sub-tracks, and effects.
+	///
+	/// `send_tracks` can only be `None` if [`has_sends`](Self::has_sends)
+	/// returns `false`.
 	#[allow(clippy::too_many_arguments)]
 	pub fn process(
 		&mut self,
 		out: &mut [Frame],
 		dt: f64,
 		clocks: &Clocks,
-		modulators: &Modulators,
+		modulators: ModulatorValues<'_>,
 		listeners: &Listeners,
 		parent_spatial_track_info: Option<SpatialTrackInfo>,
-		send_tracks: &mut ResourceStorage<SendTrack>,
+		mut send_tracks: Option<&mut ResourceStorage<SendTrack>>,
+		surround_out: &mut [f32],
+		playback_nanos: Option<i64>,
 	) {
+		let start_time = TrackProfiler::start(&self.profiler);
+
 		// get info
 		let spatial_track_info = self
 			.spatial_data
@@ -149,10 +193,11 @@ impl Track {
 			.or(parent_spatial_track_info);
 		let info = Info::new(
 			&clocks.0.resources,
-			&modulators.0.resources,
+			modulators,
 			&listeners.0.resources,
 			spatial_track_info,
-		);
+		)
+		.with_playback_nanos(playback_nanos);
 
 		// update volume parameters
 		self.volume.update(dt * out.len() as f64, &info);
@@ -169,10 +214,15 @@ impl Track {
 		}
 		if !self.playback_state_manager.playback_state().is_advancing() {
 			out.fill(Frame::ZERO);
+			if let Some(profiler) = &mut self.profiler {
+				profiler.record_idle(start_time);
+			}
 			return;
 		}
 
 		let num_frames = out.len();
+		let num_surround_channels = self.channel_layout.num_channels() as usize;
+		let surround_buffer_len = surround_out.len();
 
 		// process sub tracks
 		for (_, sub_track) in &mut self.sub_tracks {
@@ -183,7 +233,9 @@ impl Track {
 				modulators,
 				listeners,
 				spatial_track_info,
-				send_tracks,
+				send_tracks.as_deref_mut(),
+				&mut self.surround_buffer[..surround_buffer_len],
+				playback_nanos,
 			);
 			for (summed_out, track_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
 				*summed_out += track_out;
@@ -192,20 +244,39 @@ impl Track {
 		}
 
 		// process sounds
-		for (_, sound) in &mut self.sounds {
-			sound.process(&mut self.temp_buffer[..out.len()], dt, &info);
+		let sounds_start_time = TrackProfiler::start(&self.profiler);
+		let inaudible = self.is_inaudible(&info);
+		for (_, voice) in &mut self.sounds {
+			voice.process(&mut self.temp_buffer[..out.len()], dt, &info, inaudible);
 			for (summed_out, sound_out) in out.iter_mut().zip(self.temp_buffer.iter().copied()) {
 				*summed_out += sound_out;
 			}
 			self.temp_buffer.fill(Frame::ZERO);
 		}
+		if let Some(profiler) = &mut self.profiler {
+			profiler.record_sounds(sounds_start_time);
+		}
 
 		// apply effects
-		for effect in &mut self.effects {
-			effect.process(out, dt, &info);
+		if !self.effects.is_empty() {
+			self.surround_effects.process(
+				out,
+				&mut self.surround_buffer[..surround_buffer_len],
+				self.channel_layout,
+				|out| {
+					for (i, effect) in self.effects.iter_mut().enumerate() {
+						let effect_start_time = TrackProfiler::start(&self.profiler);
+						effect.process(out, dt, &info);
+						if let Some(profiler) = &mut self.profiler {
+							profiler.record_effect(i, effect_start_time);
+						}
+					}
+				},
+			);
 		}
 
 		// apply spatialization
+		let surround = !self.surround_buffer.is_empty();
 		if let Some(spatial_data) = &mut self.spatial_data {
 			spatial_data.position.update(dt * out.len() as f64, &info);
 			spatial_data
@@ -218,6 +289,17 @@ impl Track {
 						listener_info.interpolated_position(time_in_chunk as f32);
 					let interpolated_orientation =
 						listener_info.interpolated_orientation(time_in_chunk as f32);
+					if surround {
+						spatial_data.pan(
+							*frame,
+							interpolated_position.into(),
+							interpolated_orientation.into(),
+							time_in_chunk,
+							self.channel_layout,
+							&mut self.surround_buffer
+								[i * num_surround_channels..(i + 1) * num_surround_channels],
+						);
+					}
 					*frame = spatial_data.spatialize(
 						*frame,
 						interpolated_position.into(),
@@ -239,17 +321,67 @@ impl Track {
 				.interpolated_fade_volume(time_in_chunk)
 				.as_amplitude();
 			*frame *= volume * fade_volume;
+			if surround {
+				for sample in &mut self.surround_buffer
+					[i * num_surround_channels..(i + 1) * num_surround_channels]
+				{
+					*sample *= volume * fade_volume;
+				}
+			}
 		}
 
 		// output to send tracks
-		for (send_track_id, SendTrackRoute { volume, .. }) in &self.sends {
-			let Some(send_track) = send_tracks.get_mut(send_track_id.0) else {
-				continue;
-			};
-			send_track.add_input(out, volume.value());
+		if let Some(send_tracks) = send_tracks {
+			for (send_track_id, SendTrackRoute { volume, .. }) in &self.sends {
+				let Some(send_track) = send_tracks.get_mut(send_track_id.0) else {
+					continue;
+				};
+				send_track.add_input(out, volume.value());
+			}
+		}
+
+		/*
+			spatial tracks output directly to the surround speakers, so the
+			stereo output is only used for send tracks.
+		*/
+		if surround {
+			if self.spatial_data.is_some() {
+				out.fill(Frame::ZERO);
+			}
+			for (summed_out, track_out) in surround_out.iter_mut().zip(&self.surround_buffer) {
+				*summed_out += *track_out;
+			}
+			self.surround_buffer.fill(0.0);
+		}
+
+		if let Some(profiler) = &mut self.profiler {
+			profiler.record_total(start_time);
 		}
 	}
 
+	/// Returns `true` if sounds on this track should be virtualized because
+	/// the track's volume is at or below the [virtualization threshold](TrackBuilder::virtualize_below).
+	#[must_use]
+	fn is_inaudible(&self, info: &Info) -> bool {
+		let Some(virtualize_below) = self.virtualize_below else {
+			return false;
+		};
+		let volume = self
+			.volume
+			.interpolated_value(0.0)
+			.as_amplitude()
+			.max(self.volume.value().as_amplitude());
+		let attenuation = match &self.spatial_data {
+			Some(spatial_data) => match info.listener_info() {
+				Some(listener_info) => spatial_data
+					.attenuation(spatial_data.position.value(), listener_info.position.into()),
+				None => 0.0,
+			},
+			None => 1.0,
+		};
+		volume * attenuation <= virtualize_below.as_amplitude()
+	}
+
 	fn read_commands(&mut self) {
 		self.volume
 			.read_command(&mut self.command_readers.set_volume);
@@ -293,6 +425,55 @@ struct SpatialData {
 }
 
 impl SpatialData {
+	/// Pans the input across the speakers of a surround [`ChannelLayout`]
+	/// and adds it to the output.
+	#[allow(clippy::too_many_arguments)]
+	fn pan(
+		&self,
+		input: Frame,
+		listener_position: Vec3,
+		listener_orientation: Quat,
+		time_in_chunk: f64,
+		channel_layout: ChannelLayout,
+		out: &mut [f32],
+	) {
+		let position = self.position.interpolated_value(time_in_chunk);
+		let spatialization_strength = self
+			.spatialization_strength
+			.interpolated_value(time_in_chunk)
+			.clamp(0.0, 1.0);
+		let input = input * self.attenuation(position, listener_position);
+		// audio that isn't spatialized goes to the front left and right speakers
+		out[0] += input.left * (1.0 - spatialization_strength);
+		out[1] += input.right * (1.0 - spatialization_strength);
+		if spatialization_strength == 0.0 {
+			return;
+		}
+		let relative_position = listener_orientation.inverse() * (position - listener_position);
+		let azimuth = relative_position.x.atan2(-relative_position.z).to_degrees();
+		let mono = input.as_mono().left * spatialization_strength;
+		for (channel, gain) in channel_layout.pan(azimuth) {
+			out[channel] += mono * gain;
+		}
+	}
+
+	/// Returns the amplitude the track should be multiplied by
+	/// based on its distance from the listener.
+	fn attenuation(&self, position: Vec3, listener_position: Vec3) -> f32 {
+		let Some(attenuation_function) = self.attenuation_function else {
+			return 1.0;
+		};
+		let distance = (listener_position - position).length();
+		let relative_distance = self.distances.relative_distance(distance);
+		let relative_volume = attenuation_function.apply((1.0 - relative_distance).into()) as f32;
+		Tweenable::interpolate(
+			Decibels::SILENCE,
+			Decibels::IDENTITY,
+			relative_volume.into(),
+		)
+		.as_amplitude()
+	}
+
 	fn spatialize(
 		&self,
 		input: Frame,
@@ -307,21 +488,8 @@ impl SpatialData {
 			.clamp(0.0, 1.0);
 		let min_ear_amplitude = 1.0 - spatialization_strength;
 
-		let mut output = input;
 		// attenuate volume
-		if let Some(attenuation_function) = self.attenuation_function {
-			let distance = (listener_position - position).length();
-			let relative_distance = self.distances.relative_distance(distance);
-			let relative_volume =
-				attenuation_function.apply((1.0 - relative_distance).into()) as f32;
-			let amplitude = Tweenable::interpolate(
-				Decibels::SILENCE,
-				Decibels::IDENTITY,
-				relative_volume.into(),
-			)
-			.as_amplitude();
-			output *= amplitude;
-		}
+		let mut output = input * self.attenuation(position, listener_position);
 		// apply spatialization
 		if spatialization_strength != 0.0 {
 			output = output.as_mono();
@@ -7,14 +7,15 @@ use crate::{
 	backend::{resources::ResourceController, RendererShared},
 	command::{CommandWriter, ValueChangeCommand},
 	listener::ListenerId,
-	sound::{Sound, SoundData},
+	profiling::{SoundId, TrackProfile, TrackProfilerShared},
+	sound::SoundData,
 	track::TrackPlaybackState,
 	Decibels, PlaySoundError, ResourceLimitReached, StartTime, Tween, Value,
 };
 
 use super::{
 	CommandWriters, NonexistentRoute, SendTrackId, SpatialTrackBuilder, SpatialTrackHandle, Track,
-	TrackBuilder, TrackShared,
+	TrackBuilder, TrackShared, VoiceController,
 };
 
 /// Controls a mixer track.
@@ -26,11 +27,12 @@ pub struct TrackHandle {
 	pub(crate) renderer_shared: Arc<RendererShared>,
 	pub(crate) shared: Arc<TrackShared>,
 	pub(crate) command_writers: CommandWriters,
-	pub(crate) sound_controller: ResourceController<Box<dyn Sound>>,
+	pub(crate) sound_controller: VoiceController,
 	pub(crate) sub_track_controller: ResourceController<Track>,
 	pub(crate) send_volume_command_writers:
 		HashMap<SendTrackId, CommandWriter<ValueChangeCommand<Decibels>>>,
 	pub(crate) internal_buffer_size: usize,
+	pub(crate) profiler_shared: Option<Arc<TrackProfilerShared>>,
 }
 
 impl TrackHandle {
@@ -45,13 +47,21 @@ impl TrackHandle {
 		&mut self,
 		sound_data: D,
 	) -> Result<D::Handle, PlaySoundError<D::Error>> {
-		let (sound, handle) = sound_data
-			.into_sound()
-			.map_err(PlaySoundError::IntoSoundError)?;
-		self.sound_controller
-			.insert(sound)
-			.map_err(|_| PlaySoundError::SoundLimitReached)?;
-		Ok(handle)
+		self.play_with_priority(sound_data, 0)
+	}
+
+	/// Plays a sound with the given priority.
+	///
+	/// If the track has a [`VoiceLimit`](crate::track::VoiceLimit) that uses
+	/// [`VoiceStealing::LowestPriority`](crate::track::VoiceStealing::LowestPriority),
+	/// sounds with higher priorities are stopped last when the limit is
+	/// reached. Sounds played with [`play`](Self::play) have a priority of `0`.
+	pub fn play_with_priority<D: SoundData>(
+		&mut self,
+		sound_data: D,
+		priority: i32,
+	) -> Result<D::Handle, PlaySoundError<D::Error>> {
+		self.sound_controller.play(sound_data, priority)
 	}
 
 	/// Adds a child track to this track.
@@ -156,6 +166,27 @@ impl TrackHandle {
 	pub fn num_sub_tracks(&self) -> usize {
 		self.sub_track_controller.len()
 	}
+
+	/// Returns statistics about how long the track took to process recently.
+	///
+	/// Returns [`None`] if [profiling](crate::profiling) is disabled.
+	#[must_use]
+	pub fn profile(&self) -> Option<TrackProfile> {
+		self.profiler_shared
+			.as_ref()
+			.map(|shared| shared.profile(self.sound_controller.sound_profilers()))
+	}
+
+	/// Returns the ID of the sound that was most recently played on
+	/// this track, which identifies the sound in the track's
+	/// [`profile`](Self::profile).
+	///
+	/// Returns [`None`] if [profiling](crate::profiling) is disabled
+	/// or no sounds have been played.
+	#[must_use]
+	pub fn last_sound_id(&self) -> Option<SoundId> {
+		self.sound_controller.last_sound_id()
+	}
 }
 
 impl Drop for TrackHandle {
@@ -8,13 +8,14 @@ use crate::{
 	effect::EffectBuilder,
 	listener::ListenerId,
 	playback_state_manager::PlaybackStateManager,
+	profiling::TrackProfiler,
 	tween::Easing,
 	Decibels, Frame, Parameter, Value,
 };
 
 use super::{
 	command_writers_and_readers, Effect, SendTrackId, SendTrackRoute, SpatialData,
-	SpatialTrackHandle, Track, TrackShared,
+	SpatialTrackHandle, SurroundEffects, Track, TrackShared, VoiceController, VoiceLimit,
 };
 
 /// Configures a spatial mixer track.
@@ -28,6 +29,12 @@ pub struct SpatialTrackBuilder {
 	pub(crate) sub_track_capacity: usize,
 	/// The maximum number of sounds that can be played simultaneously on this track.
 	pub(crate) sound_capacity: usize,
+	/// The maximum number of sounds that can play at the same time
+	/// before sounds are stolen or rejected.
+	pub(crate) voice_limit: Option<VoiceLimit>,
+	/// Sounds on this track are virtualized while the track's volume
+	/// is at or below this.
+	pub(crate) virtualize_below: Option<Decibels>,
 	pub(crate) sends: HashMap<SendTrackId, Value<Decibels>>,
 	pub(crate) persist_until_sounds_finish: bool,
 	/// The distances from a listener at which the track is loudest and quietest.
@@ -53,6 +60,8 @@ impl SpatialTrackBuilder {
 			effects: vec![],
 			sub_track_capacity: 128,
 			sound_capacity: 128,
+			voice_limit: None,
+			virtualize_below: None,
 			sends: HashMap::new(),
 			persist_until_sounds_finish: false,
 			distances: SpatialTrackDistances::default(),
@@ -125,6 +134,51 @@ impl SpatialTrackBuilder {
 		}
 	}
 
+	/**
+	Sets the maximum number of sounds that can play on this track at the
+	same time, and what happens when more sounds are played.
+
+	Unlike the [sound capacity](Self::sound_capacity), reaching the voice limit
+	can stop sounds that are already playing to make room for new sounds.
+	Sounds playing on sub-tracks don't count toward the limit, so sub-tracks
+	can be used to give groups of sounds their own limits.
+
+	# Examples
+
+	```
+	use kira::track::{SpatialTrackBuilder, VoiceLimit, VoiceStealing};
+
+	let builder = SpatialTrackBuilder::new()
+		.voice_limit(VoiceLimit::new(8).stealing(VoiceStealing::Quietest));
+	```
+	*/
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn voice_limit(self, voice_limit: impl Into<Option<VoiceLimit>>) -> Self {
+		Self {
+			voice_limit: voice_limit.into(),
+			..self
+		}
+	}
+
+	/**
+	Virtualizes the sounds on this track while the track's volume and distance attenuation
+	is at or below the given volume.
+
+	Virtual sounds don't output any audio, but they keep advancing their
+	playback position, so they pick up from the right spot once the track
+	is loud enough to hear them again. This makes it cheap to keep many
+	inaudible sounds playing.
+
+	By default, sounds are never virtualized.
+	*/
+	#[must_use = "This method consumes self and returns a modified SpatialTrackBuilder, so the return value should be used"]
+	pub fn virtualize_below(self, volume: impl Into<Option<Decibels>>) -> Self {
+		Self {
+			virtualize_below: volume.into(),
+			..self
+		}
+	}
+
 	/// Routes this track to the given send track with the given volume.
 	pub fn with_send(
 		mut self,
@@ -287,6 +341,11 @@ impl SpatialTrackBuilder {
 		let (command_writers, command_readers) = command_writers_and_readers();
 		let shared = Arc::new(TrackShared::new());
 		let (sounds, sound_controller) = ResourceStorage::new(self.sound_capacity);
+		let sound_controller = VoiceController::new(
+			sound_controller,
+			self.voice_limit,
+			renderer_shared.profiling,
+		);
 		let (sub_tracks, sub_track_controller) = ResourceStorage::new(self.sub_track_capacity);
 		let mut sends = vec![];
 		let mut send_volume_command_writers = HashMap::new();
@@ -302,11 +361,17 @@ impl SpatialTrackBuilder {
 			));
 			send_volume_command_writers.insert(send_track_id, set_volume_command_writer);
 		}
+		let channel_layout = renderer_shared.channel_layout;
+		let profiler = TrackProfiler::new(renderer_shared.profiling, self.effects.len());
+		let profiler_shared = profiler.as_ref().map(TrackProfiler::shared);
 		let track = Track {
 			shared: shared.clone(),
 			command_readers,
 			volume: Parameter::new(self.volume, Decibels::IDENTITY),
 			sounds,
+			voice_limit: self.voice_limit,
+			voice_keys: Vec::with_capacity(self.sound_capacity),
+			virtualize_below: self.virtualize_below,
 			sub_tracks,
 			effects: self.effects,
 			sends,
@@ -320,7 +385,15 @@ impl SpatialTrackBuilder {
 			}),
 			playback_state_manager: PlaybackStateManager::new(None),
 			temp_buffer: vec![Frame::ZERO; internal_buffer_size],
+			surround_buffer: if channel_layout.is_surround() {
+				vec![0.0; internal_buffer_size * channel_layout.num_channels() as usize]
+			} else {
+				vec![]
+			},
+			surround_effects: SurroundEffects::new(),
+			channel_layout,
 			internal_buffer_size,
+			profiler,
 		};
 		let handle = SpatialTrackHandle {
 			renderer_shared,
@@ -330,6 +403,7 @@ impl SpatialTrackBuilder {
 			sub_track_controller,
 			send_volume_command_writers,
 			internal_buffer_size,
+			profiler_shared,
 		};
 		(track, handle)
 	}
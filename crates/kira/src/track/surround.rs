@@ -0,0 +1,120 @@
+use crate::{channel_layout::MAX_CHANNELS, ChannelLayout, Frame};
+
+/// If a side of the downmixed audio has less total energy than
+/// this in a chunk, the panning from the previous chunk is kept.
+const MIN_ENERGY: f32 = 1e-12;
+
+/**
+Runs a track's effects on audio that's panned across surround speakers.
+
+Effects can only process stereo audio, so before the effects run, the
+surround audio is downmixed to stereo and mixed with the track's stereo
+audio. Afterward, each side of the processed audio is panned back across
+the speakers in proportion to how loud each speaker was on that side
+before the effects ran. This means effects hear everything the track
+outputs, and sounds stay in roughly the same place.
+
+If a side of the audio is silent, the panning from the previous chunk is
+kept so that effects with tails (like reverb) keep their position.
+*/
+pub(crate) struct SurroundEffects {
+	/// For each side of the downmixed audio, the gain of each
+	/// channel when panning processed audio back across the speakers.
+	gains: [[f32; MAX_CHANNELS]; 2],
+}
+
+impl SurroundEffects {
+	#[must_use]
+	pub fn new() -> Self {
+		let mut gains = [[0.0; MAX_CHANNELS]; 2];
+		gains[0][0] = 1.0;
+		gains[1][1] = 1.0;
+		Self { gains }
+	}
+
+	/// Processes the stereo audio in `out` and the surround audio in
+	/// `surround` with the effects run by `process_effects`.
+	///
+	/// Afterward, the audio for the front left and front right speakers
+	/// is in `out`, and the audio for the other speakers is in `surround`.
+	pub fn process(
+		&mut self,
+		out: &mut [Frame],
+		surround: &mut [f32],
+		channel_layout: ChannelLayout,
+		process_effects: impl FnOnce(&mut [Frame]),
+	) {
+		if surround.is_empty() || out.is_empty() {
+			process_effects(out);
+			return;
+		}
+		let num_channels = channel_layout.num_channels() as usize;
+		let downmix = channel_layout.mixing_matrix(ChannelLayout::Stereo);
+
+		// downmix the surround audio and measure how much each channel
+		// contributes to each side
+		let mut energies = [[0.0; MAX_CHANNELS]; 2];
+		for (frame, surround_frame) in out.iter_mut().zip(surround.chunks_exact_mut(num_channels)) {
+			surround_frame[0] += frame.left;
+			surround_frame[1] += frame.right;
+			let mut downmixed = [0.0; 2];
+			for side in 0..2 {
+				for (channel, sample) in surround_frame.iter().enumerate() {
+					let sample = downmix[side][channel] * sample;
+					downmixed[side] += sample;
+					energies[side][channel] += sample * sample;
+				}
+			}
+			*frame = Frame::new(downmixed[0], downmixed[1]);
+			// channels that aren't in the downmix (like the low frequency
+			// channel) aren't processed by the effects
+			for (channel, sample) in surround_frame.iter_mut().enumerate() {
+				if downmix[0][channel] != 0.0 || downmix[1][channel] != 0.0 {
+					*sample = 0.0;
+				}
+			}
+		}
+
+		process_effects(out);
+
+		let previous_gains = self.gains;
+		for side in 0..2 {
+			let total_energy = energies[side].iter().sum::<f32>();
+			if total_energy < MIN_ENERGY {
+				continue;
+			}
+			for channel in 0..num_channels {
+				let downmix_power = downmix[0][channel].powi(2) + downmix[1][channel].powi(2);
+				self.gains[side][channel] = if downmix_power == 0.0 {
+					0.0
+				} else {
+					// undo the downmix gain so that audio that isn't changed by
+					// the effects is panned back to its original volume
+					energies[side][channel] / total_energy * downmix[side][channel] / downmix_power
+				};
+			}
+		}
+
+		// pan the processed audio back across the speakers, smoothly
+		// changing the gains to avoid clicks
+		let num_frames = out.len();
+		for (i, (frame, surround_frame)) in out
+			.iter_mut()
+			.zip(surround.chunks_exact_mut(num_channels))
+			.enumerate()
+		{
+			let time_in_chunk = (i + 1) as f32 / num_frames as f32;
+			for (side, processed) in [frame.left, frame.right].into_iter().enumerate() {
+				for (channel, sample) in surround_frame.iter_mut().enumerate() {
+					let previous_gain = previous_gains[side][channel];
+					let gain =
+						previous_gain + (self.gains[side][channel] - previous_gain) * time_in_chunk;
+					*sample += processed * gain;
+				}
+			}
+			*frame = Frame::new(surround_frame[0], surround_frame[1]);
+			surround_frame[0] = 0.0;
+			surround_frame[1] = 0.0;
+		}
+	}
+}
@@ -0,0 +1,405 @@
+#[cfg(test)]
+mod test;
+
+use std::{cmp::Ordering, time::Duration};
+
+use atomic_arena::Key;
+
+use crate::{
+	backend::resources::{ResourceController, ResourceStorage},
+	frame::Frame,
+	info::Info,
+	profiling::{SoundId, SoundProfilers},
+	sound::{Sound, SoundData},
+	PlaySoundError,
+};
+
+/// The default duration of the fade used when a sound is stolen,
+/// virtualized, or made real again.
+const DEFAULT_FADE_DURATION: Duration = Duration::from_millis(10);
+
+/**
+Limits how many sounds can play on a mixer track at the same time.
+
+When a sound is played on a track that's already playing the maximum
+number of sounds, the track either stops one of the sounds that are
+already playing (known as "voice stealing") or refuses to play the
+new sound, depending on the [`VoiceStealing`] policy.
+
+Stolen sounds are quickly faded out to avoid clicks. Since stolen
+sounds keep playing while they fade out, the track's sound capacity
+should be larger than the voice limit.
+
+Instead of stopping sounds, the track can also
+[virtualize](VoiceLimit::virtualize) them.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VoiceLimit {
+	/// The maximum number of sounds that can play at the same time.
+	pub max_voices: usize,
+	/// What happens when a sound is played while the maximum
+	/// number of sounds is already playing.
+	pub stealing: VoiceStealing,
+	/// How long stolen sounds take to fade out.
+	///
+	/// This is also how long sounds take to fade out when they
+	/// become virtual and fade back in when they become real.
+	pub steal_fade_duration: Duration,
+	/// Whether sounds over the limit should be virtualized instead
+	/// of stopped.
+	///
+	/// Virtual sounds don't output any audio, but they keep advancing
+	/// their playback position. Every time the track processes audio, the
+	/// sounds that would be stopped last by the [`VoiceStealing`] policy
+	/// play normally, and the rest are virtual. Virtual sounds become
+	/// audible again once there's room for them.
+	///
+	/// This has no effect if the policy is [`VoiceStealing::Reject`].
+	pub virtualize: bool,
+}
+
+impl VoiceLimit {
+	/// Creates a new [`VoiceLimit`] with the given maximum number of sounds
+	/// that steals the oldest sound when the limit is reached.
+	#[must_use]
+	pub fn new(max_voices: usize) -> Self {
+		Self {
+			max_voices,
+			stealing: VoiceStealing::Oldest,
+			steal_fade_duration: DEFAULT_FADE_DURATION,
+			virtualize: false,
+		}
+	}
+
+	/// Sets what happens when a sound is played while the maximum
+	/// number of sounds is already playing.
+	#[must_use = "This method consumes self and returns a modified VoiceLimit, so the return value should be used"]
+	pub fn stealing(self, stealing: VoiceStealing) -> Self {
+		Self { stealing, ..self }
+	}
+
+	/// Sets how long stolen sounds take to fade out.
+	#[must_use = "This method consumes self and returns a modified VoiceLimit, so the return value should be used"]
+	pub fn steal_fade_duration(self, steal_fade_duration: Duration) -> Self {
+		Self {
+			steal_fade_duration,
+			..self
+		}
+	}
+
+	/// Sets whether sounds over the limit should be virtualized
+	/// instead of stopped.
+	#[must_use = "This method consumes self and returns a modified VoiceLimit, so the return value should be used"]
+	pub fn virtualize(self, virtualize: bool) -> Self {
+		Self { virtualize, ..self }
+	}
+
+	/// Stops or virtualizes sounds until no more than the maximum
+	/// number of sounds are audible.
+	///
+	/// `keys` is used as scratch space for sorting the voices, so
+	/// it should have enough capacity for every voice.
+	pub(crate) fn update_voices(&self, voices: &mut ResourceStorage<Voice>, keys: &mut Vec<Key>) {
+		if self.stealing == VoiceStealing::Reject {
+			return;
+		}
+		// sort the voices from the first one to be stolen to the last one
+		keys.clear();
+		keys.extend(voices.iter().map(|(key, _)| key));
+		keys.sort_unstable_by(|a, b| {
+			let a = voices.get(*a).expect("the voice should exist");
+			let b = voices.get(*b).expect("the voice should exist");
+			self.stealing.compare(a, b)
+		});
+		if self.virtualize {
+			self.virtualize_voices(voices, keys);
+		} else {
+			self.steal_voices(voices, keys);
+		}
+	}
+
+	/// Fades out sounds until no more than the maximum number of
+	/// sounds are playing.
+	fn steal_voices(&self, voices: &mut ResourceStorage<Voice>, sorted_keys: &[Key]) {
+		let num_playing_voices = voices
+			.iter()
+			.filter(|(_, voice)| !voice.is_stolen())
+			.count();
+		let mut num_voices_to_steal = num_playing_voices.saturating_sub(self.max_voices);
+		for key in sorted_keys {
+			if num_voices_to_steal == 0 {
+				return;
+			}
+			let voice = voices.get_mut(*key).expect("the voice should exist");
+			if !voice.is_stolen() {
+				voice.steal();
+				num_voices_to_steal -= 1;
+			}
+		}
+	}
+
+	/// Marks the sounds that would be stolen as virtual.
+	fn virtualize_voices(&self, voices: &mut ResourceStorage<Voice>, sorted_keys: &[Key]) {
+		// a sound is over budget if there's enough sounds that
+		// would be stolen after it to fill every voice
+		let mut num_sounds_kept_instead = 0;
+		for key in sorted_keys.iter().rev() {
+			let voice = voices.get_mut(*key).expect("the voice should exist");
+			voice.over_budget = num_sounds_kept_instead >= self.max_voices;
+			if !voice.is_stolen() {
+				num_sounds_kept_instead += 1;
+			}
+		}
+	}
+}
+
+/// Which sound is stopped when a sound is played on a track that's
+/// already playing the maximum number of sounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VoiceStealing {
+	/// Stops the sound that started playing first.
+	#[default]
+	Oldest,
+	/// Stops the sound with the quietest output.
+	///
+	/// Sounds that haven't output any audio yet, like sounds that
+	/// are waiting for their start time, are never considered
+	/// the quietest.
+	Quietest,
+	/// Stops the sound with the lowest priority. If several sounds have
+	/// the lowest priority, the one that started playing first is stopped.
+	///
+	/// If the new sound has a lower priority than every sound that's
+	/// already playing, the new sound is stopped before it's heard.
+	///
+	/// The priority of a sound can be set by playing it with
+	/// [`TrackHandle::play_with_priority`](crate::track::TrackHandle::play_with_priority).
+	LowestPriority,
+	/// Doesn't stop any sounds. Instead, playing a new sound fails with
+	/// [`PlaySoundError::SoundLimitReached`](crate::PlaySoundError::SoundLimitReached).
+	Reject,
+}
+
+impl VoiceStealing {
+	/// Returns [`Ordering::Less`] if `a` should be stolen before `b`.
+	#[must_use]
+	fn compare(self, a: &Voice, b: &Voice) -> Ordering {
+		match self {
+			VoiceStealing::Oldest | VoiceStealing::Reject => a.order.cmp(&b.order),
+			VoiceStealing::Quietest => a
+				.loudness()
+				.total_cmp(&b.loudness())
+				.then(a.order.cmp(&b.order)),
+			VoiceStealing::LowestPriority => (a.priority, a.order).cmp(&(b.priority, b.order)),
+		}
+	}
+}
+
+/// Sends sounds to a mixer track, enforcing the track's [`VoiceLimit`].
+#[derive(Debug)]
+pub(crate) struct VoiceController {
+	controller: ResourceController<Voice>,
+	voice_limit: Option<VoiceLimit>,
+	next_order: u64,
+	sound_profilers: Option<SoundProfilers>,
+}
+
+impl VoiceController {
+	#[must_use]
+	pub fn new(
+		controller: ResourceController<Voice>,
+		voice_limit: Option<VoiceLimit>,
+		profiling: bool,
+	) -> Self {
+		Self {
+			controller,
+			voice_limit,
+			next_order: 0,
+			sound_profilers: SoundProfilers::new(profiling),
+		}
+	}
+
+	pub fn play<D: SoundData>(
+		&mut self,
+		sound_data: D,
+		priority: i32,
+	) -> Result<D::Handle, PlaySoundError<D::Error>> {
+		if let Some(VoiceLimit {
+			max_voices,
+			stealing: VoiceStealing::Reject,
+			..
+		}) = self.voice_limit
+		{
+			if self.controller.len() >= max_voices {
+				return Err(PlaySoundError::SoundLimitReached);
+			}
+		}
+		let (mut sound, handle) = sound_data
+			.into_sound()
+			.map_err(PlaySoundError::IntoSoundError)?;
+		if let Some(sound_profilers) = &mut self.sound_profilers {
+			sound = sound_profilers.add(sound);
+		}
+		let fade_duration = self
+			.voice_limit
+			.map_or(DEFAULT_FADE_DURATION, |voice_limit| {
+				voice_limit.steal_fade_duration
+			});
+		self.controller
+			.insert(Voice::new(sound, priority, self.next_order, fade_duration))
+			.map_err(|_| PlaySoundError::SoundLimitReached)?;
+		self.next_order += 1;
+		Ok(handle)
+	}
+
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.controller.capacity()
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.controller.len()
+	}
+
+	#[must_use]
+	pub fn sound_profilers(&self) -> Option<&SoundProfilers> {
+		self.sound_profilers.as_ref()
+	}
+
+	#[must_use]
+	pub fn last_sound_id(&self) -> Option<SoundId> {
+		self.sound_profilers
+			.as_ref()
+			.and_then(SoundProfilers::last_sound_id)
+	}
+}
+
+/// A sound playing on a mixer track.
+pub(crate) struct Voice {
+	sound: Box<dyn Sound>,
+	priority: i32,
+	/// Sounds that started playing earlier have lower numbers.
+	order: u64,
+	/// The peak amplitude of the most recent batch of audio, or
+	/// `None` if the sound hasn't output any audible audio yet.
+	peak: Option<f32>,
+	/// How long the voice takes to fade in or out (in seconds).
+	fade_duration: f64,
+	/// The volume of the fade between playing the sound normally
+	/// and virtualizing or stopping it.
+	///
+	/// When this reaches `0.0`, the sound is virtual.
+	volume: f64,
+	/// Whether the sound should be virtual because of the voice limit.
+	over_budget: bool,
+	stolen: bool,
+	finished_stealing: bool,
+}
+
+impl Voice {
+	#[must_use]
+	pub fn new(sound: Box<dyn Sound>, priority: i32, order: u64, fade_duration: Duration) -> Self {
+		Self {
+			sound,
+			priority,
+			order,
+			peak: None,
+			fade_duration: fade_duration.as_secs_f64(),
+			volume: 1.0,
+			over_budget: false,
+			stolen: false,
+			finished_stealing: false,
+		}
+	}
+
+	pub fn on_start_processing(&mut self) {
+		self.sound.on_start_processing();
+	}
+
+	/// Produces the next batch of audio for the sound.
+	///
+	/// If `inaudible` is `true`, the sound is virtualized.
+	pub fn process(&mut self, out: &mut [Frame], dt: f64, info: &Info, inaudible: bool) {
+		if self.finished() {
+			out.fill(Frame::ZERO);
+			return;
+		}
+		let target_volume = if self.stolen || self.over_budget || inaudible {
+			0.0
+		} else {
+			1.0
+		};
+		if self.volume == 0.0 && target_volume == 0.0 {
+			out.fill(Frame::ZERO);
+			if self.stolen {
+				self.finish_steal();
+			} else {
+				self.sound.process_virtual(out.len(), dt, info);
+			}
+			return;
+		}
+		self.sound.process(out, dt, info);
+		let peak = out
+			.iter()
+			.map(|frame| frame.left.abs().max(frame.right.abs()))
+			.fold(0.0, f32::max);
+		// sounds output silence while they're waiting to start, so
+		// silence before the first audible batch doesn't count
+		if self.peak.is_some() || peak > 0.0 {
+			self.peak = Some(peak);
+		}
+		if self.volume != target_volume {
+			let step = if self.fade_duration == 0.0 {
+				1.0
+			} else {
+				dt / self.fade_duration
+			};
+			for frame in out.iter_mut() {
+				self.volume = if self.volume < target_volume {
+					(self.volume + step).min(target_volume)
+				} else {
+					(self.volume - step).max(target_volume)
+				};
+				*frame *= self.volume as f32;
+			}
+			// allow for rounding error when the fade should have finished
+			if (self.volume - target_volume).abs() < 1e-6 {
+				self.volume = target_volume;
+			}
+		}
+		if self.stolen && self.volume == 0.0 {
+			self.finish_steal();
+		}
+	}
+
+	#[must_use]
+	pub fn finished(&self) -> bool {
+		self.sound.finished() || self.finished_stealing
+	}
+
+	#[must_use]
+	fn is_stolen(&self) -> bool {
+		self.stolen
+	}
+
+	#[must_use]
+	fn loudness(&self) -> f32 {
+		self.peak.unwrap_or(f32::INFINITY)
+	}
+
+	fn steal(&mut self) {
+		self.stolen = true;
+		// sounds that can't be heard don't need to fade out
+		if self.peak.is_none() || self.volume == 0.0 || self.fade_duration == 0.0 {
+			self.finish_steal();
+		}
+	}
+
+	fn finish_steal(&mut self) {
+		if !self.finished_stealing {
+			self.finished_stealing = true;
+			self.sound.on_stolen();
+		}
+	}
+}
@@ -0,0 +1,272 @@
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use atomic_arena::Key;
+
+use crate::{
+	backend::resources::ResourceStorage,
+	frame::Frame,
+	info::{Info, MockInfoBuilder},
+	sound::{Sound, SoundData},
+	PlaySoundError,
+};
+
+use super::{Voice, VoiceController, VoiceLimit, VoiceStealing};
+
+const DT: f64 = 0.001;
+
+/// A sound that outputs a constant value and keeps track of how
+/// many frames it's played.
+struct TestSound {
+	value: f32,
+	shared: Arc<TestSoundShared>,
+}
+
+impl Sound for TestSound {
+	fn process(&mut self, out: &mut [Frame], _dt: f64, _info: &Info) {
+		out.fill(Frame::from_mono(self.value));
+		self.shared.frames.fetch_add(out.len(), Ordering::SeqCst);
+	}
+
+	fn process_virtual(&mut self, num_frames: usize, _dt: f64, _info: &Info) {
+		self.shared.frames.fetch_add(num_frames, Ordering::SeqCst);
+		self.shared
+			.virtual_frames
+			.fetch_add(num_frames, Ordering::SeqCst);
+	}
+
+	fn finished(&self) -> bool {
+		self.shared.finished.load(Ordering::SeqCst)
+	}
+
+	fn on_stolen(&mut self) {
+		self.shared.stolen.store(true, Ordering::SeqCst);
+	}
+}
+
+#[derive(Default)]
+struct TestSoundShared {
+	frames: AtomicUsize,
+	virtual_frames: AtomicUsize,
+	stolen: AtomicBool,
+	finished: AtomicBool,
+}
+
+impl TestSoundShared {
+	fn stolen(&self) -> bool {
+		self.stolen.load(Ordering::SeqCst)
+	}
+}
+
+struct TestSoundData(f32);
+
+impl SoundData for TestSoundData {
+	type Error = ();
+
+	type Handle = Arc<TestSoundShared>;
+
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let shared = Arc::new(TestSoundShared::default());
+		Ok((
+			Box::new(TestSound {
+				value: self.0,
+				shared: shared.clone(),
+			}),
+			shared,
+		))
+	}
+}
+
+/// Mimics how a mixer track plays its voices.
+struct TestTrack {
+	voices: ResourceStorage<Voice>,
+	controller: VoiceController,
+	voice_limit: VoiceLimit,
+	voice_keys: Vec<Key>,
+}
+
+impl TestTrack {
+	fn new(voice_limit: VoiceLimit) -> Self {
+		let (voices, controller) = ResourceStorage::new(16);
+		Self {
+			voices,
+			controller: VoiceController::new(controller, Some(voice_limit), false),
+			voice_limit,
+			voice_keys: Vec::with_capacity(16),
+		}
+	}
+
+	fn play(&mut self, value: f32, priority: i32) -> Arc<TestSoundShared> {
+		self.controller
+			.play(TestSoundData(value), priority)
+			.unwrap()
+	}
+
+	/// Processes a batch of audio and returns the summed output.
+	fn process(&mut self, num_frames: usize) -> Vec<f32> {
+		self.process_with_audibility(num_frames, false)
+	}
+
+	fn process_with_audibility(&mut self, num_frames: usize, inaudible: bool) -> Vec<f32> {
+		self.voices.remove_and_add(|voice| voice.finished());
+		self.voice_limit
+			.update_voices(&mut self.voices, &mut self.voice_keys);
+		let info = MockInfoBuilder::new().build();
+		let mut out = vec![0.0; num_frames];
+		let mut voice_out = vec![Frame::ZERO; num_frames];
+		for (_, voice) in &mut self.voices {
+			voice.on_start_processing();
+			voice.process(&mut voice_out, DT, &info, inaudible);
+			for (summed_out, frame) in out.iter_mut().zip(&voice_out) {
+				*summed_out += frame.left / Frame::from_mono(1.0).left;
+			}
+		}
+		out
+	}
+
+	fn num_voices(&self) -> usize {
+		self.voices.iter().count()
+	}
+}
+
+/// Tests that the oldest sound is faded out and removed when
+/// the voice limit is exceeded.
+#[test]
+fn steals_oldest() {
+	let mut track = TestTrack::new(VoiceLimit::new(2));
+	let oldest = track.play(1.0, 0);
+	track.play(10.0, 0);
+	assert_eq!(track.process(1), [11.0]);
+	track.play(100.0, 0);
+	let out = track.process(10);
+	assert!(out.windows(2).all(|pair| pair[1] < pair[0]));
+	assert!(out[0] > 110.0 && out[0] < 111.0);
+	assert!((out[9] - 110.0).abs() < 1e-4);
+	assert!(oldest.stolen());
+	assert_eq!(track.process(1), [110.0]);
+	assert_eq!(track.num_voices(), 2);
+}
+
+/// Tests that the quietest sound is stolen, and that sounds that
+/// haven't been heard yet aren't considered the quietest.
+#[test]
+fn steals_quietest() {
+	let mut track = TestTrack::new(VoiceLimit::new(2).stealing(VoiceStealing::Quietest));
+	let loud = track.play(1.0, 0);
+	let quiet = track.play(0.5, 0);
+	track.process(1);
+	let new = track.play(0.25, 0);
+	track.process(20);
+	assert!(!loud.stolen());
+	assert!(quiet.stolen());
+	assert!(!new.stolen());
+	assert_eq!(track.process(1), [1.25]);
+}
+
+/// Tests that sounds that have only output silence, like sounds
+/// waiting for their start time, aren't considered the quietest.
+#[test]
+fn silent_sounds_are_not_quietest() {
+	let mut track = TestTrack::new(VoiceLimit::new(3).stealing(VoiceStealing::Quietest));
+	let loud = track.play(1.0, 0);
+	let quiet = track.play(0.5, 0);
+	let waiting = track.play(0.0, 0);
+	track.process(1);
+	let new = track.play(0.25, 0);
+	track.process(20);
+	assert!(!loud.stolen());
+	assert!(quiet.stolen());
+	assert!(!waiting.stolen());
+	assert!(!new.stolen());
+}
+
+/// Tests that the sound with the lowest priority is stolen, and that
+/// new sounds with the lowest priority are never heard.
+#[test]
+fn steals_lowest_priority() {
+	let mut track = TestTrack::new(VoiceLimit::new(2).stealing(VoiceStealing::LowestPriority));
+	let high = track.play(1.0, 2);
+	let low = track.play(10.0, 1);
+	track.process(1);
+	let lowest = track.play(100.0, 0);
+	assert_eq!(track.process(1), [11.0]);
+	assert!(lowest.stolen());
+	let highest = track.play(1000.0, 3);
+	track.process(20);
+	assert!(!high.stolen());
+	assert!(low.stolen());
+	assert!(!highest.stolen());
+	assert_eq!(track.process(1), [1001.0]);
+}
+
+/// Tests that new sounds are rejected when the voice limit is
+/// reached and the stealing policy is `Reject`.
+#[test]
+fn rejects_new_sounds() {
+	let mut track = TestTrack::new(VoiceLimit::new(2).stealing(VoiceStealing::Reject));
+	track.play(1.0, 0);
+	track.play(10.0, 0);
+	assert!(matches!(
+		track.controller.play(TestSoundData(100.0), 0),
+		Err(PlaySoundError::SoundLimitReached)
+	));
+	assert_eq!(track.process(1), [11.0]);
+}
+
+/// Tests that stolen sounds stop immediately if the fade duration is zero.
+#[test]
+fn zero_fade_duration() {
+	let mut track = TestTrack::new(VoiceLimit::new(1).steal_fade_duration(Duration::ZERO));
+	let old = track.play(1.0, 0);
+	track.process(1);
+	track.play(10.0, 0);
+	assert_eq!(track.process(1), [10.0]);
+	assert!(old.stolen());
+}
+
+/// Tests that sounds over the voice limit are virtualized instead of
+/// stopped if virtualization is enabled, and that they become audible
+/// again when there's room for them.
+#[test]
+fn virtualizes_over_budget_sounds() {
+	let mut track = TestTrack::new(VoiceLimit::new(1).virtualize(true));
+	let old = track.play(1.0, 0);
+	track.process(1);
+	let new = track.play(10.0, 0);
+	let out = track.process(10);
+	assert!(out.windows(2).all(|pair| pair[1] < pair[0]));
+	assert!((out[9] - 10.0).abs() < 1e-4);
+	assert_eq!(track.process(5), [10.0; 5]);
+	assert_eq!(old.virtual_frames.load(Ordering::SeqCst), 5);
+	assert_eq!(old.frames.load(Ordering::SeqCst), 16);
+	assert!(!old.stolen());
+	assert_eq!(track.num_voices(), 2);
+
+	// when the newer sound finishes, the older sound fades back in
+	new.finished.store(true, Ordering::SeqCst);
+	let out = track.process(10);
+	assert!(out.windows(2).all(|pair| pair[1] > pair[0]));
+	assert!((out[9] - 1.0).abs() < 1e-4);
+	assert_eq!(old.virtual_frames.load(Ordering::SeqCst), 5);
+	assert_eq!(old.frames.load(Ordering::SeqCst), 26);
+}
+
+/// Tests that sounds are virtualized while they're inaudible.
+#[test]
+fn virtualizes_inaudible_sounds() {
+	let mut track = TestTrack::new(VoiceLimit::new(4));
+	let sound = track.play(1.0, 0);
+	assert_eq!(track.process(1), [1.0]);
+	track.process_with_audibility(10, true);
+	track.process_with_audibility(5, true);
+	assert_eq!(sound.virtual_frames.load(Ordering::SeqCst), 5);
+	assert_eq!(sound.frames.load(Ordering::SeqCst), 16);
+	let out = track.process(10);
+	assert!((out[9] - 1.0).abs() < 1e-4);
+	assert!(!sound.stolen());
+}
@@ -66,7 +66,10 @@ impl EffectBuilder for TestEffectBuilder {
 #[test]
 fn change_sample_rate() {
 	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
-		backend_settings: MockBackendSettings { sample_rate: 100 },
+		backend_settings: MockBackendSettings {
+			sample_rate: 100,
+			..Default::default()
+		},
 		..Default::default()
 	})
 	.unwrap();
@@ -88,3 +91,37 @@ fn change_sample_rate() {
 	backend.process();
 	assert_eq!(effect_handle.dt_consumer.try_pop(), Some(1.0 / 200.0));
 }
+
+#[test]
+fn internal_sample_rate() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		internal_sample_rate: Some(100),
+		backend_settings: MockBackendSettings {
+			sample_rate: 200,
+			..Default::default()
+		},
+		..Default::default()
+	})
+	.unwrap();
+	let mut effect_handle;
+	let _track = manager
+		.add_sub_track({
+			let mut builder = TrackBuilder::new();
+			effect_handle = builder.add_effect(TestEffectBuilder);
+			builder
+		})
+		.unwrap();
+	let backend = manager.backend_mut();
+	backend.on_start_processing();
+	assert_eq!(effect_handle.sample_rate.load(Ordering::SeqCst), 100);
+	backend.process();
+	assert_eq!(effect_handle.dt_consumer.try_pop(), Some(1.0 / 100.0));
+	// changing the device's sample rate doesn't affect the mixer
+	backend.set_sample_rate(300);
+	assert_eq!(effect_handle.sample_rate.load(Ordering::SeqCst), 100);
+	// the mixer renders less often than the backend asks for audio
+	for _ in 0..3 {
+		backend.process();
+	}
+	assert_eq!(effect_handle.dt_consumer.try_pop(), Some(1.0 / 100.0));
+}